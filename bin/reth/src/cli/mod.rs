@@ -12,9 +12,9 @@ use crate::{
 use clap::{value_parser, Parser, Subcommand};
 use reth_chainspec::ChainSpec;
 use reth_cli_commands::{
-    config_cmd, db, dump_genesis, import, init_cmd, init_state,
+    config_cmd, db, dump_genesis, export_era, import, import_era, init_cmd, init_state,
     node::{self, NoArgs},
-    p2p, prune, recover, stage,
+    p2p, prune, recover, snapshot, stage,
 };
 use reth_cli_runner::CliRunner;
 use reth_db::DatabaseEnv;
@@ -154,6 +154,8 @@ impl<Ext: clap::Args + fmt::Debug> Cli<Ext> {
             Commands::Import(command) => runner.run_blocking_until_ctrl_c(
                 command.execute(|chain_spec| block_executor!(chain_spec)),
             ),
+            Commands::ImportEra(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::ExportEra(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             #[cfg(feature = "optimism")]
             Commands::ImportOp(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             #[cfg(feature = "optimism")]
@@ -172,6 +174,7 @@ impl<Ext: clap::Args + fmt::Debug> Cli<Ext> {
             Commands::Debug(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
             Commands::Recover(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
             Commands::Prune(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::Snapshot(command) => runner.run_blocking_until_ctrl_c(command.execute()),
         }
     }
 
@@ -200,6 +203,12 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// This syncs RLP encoded blocks from a file.
     #[command(name = "import")]
     Import(import::ImportCommand),
+    /// This imports pre-merge history from era1 archives.
+    #[command(name = "import-era")]
+    ImportEra(import_era::ImportEraCommand),
+    /// Exports pre-merge history to era1 archives.
+    #[command(name = "export-era")]
+    ExportEra(export_era::ExportEraCommand),
     /// This syncs RLP encoded OP blocks below Bedrock from a file, without executing.
     #[cfg(feature = "optimism")]
     #[command(name = "import-op")]
@@ -235,6 +244,9 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// Prune according to the configuration without any limits
     #[command(name = "prune")]
     Prune(prune::PruneCommand),
+    /// Export and import node snapshots
+    #[command(name = "snapshot")]
+    Snapshot(snapshot::Command),
 }
 
 #[cfg(test)]
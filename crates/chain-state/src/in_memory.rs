@@ -792,12 +792,13 @@ mod tests {
     use rand::Rng;
     use reth_errors::ProviderResult;
     use reth_primitives::{
-        Account, BlockNumber, Bytecode, Bytes, Receipt, StorageKey, StorageValue,
+        Account, BlockNumber, Bytecode, Bytes, Receipt, StorageKey, StorageValue, U256,
     };
     use reth_storage_api::{
-        AccountReader, BlockHashReader, StateProofProvider, StateProvider, StateRootProvider,
+        AccountReader, BlockHashReader, HashedStateRange, StateProofProvider, StateProvider,
+        StateRangeProvider, StateRootProvider,
     };
-    use reth_trie::{AccountProof, HashedStorage};
+    use reth_trie::{AccountProof, HashedStorage, TrieAccount};
 
     fn create_mock_state(
         test_block_builder: &mut TestBlockBuilder,
@@ -906,6 +907,42 @@ mod tests {
         }
     }
 
+    impl StateRangeProvider for MockStateProvider {
+        fn hashed_account_range(
+            &self,
+            _hashed_state: HashedPostState,
+            _start: B256,
+            _limit: usize,
+        ) -> ProviderResult<HashedStateRange<TrieAccount>> {
+            Ok(HashedStateRange::default())
+        }
+
+        fn hashed_storage_range(
+            &self,
+            _hashed_state: HashedPostState,
+            _hashed_address: B256,
+            _start: B256,
+            _limit: usize,
+        ) -> ProviderResult<HashedStateRange<U256>> {
+            Ok(HashedStateRange::default())
+        }
+
+        fn account_preimages(
+            &self,
+            _hashed_addresses: &[B256],
+        ) -> ProviderResult<HashMap<B256, Address>> {
+            Ok(HashMap::default())
+        }
+
+        fn storage_preimages(
+            &self,
+            _address: Address,
+            _hashed_slots: &[B256],
+        ) -> ProviderResult<HashMap<B256, B256>> {
+            Ok(HashMap::default())
+        }
+    }
+
     #[test]
     fn test_in_memory_state_impl_state_by_hash() {
         let mut state_by_hash = HashMap::new();
@@ -3,13 +3,13 @@ use std::collections::HashMap;
 use super::ExecutedBlock;
 use reth_errors::ProviderResult;
 use reth_primitives::{
-    Account, Address, BlockNumber, Bytecode, Bytes, StorageKey, StorageValue, B256,
+    keccak256, Account, Address, BlockNumber, Bytecode, Bytes, StorageKey, StorageValue, B256, U256,
 };
 use reth_storage_api::{
-    AccountReader, BlockHashReader, StateProofProvider, StateProvider, StateProviderBox,
-    StateRootProvider,
+    AccountReader, BlockHashReader, HashedStateRange, StateProofProvider, StateProvider,
+    StateProviderBox, StateRangeProvider, StateRootProvider,
 };
-use reth_trie::{updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage};
+use reth_trie::{updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, TrieAccount};
 
 /// A state provider that stores references to in-memory blocks along with their state as well as
 /// the historical state provider for fallback lookups.
@@ -143,6 +143,67 @@ impl StateProofProvider for MemoryOverlayStateProvider {
     }
 }
 
+impl StateRangeProvider for MemoryOverlayStateProvider {
+    fn hashed_account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStateRange<TrieAccount>> {
+        let mut state = self.hashed_post_state.clone();
+        state.extend(hashed_state);
+        self.historical.hashed_account_range(state, start, limit)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_state: HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStateRange<U256>> {
+        let mut state = self.hashed_post_state.clone();
+        state.extend(hashed_state);
+        self.historical.hashed_storage_range(state, hashed_address, start, limit)
+    }
+
+    fn account_preimages(
+        &self,
+        hashed_addresses: &[B256],
+    ) -> ProviderResult<HashMap<B256, Address>> {
+        let mut preimages = self.historical.account_preimages(hashed_addresses)?;
+        for block in &self.in_memory {
+            for address in block.execution_output.bundle.state.keys() {
+                let hashed_address = keccak256(address);
+                if hashed_addresses.contains(&hashed_address) {
+                    preimages.insert(hashed_address, *address);
+                }
+            }
+        }
+        Ok(preimages)
+    }
+
+    fn storage_preimages(
+        &self,
+        address: Address,
+        hashed_slots: &[B256],
+    ) -> ProviderResult<HashMap<B256, B256>> {
+        let mut preimages = self.historical.storage_preimages(address, hashed_slots)?;
+        for block in &self.in_memory {
+            if let Some(account) = block.execution_output.bundle.state.get(&address) {
+                for slot in account.storage.keys() {
+                    let slot = B256::from(*slot);
+                    let hashed_slot = keccak256(slot);
+                    if hashed_slots.contains(&hashed_slot) {
+                        preimages.insert(hashed_slot, slot);
+                    }
+                }
+            }
+        }
+        Ok(preimages)
+    }
+}
+
 impl StateProvider for MemoryOverlayStateProvider {
     fn storage(
         &self,
@@ -112,7 +112,10 @@ impl EnvironmentArgs {
         let prune_modes =
             config.prune.as_ref().map(|prune| prune.segments.clone()).unwrap_or_default();
         let factory = ProviderFactory::new(db, self.chain.clone(), static_file_provider)
-            .with_prune_modes(prune_modes.clone());
+            .with_prune_modes(prune_modes.clone())
+            .with_address_transactions_internal_calls(
+                config.stages.index_address_transactions.internal_calls,
+            );
 
         // Check for consistency between database and static files.
         if let Some(unwind_target) = factory
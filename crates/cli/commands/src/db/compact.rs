@@ -0,0 +1,70 @@
+use clap::Parser;
+use human_bytes::human_bytes;
+use reth_db::{
+    mdbx::{COMPACT_DIR_NAME, DATA_FILE_NAME},
+    DatabaseEnv,
+};
+use reth_provider::ProviderFactory;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::{info, warn};
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db compact` command
+pub struct Command {
+    /// Writes the compacted copy of the database to this directory instead of replacing the
+    /// database with it.
+    ///
+    /// The database is only read in this case, so the node can keep running.
+    #[arg(long, value_name = "DIR")]
+    pub(crate) output: Option<PathBuf>,
+}
+
+impl Command {
+    /// Execute `db compact` command
+    pub fn execute(
+        self,
+        db_path: &Path,
+        provider_factory: ProviderFactory<Arc<DatabaseEnv>>,
+    ) -> eyre::Result<()> {
+        let size_before = data_file_size(db_path)?;
+
+        if let Some(output) = self.output {
+            info!(target: "reth::cli", ?output, "Writing compacted copy of the database");
+            provider_factory.db_ref().compact_to(&output)?;
+            info!(
+                target: "reth::cli",
+                size = human_bytes(size_before as f64),
+                compacted_size = human_bytes(data_file_size(&output)? as f64),
+                ?output,
+                "Wrote compacted copy of the database"
+            );
+            return Ok(())
+        }
+
+        warn!("This command should be run without the node running!");
+
+        info!(target: "reth::cli", ?db_path, "Compacting database");
+        let compact_path = provider_factory.db_ref().compact()?;
+        // Close the database before its data file is replaced.
+        drop(provider_factory);
+
+        reth_fs_util::rename(compact_path.join(DATA_FILE_NAME), db_path.join(DATA_FILE_NAME))?;
+        reth_fs_util::remove_dir_all(db_path.join(COMPACT_DIR_NAME))?;
+
+        info!(
+            target: "reth::cli",
+            size = human_bytes(size_before as f64),
+            compacted_size = human_bytes(data_file_size(db_path)? as f64),
+            "Compacted database"
+        );
+        Ok(())
+    }
+}
+
+/// Returns the size of the MDBX data file in the given database directory.
+fn data_file_size(db_path: &Path) -> eyre::Result<u64> {
+    Ok(reth_fs_util::metadata(db_path.join(DATA_FILE_NAME))?.len())
+}
@@ -6,12 +6,14 @@ use std::io::{self, Write};
 
 mod checksum;
 mod clear;
+mod compact;
 mod diff;
 mod get;
 mod list;
 mod stats;
 /// DB List TUI
 mod tui;
+mod verify_static_files;
 
 /// `reth db` command
 #[derive(Debug, Parser)]
@@ -44,6 +46,10 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Compacts the database by replacing it with a compacted copy
+    Compact(compact::Command),
+    /// Verifies the checksums, offsets and ranges of all static files
+    VerifyStaticFiles(verify_static_files::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -128,6 +134,18 @@ impl Command {
                 let Environment { provider_factory, .. } = self.env.init(AccessRights::RW)?;
                 command.execute(provider_factory)?;
             }
+            Subcommands::Compact(command) => {
+                let access_rights =
+                    if command.output.is_some() { AccessRights::RO } else { AccessRights::RW };
+                let Environment { provider_factory, .. } = self.env.init(access_rights)?;
+                command.execute(&db_path, provider_factory)?;
+            }
+            Subcommands::VerifyStaticFiles(command) => {
+                let access_rights =
+                    if command.repair { AccessRights::RW } else { AccessRights::RO };
+                let Environment { provider_factory, config, .. } = self.env.init(access_rights)?;
+                command.execute(provider_factory, config)?;
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
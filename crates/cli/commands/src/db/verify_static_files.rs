@@ -0,0 +1,305 @@
+use crate::stage::unwind::build_unwind_pipeline;
+use clap::Parser;
+use eyre::{bail, ensure};
+use reth_config::Config;
+use reth_db_api::database::Database;
+use reth_nippy_jar::NippyJar;
+use reth_primitives::{BlockNumber, StaticFileSegment, TxNumber};
+use reth_provider::{ProviderFactory, StaticFileProviderFactory};
+use reth_static_file_types::{SegmentHeader, SegmentRangeInclusive};
+use std::{collections::BTreeMap, path::Path, sync::Arc};
+use tracing::{info, warn};
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db verify-static-files` command
+pub struct Command {
+    /// Unwinds the node to the block before the first invalid static file, so that its data is
+    /// synced again.
+    #[arg(long)]
+    pub(crate) repair: bool,
+}
+
+impl Command {
+    /// Execute `db verify-static-files` command
+    pub fn execute<DB: Database + 'static>(
+        self,
+        provider_factory: ProviderFactory<Arc<DB>>,
+        config: Config,
+    ) -> eyre::Result<()> {
+        warn!("This command should be run without the node running!");
+
+        let static_files_dir = provider_factory.static_file_provider().directory().to_path_buf();
+        let report = verify_static_files(&static_files_dir)?;
+        report.log();
+
+        let Some(first_invalid_block) = report.first_invalid_block() else { return Ok(()) };
+        if !self.repair {
+            bail!(
+                "found {} invalid static files, run with `--repair` to unwind to block {}",
+                report.invalid.len(),
+                first_invalid_block.saturating_sub(1)
+            )
+        }
+        ensure!(first_invalid_block > 0, "static files of the genesis block are invalid, resync");
+
+        let target = first_invalid_block - 1;
+        info!(target: "reth::cli", target, "Unwinding to remove invalid static files");
+
+        let mut pipeline = build_unwind_pipeline(config, provider_factory, false)?;
+        pipeline.move_to_static_files()?;
+        pipeline.unwind(target, None)?;
+
+        let report = verify_static_files(&static_files_dir)?;
+        report.log();
+        ensure!(report.invalid.is_empty(), "static files are still invalid after unwinding");
+
+        info!(target: "reth::cli", target, "Unwound invalid static files");
+        Ok(())
+    }
+}
+
+/// Outcome of verifying all static files of a directory.
+#[derive(Debug, Default)]
+struct VerificationReport {
+    /// Number of verified static files.
+    files: usize,
+    /// Number of static files without a checksum, whose content could not be re-hashed.
+    without_checksum: usize,
+    /// Static files which failed verification.
+    invalid: Vec<InvalidStaticFile>,
+}
+
+impl VerificationReport {
+    /// Returns the first block of the earliest invalid static file.
+    fn first_invalid_block(&self) -> Option<BlockNumber> {
+        self.invalid.iter().map(|file| file.block_range.start()).min()
+    }
+
+    fn log(&self) {
+        for file in &self.invalid {
+            warn!(
+                target: "reth::cli",
+                segment = %file.segment,
+                block_range = %file.block_range,
+                reason = %file.reason,
+                "Invalid static file"
+            );
+        }
+        info!(
+            target: "reth::cli",
+            files = self.files,
+            without_checksum = self.without_checksum,
+            invalid = self.invalid.len(),
+            "Verified static files"
+        );
+    }
+}
+
+/// A static file which failed verification.
+#[derive(Debug)]
+struct InvalidStaticFile {
+    segment: StaticFileSegment,
+    /// Expected block range of the static file.
+    block_range: SegmentRangeInclusive,
+    reason: String,
+}
+
+/// Verifies every static file in `dir`, and that the static files of each segment are contiguous.
+fn verify_static_files(dir: &Path) -> eyre::Result<VerificationReport> {
+    let mut static_files = BTreeMap::<_, Vec<_>>::new();
+    for entry in reth_fs_util::read_dir(dir)? {
+        let path = entry?.path();
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        if let Some((segment, block_range)) = StaticFileSegment::parse_filename(&file_name) {
+            static_files.entry(segment).or_default().push((block_range, path));
+        }
+    }
+
+    let mut report = VerificationReport::default();
+    for (segment, mut files) in static_files {
+        files.sort_by_key(|(block_range, _)| block_range.start());
+
+        let mut previous: Option<(SegmentHeader, Option<TxNumber>)> = None;
+        for (block_range, path) in files {
+            report.files += 1;
+
+            let result = verify_static_file(segment, &block_range, &path).and_then(|jar| {
+                if let Some((previous, previous_tx_end)) = &previous {
+                    verify_continuity(previous, *previous_tx_end, jar.user_header())?;
+                }
+                Ok(jar)
+            });
+
+            match result {
+                Ok(jar) => {
+                    if jar.checksum().is_none() {
+                        report.without_checksum += 1;
+                    }
+                    let tx_end = jar
+                        .user_header()
+                        .tx_end()
+                        .or_else(|| previous.as_ref().and_then(|(_, tx_end)| *tx_end));
+                    previous = Some((jar.user_header().clone(), tx_end));
+                }
+                Err(err) => {
+                    report.invalid.push(InvalidStaticFile {
+                        segment,
+                        block_range,
+                        reason: err.to_string(),
+                    });
+                    previous = None;
+                }
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+/// Verifies the segment header, offsets and checksum of a single static file.
+fn verify_static_file(
+    segment: StaticFileSegment,
+    expected_block_range: &SegmentRangeInclusive,
+    path: &Path,
+) -> eyre::Result<NippyJar<SegmentHeader>> {
+    let jar = NippyJar::<SegmentHeader>::load(path)?;
+    let header = jar.user_header();
+
+    ensure!(header.segment() == segment, "segment header belongs to {}", header.segment());
+    ensure!(
+        header.expected_block_start() == expected_block_range.start() &&
+            header.expected_block_end() == expected_block_range.end(),
+        "expected block range {}..={} does not match the file name",
+        header.expected_block_start(),
+        header.expected_block_end()
+    );
+    ensure!(
+        jar.columns() == segment.columns(),
+        "found {} columns, expected {}",
+        jar.columns(),
+        segment.columns()
+    );
+    if let Some(block_range) = header.block_range() {
+        ensure!(
+            block_range.start() == expected_block_range.start() &&
+                block_range.end() <= expected_block_range.end(),
+            "block range {block_range} is outside of the expected block range"
+        );
+    } else {
+        ensure!(header.tx_range().is_none(), "transaction range without a block range");
+    }
+
+    let expected_rows = if segment.is_block_based() { header.block_len() } else { header.tx_len() };
+    ensure!(
+        jar.rows() as u64 == expected_rows.unwrap_or_default(),
+        "found {} rows, but the segment header expects {}",
+        jar.rows(),
+        expected_rows.unwrap_or_default()
+    );
+
+    // The last offset is the size of the data file. Fully pruned files have no offsets at all.
+    let reader = jar.open_data_reader()?;
+    let offsets = reader.offsets_count()?;
+    let expected_offsets = jar.rows() * jar.columns() + 1;
+    ensure!(
+        offsets == expected_offsets || (jar.rows() == 0 && offsets == 0),
+        "found {offsets} offsets, expected {expected_offsets}"
+    );
+    let mut last_offset = 0;
+    for index in 0..offsets {
+        let offset = reader.offset(index)?;
+        ensure!(offset >= last_offset, "offset {index} is smaller than the previous one");
+        last_offset = offset;
+    }
+    ensure!(
+        last_offset == reader.size() as u64,
+        "last offset {last_offset} does not match the data file size {}",
+        reader.size()
+    );
+
+    if let Some(checksum) = jar.checksum() {
+        ensure!(jar.compute_checksum()? == *checksum, "checksum mismatch");
+    }
+
+    Ok(jar)
+}
+
+/// Verifies that a static file continues where the previous static file of its segment ends.
+fn verify_continuity(
+    previous: &SegmentHeader,
+    previous_tx_end: Option<TxNumber>,
+    header: &SegmentHeader,
+) -> eyre::Result<()> {
+    ensure!(
+        previous.block_end() == Some(previous.expected_block_end()),
+        "previous static file ends at block {:?} instead of {}",
+        previous.block_end(),
+        previous.expected_block_end()
+    );
+    ensure!(
+        header.expected_block_start() == previous.expected_block_end() + 1,
+        "previous static file ends at block {}",
+        previous.expected_block_end()
+    );
+    if let Some(tx_start) = header.tx_start() {
+        let expected_tx_start = previous_tx_end.map_or(0, |tx_end| tx_end + 1);
+        ensure!(
+            tx_start == expected_tx_start,
+            "transaction range starts at {tx_start}, expected {expected_tx_start}"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, B256, U256};
+    use reth_provider::{test_utils::create_test_provider_factory, StaticFileWriter};
+    use std::fs;
+
+    #[test]
+    fn verify_headers_static_file() {
+        let factory = create_test_provider_factory();
+        let static_file_provider = factory.static_file_provider();
+        {
+            let mut writer =
+                static_file_provider.latest_writer(StaticFileSegment::Headers).unwrap();
+            for number in 0..10 {
+                let header = Header { number, ..Default::default() };
+                writer.append_header(&header, U256::ZERO, &B256::random()).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        let report = verify_static_files(static_file_provider.directory()).unwrap();
+        assert_eq!((report.files, report.without_checksum), (1, 1));
+        assert!(report.invalid.is_empty());
+
+        static_file_provider
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .inner()
+            .commit_with_checksum()
+            .unwrap();
+        let report = verify_static_files(static_file_provider.directory()).unwrap();
+        assert_eq!((report.files, report.without_checksum), (1, 0));
+        assert!(report.invalid.is_empty());
+
+        // Flipping a bit of the data file is caught by the checksum
+        let data_path = static_file_provider
+            .directory()
+            .join(StaticFileSegment::Headers.filename(&SegmentRangeInclusive::new(0, 499_999)));
+        let mut data = fs::read(&data_path).unwrap();
+        data[0] ^= 1;
+        fs::write(&data_path, &data).unwrap();
+        let report = verify_static_files(static_file_provider.directory()).unwrap();
+        assert_eq!(report.invalid[0].reason, "checksum mismatch");
+        assert_eq!(report.first_invalid_block(), Some(0));
+
+        // Truncating the data file is caught by the offsets
+        fs::write(&data_path, &data[..data.len() - 1]).unwrap();
+        let report = verify_static_files(static_file_provider.directory()).unwrap();
+        assert!(report.invalid[0].reason.starts_with("last offset"));
+    }
+}
@@ -0,0 +1,149 @@
+//! Command that exports pre-merge history to era1 archives.
+use crate::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::Parser;
+use eyre::{bail, OptionExt};
+use reth_db_api::database::Database;
+use reth_era::{era1_file_name, Era1Block, Era1Body, Era1Writer, MAX_BLOCKS_PER_ERA1};
+use reth_node_core::version::SHORT_VERSION;
+use reth_primitives::{BlockNumber, Receipt};
+use reth_provider::{
+    BlockNumReader, BlockReader, HeaderProvider, ProviderFactory, ReceiptProvider,
+    TransactionsProvider,
+};
+use std::{
+    io::BufWriter,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// Exports pre-merge history to era1 archives.
+///
+/// Blocks are read from the static file `Headers`, `Transactions` and `Receipts` segments (or the
+/// database, for blocks not yet moved to static files) and written to one file per epoch of
+/// [`MAX_BLOCKS_PER_ERA1`] blocks, named `<network>-<epoch>-<short accumulator root>.era1`.
+/// Receipts must not have been pruned.
+#[derive(Debug, Parser)]
+pub struct ExportEraCommand {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The first block to export.
+    #[arg(long, value_name = "BLOCK_NUMBER", default_value_t = 0, verbatim_doc_comment)]
+    from: BlockNumber,
+
+    /// The last block to export.
+    ///
+    /// Defaults to the last pre-merge block, or the database tip if it has not reached the merge.
+    #[arg(long, value_name = "BLOCK_NUMBER", verbatim_doc_comment)]
+    to: Option<BlockNumber>,
+
+    /// The network name used in the file names. Defaults to the chain name.
+    #[arg(long, value_name = "NETWORK", verbatim_doc_comment)]
+    network: Option<String>,
+
+    /// The directory to write the era1 files to.
+    #[arg(value_name = "EXPORT_DIR", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+impl ExportEraCommand {
+    /// Execute `export-era` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+
+        let tip = provider_factory.last_block_number()?;
+        let last_pre_merge_block = self
+            .env
+            .chain
+            .paris_block_and_final_difficulty
+            .map(|(paris_block, _)| paris_block.saturating_sub(1));
+        let to = match (self.to, last_pre_merge_block) {
+            (Some(to), Some(last)) if to > last => {
+                bail!("era1 files only contain pre-merge blocks, the last one is block {last}")
+            }
+            (Some(to), _) => to,
+            (None, last) => last.map_or(tip, |last| last.min(tip)),
+        };
+        if to > tip {
+            bail!("block {to} is above the database tip {tip}")
+        }
+        if self.from > to {
+            bail!("invalid block range {}..={to}", self.from)
+        }
+
+        let network = self.network.unwrap_or_else(|| self.env.chain.chain.to_string());
+        reth_fs_util::create_dir_all(&self.path)?;
+
+        for epoch in self.from / MAX_BLOCKS_PER_ERA1..=to / MAX_BLOCKS_PER_ERA1 {
+            let start = self.from.max(epoch * MAX_BLOCKS_PER_ERA1);
+            let end = to.min((epoch + 1) * MAX_BLOCKS_PER_ERA1 - 1);
+            let path = export_era1_file(&provider_factory, &self.path, &network, start..=end)?;
+            info!(target: "reth::cli", path = %path.display(), start, end, "Exported era1 file");
+        }
+
+        Ok(())
+    }
+}
+
+/// Exports a range of blocks of a single epoch to an era1 file in `dir`, returning its path.
+pub fn export_era1_file<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    dir: &Path,
+    network: &str,
+    range: RangeInclusive<BlockNumber>,
+) -> eyre::Result<PathBuf> {
+    let epoch = range.start() / MAX_BLOCKS_PER_ERA1;
+    let tmp_path = dir.join(format!("{network}-{epoch:05}.era1.tmp"));
+    let mut writer = Era1Writer::new(BufWriter::new(reth_fs_util::create_file(&tmp_path)?))?;
+
+    let provider = provider_factory.provider()?;
+    for number in range {
+        let header = provider.header_by_number(number)?.ok_or_eyre("missing header")?;
+        let total_difficulty =
+            provider.header_td_by_number(number)?.ok_or_eyre("missing total difficulty")?;
+        let transactions =
+            provider.transactions_by_block(number.into())?.ok_or_eyre("missing transactions")?;
+        let ommers = provider.ommers(number.into())?.unwrap_or_default();
+        let receipts = provider
+            .receipts_by_block(number.into())?
+            .ok_or_else(|| eyre::eyre!("receipts of block {number} are not available"))?;
+
+        let block = Era1Block {
+            header,
+            body: Era1Body { transactions, ommers },
+            receipts: receipts.into_iter().map(Receipt::with_bloom).collect(),
+            total_difficulty,
+        };
+        block.validate()?;
+        writer.append_block(&block)?;
+    }
+
+    let (_, summary) = writer.finish()?;
+    let path = dir.join(era1_file_name(network, epoch, summary.accumulator_root));
+    reth_fs_util::rename(&tmp_path, &path)?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_node_core::args::utils::SUPPORTED_CHAINS;
+
+    #[test]
+    fn parse_export_era_command_chain_args() {
+        for chain in SUPPORTED_CHAINS {
+            let args: ExportEraCommand =
+                ExportEraCommand::parse_from(["reth", "--chain", chain, "--to", "10", "."]);
+            assert_eq!(
+                Ok(args.env.chain.chain),
+                chain.parse::<reth_chainspec::Chain>(),
+                "failed to parse chain {chain}"
+            );
+            assert_eq!(args.to, Some(10));
+        }
+    }
+}
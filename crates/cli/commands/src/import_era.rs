@@ -0,0 +1,357 @@
+//! Command that imports pre-merge history from era1 archives.
+use crate::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::Parser;
+use eyre::{bail, OptionExt};
+use reth_db::tables;
+use reth_db_api::{
+    cursor::DbCursorRW,
+    database::Database,
+    models::{StoredBlockBodyIndices, StoredBlockOmmers},
+    transaction::DbTxMut,
+};
+use reth_era::{EpochAccumulators, Era1Reader, Era1Summary};
+use reth_fs_util::FsPathError;
+use reth_node_core::version::SHORT_VERSION;
+use reth_primitives::{hex, BlockNumber, StaticFileSegment};
+use reth_provider::{
+    writer::UnifiedStorageWriter, BlockHashReader, BlockNumReader, BlockReader, HeaderProvider,
+    ProviderFactory, StageCheckpointWriter, StaticFileProviderFactory, StaticFileWriter,
+};
+use reth_stages::{StageCheckpoint, StageId};
+use std::{
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+};
+use tracing::info;
+
+/// Imports pre-merge history from era1 archives.
+///
+/// Only the canonical file of each epoch is imported: its accumulator root has to be the root of
+/// its epoch in the given list of canonical epoch accumulator roots of the chain.
+///
+/// Headers and transactions are written straight into static files, skipping the online stages.
+/// Receipts are verified against each header's receipts root but not stored: the execution stage
+/// produces them, and static file receipts above its checkpoint are pruned on startup.
+#[derive(Debug, Parser)]
+pub struct ImportEraCommand {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The path to the canonical epoch accumulator roots of the chain, one hex encoded root per
+    /// line in epoch order, e.g. the published pre-merge accumulators.
+    ///
+    /// Files of epochs that are not listed, or whose accumulator root differs, are rejected.
+    #[arg(long, value_name = "FILE", verbatim_doc_comment)]
+    accumulators: PathBuf,
+
+    /// The path to an era1 file, or to a directory of era1 files to import in file name order.
+    #[arg(value_name = "IMPORT_PATH", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+impl ImportEraCommand {
+    /// Execute `import-era` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RW)?;
+
+        let accumulators =
+            reth_fs_util::read_to_string(&self.accumulators)?.parse::<EpochAccumulators>()?;
+
+        let files = era1_files(&self.path)?;
+        if files.is_empty() {
+            bail!("no era1 files found at {}", self.path.display())
+        }
+
+        let mut total_imported_blocks = 0;
+        for path in files {
+            total_imported_blocks += import_era1_file(&provider_factory, &accumulators, &path)?;
+        }
+
+        info!(target: "reth::cli",
+            total_imported_blocks,
+            tip = provider_factory.last_block_number()?,
+            "Era1 files imported"
+        );
+
+        Ok(())
+    }
+}
+
+/// Returns the era1 file at `path`, or the era1 files of the directory at `path` sorted by name.
+fn era1_files(path: &Path) -> eyre::Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()])
+    }
+
+    let mut files = Vec::new();
+    for entry in reth_fs_util::read_dir(path)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|extension| extension == "era1") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Verifies and imports an era1 file, returning the number of imported blocks.
+///
+/// The file is read twice: first to verify its accumulator against its content and the canonical
+/// root of its epoch, then to import the blocks that extend
+/// the current tip. Blocks the database already has are skipped, as long as the file agrees with
+/// the stored hash of the tip.
+pub fn import_era1_file<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    accumulators: &EpochAccumulators,
+    path: &Path,
+) -> eyre::Result<u64> {
+    let summary = open_era1(path)?.finish()?;
+    ensure_file_name_matches(path, &summary)?;
+    accumulators.verify(&summary)?;
+
+    let tip = provider_factory.last_block_number()?;
+    let range = summary.block_range();
+    if *range.start() > tip + 1 {
+        bail!(
+            "{} starts at block {}, but the database tip is block {tip}",
+            path.display(),
+            range.start()
+        )
+    }
+    if *range.end() <= tip {
+        ensure_tip_matches(provider_factory, path, &summary, tip)?;
+        info!(target: "reth::cli", path = %path.display(), ?range, "Era1 file already imported");
+        return Ok(0)
+    }
+
+    info!(target: "reth::cli", path = %path.display(), ?range, "Importing era1 file");
+
+    let provider = provider_factory.provider_rw()?;
+    let static_file_provider = provider_factory.static_file_provider();
+
+    let parent_hash = provider.block_hash(tip)?.ok_or_eyre("missing hash of the database tip")?;
+    let parent_td =
+        provider.header_td_by_number(tip)?.ok_or_eyre("missing total difficulty of the tip")?;
+    let mut next_tx_num = provider
+        .block_body_indices(tip)?
+        .ok_or_eyre("missing body indices of the database tip")?
+        .next_tx_num();
+
+    {
+        let tx = provider.tx_ref();
+        let mut block_indices_cursor = tx.cursor_write::<tables::BlockBodyIndices>()?;
+        let mut tx_block_cursor = tx.cursor_write::<tables::TransactionBlocks>()?;
+        let mut ommers_cursor = tx.cursor_write::<tables::BlockOmmers>()?;
+
+        let mut headers_writer = static_file_provider.latest_writer(StaticFileSegment::Headers)?;
+        let mut transactions_writer =
+            static_file_provider.latest_writer(StaticFileSegment::Transactions)?;
+
+        let mut reader = open_era1(path)?;
+        while let Some(block) = reader.next_block()? {
+            let number = block.header.number;
+            if number <= tip {
+                continue
+            }
+
+            // The first imported block has to extend the database tip. The file itself was
+            // already verified to be contiguous.
+            if number == tip + 1 {
+                if block.header.parent_hash != parent_hash {
+                    bail!("block {number} of {} does not extend the database tip", path.display())
+                }
+                if block.total_difficulty != parent_td + block.header.difficulty {
+                    bail!("total difficulty mismatch for block {number} of {}", path.display())
+                }
+            }
+
+            let hash = block.header.hash_slow();
+            headers_writer.append_header(&block.header, block.total_difficulty, &hash)?;
+            tx.put::<tables::HeaderNumbers>(hash, number)?;
+
+            transactions_writer.increment_block(number)?;
+            let block_indices = StoredBlockBodyIndices {
+                first_tx_num: next_tx_num,
+                tx_count: block.body.transactions.len() as u64,
+            };
+            if !block.body.transactions.is_empty() {
+                tx_block_cursor.append(block_indices.last_tx_num(), number)?;
+            }
+            for transaction in block.body.transactions {
+                transactions_writer.append_transaction(next_tx_num, &transaction.into())?;
+                next_tx_num += 1;
+            }
+            if !block.body.ommers.is_empty() {
+                ommers_cursor.append(number, StoredBlockOmmers { ommers: block.body.ommers })?;
+            }
+            block_indices_cursor.append(number, block_indices)?;
+        }
+    }
+
+    let imported = range.end() - tip;
+    for stage in [StageId::Headers, StageId::Bodies] {
+        provider.save_stage_checkpoint(stage, StageCheckpoint::new(*range.end()))?;
+    }
+    UnifiedStorageWriter::commit(provider, static_file_provider)?;
+
+    Ok(imported)
+}
+
+/// Opens an era1 file for reading.
+fn open_era1(path: &Path) -> eyre::Result<Era1Reader<BufReader<File>>> {
+    let file = File::open(path).map_err(|err| FsPathError::open(err, path))?;
+    Ok(Era1Reader::new(BufReader::new(file))?)
+}
+
+/// Ensures that a file which is already fully imported belongs to the stored chain.
+fn ensure_tip_matches<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    path: &Path,
+    summary: &Era1Summary,
+    tip: BlockNumber,
+) -> eyre::Result<()> {
+    let last = *summary.block_range().end();
+    let mut reader = open_era1(path)?;
+    while let Some(block) = reader.next_block()? {
+        if block.header.number == last {
+            if provider_factory.block_hash(last)? != Some(block.header.hash_slow()) {
+                bail!(
+                    "block {last} of {} does not match the stored chain at tip {tip}",
+                    path.display()
+                )
+            }
+            break
+        }
+    }
+    Ok(())
+}
+
+/// Ensures that the short accumulator root of a canonically named era1 file,
+/// `<network>-<epoch>-<short root>.era1`, matches the accumulator of its contents.
+fn ensure_file_name_matches(path: &Path, summary: &Era1Summary) -> eyre::Result<()> {
+    let Some(short_root) = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.rsplit('-').next())
+        .filter(|short_root| short_root.len() == 8)
+    else {
+        return Ok(())
+    };
+
+    if short_root != hex::encode(&summary.accumulator_root[..4]) {
+        bail!(
+            "accumulator root {} of {} does not match its file name",
+            summary.accumulator_root,
+            path.display()
+        )
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_era::{Era1Block, Era1Body, Era1Writer};
+    use reth_node_core::args::utils::SUPPORTED_CHAINS;
+    use reth_primitives::{proofs::calculate_receipt_root, ReceiptWithBloom, B256, U256};
+    use reth_provider::{test_utils::create_test_provider_factory, StageCheckpointReader};
+    use reth_testing_utils::generators::{self, random_block_range, random_receipt};
+
+    #[test]
+    fn parse_import_era_command_chain_args() {
+        for chain in SUPPORTED_CHAINS {
+            let args: ImportEraCommand = ImportEraCommand::parse_from([
+                "reth",
+                "--chain",
+                chain,
+                "--accumulators",
+                "accumulators.txt",
+                ".",
+            ]);
+            assert_eq!(
+                Ok(args.env.chain.chain),
+                chain.parse::<reth_chainspec::Chain>(),
+                "failed to parse chain {chain}"
+            );
+        }
+    }
+
+    #[test]
+    fn import_era1() {
+        let factory = create_test_provider_factory();
+        reth_db_common::init::init_genesis(factory.clone()).unwrap();
+        let genesis = factory.sealed_header(0).unwrap().unwrap();
+
+        let mut rng = generators::rng();
+        let mut parent_hash = genesis.hash();
+        let mut total_difficulty = genesis.difficulty;
+        let blocks = random_block_range(&mut rng, 1..=20, parent_hash, 0..3)
+            .into_iter()
+            .map(|block| {
+                let receipts = block
+                    .body
+                    .iter()
+                    .map(|tx| ReceiptWithBloom::from(random_receipt(&mut rng, tx, Some(1))))
+                    .collect::<Vec<_>>();
+
+                let mut header = block.header.unseal();
+                header.parent_hash = parent_hash;
+                header.difficulty = U256::from(1_000);
+                header.receipts_root = calculate_receipt_root(&receipts);
+                parent_hash = header.hash_slow();
+                total_difficulty += header.difficulty;
+
+                Era1Block {
+                    header,
+                    body: Era1Body { transactions: block.body, ommers: block.ommers },
+                    receipts,
+                    total_difficulty,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        for block in &blocks {
+            writer.append_block(block).unwrap();
+        }
+        let (file, summary) = writer.finish().unwrap();
+        let path = dir.path().join("test.era1");
+        std::fs::write(&path, file).unwrap();
+
+        // Files of unknown epochs or with a non-canonical accumulator are rejected.
+        for accumulators in [EpochAccumulators::default(), EpochAccumulators::new(vec![B256::ZERO])]
+        {
+            assert!(import_era1_file(&factory, &accumulators, &path).is_err());
+        }
+        assert_eq!(factory.last_block_number().unwrap(), 0);
+
+        let accumulators = EpochAccumulators::new(vec![summary.accumulator_root]);
+        assert_eq!(import_era1_file(&factory, &accumulators, &path).unwrap(), 20);
+        // Importing the same file again is a no-op.
+        assert_eq!(import_era1_file(&factory, &accumulators, &path).unwrap(), 0);
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.last_block_number().unwrap(), 20);
+        assert_eq!(
+            provider.get_stage_checkpoint(StageId::Bodies).unwrap(),
+            Some(StageCheckpoint::new(20))
+        );
+        for block in &blocks {
+            let number = block.header.number;
+            assert_eq!(provider.block_hash(number).unwrap(), Some(block.header.hash_slow()));
+            assert_eq!(provider.header_td_by_number(number).unwrap(), Some(block.total_difficulty));
+            assert_eq!(
+                reth_provider::TransactionsProvider::transactions_by_block(
+                    &provider,
+                    number.into()
+                )
+                .unwrap(),
+                Some(block.body.transactions.clone())
+            );
+        }
+    }
+}
@@ -12,13 +12,16 @@ pub mod common;
 pub mod config_cmd;
 pub mod db;
 pub mod dump_genesis;
+pub mod export_era;
 pub mod import;
+pub mod import_era;
 pub mod init_cmd;
 pub mod init_state;
 pub mod node;
 pub mod p2p;
 pub mod prune;
 pub mod recover;
+pub mod snapshot;
 pub mod stage;
 #[cfg(feature = "dev")]
 pub mod test_vectors;
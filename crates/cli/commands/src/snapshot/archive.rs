@@ -0,0 +1,386 @@
+//! Snapshot archive format.
+//!
+//! A snapshot is a directory with a [`SnapshotManifest`] and a byte stream split into chunk files
+//! of a fixed maximum size. The stream is a sequence of records:
+//!
+//! ```text
+//! table := 0x01 | name_len: u8 | name | (key_len: u32 | key | value_len: u32 | value)* | u32::MAX
+//! file  := 0x02 | name_len: u8 | name | len: u64 | data
+//! ```
+//!
+//! All integers are little-endian.
+
+use reth_fs_util::FsPathError;
+use reth_primitives::{BlockNumber, StaticFileSegment, TxNumber, B256};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+
+/// Name of the manifest file of a snapshot.
+pub(crate) const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Version of the snapshot format.
+pub(crate) const SNAPSHOT_VERSION: u32 = 1;
+
+const TABLE_RECORD: u8 = 0x01;
+const FILE_RECORD: u8 = 0x02;
+const END_OF_TABLE: u32 = u32::MAX;
+
+/// Describes the contents of a snapshot.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SnapshotManifest {
+    /// Version of the snapshot format.
+    pub(crate) version: u32,
+    /// Genesis hash of the chain of the snapshot.
+    pub(crate) genesis_hash: B256,
+    /// Block of the `Finish` stage checkpoint.
+    pub(crate) block_number: BlockNumber,
+    /// Hash of [`Self::block_number`].
+    pub(crate) block_hash: B256,
+    /// Whether receipts are pruned, which means they are not stored in static files.
+    pub(crate) has_receipt_pruning: bool,
+    /// Block number of every stage checkpoint.
+    pub(crate) stage_checkpoints: BTreeMap<String, BlockNumber>,
+    /// Highest block and transaction of every static file segment.
+    pub(crate) static_files: BTreeMap<StaticFileSegment, StaticFileManifest>,
+    /// Number of entries of every database table.
+    pub(crate) tables: BTreeMap<String, u64>,
+    /// Chunk files, in stream order.
+    pub(crate) chunks: Vec<ChunkManifest>,
+}
+
+/// Highest block and transaction of a static file segment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct StaticFileManifest {
+    pub(crate) highest_block: Option<BlockNumber>,
+    pub(crate) highest_tx: Option<TxNumber>,
+}
+
+/// A chunk file of a snapshot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ChunkManifest {
+    /// File name of the chunk.
+    pub(crate) name: String,
+    /// Size of the chunk in bytes.
+    pub(crate) size: u64,
+    /// SHA-256 checksum of the chunk.
+    pub(crate) sha256: B256,
+}
+
+/// A chunk that is being written.
+#[derive(Debug)]
+struct OpenChunk {
+    writer: BufWriter<File>,
+    hasher: Sha256,
+    name: String,
+    size: u64,
+}
+
+/// Splits a byte stream into chunk files of at most `chunk_size` bytes.
+#[derive(Debug)]
+pub(crate) struct ChunkWriter {
+    dir: PathBuf,
+    chunk_size: u64,
+    current: Option<OpenChunk>,
+    chunks: Vec<ChunkManifest>,
+}
+
+impl ChunkWriter {
+    pub(crate) fn new(dir: impl Into<PathBuf>, chunk_size: u64) -> Self {
+        Self { dir: dir.into(), chunk_size, current: None, chunks: Vec::new() }
+    }
+
+    /// Closes the last chunk and returns the manifests of all chunks.
+    pub(crate) fn finish(mut self) -> io::Result<Vec<ChunkManifest>> {
+        self.close_chunk()?;
+        Ok(self.chunks)
+    }
+
+    fn close_chunk(&mut self) -> io::Result<()> {
+        if let Some(OpenChunk { mut writer, hasher, name, size }) = self.current.take() {
+            writer.flush()?;
+            self.chunks.push(ChunkManifest {
+                name,
+                size,
+                sha256: B256::from_slice(&hasher.finalize()),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.current.as_ref().is_some_and(|chunk| chunk.size == self.chunk_size) {
+            self.close_chunk()?;
+        }
+        if self.current.is_none() {
+            let name = format!("chunk-{:05}", self.chunks.len());
+            let file = reth_fs_util::create_file(self.dir.join(&name)).map_err(io::Error::other)?;
+            self.current = Some(OpenChunk {
+                writer: BufWriter::new(file),
+                hasher: Sha256::new(),
+                name,
+                size: 0,
+            });
+        }
+
+        let chunk = self.current.as_mut().expect("chunk is open");
+        let len = buf.len().min((self.chunk_size - chunk.size) as usize);
+        chunk.writer.write_all(&buf[..len])?;
+        chunk.hasher.update(&buf[..len]);
+        chunk.size += len as u64;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.current.as_mut().map_or(Ok(()), |chunk| chunk.writer.flush())
+    }
+}
+
+/// Reads the chunk files of a snapshot as a single byte stream.
+#[derive(Debug)]
+pub(crate) struct ChunkReader {
+    dir: PathBuf,
+    chunks: VecDeque<ChunkManifest>,
+    current: Option<BufReader<File>>,
+}
+
+impl ChunkReader {
+    pub(crate) fn new(dir: impl Into<PathBuf>, chunks: Vec<ChunkManifest>) -> Self {
+        Self { dir: dir.into(), chunks: chunks.into(), current: None }
+    }
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if let Some(reader) = self.current.as_mut() {
+                let read = reader.read(buf)?;
+                if read > 0 || buf.is_empty() {
+                    return Ok(read)
+                }
+                self.current = None;
+            }
+
+            let Some(chunk) = self.chunks.pop_front() else { return Ok(0) };
+            self.current = Some(BufReader::new(open(&self.dir.join(chunk.name))?));
+        }
+    }
+}
+
+/// Verifies the sizes and checksums of the chunk files of a snapshot.
+pub(crate) fn verify_chunks(dir: &Path, chunks: &[ChunkManifest]) -> eyre::Result<()> {
+    for chunk in chunks {
+        let path = dir.join(&chunk.name);
+        let mut file = open(&path)?;
+        let mut hasher = Sha256::new();
+        let size = io::copy(&mut file, &mut hasher)?;
+
+        eyre::ensure!(
+            size == chunk.size,
+            "size mismatch for {}: {size} != {}",
+            path.display(),
+            chunk.size
+        );
+        let sha256 = B256::from_slice(&hasher.finalize());
+        eyre::ensure!(
+            sha256 == chunk.sha256,
+            "checksum mismatch for {}: {sha256} != {}",
+            path.display(),
+            chunk.sha256
+        );
+    }
+    Ok(())
+}
+
+fn open(path: &Path) -> io::Result<File> {
+    File::open(path).map_err(|err| io::Error::other(FsPathError::open(err, path)))
+}
+
+/// Writes records to a snapshot stream.
+#[derive(Debug)]
+pub(crate) struct ArchiveWriter<W> {
+    writer: W,
+}
+
+impl<W: Write> ArchiveWriter<W> {
+    pub(crate) const fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Starts a table record. Entries are written with [`Self::write_entry`] until
+    /// [`Self::end_table`] is called.
+    pub(crate) fn begin_table(&mut self, name: &str) -> io::Result<()> {
+        self.writer.write_all(&[TABLE_RECORD])?;
+        self.write_name(name)
+    }
+
+    pub(crate) fn write_entry(&mut self, key: &[u8], value: &[u8]) -> io::Result<()> {
+        self.write_bytes(key)?;
+        self.write_bytes(value)
+    }
+
+    pub(crate) fn end_table(&mut self) -> io::Result<()> {
+        self.writer.write_all(&END_OF_TABLE.to_le_bytes())
+    }
+
+    /// Writes a file record with `len` bytes read from `reader`.
+    pub(crate) fn write_file(&mut self, name: &str, len: u64, reader: impl Read) -> io::Result<()> {
+        self.writer.write_all(&[FILE_RECORD])?;
+        self.write_name(name)?;
+        self.writer.write_all(&len.to_le_bytes())?;
+        let copied = io::copy(&mut reader.take(len), &mut self.writer)?;
+        if copied != len {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("file {name} is shorter than {len} bytes"),
+            ))
+        }
+        Ok(())
+    }
+
+    pub(crate) fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write_name(&mut self, name: &str) -> io::Result<()> {
+        let len = u8::try_from(name.len()).map_err(io::Error::other)?;
+        self.writer.write_all(&[len])?;
+        self.writer.write_all(name.as_bytes())
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let len = u32::try_from(bytes.len())
+            .ok()
+            .filter(|len| *len != END_OF_TABLE)
+            .ok_or_else(|| io::Error::other("entry too large"))?;
+        self.writer.write_all(&len.to_le_bytes())?;
+        self.writer.write_all(bytes)
+    }
+}
+
+/// Header of a snapshot record.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Record {
+    /// A database table, whose entries are read with [`ArchiveReader::next_entry`].
+    Table(String),
+    /// A static file, whose data is read with [`ArchiveReader::copy_file`].
+    File { name: String, len: u64 },
+}
+
+/// Reads records from a snapshot stream.
+#[derive(Debug)]
+pub(crate) struct ArchiveReader<R> {
+    reader: R,
+}
+
+impl<R: Read> ArchiveReader<R> {
+    pub(crate) const fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Reads the next record header, returning [`None`] at the end of the stream.
+    pub(crate) fn next_record(&mut self) -> io::Result<Option<Record>> {
+        let mut tag = [0u8];
+        if self.reader.read(&mut tag)? == 0 {
+            return Ok(None)
+        }
+
+        let name = self.read_name()?;
+        match tag[0] {
+            TABLE_RECORD => Ok(Some(Record::Table(name))),
+            FILE_RECORD => {
+                let mut len = [0u8; 8];
+                self.reader.read_exact(&mut len)?;
+                Ok(Some(Record::File { name, len: u64::from_le_bytes(len) }))
+            }
+            tag => Err(io::Error::other(format!("unknown snapshot record {tag:#04x}"))),
+        }
+    }
+
+    /// Reads the next entry of the current table record, returning [`None`] at its end.
+    pub(crate) fn next_entry(&mut self) -> io::Result<Option<(Vec<u8>, Vec<u8>)>> {
+        let Some(key) = self.read_bytes()? else { return Ok(None) };
+        let value = self.read_bytes()?.ok_or_else(|| io::Error::other("missing entry value"))?;
+        Ok(Some((key, value)))
+    }
+
+    /// Copies the data of the current file record to `writer`.
+    pub(crate) fn copy_file(&mut self, len: u64, writer: &mut impl Write) -> io::Result<()> {
+        let copied = io::copy(&mut (&mut self.reader).take(len), writer)?;
+        if copied != len {
+            return Err(io::ErrorKind::UnexpectedEof.into())
+        }
+        Ok(())
+    }
+
+    fn read_name(&mut self) -> io::Result<String> {
+        let mut len = [0u8];
+        self.reader.read_exact(&mut len)?;
+        let mut name = vec![0u8; len[0] as usize];
+        self.reader.read_exact(&mut name)?;
+        String::from_utf8(name).map_err(io::Error::other)
+    }
+
+    fn read_bytes(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let mut len = [0u8; 4];
+        self.reader.read_exact(&mut len)?;
+        let len = u32::from_le_bytes(len);
+        if len == END_OF_TABLE {
+            return Ok(None)
+        }
+        let mut bytes = vec![0u8; len as usize];
+        self.reader.read_exact(&mut bytes)?;
+        Ok(Some(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archive_roundtrip_across_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut archive = ArchiveWriter::new(ChunkWriter::new(dir.path(), 7));
+        archive.begin_table("Headers").unwrap();
+        archive.write_entry(&[1, 2, 3], &[4; 20]).unwrap();
+        archive.write_entry(&[5], &[]).unwrap();
+        archive.end_table().unwrap();
+        archive.write_file("static_file_headers_0_499999", 3, &[7, 8, 9][..]).unwrap();
+        let chunks = archive.into_inner().unwrap().finish().unwrap();
+
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|chunk| chunk.size <= 7));
+        verify_chunks(dir.path(), &chunks).unwrap();
+
+        let mut archive = ArchiveReader::new(ChunkReader::new(dir.path(), chunks.clone()));
+        assert_eq!(archive.next_record().unwrap(), Some(Record::Table("Headers".to_string())));
+        assert_eq!(archive.next_entry().unwrap(), Some((vec![1, 2, 3], vec![4; 20])));
+        assert_eq!(archive.next_entry().unwrap(), Some((vec![5], vec![])));
+        assert_eq!(archive.next_entry().unwrap(), None);
+        assert_eq!(
+            archive.next_record().unwrap(),
+            Some(Record::File { name: "static_file_headers_0_499999".to_string(), len: 3 })
+        );
+        let mut data = Vec::new();
+        archive.copy_file(3, &mut data).unwrap();
+        assert_eq!(data, vec![7, 8, 9]);
+        assert_eq!(archive.next_record().unwrap(), None);
+
+        // Corrupt a chunk.
+        let path = dir.path().join(&chunks[1].name);
+        let mut data = std::fs::read(&path).unwrap();
+        data[0] ^= 1;
+        std::fs::write(&path, data).unwrap();
+        assert!(verify_chunks(dir.path(), &chunks).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+use super::archive::{
+    ArchiveWriter, ChunkWriter, SnapshotManifest, StaticFileManifest, MANIFEST_FILE_NAME,
+    SNAPSHOT_VERSION,
+};
+use crate::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::Parser;
+use eyre::OptionExt;
+use reth_db::{lockfile::StorageLock, tables, RawTable, TableViewer, Tables};
+use reth_db_api::{cursor::DbCursorRO, database::Database, table::Table, transaction::DbTx};
+use reth_fs_util::FsPathError;
+use reth_primitives::{BlockNumber, StaticFileSegment};
+use reth_provider::{
+    providers::StaticFileProvider, BlockHashReader, ProviderFactory, StageCheckpointReader,
+};
+use reth_stages::StageId;
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+use strum::IntoEnumIterator;
+use tracing::info;
+
+/// Default maximum size of a snapshot chunk file, 1 GiB.
+const DEFAULT_CHUNK_SIZE: u64 = 1 << 30;
+
+/// Name of the lock file of the static files directory, which is not part of a snapshot.
+const LOCK_FILE_NAME: &str = "lock";
+
+/// `reth snapshot export` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// Maximum size of a chunk file in bytes.
+    #[arg(long, value_name = "BYTES", default_value_t = DEFAULT_CHUNK_SIZE)]
+    chunk_size: u64,
+
+    /// The block to export the snapshot at.
+    ///
+    /// Snapshots can only be exported at the `Finish` stage checkpoint of the database, so this
+    /// only ensures that the database is at the expected block. Unwind the database first to
+    /// export an earlier block.
+    #[arg(long, value_name = "BLOCK_NUMBER")]
+    block: Option<BlockNumber>,
+
+    /// The directory to write the snapshot to. Must not exist or be empty.
+    #[arg(value_name = "SNAPSHOT_DIR")]
+    path: PathBuf,
+}
+
+impl Command {
+    /// Execute `snapshot export` command
+    pub fn execute(self) -> eyre::Result<()> {
+        eyre::ensure!(self.chunk_size > 0, "chunk size must be positive");
+        eyre::ensure!(
+            reth_db::is_database_empty(&self.path),
+            "snapshot directory {} is not empty",
+            self.path.display()
+        );
+
+        let Environment { config, provider_factory, data_dir } = self.env.init(AccessRights::RO)?;
+        let has_receipt_pruning = config.prune.as_ref().is_some_and(|a| a.has_receipts_pruning());
+
+        // Holding the storage locks refuses to export the datadir of a running node, and keeps a
+        // node from starting on it until the copy is done.
+        let _locks = [data_dir.db(), data_dir.static_files()]
+            .map(|path| {
+                StorageLock::try_acquire(&path).map_err(|err| {
+                    eyre::eyre!(
+                        "can't export {} while a node is running on it: {err}",
+                        path.display()
+                    )
+                })
+            })
+            .into_iter()
+            .collect::<eyre::Result<Vec<_>>>()?;
+
+        reth_fs_util::create_dir_all(&self.path)?;
+        let manifest = export_snapshot(
+            &provider_factory,
+            &data_dir.static_files(),
+            has_receipt_pruning,
+            self.block,
+            &self.path,
+            self.chunk_size,
+        )?;
+
+        info!(target: "reth::cli",
+            block_number = manifest.block_number,
+            block_hash = %manifest.block_hash,
+            chunks = manifest.chunks.len(),
+            path = %self.path.display(),
+            "Snapshot exported"
+        );
+
+        Ok(())
+    }
+}
+
+/// Exports the database and static files to a snapshot in `dir`.
+///
+/// All tables are read within a single read transaction, so the snapshot is a consistent view of
+/// the database at the `Finish` stage checkpoint of that transaction. If `block` is set, it must
+/// be that checkpoint.
+///
+/// The static files must not be written to while they are copied, which the caller ensures by
+/// holding the storage locks of the datadir.
+pub(crate) fn export_snapshot<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    static_files_dir: &Path,
+    has_receipt_pruning: bool,
+    block: Option<BlockNumber>,
+    dir: &Path,
+    chunk_size: u64,
+) -> eyre::Result<SnapshotManifest> {
+    let provider = provider_factory.provider()?.disable_long_read_transaction_safety();
+    let tx = provider.tx_ref();
+
+    let genesis_hash = provider.block_hash(0)?.ok_or_eyre("missing genesis block")?;
+    let block_number =
+        provider.get_stage_checkpoint(StageId::Finish)?.unwrap_or_default().block_number;
+    if let Some(block) = block {
+        eyre::ensure!(
+            block == block_number,
+            "can't export block {block}, snapshots can only be exported at the tip of the database, block {block_number}"
+        );
+    }
+    let block_hash = provider.block_hash(block_number)?.ok_or_eyre("missing tip block hash")?;
+    let stage_checkpoints = tx
+        .cursor_read::<tables::StageCheckpoints>()?
+        .walk(None)?
+        .map(|entry| entry.map(|(stage, checkpoint)| (stage, checkpoint.block_number)))
+        .collect::<Result<BTreeMap<_, _>, _>>()?;
+
+    // Static files are indexed only after the read transaction is open.
+    let static_file_provider = StaticFileProvider::read_only(static_files_dir)?;
+    let static_files = StaticFileSegment::iter()
+        .map(|segment| {
+            let manifest = StaticFileManifest {
+                highest_block: static_file_provider.get_highest_static_file_block(segment),
+                highest_tx: static_file_provider.get_highest_static_file_tx(segment),
+            };
+            (segment, manifest)
+        })
+        .collect();
+
+    info!(target: "reth::cli", block_number, %block_hash, "Exporting snapshot");
+
+    let archive = RefCell::new(ArchiveWriter::new(ChunkWriter::new(dir, chunk_size)));
+    let mut tables = BTreeMap::new();
+    for table in Tables::ALL {
+        let entries = table.view(&ExportTableViewer { tx, archive: &archive })?;
+        info!(target: "reth::cli", table = table.name(), entries, "Exported table");
+        tables.insert(table.name().to_string(), entries);
+    }
+
+    let mut archive = archive.into_inner();
+    for path in static_file_paths(static_files_dir)? {
+        let name = path.file_name().and_then(|name| name.to_str()).ok_or_eyre("invalid name")?;
+        let file = File::open(&path).map_err(|err| FsPathError::open(err, &path))?;
+        archive.write_file(name, file.metadata()?.len(), file)?;
+    }
+    let chunks = archive.into_inner()?.finish()?;
+
+    let manifest = SnapshotManifest {
+        version: SNAPSHOT_VERSION,
+        genesis_hash,
+        block_number,
+        block_hash,
+        has_receipt_pruning,
+        stage_checkpoints,
+        static_files,
+        tables,
+        chunks,
+    };
+    reth_fs_util::write_json_file(&dir.join(MANIFEST_FILE_NAME), &manifest)?;
+
+    Ok(manifest)
+}
+
+/// Returns the files of the static files directory, except its lock file, sorted by name.
+fn static_file_paths(dir: &Path) -> eyre::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for entry in reth_fs_util::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.file_name().is_some_and(|name| name != LOCK_FILE_NAME) {
+            paths.push(path);
+        }
+    }
+
+    paths.sort();
+    Ok(paths)
+}
+
+/// Writes the raw entries of a table to the snapshot archive.
+struct ExportTableViewer<'a, TX, W> {
+    tx: &'a TX,
+    archive: &'a RefCell<ArchiveWriter<W>>,
+}
+
+impl<TX: DbTx, W: Write> TableViewer<u64> for ExportTableViewer<'_, TX, W> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<u64, Self::Error> {
+        let mut archive = self.archive.borrow_mut();
+        archive.begin_table(T::NAME)?;
+
+        let mut entries = 0;
+        for entry in self.tx.cursor_read::<RawTable<T>>()?.walk(None)? {
+            let (key, value) = entry?;
+            archive.write_entry(key.raw_key(), value.raw_value())?;
+            entries += 1;
+        }
+
+        archive.end_table()?;
+        Ok(entries)
+    }
+}
@@ -0,0 +1,341 @@
+use super::archive::{
+    verify_chunks, ArchiveReader, ChunkReader, Record, SnapshotManifest, MANIFEST_FILE_NAME,
+    SNAPSHOT_VERSION,
+};
+use crate::common::EnvironmentArgs;
+use clap::Parser;
+use eyre::{bail, ensure};
+use reth_chainspec::ChainSpec;
+use reth_db::{
+    create_db, mdbx::DatabaseArguments, tables, DatabaseEnv, RawDupSort, RawKey, RawTable,
+    RawValue, TableViewer, Tables,
+};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRW},
+    database::Database,
+    table::{DupSort, Table},
+    transaction::{DbTx, DbTxMut},
+};
+use reth_node_core::version::default_client_version;
+use reth_provider::{
+    providers::StaticFileProvider, BlockHashReader, ProviderFactory, StaticFileProviderFactory,
+};
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    ffi::OsStr,
+    io::{BufWriter, Read},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use strum::IntoEnumIterator;
+use tracing::info;
+
+/// Number of entries written to a table per database transaction.
+const ENTRIES_PER_TRANSACTION: u64 = 1_000_000;
+
+/// `reth snapshot import` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The directory of the snapshot to import.
+    #[arg(value_name = "SNAPSHOT_DIR")]
+    path: PathBuf,
+}
+
+impl Command {
+    /// Execute `snapshot import` command
+    pub fn execute(self) -> eyre::Result<()> {
+        let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain);
+        let manifest = import_snapshot(
+            &self.path,
+            self.env.chain.clone(),
+            &data_dir.db(),
+            self.env.db.database_args(),
+            &data_dir.static_files(),
+        )?;
+
+        info!(target: "reth::cli",
+            block_number = manifest.block_number,
+            block_hash = %manifest.block_hash,
+            datadir = %data_dir.data_dir().display(),
+            "Snapshot imported"
+        );
+
+        Ok(())
+    }
+}
+
+/// Verifies the snapshot in `dir` and installs it into empty database and static files
+/// directories.
+///
+/// Chunk checksums are verified before anything is written. Once installed, the database and
+/// static files are checked against the manifest: table sizes and stage checkpoints must match, and
+/// static files must be consistent with the database without requiring an unwind.
+pub(crate) fn import_snapshot(
+    dir: &Path,
+    chain_spec: Arc<ChainSpec>,
+    db_path: &Path,
+    db_args: DatabaseArguments,
+    static_files_dir: &Path,
+) -> eyre::Result<SnapshotManifest> {
+    let manifest: SnapshotManifest = reth_fs_util::read_json_file(&dir.join(MANIFEST_FILE_NAME))?;
+    ensure!(
+        manifest.version == SNAPSHOT_VERSION,
+        "unsupported snapshot version {}, expected {SNAPSHOT_VERSION}",
+        manifest.version
+    );
+    ensure!(
+        manifest.genesis_hash == chain_spec.genesis_hash(),
+        "snapshot genesis hash {} does not match chain genesis hash {}",
+        manifest.genesis_hash,
+        chain_spec.genesis_hash()
+    );
+    ensure!(reth_db::is_database_empty(db_path), "database {} is not empty", db_path.display());
+    ensure!(
+        reth_db::is_database_empty(static_files_dir),
+        "static files directory {} is not empty",
+        static_files_dir.display()
+    );
+
+    info!(target: "reth::cli", chunks = manifest.chunks.len(), "Verifying snapshot checksums");
+    verify_chunks(dir, &manifest.chunks)?;
+
+    info!(target: "reth::cli", block_number = manifest.block_number, "Installing snapshot");
+    reth_fs_util::create_dir_all(db_path)?;
+    reth_fs_util::create_dir_all(static_files_dir)?;
+    let db = create_db(db_path, db_args)?;
+    db.create_tables()?;
+
+    let archive = RefCell::new(ArchiveReader::new(ChunkReader::new(dir, manifest.chunks.clone())));
+    let mut tables = BTreeMap::new();
+    loop {
+        let Some(record) = archive.borrow_mut().next_record()? else { break };
+        match record {
+            Record::Table(name) => {
+                let table = name.parse::<Tables>().map_err(|err| eyre::eyre!(err))?;
+                let entries = table.view(&ImportTableViewer { db: &db, archive: &archive })?;
+                info!(target: "reth::cli", table = table.name(), entries, "Imported table");
+                tables.insert(name, entries);
+            }
+            Record::File { name, len } => {
+                ensure!(
+                    Path::new(&name).file_name() == Some(OsStr::new(&name)),
+                    "invalid static file name {name}"
+                );
+                let file = reth_fs_util::create_file(static_files_dir.join(&name))?;
+                let mut writer = BufWriter::new(file);
+                archive.borrow_mut().copy_file(len, &mut writer)?;
+                writer.into_inner().map_err(|err| err.into_error())?.sync_all()?;
+            }
+        }
+    }
+    ensure!(tables == manifest.tables, "imported tables do not match the snapshot manifest");
+
+    db.record_client_version(default_client_version())?;
+    validate_snapshot(&manifest, db, chain_spec, static_files_dir)?;
+
+    Ok(manifest)
+}
+
+/// Validates an installed snapshot against its manifest.
+fn validate_snapshot(
+    manifest: &SnapshotManifest,
+    db: DatabaseEnv,
+    chain_spec: Arc<ChainSpec>,
+    static_files_dir: &Path,
+) -> eyre::Result<()> {
+    let factory =
+        ProviderFactory::new(db, chain_spec, StaticFileProvider::read_write(static_files_dir)?);
+    let provider = factory.provider()?;
+
+    let stage_checkpoints = provider
+        .tx_ref()
+        .cursor_read::<tables::StageCheckpoints>()?
+        .walk(None)?
+        .map(|entry| entry.map(|(stage, checkpoint)| (stage, checkpoint.block_number)))
+        .collect::<Result<BTreeMap<_, _>, _>>()?;
+    ensure!(
+        stage_checkpoints == manifest.stage_checkpoints,
+        "stage checkpoints do not match the snapshot manifest"
+    );
+    ensure!(
+        provider.block_hash(manifest.block_number)? == Some(manifest.block_hash),
+        "hash of block {} does not match the snapshot manifest",
+        manifest.block_number
+    );
+
+    // Rows appended to static files after the snapshot read transaction are pruned here. Missing
+    // rows would require an unwind, which means the snapshot is incomplete.
+    let static_file_provider = factory.static_file_provider();
+    if let Some(target) =
+        static_file_provider.check_consistency(&provider, manifest.has_receipt_pruning)?
+    {
+        bail!("static files are inconsistent with the database, unwind required: {target}")
+    }
+    for segment in reth_primitives::StaticFileSegment::iter() {
+        let expected = manifest.static_files.get(&segment).copied().unwrap_or_default();
+        let highest_block = static_file_provider.get_highest_static_file_block(segment);
+        let highest_tx = static_file_provider.get_highest_static_file_tx(segment);
+        ensure!(
+            highest_block <= expected.highest_block && highest_tx <= expected.highest_tx,
+            "{segment} static files are ahead of the snapshot manifest"
+        );
+    }
+
+    Ok(())
+}
+
+/// Writes the raw entries of a table from the snapshot archive.
+struct ImportTableViewer<'a, R> {
+    db: &'a DatabaseEnv,
+    archive: &'a RefCell<ArchiveReader<R>>,
+}
+
+impl<R: Read> ImportTableViewer<'_, R> {
+    /// Writes entries in batches of [`ENTRIES_PER_TRANSACTION`], one database transaction per
+    /// batch, returning the number of entries.
+    fn import(
+        &self,
+        write_batch: impl Fn(
+            &<DatabaseEnv as Database>::TXMut,
+            &mut ArchiveReader<R>,
+        ) -> eyre::Result<(u64, bool)>,
+    ) -> eyre::Result<u64> {
+        let mut archive = self.archive.borrow_mut();
+        let mut entries = 0;
+        loop {
+            let tx = self.db.tx_mut()?;
+            let (written, done) = write_batch(&tx, &mut archive)?;
+            tx.commit()?;
+            entries += written;
+            if done {
+                return Ok(entries)
+            }
+        }
+    }
+}
+
+/// Reads up to [`ENTRIES_PER_TRANSACTION`] entries of the current table and passes them to
+/// `append`. Returns the number of entries and whether the end of the table was reached.
+fn append_batch<R: Read>(
+    archive: &mut ArchiveReader<R>,
+    mut append: impl FnMut(Vec<u8>, Vec<u8>) -> eyre::Result<()>,
+) -> eyre::Result<(u64, bool)> {
+    let mut entries = 0;
+    while entries < ENTRIES_PER_TRANSACTION {
+        let Some((key, value)) = archive.next_entry()? else { return Ok((entries, true)) };
+        append(key, value)?;
+        entries += 1;
+    }
+    Ok((entries, false))
+}
+
+impl<R: Read> TableViewer<u64> for ImportTableViewer<'_, R> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<u64, Self::Error> {
+        self.import(|tx, archive| {
+            let mut cursor = tx.cursor_write::<RawTable<T>>()?;
+            append_batch(archive, |key, value| {
+                Ok(cursor.append(RawKey::from_vec(key), RawValue::from_vec(value))?)
+            })
+        })
+    }
+
+    fn view_dupsort<T: DupSort>(&self) -> Result<u64, Self::Error> {
+        self.import(|tx, archive| {
+            let mut cursor = tx.cursor_dup_write::<RawDupSort<T>>()?;
+            append_batch(archive, |key, value| {
+                Ok(cursor.append_dup(RawKey::from_vec(key), RawValue::from_vec(value))?)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::export::export_snapshot;
+    use reth_db_common::init::init_genesis;
+    use reth_primitives::StaticFileSegment;
+    use reth_provider::{
+        test_utils::create_test_provider_factory, ChainSpecProvider, HeaderProvider,
+    };
+
+    #[test]
+    fn snapshot_roundtrip() {
+        let factory = create_test_provider_factory();
+        init_genesis(factory.clone()).unwrap();
+
+        let snapshot_dir = tempfile::tempdir().unwrap();
+        assert!(export_snapshot(
+            &factory,
+            factory.static_file_provider().directory(),
+            false,
+            Some(1),
+            snapshot_dir.path(),
+            64 * 1024,
+        )
+        .is_err());
+
+        let exported = export_snapshot(
+            &factory,
+            factory.static_file_provider().directory(),
+            false,
+            Some(0),
+            snapshot_dir.path(),
+            64 * 1024,
+        )
+        .unwrap();
+        assert!(exported.chunks.len() > 1);
+        assert_eq!(exported.block_hash, factory.chain_spec().genesis_hash());
+
+        let datadir = tempfile::tempdir().unwrap();
+        let (db_path, static_files_dir) = (datadir.path().join("db"), datadir.path().join("sf"));
+        let imported = import_snapshot(
+            snapshot_dir.path(),
+            factory.chain_spec(),
+            &db_path,
+            DatabaseArguments::new(default_client_version()),
+            &static_files_dir,
+        )
+        .unwrap();
+        assert_eq!(imported.tables, exported.tables);
+
+        let db = reth_db::open_db_read_only(&db_path, DatabaseArguments::default()).unwrap();
+        let imported_factory = ProviderFactory::new(
+            db,
+            factory.chain_spec(),
+            StaticFileProvider::read_only(&static_files_dir).unwrap(),
+        );
+        assert_eq!(imported_factory.sealed_header(0).unwrap(), factory.sealed_header(0).unwrap());
+        assert_eq!(
+            imported_factory
+                .provider()
+                .unwrap()
+                .tx_ref()
+                .entries::<tables::PlainAccountState>()
+                .unwrap(),
+            factory.provider().unwrap().tx_ref().entries::<tables::PlainAccountState>().unwrap()
+        );
+        assert_eq!(
+            imported_factory
+                .static_file_provider()
+                .get_highest_static_file_block(StaticFileSegment::Headers),
+            Some(0)
+        );
+
+        // Importing into a non-empty datadir fails.
+        assert!(import_snapshot(
+            snapshot_dir.path(),
+            factory.chain_spec(),
+            &db_path,
+            DatabaseArguments::new(default_client_version()),
+            &static_files_dir,
+        )
+        .is_err());
+    }
+}
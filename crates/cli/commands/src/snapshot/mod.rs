@@ -0,0 +1,33 @@
+//! `reth snapshot` command.
+
+use clap::{Parser, Subcommand};
+
+mod archive;
+mod export;
+mod import;
+
+/// `reth snapshot` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth snapshot` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Exports the database and static files to a chunked, checksummed snapshot.
+    Export(export::Command),
+    /// Verifies a snapshot and installs it into an empty datadir.
+    Import(import::Command),
+}
+
+impl Command {
+    /// Execute `snapshot` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Export(command) => command.execute(),
+            Subcommands::Import(command) => command.execute(),
+        }
+    }
+}
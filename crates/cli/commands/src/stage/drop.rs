@@ -162,6 +162,19 @@ impl Command {
                 )?;
                 insert_genesis_history(&provider_rw, self.env.chain.genesis.alloc.iter())?;
             }
+            StageEnum::Logs => {
+                tx.clear::<tables::LogAddressIndex>()?;
+                tx.clear::<tables::LogTopicIndex>()?;
+                tx.delete::<tables::StageCheckpoints>(StageId::IndexLogs.to_string(), None)?;
+            }
+            StageEnum::AddressTransactions => {
+                tx.clear::<tables::AddressTransactions>()?;
+                tx.clear::<tables::TransactionAddresses>()?;
+                tx.delete::<tables::StageCheckpoints>(
+                    StageId::IndexAddressTransactions.to_string(),
+                    None,
+                )?;
+            }
             StageEnum::TxLookup => {
                 tx.clear::<tables::TransactionHashNumbers>()?;
                 tx.put::<tables::StageCheckpoints>(
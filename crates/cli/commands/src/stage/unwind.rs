@@ -78,7 +78,7 @@ impl Command {
             }
 
             // This will build an offline-only pipeline if the `offline` flag is enabled
-            let mut pipeline = self.build_pipeline(config, provider_factory)?;
+            let mut pipeline = build_unwind_pipeline(config, provider_factory, self.offline)?;
 
             // Move all applicable data from database to static files.
             pipeline.move_to_static_files()?;
@@ -109,61 +109,64 @@ impl Command {
 
         Ok(())
     }
+}
 
-    fn build_pipeline<DB: Database + 'static>(
-        self,
-        config: Config,
-        provider_factory: ProviderFactory<Arc<DB>>,
-    ) -> Result<Pipeline<Arc<DB>>, eyre::Error> {
-        let consensus: Arc<dyn Consensus> =
-            Arc::new(EthBeaconConsensus::new(provider_factory.chain_spec()));
-        let stage_conf = &config.stages;
-        let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
-
-        let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
-
-        // Unwinding does not require a valid executor
-        let executor = NoopBlockExecutorProvider::default();
-
-        let builder = if self.offline {
-            Pipeline::builder().add_stages(
-                OfflineStages::new(executor, config.stages, PruneModes::default())
-                    .builder()
-                    .disable(reth_stages::StageId::SenderRecovery),
-            )
-        } else {
-            Pipeline::builder().with_tip_sender(tip_tx).add_stages(
-                DefaultStages::new(
-                    provider_factory.clone(),
-                    tip_rx,
-                    Arc::clone(&consensus),
-                    NoopHeaderDownloader::default(),
-                    NoopBodiesDownloader::default(),
-                    executor.clone(),
-                    stage_conf.clone(),
-                    prune_modes.clone(),
-                )
-                .set(ExecutionStage::new(
-                    executor,
-                    ExecutionStageThresholds {
-                        max_blocks: None,
-                        max_changes: None,
-                        max_cumulative_gas: None,
-                        max_duration: None,
-                    },
-                    stage_conf.execution_external_clean_threshold(),
-                    prune_modes,
-                    ExExManagerHandle::empty(),
-                )),
+/// Builds a pipeline that is only used for unwinding.
+///
+/// If `offline` is set, only the offline stages except sender recovery are unwound.
+pub(crate) fn build_unwind_pipeline<DB: Database + 'static>(
+    config: Config,
+    provider_factory: ProviderFactory<Arc<DB>>,
+    offline: bool,
+) -> Result<Pipeline<Arc<DB>>, eyre::Error> {
+    let consensus: Arc<dyn Consensus> =
+        Arc::new(EthBeaconConsensus::new(provider_factory.chain_spec()));
+    let stage_conf = &config.stages;
+    let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
+
+    let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
+
+    // Unwinding does not require a valid executor
+    let executor = NoopBlockExecutorProvider::default();
+
+    let builder = if offline {
+        Pipeline::builder().add_stages(
+            OfflineStages::new(executor, config.stages, PruneModes::default())
+                .builder()
+                .disable(reth_stages::StageId::SenderRecovery),
+        )
+    } else {
+        Pipeline::builder().with_tip_sender(tip_tx).add_stages(
+            DefaultStages::new(
+                provider_factory.clone(),
+                tip_rx,
+                Arc::clone(&consensus),
+                NoopHeaderDownloader::default(),
+                NoopBodiesDownloader::default(),
+                executor.clone(),
+                stage_conf.clone(),
+                prune_modes.clone(),
             )
-        };
-
-        let pipeline = builder.build(
-            provider_factory.clone(),
-            StaticFileProducer::new(provider_factory, PruneModes::default()),
-        );
-        Ok(pipeline)
-    }
+            .set(ExecutionStage::new(
+                executor,
+                ExecutionStageThresholds {
+                    max_blocks: None,
+                    max_changes: None,
+                    max_cumulative_gas: None,
+                    max_duration: None,
+                },
+                stage_conf.execution_external_clean_threshold(),
+                prune_modes,
+                ExExManagerHandle::empty(),
+            )),
+        )
+    };
+
+    let pipeline = builder.build(
+        provider_factory.clone(),
+        StaticFileProducer::new(provider_factory, PruneModes::default()),
+    );
+    Ok(pipeline)
 }
 
 /// `reth stage unwind` subcommand
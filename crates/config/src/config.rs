@@ -5,6 +5,7 @@ use reth_prune_types::PruneModes;
 use reth_stages_types::ExecutionStageThresholds;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{
+    collections::HashMap,
     ffi::OsStr,
     path::{Path, PathBuf},
     time::Duration,
@@ -26,6 +27,9 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for the execution extensions.
+    #[serde(skip_serializing_if = "ExExConfig::is_empty")]
+    pub exex: ExExConfig,
 }
 
 impl Config {
@@ -85,6 +89,10 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Logs stage configuration.
+    pub index_logs: IndexLogsConfig,
+    /// Index Address Transactions stage configuration.
+    pub index_address_transactions: IndexAddressTransactionsConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -338,6 +346,42 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Log index stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct IndexLogsConfig {
+    /// Whether to build and maintain the log index used by `eth_getLogs`.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexLogsConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 100_000 }
+    }
+}
+
+/// Address transactions index stage configuration.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct IndexAddressTransactionsConfig {
+    /// Whether to build and maintain the index of the transactions sent by or to each address.
+    pub enabled: bool,
+    /// Whether to also index the addresses called or created by internal calls. This requires
+    /// re-executing every indexed block, which only the pipeline does, so the node refuses to
+    /// start with it enabled.
+    pub internal_calls: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexAddressTransactionsConfig {
+    fn default() -> Self {
+        Self { enabled: false, internal_calls: false, commit_threshold: 100_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default)]
@@ -362,6 +406,65 @@ impl PruneConfig {
     }
 }
 
+/// Execution extensions configuration.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct ExExConfig {
+    /// Lag policies of the `ExEx`'s, keyed by their IDs.
+    ///
+    /// `ExEx`'s without a policy can lag behind the node indefinitely.
+    pub lag_policies: HashMap<String, ExExLagPolicy>,
+}
+
+impl ExExConfig {
+    /// Returns `true` if nothing is configured.
+    pub fn is_empty(&self) -> bool {
+        self.lag_policies.is_empty()
+    }
+}
+
+/// The maximum lag of an `ExEx` behind the node, and what to do when it's exceeded.
+///
+/// A lagging `ExEx` holds back pruning, because the node keeps the data it hasn't finished.
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct ExExLagPolicy {
+    /// The maximum number of blocks the finished height of the `ExEx` can be behind the node.
+    pub max_blocks: Option<u64>,
+    /// The maximum time the `ExEx` can be behind the node without finishing new blocks.
+    #[serde(
+        serialize_with = "humantime_serde::serialize",
+        deserialize_with = "deserialize_duration"
+    )]
+    pub max_duration: Option<Duration>,
+    /// The action taken when the `ExEx` exceeds any of the maximums.
+    pub action: ExExLagAction,
+}
+
+impl ExExLagPolicy {
+    /// Returns `true` if the lag is within the maximums of the policy.
+    pub fn is_within(&self, blocks: Option<u64>, duration: Option<Duration>) -> bool {
+        fn exceeds<T: PartialOrd>(lag: Option<T>, max: Option<T>) -> bool {
+            lag.zip(max).is_some_and(|(lag, max)| lag > max)
+        }
+
+        !exceeds(blocks, self.max_blocks) && !exceeds(duration, self.max_duration)
+    }
+}
+
+/// The action taken when an `ExEx` exceeds its [`ExExLagPolicy`].
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExExLagAction {
+    /// Log a warning and report the `ExEx` as lagging.
+    #[default]
+    Alert,
+    /// Shut the node down.
+    Shutdown,
+    /// Stop sending notifications to the `ExEx` and stop waiting for it before pruning.
+    Detach,
+}
+
 /// Helper type to support older versions of Duration deserialization.
 fn deserialize_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
 where
@@ -382,7 +485,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{Config, EXTENSION};
+    use super::{Config, ExExLagAction, ExExLagPolicy, EXTENSION};
     use reth_network_peers::TrustedPeer;
     use std::{str::FromStr, time::Duration};
 
@@ -434,6 +537,31 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_load_exex_lag_policies() {
+        let config = r#"
+[exex.lag_policies.indexer]
+max_blocks = 1000
+max_duration = "1h"
+action = "detach"
+"#;
+        let config: Config = toml::from_str(config).unwrap();
+        let policy = config.exex.lag_policies["indexer"];
+        assert_eq!(
+            policy,
+            ExExLagPolicy {
+                max_blocks: Some(1000),
+                max_duration: Some(Duration::from_secs(60 * 60)),
+                action: ExExLagAction::Detach,
+            }
+        );
+
+        assert!(policy.is_within(Some(1000), None));
+        assert!(!policy.is_within(Some(1001), Some(Duration::ZERO)));
+        assert!(!policy.is_within(None, Some(Duration::from_secs(60 * 60 + 1))));
+        assert!(ExExLagPolicy::default().is_within(Some(u64::MAX), Some(Duration::MAX)));
+    }
+
     // ensures config deserialization is backwards compatible
     #[test]
     fn test_backwards_compatibility() {
@@ -19,8 +19,8 @@ use reth_primitives::{
     B256,
 };
 use reth_provider::{
-    BlockIdReader, BlockReader, BlockSource, CanonChainTracker, ChainSpecProvider, ProviderError,
-    StageCheckpointReader,
+    BadBlockWriter, BlockIdReader, BlockReader, BlockSource, CanonChainTracker, ChainSpecProvider,
+    ProviderError, StageCheckpointReader,
 };
 use reth_rpc_types::engine::{
     CancunPayloadFields, ExecutionPayload, ForkchoiceState, PayloadStatus, PayloadStatusEnum,
@@ -175,6 +175,7 @@ where
         + BlockReader
         + BlockIdReader
         + CanonChainTracker
+        + BadBlockWriter
         + StageCheckpointReader,
     EngineT: EngineTypes,
 {
@@ -230,6 +231,7 @@ where
         + BlockReader
         + BlockIdReader
         + CanonChainTracker
+        + BadBlockWriter
         + StageCheckpointReader
         + ChainSpecProvider
         + 'static,
@@ -727,6 +729,17 @@ where
         }
     }
 
+    /// Records a block that failed validation, so that it can be inspected later.
+    ///
+    /// The block is only kept in the in-memory store of the provider, since the engine must not
+    /// wait for the database writer.
+    fn record_bad_block(&self, block: SealedBlock) {
+        let hash = block.hash();
+        if let Err(err) = self.blockchain.insert_bad_block(block) {
+            warn!(target: "consensus::engine", %err, invalid_hash=?hash, "Failed to record bad block");
+        }
+    }
+
     /// If validation fails, the response MUST contain the latest valid hash:
     ///
     ///   - The block hash of the ancestor of the invalid payload satisfying the following two
@@ -1661,7 +1674,9 @@ where
                         } else {
                             self.latest_valid_hash_for_invalid_payload(block.parent_hash)?
                         };
-                        // keep track of the invalid header
+                        // keep track of the invalid header and the full block, so it can be
+                        // inspected later
+                        self.record_bad_block(block.clone());
                         self.invalid_headers.insert(block.header);
                         PayloadStatus::new(
                             PayloadStatusEnum::Invalid { validation_error: error.to_string() },
@@ -1771,6 +1786,7 @@ where
                             let (block, err) = err.split();
                             warn!(target: "consensus::engine", invalid_number=?block.number, invalid_hash=?block.hash(), %err, "Marking block as invalid");
 
+                            self.record_bad_block(block.clone());
                             self.invalid_headers.insert(block.header);
                         }
                     }
@@ -1796,6 +1812,7 @@ where
         + BlockReader
         + BlockIdReader
         + CanonChainTracker
+        + BadBlockWriter
         + StageCheckpointReader
         + ChainSpecProvider
         + Unpin
@@ -2795,6 +2812,9 @@ mod tests {
             .with_latest_valid_hash(B256::ZERO);
             assert_eq!(result, expected_result);
 
+            // the invalid payload is recorded as a bad block
+            assert_eq!(env.bad_block_hashes(), vec![block2.hash()]);
+
             assert_matches!(engine_rx.try_recv(), Err(TryRecvError::Empty));
         }
     }
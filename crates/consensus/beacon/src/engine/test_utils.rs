@@ -23,7 +23,7 @@ use reth_payload_builder::test_utils::spawn_test_payload_service;
 use reth_primitives::{BlockNumber, B256};
 use reth_provider::{
     providers::BlockchainProvider, test_utils::create_test_provider_factory_with_chain_spec,
-    ExecutionOutcome, ProviderFactory,
+    BadBlockReader, ExecutionOutcome, ProviderFactory,
 };
 use reth_prune::Pruner;
 use reth_prune_types::PruneModes;
@@ -45,22 +45,38 @@ type TestBeaconConsensusEngine<Client> = BeaconConsensusEngine<
     EthEngineTypes,
 >;
 
-#[derive(Debug)]
 pub struct TestEnv<DB> {
     pub db: DB,
     // Keep the tip receiver around, so it's not dropped.
     #[allow(dead_code)]
     tip_rx: watch::Receiver<B256>,
     engine_handle: BeaconConsensusEngineHandle<EthEngineTypes>,
+    bad_blocks: Arc<dyn BadBlockReader>,
+}
+
+impl<DB: std::fmt::Debug> std::fmt::Debug for TestEnv<DB> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TestEnv")
+            .field("db", &self.db)
+            .field("engine_handle", &self.engine_handle)
+            .finish_non_exhaustive()
+    }
 }
 
 impl<DB> TestEnv<DB> {
-    const fn new(
+    fn new(
         db: DB,
         tip_rx: watch::Receiver<B256>,
         engine_handle: BeaconConsensusEngineHandle<EthEngineTypes>,
+        bad_blocks: Arc<dyn BadBlockReader>,
     ) -> Self {
-        Self { db, tip_rx, engine_handle }
+        Self { db, tip_rx, engine_handle, bad_blocks }
+    }
+
+    /// Returns the hashes of the bad blocks recorded by the engine, from the highest to the lowest
+    /// block number.
+    pub fn bad_block_hashes(&self) -> Vec<B256> {
+        self.bad_blocks.bad_blocks().unwrap().iter().map(|block| block.hash()).collect()
     }
 
     pub async fn send_new_payload<T: Into<ExecutionPayload>>(
@@ -416,7 +432,7 @@ where
         let (mut engine, handle) = BeaconConsensusEngine::new(
             client,
             pipeline,
-            blockchain_provider,
+            blockchain_provider.clone(),
             Box::<TokioTaskExecutor>::default(),
             Box::<NoopSyncStateUpdater>::default(),
             None,
@@ -431,7 +447,13 @@ where
             engine.sync.set_max_block(max_block)
         }
 
-        (engine, TestEnv::new(provider_factory.db_ref().clone(), tip_rx, handle))
+        let env = TestEnv::new(
+            provider_factory.db_ref().clone(),
+            tip_rx,
+            handle,
+            Arc::new(blockchain_provider),
+        );
+        (engine, env)
     }
 }
 
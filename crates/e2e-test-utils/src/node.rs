@@ -10,7 +10,7 @@ use reth::{
     payload::PayloadTypes,
     providers::{BlockReader, BlockReaderIdExt, CanonStateSubscriptions, StageCheckpointReader},
     rpc::{
-        api::eth::helpers::{EthApiSpec, EthTransactions, TraceExt},
+        api::eth::helpers::{EthApiSpec, EthState, EthTransactions, TraceExt},
         types::engine::PayloadStatusEnum,
     },
 };
@@ -91,7 +91,7 @@ where
     where
         <Node::Engine as EngineTypes>::ExecutionPayloadV3:
             From<<Node::Engine as PayloadTypes>::BuiltPayload> + PayloadEnvelopeExt,
-        AddOns::EthApi: EthApiSpec + EthTransactions + TraceExt,
+        AddOns::EthApi: EthApiSpec + EthState + EthTransactions + TraceExt,
     {
         let mut chain = Vec::with_capacity(length as usize);
         for i in 0..length {
@@ -3,7 +3,7 @@ use alloy_network::eip2718::Decodable2718;
 use reth::{
     builder::{rpc::RpcRegistry, FullNodeComponents},
     rpc::api::{
-        eth::helpers::{EthApiSpec, EthTransactions, TraceExt},
+        eth::helpers::{EthApiSpec, EthState, EthTransactions, TraceExt},
         DebugApiServer,
     },
 };
@@ -16,7 +16,7 @@ pub struct RpcTestContext<Node: FullNodeComponents, EthApi> {
 
 impl<Node: FullNodeComponents, EthApi> RpcTestContext<Node, EthApi>
 where
-    EthApi: EthApiSpec + EthTransactions + TraceExt,
+    EthApi: EthApiSpec + EthState + EthTransactions + TraceExt,
 {
     /// Injects a raw transaction into the node tx pool via RPC server
     pub async fn inject_tx(&self, raw_tx: Bytes) -> Result<B256, EthApi::Error> {
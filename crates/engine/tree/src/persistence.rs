@@ -4,7 +4,9 @@ use reth_chain_state::ExecutedBlock;
 use reth_db::Database;
 use reth_errors::ProviderError;
 use reth_primitives::{SealedBlock, B256};
-use reth_provider::{writer::UnifiedStorageWriter, ProviderFactory, StaticFileProviderFactory};
+use reth_provider::{
+    writer::UnifiedStorageWriter, BadBlockWriter, ProviderFactory, StaticFileProviderFactory,
+};
 use reth_prune::{Pruner, PrunerError, PrunerOutput};
 use std::sync::{
     mpsc::{Receiver, SendError, Sender},
@@ -95,6 +97,13 @@ where
                     // we ignore the error because the caller may or may not care about the result
                     let _ = sender.send(res);
                 }
+                PersistenceAction::SaveBadBlock(block) => {
+                    let hash = block.hash();
+                    let provider_rw = self.provider.provider_rw()?;
+                    provider_rw.insert_bad_block(block)?;
+                    provider_rw.commit()?;
+                    debug!(target: "tree::persistence", ?hash, "Saved bad block");
+                }
                 PersistenceAction::WriteTransactions(_block, _sender) => {
                     unimplemented!()
                     // let (block_num, td) =
@@ -148,6 +157,10 @@ pub enum PersistenceAction {
     /// Prune associated block data before the given block number, according to already-configured
     /// prune modes.
     PruneBefore(u64, oneshot::Sender<PrunerOutput>),
+
+    /// The given block failed validation, and should be persisted so that it can be inspected
+    /// later, also after a restart.
+    SaveBadBlock(SealedBlock),
 }
 
 /// A handle to the persistence service
@@ -236,20 +249,35 @@ impl PersistenceHandle {
     ) -> Result<(), SendError<PersistenceAction>> {
         self.send_action(PersistenceAction::PruneBefore(block_num, tx))
     }
+
+    /// Tells the persistence service to save a block that failed validation.
+    pub fn save_bad_block(&self, block: SealedBlock) -> Result<(), SendError<PersistenceAction>> {
+        self.send_action(PersistenceAction::SaveBadBlock(block))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use reth_chain_state::test_utils::TestBlockBuilder;
+    use reth_db::{test_utils::TempDatabase, DatabaseEnv};
     use reth_exex_types::FinishedExExHeight;
-    use reth_primitives::B256;
-    use reth_provider::{test_utils::create_test_provider_factory, ProviderFactory};
+    use reth_primitives::{Address, Bytes, Log, Receipt, Requests, B256};
+    use reth_provider::{
+        test_utils::create_test_provider_factory, BadBlockReader, ExecutionOutcome, LogIndexReader,
+        ProviderFactory, StageCheckpointWriter,
+    };
     use reth_prune::Pruner;
+    use reth_stages::{StageCheckpoint, StageId};
+    use std::sync::Arc;
 
     fn default_persistence_handle() -> PersistenceHandle {
-        let provider = create_test_provider_factory();
+        persistence_handle(create_test_provider_factory())
+    }
 
+    fn persistence_handle(
+        provider: ProviderFactory<Arc<TempDatabase<DatabaseEnv>>>,
+    ) -> PersistenceHandle {
         let (_finished_exex_height_tx, finished_exex_height_rx) =
             tokio::sync::watch::channel(FinishedExExHeight::NoExExs);
 
@@ -337,4 +365,75 @@ mod tests {
             assert_eq!(last_hash, actual_hash);
         }
     }
+
+    #[tokio::test]
+    async fn test_save_bad_block() {
+        reth_tracing::init_test_tracing();
+        let provider_factory = create_test_provider_factory();
+        let persistence_handle = persistence_handle(provider_factory.clone());
+
+        let block = TestBlockBuilder::default().generate_random_block(1, B256::random()).block;
+        persistence_handle.save_bad_block(block.clone()).unwrap();
+
+        // wait for the bad block to be persisted by an action queued after it
+        let (tx, rx) = oneshot::channel();
+        persistence_handle.save_blocks(vec![], tx).unwrap();
+        rx.await.unwrap();
+
+        let provider = provider_factory.provider().unwrap();
+        assert_eq!(provider.bad_blocks().unwrap(), vec![block]);
+    }
+
+    #[tokio::test]
+    async fn test_save_blocks_extends_log_index() {
+        reth_tracing::init_test_tracing();
+        let provider_factory = create_test_provider_factory();
+        let persistence_handle = persistence_handle(provider_factory.clone());
+        let address = Address::with_last_byte(1);
+
+        let mut test_block_builder = TestBlockBuilder::default();
+        let mut blocks = test_block_builder.get_executed_blocks(0..4).collect::<Vec<_>>();
+        // every transaction of the blocks after genesis emits a log
+        for block in &mut blocks[1..] {
+            let receipts: Vec<_> = block
+                .block
+                .body
+                .iter()
+                .map(|_| {
+                    Some(Receipt {
+                        success: true,
+                        logs: vec![Log::new_unchecked(address, vec![], Bytes::default())],
+                        ..Default::default()
+                    })
+                })
+                .collect();
+            block.execution_output = Arc::new(ExecutionOutcome::new(
+                Default::default(),
+                vec![receipts].into(),
+                block.block.number,
+                vec![Requests::default()],
+            ));
+        }
+        let with_logs = blocks[1..]
+            .iter()
+            .filter(|block| !block.block.body.is_empty())
+            .map(|block| block.block.number)
+            .collect::<Vec<_>>();
+
+        // the log index is maintained from genesis on
+        let (tx, rx) = oneshot::channel();
+        persistence_handle.save_blocks(blocks.drain(..1).collect(), tx).unwrap();
+        rx.await.unwrap().unwrap();
+        let provider = provider_factory.provider_rw().unwrap();
+        provider.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(0)).unwrap();
+        provider.commit().unwrap();
+
+        let (tx, rx) = oneshot::channel();
+        persistence_handle.save_blocks(blocks, tx).unwrap();
+        rx.await.unwrap().unwrap();
+
+        let provider = provider_factory.provider().unwrap();
+        assert_eq!(provider.log_index_range().unwrap(), Some(0..=3));
+        assert_eq!(provider.log_address_blocks(address, 0..=3).unwrap(), with_logs);
+    }
 }
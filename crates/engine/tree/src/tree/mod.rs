@@ -28,8 +28,8 @@ use reth_primitives::{
     SealedBlockWithSenders, SealedHeader, B256, U256,
 };
 use reth_provider::{
-    BlockReader, ExecutionOutcome, ProviderError, StateProviderBox, StateProviderFactory,
-    StateRootProvider,
+    BadBlockWriter, BlockReader, ExecutionOutcome, ProviderError, StateProviderBox,
+    StateProviderFactory, StateRootProvider,
 };
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_types::{
@@ -398,7 +398,7 @@ pub struct EngineApiTreeHandler<P, E, T: EngineTypes> {
 
 impl<P, E, T> EngineApiTreeHandler<P, E, T>
 where
-    P: BlockReader + StateProviderFactory + Clone + 'static,
+    P: BlockReader + BadBlockWriter + StateProviderFactory + Clone + 'static,
     E: BlockExecutorProvider,
     T: EngineTypes,
 {
@@ -1791,7 +1791,13 @@ where
             self.latest_valid_hash_for_invalid_payload(block.parent_hash)?
         };
 
-        // keep track of the invalid header
+        // keep track of the invalid header and the full block, so it can be inspected later. The
+        // block is only recorded in memory here, and written to the database by the persistence
+        // task, so that the engine never waits for the database writer.
+        if let Err(err) = self.provider.insert_bad_block(block.clone()) {
+            warn!(target: "engine::tree", %err, invalid_hash=?block.hash(), "Failed to record bad block");
+        }
+        let _ = self.persistence.save_bad_block(block.clone());
         self.state.invalid_headers.insert(block.header);
         Ok(PayloadStatus::new(
             PayloadStatusEnum::Invalid { validation_error: validation_err.to_string() },
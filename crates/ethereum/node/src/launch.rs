@@ -126,7 +126,7 @@ where
             ctx.configs().clone(),
         )
         .launch()
-        .await;
+        .await?;
 
         // create pipeline
         let network_client = ctx.components().network().fetch_client().await?;
@@ -232,6 +232,7 @@ where
             Box::new(ctx.task_executor().clone()),
             client,
             EngineCapabilities::default(),
+            ctx.components().pool().clone(),
         );
         info!(target: "reth::cli", "Engine API handler initialized");
 
@@ -244,6 +245,7 @@ where
             engine_api,
             ctx.node_config(),
             jwt_secret,
+            exex_manager_handle.as_ref(),
             rpc,
         )
         .await?;
@@ -1,4 +1,4 @@
-use crate::StreamBackfillJob;
+use crate::{ParallelBackfillJob, StreamBackfillJob};
 use std::{
     ops::RangeInclusive,
     time::{Duration, Instant},
@@ -15,6 +15,7 @@ use reth_provider::{
 use reth_prune_types::PruneModes;
 use reth_revm::database::StateProviderDatabase;
 use reth_stages_api::ExecutionStageThresholds;
+use reth_tasks::TaskSpawner;
 use reth_tracing::tracing::{debug, trace};
 
 /// Backfill job started for a specific range.
@@ -62,6 +63,12 @@ where
         self.into()
     }
 
+    /// Converts the backfill job into a job executing the range on multiple blocking tasks,
+    /// spawned with the given task spawner.
+    pub fn into_parallel(self, task_spawner: Box<dyn TaskSpawner>) -> ParallelBackfillJob<E, P> {
+        ParallelBackfillJob::new(self, task_spawner)
+    }
+
     fn execute_range(&mut self) -> Result<Chain, BlockExecutionError> {
         let mut executor = self.executor.batch_executor(StateProviderDatabase::new(
             self.provider.history_by_block_number(self.range.start().saturating_sub(1))?,
@@ -1,9 +1,11 @@
 mod factory;
 mod job;
+mod parallel;
 mod stream;
 #[cfg(test)]
-mod test_utils;
+pub(crate) mod test_utils;
 
 pub use factory::BackfillJobFactory;
 pub use job::{BackfillJob, SingleBlockBackfillJob};
+pub use parallel::ParallelBackfillJob;
 pub use stream::StreamBackfillJob;
@@ -0,0 +1,293 @@
+use crate::BackfillJob;
+use std::{
+    collections::VecDeque,
+    num::NonZeroUsize,
+    ops::RangeInclusive,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver},
+        Arc,
+    },
+    thread,
+};
+
+use reth_evm::execute::{BlockExecutionError, BlockExecutorProvider};
+use reth_primitives::BlockNumber;
+use reth_provider::{BlockReader, Chain, HeaderProvider, StateProviderFactory};
+use reth_prune_types::PruneModes;
+use reth_stages_api::ExecutionStageThresholds;
+use reth_tasks::TaskSpawner;
+use reth_tracing::tracing::debug;
+
+/// The default number of blocks executed by a worker of [`ParallelBackfillJob`] at once.
+const DEFAULT_CHUNK_SIZE: u64 = 1_000;
+
+/// A chunk of the range being executed by a worker.
+#[derive(Debug)]
+struct ChunkTask {
+    range: RangeInclusive<BlockNumber>,
+    /// The first block of the chunk that wasn't received from the worker yet.
+    next_block: BlockNumber,
+    /// The chains of the chunk, sent by the worker as soon as they are executed.
+    chains: Receiver<Result<Chain, BlockExecutionError>>,
+}
+
+/// Backfill job that executes a range on multiple blocking tasks.
+///
+/// The range is split into chunks of consecutive blocks, and every chunk is executed by a
+/// [`BackfillJob`] on its own blocking task, on top of the historical state before the chunk. Up
+/// to `parallelism` chunks are executed at once.
+///
+/// It implements [`Iterator`] that yields the [`Chain`]s in order. Within a chunk, the blocks are
+/// batched into chains according to the provided thresholds, the same way [`BackfillJob`] does, so
+/// the thresholds of the execution stage can be used by converting the `[stages.execution]`
+/// config into [`ExecutionStageThresholds`]. A worker only executes the next chain of its chunk
+/// once the previous one was yielded, so at most two chains per chunk are held in memory.
+///
+/// On the first error, the remaining workers are stopped and waited for before the error is
+/// returned.
+#[derive(Debug)]
+pub struct ParallelBackfillJob<E, P> {
+    executor: E,
+    provider: P,
+    prune_modes: PruneModes,
+    thresholds: ExecutionStageThresholds,
+    range: RangeInclusive<BlockNumber>,
+    parallelism: usize,
+    chunk_size: u64,
+    task_spawner: Box<dyn TaskSpawner>,
+    /// Signals the workers to stop before executing their next chain.
+    cancelled: Arc<AtomicBool>,
+    /// The chunks being executed, in order.
+    tasks: VecDeque<ChunkTask>,
+}
+
+impl<E, P> ParallelBackfillJob<E, P> {
+    /// Creates a new [`ParallelBackfillJob`] for the range of the given job, spawning the workers
+    /// with the given task spawner.
+    pub(crate) fn new(job: BackfillJob<E, P>, task_spawner: Box<dyn TaskSpawner>) -> Self {
+        Self {
+            executor: job.executor,
+            provider: job.provider,
+            prune_modes: job.prune_modes,
+            thresholds: job.thresholds,
+            range: job.range,
+            parallelism: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            task_spawner,
+            cancelled: Arc::new(AtomicBool::new(false)),
+            tasks: VecDeque::new(),
+        }
+    }
+
+    /// Configures the maximum number of chunks executed at once.
+    ///
+    /// Defaults to the available parallelism of the machine.
+    pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism.max(1);
+        self
+    }
+
+    /// Configures the number of blocks in each chunk.
+    pub fn with_chunk_size(mut self, chunk_size: u64) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Stops all workers and waits for them to exit.
+    fn cancel(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+        self.range.nth(usize::MAX);
+        for task in self.tasks.drain(..) {
+            // The worker drops its sender once it exits, which happens at the latest after the
+            // chain it is currently executing
+            while task.chains.recv().is_ok() {}
+        }
+    }
+}
+
+impl<E, P> ParallelBackfillJob<E, P>
+where
+    E: BlockExecutorProvider + Clone + Send + 'static,
+    P: HeaderProvider + BlockReader + StateProviderFactory + Clone + Send + 'static,
+{
+    /// Spawns the workers for the next chunks of the range, until `parallelism` chunks are being
+    /// executed.
+    fn spawn_tasks(&mut self) {
+        while self.tasks.len() < self.parallelism && !self.range.is_empty() {
+            let start = *self.range.start();
+            let end = start.saturating_add(self.chunk_size - 1).min(*self.range.end());
+            // Advance the range past the chunk
+            self.range.nth((end - start) as usize);
+
+            let mut job = BackfillJob {
+                executor: self.executor.clone(),
+                provider: self.provider.clone(),
+                prune_modes: self.prune_modes.clone(),
+                thresholds: self.thresholds.clone(),
+                range: start..=end,
+                stream_parallelism: 1,
+            };
+            let (tx, rx) = mpsc::sync_channel(1);
+            let cancelled = self.cancelled.clone();
+
+            debug!(target: "exex::backfill", range = ?start..=end, "Spawning backfill worker");
+            self.task_spawner.spawn_blocking(Box::pin(async move {
+                while !cancelled.load(Ordering::Relaxed) {
+                    let Some(chain) = job.next() else { break };
+                    let failed = chain.is_err();
+                    // Stop if the job was dropped, or after sending the first error
+                    if tx.send(chain).is_err() || failed {
+                        break
+                    }
+                }
+            }));
+            self.tasks.push_back(ChunkTask { range: start..=end, next_block: start, chains: rx });
+        }
+    }
+}
+
+impl<E, P> Iterator for ParallelBackfillJob<E, P>
+where
+    E: BlockExecutorProvider + Clone + Send + 'static,
+    P: HeaderProvider + BlockReader + StateProviderFactory + Clone + Send + 'static,
+{
+    type Item = Result<Chain, BlockExecutionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.spawn_tasks();
+            let task = self.tasks.front_mut()?;
+            match task.chains.recv() {
+                Ok(Ok(chain)) => {
+                    task.next_block = chain.tip().number + 1;
+                    return Some(Ok(chain))
+                }
+                Ok(Err(err)) => {
+                    self.cancel();
+                    return Some(Err(err))
+                }
+                Err(_) => {
+                    // The worker exited, either after executing the whole chunk or by panicking
+                    let task = self.tasks.pop_front().expect("front task exists");
+                    if task.next_block <= *task.range.end() {
+                        self.cancel();
+                        return Some(Err(BlockExecutionError::msg(format!(
+                            "backfill worker for blocks {:?} exited before block {}",
+                            task.range, task.next_block
+                        ))))
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<E, P> Drop for ParallelBackfillJob<E, P> {
+    fn drop(&mut self) {
+        // The workers exit on their own once they notice the job is gone
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        backfill::test_utils::{blocks_and_execution_outputs, chain_spec, to_execution_outcome},
+        BackfillJobFactory,
+    };
+    use reth_blockchain_tree::noop::NoopBlockchainTree;
+    use reth_db_common::init::init_genesis;
+    use reth_evm_ethereum::execute::EthExecutorProvider;
+    use reth_primitives::public_key_to_address;
+    use reth_provider::{
+        providers::BlockchainProvider, test_utils::create_test_provider_factory_with_chain_spec,
+    };
+    use reth_tasks::TokioTaskExecutor;
+    use reth_testing_utils::generators;
+    use secp256k1::Keypair;
+
+    #[tokio::test]
+    async fn test_parallel_backfill() -> eyre::Result<()> {
+        reth_tracing::init_test_tracing();
+
+        // Create a key pair for the sender
+        let key_pair = Keypair::new_global(&mut generators::rng());
+        let address = public_key_to_address(key_pair.public_key());
+
+        let chain_spec = chain_spec(address);
+
+        let executor = EthExecutorProvider::ethereum(chain_spec.clone());
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(provider_factory.clone())?;
+
+        let blocks_and_execution_outputs =
+            blocks_and_execution_outputs(provider_factory.clone(), chain_spec, key_pair)?;
+
+        // The provider is created after the blocks are committed, so that the state at the first
+        // block is available
+        let blockchain_db =
+            BlockchainProvider::new(provider_factory, Arc::new(NoopBlockchainTree::default()))?;
+
+        // Backfill both blocks on separate workers
+        let factory = BackfillJobFactory::new(executor, blockchain_db);
+        let job = factory
+            .backfill(1..=2)
+            .into_parallel(Box::new(TokioTaskExecutor::default()))
+            .with_parallelism(2)
+            .with_chunk_size(1);
+        let chains = job.collect::<Result<Vec<_>, _>>()?;
+
+        // Assert that every worker produced the chain of its block, in order
+        assert_eq!(chains.len(), 2);
+        for (mut chain, (block, block_execution_output)) in
+            chains.into_iter().zip(blocks_and_execution_outputs)
+        {
+            chain.execution_outcome_mut().bundle.reverts.sort();
+            assert_eq!(chain.blocks(), &[(block.number, block.clone())].into());
+            assert_eq!(
+                chain.execution_outcome(),
+                &to_execution_outcome(block.number, &block_execution_output)
+            );
+        }
+
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_parallel_backfill_error() -> eyre::Result<()> {
+        reth_tracing::init_test_tracing();
+
+        // Create a key pair for the sender
+        let key_pair = Keypair::new_global(&mut generators::rng());
+        let address = public_key_to_address(key_pair.public_key());
+
+        let chain_spec = chain_spec(address);
+
+        let executor = EthExecutorProvider::ethereum(chain_spec.clone());
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(provider_factory.clone())?;
+
+        blocks_and_execution_outputs(provider_factory.clone(), chain_spec, key_pair)?;
+
+        let blockchain_db =
+            BlockchainProvider::new(provider_factory, Arc::new(NoopBlockchainTree::default()))?;
+
+        // The third block doesn't exist, so its worker fails after the first two chunks succeed
+        let factory = BackfillJobFactory::new(executor, blockchain_db);
+        let mut job = factory
+            .backfill(1..=4)
+            .into_parallel(Box::new(TokioTaskExecutor::default()))
+            .with_parallelism(3)
+            .with_chunk_size(1);
+        assert_eq!(job.next().unwrap()?.tip().number, 1);
+        assert_eq!(job.next().unwrap()?.tip().number, 2);
+        assert!(job.next().unwrap().is_err());
+
+        // The job stops at the first error
+        assert!(job.next().is_none());
+
+        Ok(())
+    }
+}
@@ -4,12 +4,14 @@ use reth_node_api::FullNodeComponents;
 use reth_node_core::node_config::NodeConfig;
 use reth_primitives::Head;
 use reth_tasks::TaskExecutor;
-use tokio::sync::mpsc::{Receiver, UnboundedSender};
+use tokio::sync::mpsc::UnboundedSender;
 
-use crate::{ExExEvent, ExExNotification};
+use crate::{ExExEvent, ExExNotifications, ExExStore};
 
 /// Captures the context that an `ExEx` has access to.
 pub struct ExExContext<Node: FullNodeComponents> {
+    /// The ID of the `ExEx`, as it was installed with.
+    pub id: String,
     /// The current head of the blockchain at launch.
     pub head: Head,
     /// The config of the node
@@ -24,13 +26,16 @@ pub struct ExExContext<Node: FullNodeComponents> {
     /// Additionally, the exex can pre-emptively emit a `FinishedHeight` event to specify what
     /// blocks to receive notifications for.
     pub events: UnboundedSender<ExExEvent>,
-    /// Channel to receive [`ExExNotification`]s.
+    /// Stream of [`ExExNotification`](crate::ExExNotification)s.
     ///
     /// # Important
     ///
-    /// Once a an [`ExExNotification`] is sent over the channel, it is considered delivered by the
-    /// node.
-    pub notifications: Receiver<ExExNotification>,
+    /// Once an [`ExExNotification`](crate::ExExNotification) is sent over the channel, it is
+    /// considered delivered by the node.
+    ///
+    /// The `ExEx` can declare its own head with [`ExExNotifications::set_with_head`] to be brought
+    /// up to date with the node before receiving new notifications.
+    pub notifications: ExExNotifications<Node::Provider, Node::Executor>,
 
     /// node components
     pub components: Node,
@@ -39,6 +44,7 @@ pub struct ExExContext<Node: FullNodeComponents> {
 impl<Node: FullNodeComponents> Debug for ExExContext<Node> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ExExContext")
+            .field("id", &self.id)
             .field("head", &self.head)
             .field("config", &self.config)
             .field("reth_config", &self.reth_config)
@@ -84,4 +90,12 @@ impl<Node: FullNodeComponents> ExExContext<Node> {
     pub fn task_executor(&self) -> &TaskExecutor {
         self.components.task_executor()
     }
+
+    /// Opens the key/value store of the `ExEx` in the data directory of the node, creating it if
+    /// it doesn't exist.
+    ///
+    /// See [`ExExStore`].
+    pub fn open_store(&self) -> eyre::Result<ExExStore> {
+        ExExStore::open(self.config.datadir().exex_store(&self.id))
+    }
 }
@@ -0,0 +1,208 @@
+use crate::ExExNotification;
+use reth_primitives::{Address, Receipt, Receipts, SealedBlock, SealedBlockWithSenders, B256};
+use reth_provider::{Chain, ExecutionOutcome};
+use std::{collections::HashSet, sync::Arc};
+
+/// Filter of the [`ExExNotification`]s sent to an `ExEx`, registered together with the `ExEx`.
+///
+/// The manager slims down every notification to the content matching the filter before buffering
+/// it, and skips the committed chains without any matching content. Reverted chains are always
+/// sent, so that the `ExEx` sees every revert.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ExExNotificationFilter {
+    /// Send the notifications as they are.
+    #[default]
+    All,
+    /// Send only the headers of the blocks, without bodies, state changes and receipts.
+    HeadersOnly,
+    /// Send only the headers of the blocks and the receipts with logs matching the filter, without
+    /// bodies and state changes.
+    ///
+    /// Receipts without matching logs are replaced with `None`, and the logs not matching the
+    /// filter are removed from the remaining ones. Committed chains without any matching logs are
+    /// not sent.
+    Logs {
+        /// Addresses of the contracts emitting the logs. Matches any address if empty.
+        addresses: HashSet<Address>,
+        /// Topics of the logs, any of which must be present in a log. Matches any topics if
+        /// empty.
+        topics: HashSet<B256>,
+    },
+}
+
+impl ExExNotificationFilter {
+    /// Applies the filter to the notification.
+    ///
+    /// Returns `None` if the notification only commits a chain without anything matching the
+    /// filter.
+    pub fn apply(&self, notification: &ExExNotification) -> Option<ExExNotification> {
+        let filter_chain = |chain: Arc<Chain>, reverted: bool| match self {
+            Self::All => Some(chain),
+            Self::HeadersOnly => Some(Arc::new(headers_only(&chain))),
+            Self::Logs { addresses, topics } => {
+                let (chain, matched) = matching_logs(&chain, addresses, topics);
+                // a reverted chain is sent even without matching logs, so that the revert isn't
+                // missed
+                (matched || reverted).then(|| Arc::new(chain))
+            }
+        };
+
+        let old = notification.reverted_chain().and_then(|chain| filter_chain(chain, true));
+        let new = notification.committed_chain().and_then(|chain| filter_chain(chain, false));
+        match (old, new) {
+            (Some(old), Some(new)) => Some(ExExNotification::ChainReorged { old, new }),
+            (Some(old), None) => Some(ExExNotification::ChainReverted { old }),
+            (None, Some(new)) => Some(ExExNotification::ChainCommitted { new }),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Returns the blocks of the chain with only their headers.
+fn header_blocks(chain: &Chain) -> impl Iterator<Item = SealedBlockWithSenders> + '_ {
+    chain.blocks_iter().map(|block| SealedBlockWithSenders {
+        block: SealedBlock {
+            header: block.header.clone(),
+            body: Vec::new(),
+            ommers: Vec::new(),
+            withdrawals: None,
+            requests: None,
+        },
+        senders: Vec::new(),
+    })
+}
+
+/// Returns the chain with only the headers of the blocks.
+fn headers_only(chain: &Chain) -> Chain {
+    let execution_outcome = ExecutionOutcome::new(
+        Default::default(),
+        Receipts { receipt_vec: vec![Vec::new(); chain.len()] },
+        chain.first().number,
+        Vec::new(),
+    );
+    Chain::new(header_blocks(chain), execution_outcome, None)
+}
+
+/// Returns the chain with only the headers of the blocks and the receipts and logs matching the
+/// filter, and whether there are any matching logs.
+fn matching_logs(
+    chain: &Chain,
+    addresses: &HashSet<Address>,
+    topics: &HashSet<B256>,
+) -> (Chain, bool) {
+    let mut matched = false;
+    let receipt_vec = chain
+        .execution_outcome()
+        .receipts
+        .iter()
+        .map(|block_receipts| {
+            block_receipts
+                .iter()
+                .map(|receipt| {
+                    let receipt = receipt.as_ref()?;
+                    let logs = receipt
+                        .logs
+                        .iter()
+                        .filter(|log| {
+                            (addresses.is_empty() || addresses.contains(&log.address)) &&
+                                (topics.is_empty() ||
+                                    log.topics().iter().any(|topic| topics.contains(topic)))
+                        })
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    (!logs.is_empty()).then(|| Receipt { logs, ..receipt.clone() })
+                })
+                .inspect(|receipt| matched |= receipt.is_some())
+                .collect()
+        })
+        .collect();
+
+    let execution_outcome = ExecutionOutcome::new(
+        Default::default(),
+        Receipts { receipt_vec },
+        chain.execution_outcome().first_block,
+        chain.execution_outcome().requests.clone(),
+    );
+    (Chain::new(header_blocks(chain), execution_outcome, None), matched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Log, LogData};
+    use reth_testing_utils::generators::{self, random_block};
+
+    fn chain(logs: Vec<Log>) -> Arc<Chain> {
+        let mut rng = generators::rng();
+        let block = random_block(&mut rng, 1, None, Some(1), Some(0));
+        let block = block.seal_with_senders().unwrap();
+        let receipt = Receipt { logs, ..Default::default() };
+        let execution_outcome = ExecutionOutcome::new(
+            Default::default(),
+            Receipts { receipt_vec: vec![vec![Some(receipt)]] },
+            1,
+            Vec::new(),
+        );
+        Arc::new(Chain::new([block], execution_outcome, None))
+    }
+
+    fn log(address: Address, topic: B256) -> Log {
+        Log { address, data: LogData::new_unchecked(vec![topic], Default::default()) }
+    }
+
+    #[test]
+    fn filter_logs() {
+        let address = Address::with_last_byte(1);
+        let topic = B256::with_last_byte(1);
+        let other_log = log(Address::with_last_byte(2), B256::with_last_byte(2));
+        let new = chain(vec![log(address, topic), other_log.clone()]);
+        let notification = ExExNotification::ChainCommitted { new: new.clone() };
+
+        assert_eq!(ExExNotificationFilter::All.apply(&notification), Some(notification.clone()));
+
+        // only the matching log is kept, and the state changes are dropped
+        let filter = ExExNotificationFilter::Logs {
+            addresses: HashSet::from([address]),
+            topics: HashSet::new(),
+        };
+        let filtered = filter.apply(&notification).unwrap().committed_chain().unwrap();
+        assert_eq!(filtered.headers().collect::<Vec<_>>(), new.headers().collect::<Vec<_>>());
+        assert!(filtered.tip().body.is_empty());
+        assert_eq!(
+            filtered.execution_outcome().receipts.receipt_vec[0][0].as_ref().unwrap().logs,
+            vec![log(address, topic)]
+        );
+
+        // a commit without matching logs is skipped, but a revert is sent with only its headers
+        let filter = ExExNotificationFilter::Logs {
+            addresses: HashSet::new(),
+            topics: HashSet::from([B256::with_last_byte(3)]),
+        };
+        let old = chain(vec![other_log.clone()]);
+        assert_eq!(filter.apply(&ExExNotification::ChainCommitted { new: old.clone() }), None);
+        let filtered = filter
+            .apply(&ExExNotification::ChainReorged {
+                old: old.clone(),
+                new: chain(vec![other_log]),
+            })
+            .unwrap();
+        assert!(filtered.committed_chain().is_none());
+        let reverted = filtered.reverted_chain().unwrap();
+        assert_eq!(reverted.headers().collect::<Vec<_>>(), old.headers().collect::<Vec<_>>());
+        assert_eq!(reverted.execution_outcome().receipts.receipt_vec, vec![vec![None]]);
+    }
+
+    #[test]
+    fn filter_headers_only() {
+        let new = chain(Vec::new());
+        let filtered = ExExNotificationFilter::HeadersOnly
+            .apply(&ExExNotification::ChainCommitted { new: new.clone() })
+            .unwrap()
+            .committed_chain()
+            .unwrap();
+
+        assert_eq!(filtered.headers().collect::<Vec<_>>(), new.headers().collect::<Vec<_>>());
+        assert!(filtered.tip().body.is_empty());
+        assert_eq!(filtered.execution_outcome().receipts.receipt_vec, vec![Vec::new()]);
+    }
+}
@@ -23,6 +23,29 @@
 //! event. To clarify: if the `ExEx` emits `ExExEvent::FinishedHeight(0)` it will receive
 //! notifications for any `block_number > 0`.
 //!
+//! # Filtering
+//!
+//! An `ExEx` only interested in a part of the notifications, e.g. the logs of a few contracts or
+//! the block headers, can be installed with an [`ExExNotificationFilter`]. The notifications are
+//! then slimmed down before they are buffered by the manager, and committed chains without
+//! matching content are skipped, advancing the finished height of an `ExEx` that has finished
+//! everything sent to it. Reverted chains are always sent.
+//!
+//! # Write-ahead log
+//!
+//! Notifications are committed to a [`Wal`] in the data directory before they are sent. When the
+//! node restarts, every `ExEx` is sent the notifications it did not finish before, based on the
+//! last `ExExEvent::FinishedHeight` it emitted. Notifications are removed from the log once all
+//! `ExEx`'s have finished them, so an `ExEx` may see a notification again after a crash and should
+//! handle it idempotently.
+//!
+//! # Head negotiation
+//!
+//! An `ExEx` that keeps its own state can declare the last block it has processed with
+//! [`ExExNotifications::set_with_head`]. Before any new notifications are sent, the `ExEx` is then
+//! brought up to the head of the node: blocks on a fork that is no longer canonical are reverted,
+//! and the missing canonical blocks are executed with a backfill job and committed.
+//!
 //! [`Future`]: std::future::Future
 //! [`ExExContext`]: crate::ExExContext
 //! [`CanonStateNotification`]: reth_provider::CanonStateNotification
@@ -43,9 +66,21 @@ pub use context::*;
 mod event;
 pub use event::*;
 
+mod filter;
+pub use filter::*;
+
 mod manager;
 pub use manager::*;
 
+mod notifications;
+pub use notifications::*;
+
+mod store;
+pub use store::*;
+
+mod wal;
+pub use wal::*;
+
 // Re-export exex types
 #[doc(inline)]
 pub use reth_exex_types::*;
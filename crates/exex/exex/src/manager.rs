@@ -1,8 +1,13 @@
-use crate::{ExExEvent, ExExNotification, FinishedExExHeight};
+use crate::{
+    notification_height, wal::WalWriter, ExExEvent, ExExNotification, ExExNotificationFilter,
+    ExExProgress, FinishedExExHeight, Wal,
+};
 use metrics::Gauge;
+use reth_config::{ExExLagAction, ExExLagPolicy};
+use reth_exex_types::{ExExHealth, ExExStatus};
 use reth_metrics::{metrics::Counter, Metrics};
 use reth_primitives::BlockNumber;
-use reth_tracing::tracing::debug;
+use reth_tracing::tracing::{debug, info, warn};
 use std::{
     collections::VecDeque,
     future::{poll_fn, Future},
@@ -12,10 +17,14 @@ use std::{
         Arc,
     },
     task::{ready, Context, Poll},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
-use tokio::sync::{
-    mpsc::{self, error::SendError, Receiver, UnboundedReceiver, UnboundedSender},
-    watch,
+use tokio::{
+    sync::{
+        mpsc::{self, error::SendError, Receiver, UnboundedReceiver, UnboundedSender},
+        watch,
+    },
+    time::Interval,
 };
 use tokio_util::sync::{PollSendError, PollSender, ReusableBoxFuture};
 
@@ -27,8 +36,13 @@ struct ExExMetrics {
     notifications_sent_total: Counter,
     /// The total number of events an `ExEx` has sent to the manager.
     events_sent_total: Counter,
+    /// The number of blocks the finished height of an `ExEx` is behind the node.
+    lag_blocks: Gauge,
 }
 
+/// The interval at which the manager checks the lag policies with a maximum duration.
+const LAG_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 /// A handle to an `ExEx` used by the [`ExExManager`] to communicate with `ExEx`'s.
 ///
 /// A handle should be created for each `ExEx` with a unique ID. The channels returned by
@@ -47,11 +61,32 @@ pub struct ExExHandle {
     receiver: UnboundedReceiver<ExExEvent>,
     /// The ID of the next notification to send to this `ExEx`.
     next_notification_id: usize,
+    /// The filter of the notifications sent to this `ExEx`.
+    filter: ExExNotificationFilter,
+    /// The index of the filter in [`ExExManager::filters`], and of the notifications slimmed down
+    /// by it in every [`BufferedNotification`].
+    filter_index: usize,
+    /// The height the last notification sent to this `ExEx` leads to.
+    sent_height: Option<BlockNumber>,
 
     /// The finished block number of the `ExEx`.
     ///
     /// If this is `None`, the `ExEx` has not emitted a `FinishedHeight` event.
     finished_height: Option<BlockNumber>,
+    /// The ID of the first notification the `ExEx` didn't finish.
+    ///
+    /// Notifications are finished once the `ExEx` emits a `FinishedHeight` event that covers the
+    /// height of the last notification sent to it, or skipped after all notifications before them
+    /// were finished.
+    finished_id: usize,
+
+    /// The lag policy of the `ExEx`.
+    lag_policy: ExExLagPolicy,
+    /// Since when the `ExEx` has been behind the node without finishing new blocks, and the
+    /// finished height it has been stalled at.
+    stalled_since: Option<(Instant, SystemTime, Option<BlockNumber>)>,
+    /// The health of the `ExEx` according to its lag policy.
+    health: ExExHealth,
 }
 
 impl ExExHandle {
@@ -70,22 +105,123 @@ impl ExExHandle {
                 sender: PollSender::new(notification_tx),
                 receiver: event_rx,
                 next_notification_id: 0,
+                filter: ExExNotificationFilter::All,
+                filter_index: 0,
+                sent_height: None,
                 finished_height: None,
+                finished_id: 0,
+                lag_policy: ExExLagPolicy::default(),
+                stalled_since: None,
+                health: ExExHealth::Healthy,
             },
             event_tx,
             notification_rx,
         )
     }
 
-    /// Reserves a slot in the `PollSender` channel and sends the notification if the slot was
-    /// successfully reserved.
+    /// Sets the lag policy of the `ExEx`, enforced by the manager.
+    pub const fn with_lag_policy(mut self, lag_policy: ExExLagPolicy) -> Self {
+        self.lag_policy = lag_policy;
+        self
+    }
+
+    /// Sets the filter of the notifications sent to the `ExEx`.
+    pub fn with_filter(mut self, filter: ExExNotificationFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Updates the lag of the `ExEx` behind the given node height.
+    ///
+    /// Returns the number of blocks the `ExEx` is behind, and for how long it has been behind
+    /// without finishing new blocks.
+    fn update_lag(&mut self, head: Option<BlockNumber>) -> (Option<u64>, Option<Duration>) {
+        let lag_blocks = self.lag_blocks(head);
+        self.metrics.lag_blocks.set(lag_blocks.unwrap_or_default() as f64);
+
+        if head.is_none() || lag_blocks == Some(0) {
+            self.stalled_since = None;
+        } else if self.stalled_since.map_or(true, |(_, _, height)| height != self.finished_height) {
+            self.stalled_since = Some((Instant::now(), SystemTime::now(), self.finished_height));
+        }
+
+        (lag_blocks, self.stalled_since.map(|(since, _, _)| since.elapsed()))
+    }
+
+    /// Records the height the `ExEx` reported as finished.
+    ///
+    /// All notifications sent to the `ExEx` so far are finished, unless the height is lower than
+    /// the one the last of them leads to.
+    fn finish_height(&mut self, height: BlockNumber) {
+        self.finished_height = Some(height);
+        if self.sent_height.map_or(true, |sent_height| height >= sent_height) {
+            self.finished_id = self.next_notification_id;
+        }
+    }
+
+    /// Marks the notification with the given ID as skipped, which finishes it if the `ExEx`
+    /// finished all notifications before it.
+    fn skip_notification(&mut self, notification_id: usize) {
+        if self.finished_id == notification_id {
+            self.finished_id = notification_id + 1;
+        }
+        self.next_notification_id = notification_id + 1;
+    }
+
+    /// Returns the progress of the `ExEx` to record in the write-ahead log, if it reported a
+    /// finished height.
+    fn progress(&self) -> Option<ExExProgress> {
+        Some(ExExProgress {
+            finished_height: self.finished_height?,
+            next_id: self.finished_id as u64,
+        })
+    }
+
+    /// Returns the number of blocks the `ExEx` is behind the given node height.
+    fn lag_blocks(&self, head: Option<BlockNumber>) -> Option<u64> {
+        head.zip(self.finished_height).map(|(head, finished)| head.saturating_sub(finished))
+    }
+
+    /// Returns the status of the `ExEx` for the given node height.
+    fn status(&self, head: Option<BlockNumber>) -> ExExStatus {
+        ExExStatus {
+            id: self.id.clone(),
+            finished_height: self.finished_height,
+            lag_blocks: self.lag_blocks(head),
+            stalled_since: self.stalled_since.map(|(_, since, _)| {
+                since.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+            }),
+            health: self.health,
+        }
+    }
+
+    /// Reserves a slot in the `PollSender` channel and sends the notification, slimmed down by the
+    /// filter of the `ExEx`, if the slot was successfully reserved.
     ///
     /// When the notification is sent, it is considered delivered.
     fn send(
         &mut self,
         cx: &mut Context<'_>,
-        (notification_id, notification): &(usize, ExExNotification),
+        buffered: &BufferedNotification,
     ) -> Poll<Result<(), PollSendError<ExExNotification>>> {
+        let notification_id = buffered.id;
+        let Some(notification) = &buffered.notifications[self.filter_index] else {
+            debug!(
+                exex_id = %self.id,
+                %notification_id,
+                "Skipping notification not matching the filter"
+            );
+
+            // If the ExEx has finished everything sent to it, it has also finished the skipped
+            // blocks.
+            if self.finished_height >= self.sent_height {
+                self.finished_height = self.finished_height.max(buffered.committed_tip);
+            }
+
+            self.skip_notification(notification_id);
+            return Poll::Ready(Ok(()))
+        };
+
         if let Some(finished_height) = self.finished_height {
             match notification {
                 ExExNotification::ChainCommitted { new } => {
@@ -101,7 +237,7 @@ impl ExExHandle {
                             "Skipping notification"
                         );
 
-                        self.next_notification_id = notification_id + 1;
+                        self.skip_notification(notification_id);
                         return Poll::Ready(Ok(()))
                     }
                 }
@@ -128,9 +264,11 @@ impl ExExHandle {
             %notification_id,
             "Sending notification"
         );
+        let height = notification_height(notification);
         match self.sender.send_item(notification.clone()) {
             Ok(()) => {
                 self.next_notification_id = notification_id + 1;
+                self.sent_height = Some(height);
                 self.metrics.notifications_sent_total.increment(1);
                 Poll::Ready(Ok(()))
             }
@@ -139,6 +277,21 @@ impl ExExHandle {
     }
 }
 
+/// A notification in the buffer of the [`ExExManager`].
+///
+/// Only the notifications slimmed down by the filters of the `ExEx`'s are kept, so that the
+/// content no `ExEx` is interested in is not held in memory.
+#[derive(Debug)]
+struct BufferedNotification {
+    /// Monotonically increasing ID unique to the notification.
+    id: usize,
+    /// The tip of the committed chain of the notification, if any.
+    committed_tip: Option<BlockNumber>,
+    /// The notification slimmed down by each filter in [`ExExManager::filters`], or `None` if
+    /// nothing in it matches the filter.
+    notifications: Vec<Option<ExExNotification>>,
+}
+
 /// Metrics for the `ExEx` manager.
 #[derive(Metrics)]
 #[metrics(scope = "exex_manager")]
@@ -153,6 +306,10 @@ pub struct ExExManagerMetrics {
     buffer_size: Gauge,
     /// Current number of `ExEx`'s on the node.
     num_exexs: Gauge,
+    /// Current number of `ExEx`'s exceeding the maximum lag of their policy.
+    lagging_exexs: Gauge,
+    /// Current number of `ExEx`'s detached for exceeding the maximum lag of their policy.
+    detached_exexs: Gauge,
 }
 
 /// The execution extension manager.
@@ -168,6 +325,10 @@ pub struct ExExManagerMetrics {
 pub struct ExExManager {
     /// Handles to communicate with the `ExEx`'s.
     exex_handles: Vec<ExExHandle>,
+    /// Handles of the `ExEx`'s detached for exceeding the maximum lag of their policy.
+    ///
+    /// They are kept around so that the `ExEx`'s don't observe a closed channel.
+    detached_exex_handles: Vec<ExExHandle>,
 
     /// [`ExExNotification`] channel from the [`ExExManagerHandle`]s.
     handle_rx: UnboundedReceiver<ExExNotification>,
@@ -176,23 +337,35 @@ pub struct ExExManager {
     min_id: usize,
     /// Monotonically increasing ID for [`ExExNotification`]s.
     next_id: usize,
+    /// The distinct filters of the `ExEx`'s.
+    filters: Vec<ExExNotificationFilter>,
     /// Internal buffer of [`ExExNotification`]s.
-    ///
-    /// The first element of the tuple is a monotonically increasing ID unique to the notification
-    /// (the second element of the tuple).
-    buffer: VecDeque<(usize, ExExNotification)>,
+    buffer: VecDeque<BufferedNotification>,
     /// Max size of the internal state notifications buffer.
     max_capacity: usize,
     /// Current state notifications buffer capacity.
     ///
     /// Used to inform the execution stage of possible batch sizes.
     current_capacity: Arc<AtomicUsize>,
+    /// The height of the node after the last received notification.
+    head: Option<BlockNumber>,
+    /// Interval of the lag policy checks, if any policy has a maximum duration.
+    lag_check_interval: Option<Interval>,
 
     /// Whether the manager is ready to receive new notifications.
     is_ready: watch::Sender<bool>,
 
     /// The finished height of all `ExEx`'s.
     finished_height: watch::Sender<FinishedExExHeight>,
+    /// The status of all `ExEx`'s.
+    status: watch::Sender<Vec<ExExStatus>>,
+
+    /// Writer of the write-ahead log of the notifications, if set.
+    wal: Option<WalWriter>,
+    /// The ID of the first notification that isn't committed to the write-ahead log yet.
+    ///
+    /// If the log is set, notifications are only sent to the `ExEx`'s once they are committed.
+    wal_committed_id: usize,
 
     /// A handle to the `ExEx` manager.
     handle: ExExManagerHandle,
@@ -208,9 +381,18 @@ impl ExExManager {
     ///
     /// When the capacity is exceeded (which can happen if an `ExEx` is slow) no one can send
     /// notifications over [`ExExManagerHandle`]s until there is capacity again.
-    pub fn new(handles: Vec<ExExHandle>, max_capacity: usize) -> Self {
+    pub fn new(mut handles: Vec<ExExHandle>, max_capacity: usize) -> Self {
         let num_exexs = handles.len();
 
+        let mut filters = Vec::<ExExNotificationFilter>::new();
+        for exex in &mut handles {
+            exex.filter_index =
+                filters.iter().position(|filter| *filter == exex.filter).unwrap_or_else(|| {
+                    filters.push(exex.filter.clone());
+                    filters.len() - 1
+                });
+        }
+
         let (handle_tx, handle_rx) = mpsc::unbounded_channel();
         let (is_ready_tx, is_ready_rx) = watch::channel(true);
         let (finished_height_tx, finished_height_rx) = watch::channel(if num_exexs == 0 {
@@ -219,6 +401,9 @@ impl ExExManager {
             FinishedExExHeight::NotReady
         });
 
+        let (status_tx, status_rx) =
+            watch::channel(handles.iter().map(|exex| exex.status(None)).collect());
+
         let current_capacity = Arc::new(AtomicUsize::new(max_capacity));
 
         let metrics = ExExManagerMetrics::default();
@@ -227,17 +412,25 @@ impl ExExManager {
 
         Self {
             exex_handles: handles,
+            detached_exex_handles: Vec::new(),
 
             handle_rx,
 
             min_id: 0,
             next_id: 0,
+            filters,
             buffer: VecDeque::with_capacity(max_capacity),
             max_capacity,
             current_capacity: Arc::clone(&current_capacity),
+            head: None,
+            lag_check_interval: None,
 
             is_ready: is_ready_tx,
             finished_height: finished_height_tx,
+            status: status_tx,
+
+            wal: None,
+            wal_committed_id: 0,
 
             handle: ExExManagerHandle {
                 exex_tx: handle_tx,
@@ -246,11 +439,48 @@ impl ExExManager {
                 is_ready: ReusableBoxFuture::new(make_wait_future(is_ready_rx)),
                 current_capacity,
                 finished_height: finished_height_rx,
+                status: status_rx,
             },
             metrics,
         }
     }
 
+    /// Sets the write-ahead log of the manager.
+    ///
+    /// Every notification received from now on is committed to the log, on a dedicated thread
+    /// and in the order of the notifications, and only sent to the `ExEx`'s once it's durable. If
+    /// writing to the log fails, the manager returns the error. The notifications already in the
+    /// log are replayed to each `ExEx`, starting from the first one the `ExEx` didn't finish
+    /// according to its [`ExExProgress`], and removed from the log once all `ExEx`'s have finished
+    /// them.
+    ///
+    /// The notification IDs of the manager continue the IDs of the log.
+    pub fn with_wal(mut self, wal: Wal) -> eyre::Result<Self> {
+        let notifications = wal.notifications()?;
+        debug!(notifications = notifications.len(), "Replaying notifications from WAL");
+
+        let first_id = wal.first_id() as usize;
+        let end_id = first_id + notifications.len();
+        self.min_id = first_id;
+        self.next_id = first_id;
+        for exex in &mut self.exex_handles {
+            exex.finished_id = first_id;
+            if let Some(progress) = wal.progress(&exex.id) {
+                // the notifications before the first one in the log were finished by every `ExEx`
+                exex.finished_id = (progress.next_id as usize).clamp(first_id, end_id);
+                exex.finished_height = Some(progress.finished_height);
+            }
+            exex.next_notification_id = exex.finished_id;
+        }
+        for notification in notifications {
+            self.push_notification(notification);
+        }
+        self.wal_committed_id = self.next_id;
+        self.wal = Some(WalWriter::spawn(wal)?);
+
+        Ok(self)
+    }
+
     /// Returns the handle to the manager.
     pub fn handle(&self) -> ExExManagerHandle {
         self.handle.clone()
@@ -270,12 +500,84 @@ impl ExExManager {
     }
 
     /// Pushes a new notification into the managers internal buffer, assigning the notification a
-    /// unique ID.
+    /// unique ID and slimming it down by the filters of the `ExEx`'s.
     fn push_notification(&mut self, notification: ExExNotification) {
-        let next_id = self.next_id;
-        self.buffer.push_back((next_id, notification));
+        self.head = Some(notification_height(&notification));
+        let notifications = self.filters.iter().map(|filter| filter.apply(&notification)).collect();
+        self.buffer.push_back(BufferedNotification {
+            id: self.next_id,
+            committed_tip: notification.committed_chain().map(|chain| chain.tip().number),
+            notifications,
+        });
         self.next_id += 1;
     }
+
+    /// Checks the lag of every `ExEx` against its policy, and takes the policy action for the ones
+    /// that exceeded it.
+    ///
+    /// Returns an error if an `ExEx` with the [`ExExLagAction::Shutdown`] action exceeded its
+    /// policy.
+    fn enforce_lag_policies(&mut self) -> eyre::Result<()> {
+        for idx in (0..self.exex_handles.len()).rev() {
+            let exex = &mut self.exex_handles[idx];
+            let (lag_blocks, stalled_for) = exex.update_lag(self.head);
+            let within_policy = exex.lag_policy.is_within(lag_blocks, stalled_for);
+
+            match exex.health {
+                ExExHealth::Lagging if within_policy => {
+                    info!(exex_id = %exex.id, ?lag_blocks, "ExEx is within its lag policy again");
+                    exex.health = ExExHealth::Healthy;
+                }
+                ExExHealth::Healthy if !within_policy => match exex.lag_policy.action {
+                    ExExLagAction::Alert => {
+                        warn!(exex_id = %exex.id, ?lag_blocks, ?stalled_for, "ExEx exceeded its lag policy");
+                        exex.health = ExExHealth::Lagging;
+                    }
+                    ExExLagAction::Shutdown => {
+                        eyre::bail!(
+                            "ExEx {} exceeded its lag policy: {lag_blocks:?} blocks behind, stalled for {stalled_for:?}",
+                            exex.id
+                        )
+                    }
+                    ExExLagAction::Detach => {
+                        warn!(exex_id = %exex.id, ?lag_blocks, ?stalled_for, "ExEx exceeded its lag policy, detaching it");
+                        exex.health = ExExHealth::Detached;
+                        let exex = self.exex_handles.swap_remove(idx);
+                        self.detached_exex_handles.push(exex);
+                    }
+                },
+                _ => {}
+            }
+        }
+
+        self.metrics.num_exexs.set(self.exex_handles.len() as f64);
+        self.metrics.lagging_exexs.set(
+            self.exex_handles.iter().filter(|exex| exex.health == ExExHealth::Lagging).count()
+                as f64,
+        );
+        self.metrics.detached_exexs.set(self.detached_exex_handles.len() as f64);
+
+        Ok(())
+    }
+
+    /// Notifies the status watchers if the status of any `ExEx` changed.
+    fn update_status(&self) {
+        let mut status = self
+            .exex_handles
+            .iter()
+            .chain(&self.detached_exex_handles)
+            .map(|exex| exex.status(self.head))
+            .collect::<Vec<_>>();
+        status.sort_unstable_by(|a, b| a.id.cmp(&b.id));
+
+        self.status.send_if_modified(|current| {
+            if *current == status {
+                return false
+            }
+            *current = status;
+            true
+        });
+    }
 }
 
 impl Future for ExExManager {
@@ -290,6 +592,9 @@ impl Future for ExExManager {
                     reverted_tip = ?notification.reverted_chain().map(|chain| chain.tip().number),
                     "Received new notification"
                 );
+                if let Some(wal) = &self.wal {
+                    wal.commit(notification.clone());
+                }
                 self.push_notification(notification);
                 continue
             }
@@ -299,8 +604,38 @@ impl Future for ExExManager {
         // update capacity
         self.update_capacity();
 
+        // only the notifications that are durable in the WAL can be sent
+        let this = &mut *self;
+        let sendable_id = match &mut this.wal {
+            Some(wal) => match wal.poll_committed(cx) {
+                Ok(committed) => {
+                    this.wal_committed_id += committed;
+                    this.wal_committed_id
+                }
+                Err(err) => {
+                    return Poll::Ready(Err(err.wrap_err("failed to write to the ExEx WAL")))
+                }
+            },
+            None => this.next_id,
+        };
+
+        // handle incoming exex events before sending, so that the finished heights emitted
+        // before a notification was sent don't count as finishing it
+        let this = &mut *self;
+        for exex in &mut this.exex_handles {
+            let progress = exex.progress();
+            while let Poll::Ready(Some(event)) = exex.receiver.poll_recv(cx) {
+                debug!(exex_id = %exex.id, ?event, "Received event from exex");
+                exex.metrics.events_sent_total.increment(1);
+                match event {
+                    ExExEvent::FinishedHeight(height) => exex.finish_height(height),
+                }
+            }
+            record_progress(&this.wal, exex, progress);
+        }
+
         // advance all poll senders
-        let mut min_id = usize::MAX;
+        let mut min_id = self.next_id;
         for idx in (0..self.exex_handles.len()).rev() {
             let mut exex = self.exex_handles.swap_remove(idx);
 
@@ -310,11 +645,19 @@ impl Future for ExExManager {
                 .next_notification_id
                 .checked_sub(self.min_id)
                 .expect("exex expected notification ID outside the manager's range");
-            if let Some(notification) = self.buffer.get(notification_index) {
+            if let Some(notification) = self
+                .buffer
+                .get(notification_index)
+                .filter(|_| exex.next_notification_id < sendable_id)
+            {
+                let progress = exex.progress();
                 if let Poll::Ready(Err(err)) = exex.send(cx, notification) {
                     // the channel was closed, which is irrecoverable for the manager
                     return Poll::Ready(Err(err.into()))
                 }
+
+                // the progress advances when a notification is skipped
+                record_progress(&self.wal, &exex, progress);
             }
             min_id = min_id.min(exex.next_notification_id);
             self.exex_handles.push(exex);
@@ -322,21 +665,38 @@ impl Future for ExExManager {
 
         // remove processed buffered notifications
         debug!(%min_id, "Updating lowest notification id in buffer");
-        self.buffer.retain(|&(id, _)| id >= min_id);
+        self.buffer.retain(|notification| notification.id >= min_id);
         self.min_id = min_id;
 
         // update capacity
         self.update_capacity();
 
-        // handle incoming exex events
-        for exex in &mut self.exex_handles {
-            while let Poll::Ready(Some(event)) = exex.receiver.poll_recv(cx) {
-                debug!(exex_id = %exex.id, ?event, "Received event from exex");
-                exex.metrics.events_sent_total.increment(1);
-                match event {
-                    ExExEvent::FinishedHeight(height) => exex.finished_height = Some(height),
-                }
+        // detach the exexs that exceeded their lag policy, or shut down
+        if let Some(interval) = &mut self.lag_check_interval {
+            while interval.poll_tick(cx).is_ready() {}
+        } else if self.exex_handles.iter().any(|exex| exex.lag_policy.max_duration.is_some()) {
+            // the first tick completes immediately and registers the waker
+            let mut interval = tokio::time::interval(LAG_CHECK_INTERVAL);
+            while interval.poll_tick(cx).is_ready() {}
+            self.lag_check_interval = Some(interval);
+        }
+        if let Err(err) = self.enforce_lag_policies() {
+            return Poll::Ready(Err(err))
+        }
+        for exex in &mut self.detached_exex_handles {
+            // the events of the detached exexs are ignored
+            while let Poll::Ready(Some(_)) = exex.receiver.poll_recv(cx) {}
+        }
+        self.update_status();
+
+        // all exexs were detached, so there is nothing to wait for
+        if self.exex_handles.is_empty() {
+            let _ = self.finished_height.send(FinishedExExHeight::NoExExs);
+            let next_id = self.next_id;
+            if let Some(wal) = &mut self.wal {
+                wal.finalize(next_id as u64);
             }
+            return Poll::Pending
         }
 
         // update watch channel block number
@@ -356,10 +716,25 @@ impl Future for ExExManager {
             let _ = self.finished_height.send(FinishedExExHeight::Height(finished_height));
         }
 
+        // all `ExEx`'s are done with the notifications before the lowest finished ID
+        let finished_id = self.exex_handles.iter().map(|exex| exex.finished_id).min();
+        if let (Some(finished_id), Some(wal)) = (finished_id, &mut self.wal) {
+            wal.finalize(finished_id as u64);
+        }
+
         Poll::Pending
     }
 }
 
+/// Records the progress of the `ExEx` in the write-ahead log, if it changed from the given one.
+fn record_progress(wal: &Option<WalWriter>, exex: &ExExHandle, previous: Option<ExExProgress>) {
+    if let (Some(progress), Some(wal)) = (exex.progress(), wal) {
+        if Some(progress) != previous {
+            wal.set_progress(exex.id.clone(), progress);
+        }
+    }
+}
+
 /// A handle to communicate with the [`ExExManager`].
 #[derive(Debug)]
 pub struct ExExManagerHandle {
@@ -380,6 +755,8 @@ pub struct ExExManagerHandle {
     current_capacity: Arc<AtomicUsize>,
     /// The finished height of all `ExEx`'s.
     finished_height: watch::Receiver<FinishedExExHeight>,
+    /// The status of all `ExEx`'s.
+    status: watch::Receiver<Vec<ExExStatus>>,
 }
 
 impl ExExManagerHandle {
@@ -392,6 +769,7 @@ impl ExExManagerHandle {
         let (exex_tx, _) = mpsc::unbounded_channel();
         let (_, is_ready_rx) = watch::channel(true);
         let (_, finished_height_rx) = watch::channel(FinishedExExHeight::NoExExs);
+        let (_, status_rx) = watch::channel(Vec::new());
 
         Self {
             exex_tx,
@@ -400,6 +778,7 @@ impl ExExManagerHandle {
             is_ready: ReusableBoxFuture::new(make_wait_future(is_ready_rx)),
             current_capacity: Arc::new(AtomicUsize::new(0)),
             finished_height: finished_height_rx,
+            status: status_rx,
         }
     }
 
@@ -445,6 +824,11 @@ impl ExExManagerHandle {
         self.finished_height.clone()
     }
 
+    /// The status of all `ExEx`'s, sorted by their IDs.
+    pub fn status(&self) -> watch::Receiver<Vec<ExExStatus>> {
+        self.status.clone()
+    }
+
     /// Wait until the manager is ready for new notifications.
     pub async fn ready(&mut self) {
         poll_fn(|cx| self.poll_ready(cx)).await
@@ -475,12 +859,19 @@ impl Clone for ExExManagerHandle {
             is_ready: ReusableBoxFuture::new(make_wait_future(self.is_ready_receiver.clone())),
             current_capacity: self.current_capacity.clone(),
             finished_height: self.finished_height.clone(),
+            status: self.status.clone(),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use futures::poll;
+    use reth_primitives::{Address, B256};
+    use reth_provider::Chain;
+    use reth_testing_utils::generators::{self, random_block_range};
+
     #[tokio::test]
     async fn delivers_events() {}
 
@@ -495,4 +886,237 @@ mod tests {
 
     #[tokio::test]
     async fn is_ready() {}
+
+    #[tokio::test]
+    async fn replays_wal() {
+        let mut rng = generators::rng();
+        let directory = tempfile::tempdir().unwrap();
+
+        let notifications = [1..=2, 3..=4]
+            .into_iter()
+            .map(|range| {
+                let blocks = random_block_range(&mut rng, range, B256::ZERO, 0..1)
+                    .into_iter()
+                    .map(|block| block.seal_with_senders().unwrap());
+                ExExNotification::ChainCommitted {
+                    new: Arc::new(Chain::new(blocks, Default::default(), None)),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // the exex finished the first notification before the restart
+        let mut wal = Wal::new(directory.path()).unwrap();
+        for notification in &notifications {
+            wal.commit(notification).unwrap();
+        }
+        wal.set_progress("exex", ExExProgress { finished_height: 2, next_id: 1 }).unwrap();
+
+        let (handle, events, mut notifications_rx) = ExExHandle::new("exex".to_string());
+        let mut manager = ExExManager::new(vec![handle], 10).with_wal(wal).unwrap();
+
+        // only the unfinished notification is replayed
+        assert!(poll!(&mut manager).is_pending());
+        assert_eq!(notifications_rx.try_recv().unwrap(), notifications[1]);
+        assert!(notifications_rx.try_recv().is_err());
+
+        // finished notifications are removed from the log
+        events.send(ExExEvent::FinishedHeight(4)).unwrap();
+        assert!(poll!(&mut manager).is_pending());
+        manager.wal.as_ref().unwrap().flush();
+        let wal = Wal::new(directory.path()).unwrap();
+        assert!(wal.is_empty());
+        assert_eq!(wal.progress("exex"), Some(ExExProgress { finished_height: 4, next_id: 2 }));
+
+        // new notifications are committed to the log
+        let new = random_block_range(&mut rng, 5..=5, B256::ZERO, 0..1)
+            .into_iter()
+            .map(|block| block.seal_with_senders().unwrap());
+        let new = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(new, Default::default(), None)),
+        };
+        manager.handle().send(new.clone()).unwrap();
+        assert!(poll!(&mut manager).is_pending());
+        manager.wal.as_ref().unwrap().flush();
+        assert_eq!(Wal::new(directory.path()).unwrap().notifications().unwrap().last(), Some(&new));
+
+        // and sent once they are committed
+        assert!(poll!(&mut manager).is_pending());
+        assert_eq!(notifications_rx.try_recv().unwrap(), new);
+    }
+
+    #[tokio::test]
+    async fn keeps_reverts_below_finished_height_in_wal() {
+        let mut rng = generators::rng();
+        let directory = tempfile::tempdir().unwrap();
+
+        let blocks = random_block_range(&mut rng, 1..=5, B256::ZERO, 0..1)
+            .into_iter()
+            .map(|block| block.seal_with_senders().unwrap())
+            .collect::<Vec<_>>();
+        let committed = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(blocks.clone(), Default::default(), None)),
+        };
+        let reverted = ExExNotification::ChainReverted {
+            old: Arc::new(Chain::new(blocks[3..].to_vec(), Default::default(), None)),
+        };
+
+        let (handle, events, mut notifications_rx) = ExExHandle::new("exex".to_string());
+        let wal = Wal::new(directory.path()).unwrap();
+        let mut manager = ExExManager::new(vec![handle], 10).with_wal(wal).unwrap();
+
+        manager.handle().send(committed.clone()).unwrap();
+        assert!(poll!(&mut manager).is_pending());
+        manager.wal.as_ref().unwrap().flush();
+        assert!(poll!(&mut manager).is_pending());
+        assert_eq!(notifications_rx.try_recv().unwrap(), committed);
+
+        // the revert is committed before the exex finishes the commit, and leads back to height 3
+        manager.handle().send(reverted.clone()).unwrap();
+        events.send(ExExEvent::FinishedHeight(5)).unwrap();
+        assert!(poll!(&mut manager).is_pending());
+        manager.wal.as_ref().unwrap().flush();
+
+        // the revert is below the finished height, but it's kept until the exex finishes it
+        let wal = Wal::new(directory.path()).unwrap();
+        assert_eq!(wal.notifications().unwrap(), vec![reverted.clone()]);
+        assert_eq!(wal.progress("exex"), Some(ExExProgress { finished_height: 5, next_id: 1 }));
+
+        // and replayed after a restart
+        drop(manager);
+        let wal = Wal::new(directory.path()).unwrap();
+        let (handle, _events, mut notifications_rx) = ExExHandle::new("exex".to_string());
+        let mut manager = ExExManager::new(vec![handle], 10).with_wal(wal).unwrap();
+        assert!(poll!(&mut manager).is_pending());
+        assert_eq!(notifications_rx.try_recv().unwrap(), reverted);
+    }
+
+    #[tokio::test]
+    async fn skips_filtered_notifications() {
+        let mut rng = generators::rng();
+
+        let (handle, _events, mut notifications_rx) = ExExHandle::new("exex".to_string());
+        let handle = handle.with_filter(ExExNotificationFilter::Logs {
+            addresses: [Address::with_last_byte(1)].into(),
+            topics: Default::default(),
+        });
+        let mut manager = ExExManager::new(vec![handle], 10);
+        let finished_height = manager.handle().finished_height();
+
+        // the notification has no logs, so it's skipped and the exex has finished its blocks
+        let blocks = random_block_range(&mut rng, 1..=2, B256::ZERO, 0..1)
+            .into_iter()
+            .map(|block| block.seal_with_senders().unwrap());
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(blocks, Default::default(), None)),
+        };
+        manager.handle().send(notification).unwrap();
+        assert!(poll!(&mut manager).is_pending());
+        assert!(notifications_rx.try_recv().is_err());
+        assert!(matches!(*finished_height.borrow(), FinishedExExHeight::Height(2)));
+    }
+
+    #[tokio::test]
+    async fn enforces_lag_policies() {
+        let mut rng = generators::rng();
+
+        let policy = |action| ExExLagPolicy { max_blocks: Some(1), max_duration: None, action };
+        let (alert, alert_events, _alert_rx) = ExExHandle::new("alert".to_string());
+        let (detach, detach_events, mut detach_rx) = ExExHandle::new("detach".to_string());
+        let mut manager = ExExManager::new(
+            vec![
+                alert.with_lag_policy(policy(ExExLagAction::Alert)),
+                detach.with_lag_policy(policy(ExExLagAction::Detach)),
+            ],
+            10,
+        );
+        let handle = manager.handle();
+        let finished_height = handle.finished_height();
+        let status = handle.status();
+
+        let blocks = random_block_range(&mut rng, 1..=3, B256::ZERO, 0..1)
+            .into_iter()
+            .map(|block| block.seal_with_senders().unwrap());
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(blocks, Default::default(), None)),
+        };
+        handle.send(notification.clone()).unwrap();
+        assert!(poll!(&mut manager).is_pending());
+        assert_eq!(detach_rx.try_recv().unwrap(), notification);
+
+        // both exexs are two blocks behind
+        alert_events.send(ExExEvent::FinishedHeight(1)).unwrap();
+        detach_events.send(ExExEvent::FinishedHeight(1)).unwrap();
+        assert!(poll!(&mut manager).is_pending());
+        assert_eq!(
+            status
+                .borrow()
+                .iter()
+                .map(|status| (status.health, status.lag_blocks))
+                .collect::<Vec<_>>(),
+            vec![(ExExHealth::Lagging, Some(2)), (ExExHealth::Detached, Some(2))]
+        );
+        assert!(matches!(*finished_height.borrow(), FinishedExExHeight::Height(1)));
+
+        // the detached exex doesn't hold back the finished height anymore
+        alert_events.send(ExExEvent::FinishedHeight(3)).unwrap();
+        assert!(poll!(&mut manager).is_pending());
+        assert_eq!(status.borrow()[0].health, ExExHealth::Healthy);
+        assert!(matches!(*finished_height.borrow(), FinishedExExHeight::Height(3)));
+    }
+
+    #[tokio::test]
+    async fn shuts_down_on_lag() {
+        let mut rng = generators::rng();
+
+        let (exex, events, _notifications_rx) = ExExHandle::new("exex".to_string());
+        let mut manager = ExExManager::new(
+            vec![exex.with_lag_policy(ExExLagPolicy {
+                max_blocks: Some(0),
+                max_duration: None,
+                action: ExExLagAction::Shutdown,
+            })],
+            10,
+        );
+
+        let blocks = random_block_range(&mut rng, 1..=2, B256::ZERO, 0..1)
+            .into_iter()
+            .map(|block| block.seal_with_senders().unwrap());
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(blocks, Default::default(), None)),
+        };
+        manager.handle().send(notification).unwrap();
+        events.send(ExExEvent::FinishedHeight(1)).unwrap();
+        assert!(matches!(poll!(&mut manager), Poll::Ready(Err(_))));
+    }
+
+    #[tokio::test]
+    async fn fails_on_wal_error() {
+        let mut rng = generators::rng();
+        let directory = tempfile::tempdir().unwrap();
+
+        let (handle, _events, mut notifications_rx) = ExExHandle::new("exex".to_string());
+        let wal = Wal::new(directory.path()).unwrap();
+        let mut manager = ExExManager::new(vec![handle], 10).with_wal(wal).unwrap();
+
+        // the commit fails, because its temporary file can't be created
+        std::fs::create_dir(directory.path().join("0-1.tmp")).unwrap();
+        let blocks = random_block_range(&mut rng, 1..=1, B256::ZERO, 0..1)
+            .into_iter()
+            .map(|block| block.seal_with_senders().unwrap());
+        manager
+            .handle()
+            .send(ExExNotification::ChainCommitted {
+                new: Arc::new(Chain::new(blocks, Default::default(), None)),
+            })
+            .unwrap();
+        let mut result = poll!(&mut manager);
+        manager.wal.as_ref().unwrap().flush();
+        if result.is_pending() {
+            result = poll!(&mut manager);
+        }
+
+        // the manager fails without sending the notification
+        assert!(matches!(result, Poll::Ready(Err(_))));
+        assert!(notifications_rx.try_recv().is_err());
+    }
 }
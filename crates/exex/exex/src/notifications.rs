@@ -0,0 +1,390 @@
+use crate::{BackfillJobFactory, ExExHead, ExExNotification, StreamBackfillJob};
+use futures::{Stream, StreamExt};
+use reth_evm::execute::BlockExecutorProvider;
+use reth_primitives::{BlockNumHash, Head};
+use reth_provider::{BlockReader, Chain, ChainSplit, ChainSplitTarget, StateProviderFactory};
+use reth_tracing::tracing::debug;
+use std::{
+    fmt::Debug,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
+use tokio::sync::mpsc::Receiver;
+
+/// A stream of [`ExExNotification`]s sent to an `ExEx`.
+///
+/// By default, the stream yields the notifications of the node as they come in. If the `ExEx`
+/// declares its head with [`ExExNotifications::set_with_head`], the stream first brings the `ExEx`
+/// up to date with the node:
+///
+/// 1. If the head of the `ExEx` is on a fork that is not canonical anymore, the orphaned blocks are
+///    reverted with [`ExExNotification::ChainReverted`], until the head is canonical.
+/// 2. The blocks from the head of the `ExEx` up to the head of the node at launch are executed and
+///    committed with [`ExExNotification::ChainCommitted`].
+/// 3. Afterwards, the notifications of the node are yielded, skipping the blocks that the `ExEx`
+///    already has.
+pub struct ExExNotifications<P, E> {
+    /// The head of the node at launch.
+    node_head: Head,
+    provider: P,
+    executor: E,
+    /// Channel to receive the notifications of the node.
+    notifications: Receiver<ExExNotification>,
+    /// The notifications in the write-ahead log at launch, used to look up the blocks of an
+    /// orphaned `ExEx` head.
+    wal_notifications: Vec<ExExNotification>,
+    /// The head of the `ExEx`, if it was declared.
+    exex_head: Option<ExExHead>,
+    /// Whether the head of the `ExEx` is known to be on the canonical chain.
+    exex_head_canonical: bool,
+    /// The backfill job bringing the `ExEx` up to the head of the node, if in progress.
+    backfill_job: Option<StreamBackfillJob<E, P, Chain>>,
+}
+
+impl<P, E> Debug for ExExNotifications<P, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExExNotifications")
+            .field("node_head", &self.node_head)
+            .field("provider", &"...")
+            .field("executor", &"...")
+            .field("notifications", &self.notifications)
+            .field("wal_notifications", &self.wal_notifications.len())
+            .field("exex_head", &self.exex_head)
+            .field("exex_head_canonical", &self.exex_head_canonical)
+            .field("backfill_job", &self.backfill_job.is_some())
+            .finish()
+    }
+}
+
+impl<P, E> ExExNotifications<P, E> {
+    /// Creates a new stream of notifications without an `ExEx` head.
+    pub const fn new(
+        node_head: Head,
+        provider: P,
+        executor: E,
+        notifications: Receiver<ExExNotification>,
+    ) -> Self {
+        Self {
+            node_head,
+            provider,
+            executor,
+            notifications,
+            wal_notifications: Vec::new(),
+            exex_head: None,
+            exex_head_canonical: false,
+            backfill_job: None,
+        }
+    }
+
+    /// Sets the notifications in the write-ahead log at launch.
+    pub fn with_wal_notifications(mut self, wal_notifications: Vec<ExExNotification>) -> Self {
+        self.wal_notifications = wal_notifications;
+        self
+    }
+
+    /// Declares the head of the `ExEx`, i.e. the last block it has processed.
+    ///
+    /// Should be called before polling the stream for the first time. See [`ExExNotifications`]
+    /// for how the `ExEx` is brought up to date.
+    pub fn set_with_head(&mut self, exex_head: ExExHead) {
+        self.exex_head = Some(exex_head);
+        self.exex_head_canonical = false;
+        self.backfill_job = None;
+    }
+
+    /// Declares the head of the `ExEx`, see [`ExExNotifications::set_with_head`].
+    pub fn with_head(mut self, exex_head: ExExHead) -> Self {
+        self.set_with_head(exex_head);
+        self
+    }
+}
+
+impl<P, E> ExExNotifications<P, E>
+where
+    P: BlockReader + StateProviderFactory + Clone + Send + Unpin + 'static,
+    E: BlockExecutorProvider + Clone + Send + 'static,
+{
+    /// Checks whether the head of the `ExEx` is on the canonical chain.
+    ///
+    /// If it's not, returns a [`ExExNotification::ChainReverted`] with the orphaned blocks up to
+    /// the head, taken from the write-ahead log, and moves the head to the parent of the first
+    /// orphaned block.
+    fn check_canonical(&mut self, exex_head: ExExHead) -> eyre::Result<Option<ExExNotification>> {
+        let canonical_hash = self.provider.block_hash(exex_head.block.number)?;
+        if canonical_hash == Some(exex_head.block.hash) {
+            debug!(target: "exex::notifications", ?exex_head, "ExEx head is on the canonical chain");
+            return Ok(None)
+        }
+        if canonical_hash.is_none() && exex_head.block.number > self.node_head.number {
+            debug!(target: "exex::notifications", ?exex_head, node_head = ?self.node_head.number, "ExEx head is ahead of the node");
+            return Ok(None)
+        }
+
+        let chain = self
+            .wal_notifications
+            .iter()
+            .rev()
+            .flat_map(|notification| {
+                [notification.committed_chain(), notification.reverted_chain()]
+            })
+            .flatten()
+            .find(|chain| chain.block_number(exex_head.block.hash).is_some())
+            .ok_or_else(|| {
+                eyre::eyre!(
+                    "ExEx head {:?} is not on the canonical chain and not in the WAL",
+                    exex_head.block
+                )
+            })?;
+
+        // Drop the blocks above the head of the `ExEx`
+        let chain = match Chain::clone(&chain).split(ChainSplitTarget::Hash(exex_head.block.hash)) {
+            ChainSplit::Split { canonical, .. } => canonical,
+            ChainSplit::NoSplitCanonical(chain) | ChainSplit::NoSplitPending(chain) => chain,
+        };
+
+        // Drop the blocks that are still on the canonical chain
+        let mut last_canonical = None;
+        for block in chain.blocks_iter() {
+            if self.provider.block_hash(block.number)? != Some(block.hash()) {
+                break
+            }
+            last_canonical = Some(block.number);
+        }
+        let orphaned = match last_canonical {
+            Some(number) => match chain.split(ChainSplitTarget::Number(number)) {
+                ChainSplit::Split { pending, .. } => pending,
+                _ => unreachable!("the head of the ExEx is not canonical"),
+            },
+            None => chain,
+        };
+
+        let first = orphaned.first();
+        let exex_head = ExExHead {
+            block: BlockNumHash::new(first.number.saturating_sub(1), first.parent_hash),
+        };
+        debug!(target: "exex::notifications", orphaned = ?orphaned.range(), ?exex_head, "Reverting orphaned ExEx head");
+        self.exex_head = Some(exex_head);
+
+        Ok(Some(ExExNotification::ChainReverted { old: Arc::new(orphaned) }))
+    }
+}
+
+/// Adjusts a notification of the node to the head of the `ExEx`.
+///
+/// Returns `None` if the `ExEx` already has the notified blocks. Reverts are only passed through if
+/// they revert the head of the `ExEx`, and committed blocks the `ExEx` already has are dropped.
+fn adjust_notification(
+    exex_head: ExExHead,
+    notification: ExExNotification,
+) -> Option<ExExNotification> {
+    let reverts_head = notification
+        .reverted_chain()
+        .is_some_and(|old| old.block_number(exex_head.block.hash).is_some());
+    if reverts_head {
+        return Some(notification)
+    }
+
+    let new = notification.committed_chain()?;
+    if new.tip().number <= exex_head.block.number {
+        return None
+    }
+
+    let new = match Chain::clone(&new).split(ChainSplitTarget::Number(exex_head.block.number)) {
+        ChainSplit::Split { pending, .. } => Arc::new(pending),
+        _ => new,
+    };
+    Some(ExExNotification::ChainCommitted { new })
+}
+
+impl<P, E> Stream for ExExNotifications<P, E>
+where
+    P: BlockReader + StateProviderFactory + Clone + Send + Unpin + 'static,
+    E: BlockExecutorProvider + Clone + Send + 'static,
+{
+    type Item = eyre::Result<ExExNotification>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        let Some(exex_head) = this.exex_head else {
+            return this.notifications.poll_recv(cx).map(|notification| notification.map(Ok))
+        };
+
+        // 1. Revert the head of the `ExEx` until it's on the canonical chain, and start the
+        //    backfill up to the head of the node.
+        if !this.exex_head_canonical {
+            if let Some(notification) = this.check_canonical(exex_head)? {
+                return Poll::Ready(Some(Ok(notification)))
+            }
+            this.exex_head_canonical = true;
+
+            if exex_head.block.number < this.node_head.number {
+                debug!(target: "exex::notifications", from = exex_head.block.number + 1, to = this.node_head.number, "Backfilling ExEx");
+                this.backfill_job = Some(
+                    BackfillJobFactory::new(this.executor.clone(), this.provider.clone())
+                        .backfill(exex_head.block.number + 1..=this.node_head.number)
+                        .into_stream(),
+                );
+            }
+        }
+
+        // 2. Commit the backfilled blocks.
+        if let Some(backfill_job) = &mut this.backfill_job {
+            match ready!(backfill_job.poll_next_unpin(cx)) {
+                Some(chain) => {
+                    let chain = chain?;
+                    this.exex_head = Some(ExExHead { block: chain.tip().num_hash() });
+                    return Poll::Ready(Some(Ok(ExExNotification::ChainCommitted {
+                        new: Arc::new(chain),
+                    })))
+                }
+                None => this.backfill_job = None,
+            }
+        }
+
+        // 3. Yield the notifications of the node, skipping the blocks the `ExEx` already has.
+        loop {
+            let Some(notification) = ready!(this.notifications.poll_recv(cx)) else {
+                return Poll::Ready(None)
+            };
+
+            let exex_head = this.exex_head.expect("exex head is set");
+            if let Some(notification) = adjust_notification(exex_head, notification) {
+                let block = match notification.committed_chain() {
+                    Some(new) => new.tip().num_hash(),
+                    None => {
+                        let old = notification.reverted_chain().expect("notification is a revert");
+                        BlockNumHash::new(
+                            old.first().number.saturating_sub(1),
+                            old.first().parent_hash,
+                        )
+                    }
+                };
+                this.exex_head = Some(ExExHead { block });
+                return Poll::Ready(Some(Ok(notification)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backfill::test_utils::{blocks_and_execution_outcome, chain_spec};
+    use futures::TryStreamExt;
+    use reth_blockchain_tree::noop::NoopBlockchainTree;
+    use reth_db_common::init::init_genesis;
+    use reth_evm_ethereum::execute::EthExecutorProvider;
+    use reth_primitives::{public_key_to_address, SealedBlockWithSenders, B256};
+    use reth_provider::{
+        providers::BlockchainProvider, test_utils::create_test_provider_factory_with_chain_spec,
+        ExecutionOutcome,
+    };
+    use reth_testing_utils::generators::{self, random_block};
+    use secp256k1::Keypair;
+
+    fn random_chain(rng: &mut impl rand::Rng, number: u64, parent: B256) -> Arc<Chain> {
+        let block = random_block(rng, number, Some(parent), Some(0), Some(0));
+        let block = SealedBlockWithSenders::new(block, vec![]).unwrap();
+        Arc::new(Chain::from_block(block, ExecutionOutcome::default(), None))
+    }
+
+    #[tokio::test]
+    async fn backfills_and_skips_known_blocks() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let key_pair = Keypair::new_global(&mut rng);
+        let chain_spec = chain_spec(public_key_to_address(key_pair.public_key()));
+
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        let genesis_hash = init_genesis(provider_factory.clone())?;
+        let (blocks, _) =
+            blocks_and_execution_outcome(provider_factory.clone(), chain_spec.clone(), key_pair)?;
+        let provider =
+            BlockchainProvider::new(provider_factory, Arc::new(NoopBlockchainTree::default()))?;
+
+        let node_head = Head { number: 2, hash: blocks[1].hash(), ..Default::default() };
+        let (notifications_tx, notifications_rx) = tokio::sync::mpsc::channel(4);
+        let mut notifications = ExExNotifications::new(
+            node_head,
+            provider,
+            EthExecutorProvider::ethereum(chain_spec),
+            notifications_rx,
+        )
+        .with_head(ExExHead { block: BlockNumHash::new(0, genesis_hash) });
+
+        // the node notifies about the last block it committed and a new one
+        let known = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::from_block(blocks[1].clone(), ExecutionOutcome::default(), None)),
+        };
+        let new =
+            ExExNotification::ChainCommitted { new: random_chain(&mut rng, 3, blocks[1].hash()) };
+        notifications_tx.send(known).await?;
+        notifications_tx.send(new.clone()).await?;
+
+        // blocks after the head of the exex are backfilled
+        let backfilled = notifications.try_next().await?.unwrap();
+        let backfilled = backfilled.committed_chain().unwrap();
+        assert_eq!(backfilled.blocks().values().cloned().collect::<Vec<_>>(), blocks);
+
+        // the already backfilled block is skipped
+        assert_eq!(notifications.try_next().await?, Some(new));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reverts_orphaned_head() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let key_pair = Keypair::new_global(&mut rng);
+        let chain_spec = chain_spec(public_key_to_address(key_pair.public_key()));
+
+        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(provider_factory.clone())?;
+        let (blocks, _) =
+            blocks_and_execution_outcome(provider_factory.clone(), chain_spec.clone(), key_pair)?;
+        let provider =
+            BlockchainProvider::new(provider_factory, Arc::new(NoopBlockchainTree::default()))?;
+
+        // the exex processed a block at height 2 that got reorged out while it was offline
+        let orphaned = random_chain(&mut rng, 2, blocks[0].hash());
+        let wal_notifications = vec![
+            ExExNotification::ChainCommitted { new: orphaned.clone() },
+            ExExNotification::ChainReorged {
+                old: orphaned.clone(),
+                new: Arc::new(Chain::from_block(
+                    blocks[1].clone(),
+                    ExecutionOutcome::default(),
+                    None,
+                )),
+            },
+        ];
+
+        let node_head = Head { number: 2, hash: blocks[1].hash(), ..Default::default() };
+        let (_notifications_tx, notifications_rx) = tokio::sync::mpsc::channel(1);
+        let mut notifications = ExExNotifications::new(
+            node_head,
+            provider,
+            EthExecutorProvider::ethereum(chain_spec),
+            notifications_rx,
+        )
+        .with_wal_notifications(wal_notifications)
+        .with_head(ExExHead { block: orphaned.tip().num_hash() });
+
+        // the orphaned block is reverted first
+        assert_eq!(
+            notifications.try_next().await?,
+            Some(ExExNotification::ChainReverted { old: orphaned })
+        );
+
+        // then the canonical block is backfilled
+        let backfilled = notifications.try_next().await?.unwrap();
+        let backfilled = backfilled.committed_chain().unwrap();
+        assert_eq!(
+            backfilled.blocks().values().cloned().collect::<Vec<_>>(),
+            vec![blocks[1].clone()]
+        );
+
+        Ok(())
+    }
+}
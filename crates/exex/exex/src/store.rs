@@ -0,0 +1,389 @@
+//! Key/value store of an `ExEx` that follows the canonical chain.
+
+use crate::{ExExEvent, ExExHead, ExExNotification};
+use eyre::{ensure, WrapErr};
+use reth_db::{
+    create_db,
+    mdbx::{tx::Tx, DatabaseArguments, RW},
+    DatabaseEnv, TableType,
+};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW},
+    database::Database,
+    models::ClientVersion,
+    table::Table,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_primitives::{BlockNumHash, BlockNumber, B256};
+use std::path::Path;
+
+/// Table of the current values of the store.
+#[derive(Debug)]
+struct ExExStoreValues;
+
+impl Table for ExExStoreValues {
+    const NAME: &'static str = "ExExStoreValues";
+    type Key = Vec<u8>;
+    type Value = Vec<u8>;
+}
+
+/// Table of the values that were overwritten by each block, keyed by the block number followed
+/// by the key of the value, see [`changeset_key`] and [`encode_previous`].
+#[derive(Debug)]
+struct ExExStoreChangeSets;
+
+impl Table for ExExStoreChangeSets {
+    const NAME: &'static str = "ExExStoreChangeSets";
+    type Key = Vec<u8>;
+    type Value = Vec<u8>;
+}
+
+/// Table of the hashes of the blocks committed to the store. The last entry is the head.
+#[derive(Debug)]
+struct ExExStoreBlocks;
+
+impl Table for ExExStoreBlocks {
+    const NAME: &'static str = "ExExStoreBlocks";
+    type Key = BlockNumber;
+    type Value = B256;
+}
+
+/// Table of the metadata of the store, see [`PRUNED_HEIGHT_KEY`].
+#[derive(Debug)]
+struct ExExStoreMetadata;
+
+impl Table for ExExStoreMetadata {
+    const NAME: &'static str = "ExExStoreMetadata";
+    type Key = Vec<u8>;
+    type Value = Vec<u8>;
+}
+
+/// Key of the block number up to which the changesets were pruned in [`ExExStoreMetadata`],
+/// encoded as big-endian bytes.
+const PRUNED_HEIGHT_KEY: &[u8] = b"pruned_height";
+
+/// Persistent key/value store of an `ExEx`, backed by its own MDBX database.
+///
+/// All writes are scoped to a block with [`ExExStore::block`], and committed together with the
+/// block, which becomes the head of the store. The values overwritten by each block are kept in a
+/// changeset, so that the writes of reverted blocks can be undone with
+/// [`ExExStore::handle_notification`] or [`ExExStore::revert_to`].
+///
+/// Since the head is committed atomically with the writes, it's the height the `ExEx` can report
+/// with [`ExExEvent::FinishedHeight`] and the head it can resume from with
+/// [`ExExNotifications::set_with_head`](crate::ExExNotifications::set_with_head) after a restart.
+#[derive(Debug)]
+pub struct ExExStore {
+    db: DatabaseEnv,
+}
+
+impl ExExStore {
+    /// Opens the store at the given path, creating it if it doesn't exist.
+    pub fn open(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = path.as_ref();
+        let db = create_db(path, DatabaseArguments::new(ClientVersion::default()))
+            .wrap_err_with(|| format!("failed to open ExEx store at {}", path.display()))?;
+        db.create_table::<ExExStoreValues>(TableType::Table)?;
+        db.create_table::<ExExStoreChangeSets>(TableType::Table)?;
+        db.create_table::<ExExStoreBlocks>(TableType::Table)?;
+        db.create_table::<ExExStoreMetadata>(TableType::Table)?;
+
+        Ok(Self { db })
+    }
+
+    /// Returns the value of the key as of the head of the store.
+    pub fn get(&self, key: &[u8]) -> eyre::Result<Option<Vec<u8>>> {
+        Ok(self.db.tx()?.get::<ExExStoreValues>(key.to_vec())?)
+    }
+
+    /// Returns the last block committed to the store.
+    pub fn head(&self) -> eyre::Result<Option<ExExHead>> {
+        let tx = self.db.tx()?;
+        let head = tx.cursor_read::<ExExStoreBlocks>()?.last()?;
+        Ok(head.map(|(number, hash)| ExExHead { block: BlockNumHash { number, hash } }))
+    }
+
+    /// Returns the [`ExExEvent::FinishedHeight`] of the head of the store.
+    pub fn finished_height(&self) -> eyre::Result<Option<ExExEvent>> {
+        Ok(self.head()?.map(|head| ExExEvent::FinishedHeight(head.block.number)))
+    }
+
+    /// Starts writing the given block to the store.
+    ///
+    /// The block must be above the head of the store. Nothing is written until
+    /// [`ExExStoreBlock::commit`] is called, and the store is locked for other writes until then.
+    pub fn block(&self, block: BlockNumHash) -> eyre::Result<ExExStoreBlock> {
+        let tx = self.db.tx_mut()?;
+        if let Some((head, _)) = tx.cursor_read::<ExExStoreBlocks>()?.last()? {
+            ensure!(
+                block.number > head,
+                "block {} is not above the head of the ExEx store {head}",
+                block.number
+            );
+        }
+
+        Ok(ExExStoreBlock { tx, block })
+    }
+
+    /// Returns the block number up to which the changesets were pruned with
+    /// [`ExExStore::prune`].
+    pub fn pruned_height(&self) -> eyre::Result<Option<BlockNumber>> {
+        pruned_height(&self.db.tx()?)
+    }
+
+    /// Undoes the writes of all blocks above the given block number.
+    ///
+    /// Returns an error if the changesets of the blocks were pruned, i.e. the given block is below
+    /// the pruned height.
+    pub fn revert_to(&self, block: BlockNumber) -> eyre::Result<()> {
+        let tx = self.db.tx_mut()?;
+        if let Some(pruned_height) = pruned_height(&tx)? {
+            ensure!(
+                block >= pruned_height,
+                "cannot revert the ExEx store to block {block}, changesets are pruned up to block {pruned_height}"
+            );
+        }
+
+        {
+            let mut values = tx.cursor_write::<ExExStoreValues>()?;
+            let mut changesets = tx.cursor_write::<ExExStoreChangeSets>()?;
+            let mut walker = changesets.walk_back(None)?;
+            while let Some((changeset_key, previous)) = walker.next().transpose()? {
+                let (number, key) = split_changeset_key(&changeset_key)?;
+                if number <= block {
+                    break
+                }
+
+                match decode_previous(previous) {
+                    Some(value) => values.upsert(key.to_vec(), value)?,
+                    None => {
+                        if values.seek_exact(key.to_vec())?.is_some() {
+                            values.delete_current()?;
+                        }
+                    }
+                }
+                walker.delete_current()?;
+            }
+
+            let mut blocks = tx.cursor_write::<ExExStoreBlocks>()?;
+            let mut walker = blocks.walk_back(None)?;
+            while let Some((number, _)) = walker.next().transpose()? {
+                if number <= block {
+                    break
+                }
+                walker.delete_current()?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Undoes the writes of the blocks reverted by the notification, if any.
+    ///
+    /// Should be called for every notification before the committed chain is processed.
+    pub fn handle_notification(&self, notification: &ExExNotification) -> eyre::Result<()> {
+        if let Some(reverted_chain) = notification.reverted_chain() {
+            self.revert_to(reverted_chain.first().number.saturating_sub(1))?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the changesets of all blocks up to and including the given block number.
+    ///
+    /// The blocks can't be reverted afterwards, so it should only be called for finalized blocks.
+    pub fn prune(&self, block: BlockNumber) -> eyre::Result<()> {
+        let tx = self.db.tx_mut()?;
+        if pruned_height(&tx)?.map_or(true, |pruned_height| pruned_height < block) {
+            tx.put::<ExExStoreMetadata>(PRUNED_HEIGHT_KEY.to_vec(), block.to_be_bytes().to_vec())?;
+        }
+
+        {
+            let mut changesets = tx.cursor_write::<ExExStoreChangeSets>()?;
+            let mut walker = changesets.walk(None)?;
+            while let Some((changeset_key, _)) = walker.next().transpose()? {
+                if split_changeset_key(&changeset_key)?.0 > block {
+                    break
+                }
+                walker.delete_current()?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+/// Reads the pruned height from the [`ExExStoreMetadata`] table.
+fn pruned_height(tx: &impl DbTx) -> eyre::Result<Option<BlockNumber>> {
+    tx.get::<ExExStoreMetadata>(PRUNED_HEIGHT_KEY.to_vec())?
+        .map(|value| {
+            Ok(BlockNumber::from_be_bytes(
+                value.as_slice().try_into().wrap_err("invalid pruned height")?,
+            ))
+        })
+        .transpose()
+}
+
+/// Writes of a single block to the [`ExExStore`], created with [`ExExStore::block`].
+///
+/// Dropping it without calling [`ExExStoreBlock::commit`] discards the writes.
+#[derive(Debug)]
+pub struct ExExStoreBlock {
+    tx: Tx<RW>,
+    block: BlockNumHash,
+}
+
+impl ExExStoreBlock {
+    /// Returns the block that is being written.
+    pub const fn block(&self) -> BlockNumHash {
+        self.block
+    }
+
+    /// Returns the value of the key, including the writes of this block.
+    pub fn get(&self, key: &[u8]) -> eyre::Result<Option<Vec<u8>>> {
+        Ok(self.tx.get::<ExExStoreValues>(key.to_vec())?)
+    }
+
+    /// Sets the value of the key.
+    pub fn put(&self, key: &[u8], value: Vec<u8>) -> eyre::Result<()> {
+        self.record_previous(key)?;
+        self.tx.put::<ExExStoreValues>(key.to_vec(), value)?;
+        Ok(())
+    }
+
+    /// Removes the value of the key.
+    pub fn delete(&self, key: &[u8]) -> eyre::Result<()> {
+        self.record_previous(key)?;
+        self.tx.delete::<ExExStoreValues>(key.to_vec(), None)?;
+        Ok(())
+    }
+
+    /// Commits the writes together with the block, which becomes the head of the store.
+    ///
+    /// Returns the [`ExExEvent::FinishedHeight`] of the block, to be sent to the node.
+    pub fn commit(self) -> eyre::Result<ExExEvent> {
+        self.tx.put::<ExExStoreBlocks>(self.block.number, self.block.hash)?;
+        self.tx.commit()?;
+        Ok(ExExEvent::FinishedHeight(self.block.number))
+    }
+
+    /// Records the current value of the key in the changeset of the block, unless it was already
+    /// written by the block.
+    fn record_previous(&self, key: &[u8]) -> eyre::Result<()> {
+        let changeset_key = changeset_key(self.block.number, key);
+        if self.tx.get::<ExExStoreChangeSets>(changeset_key.clone())?.is_none() {
+            let previous = self.tx.get::<ExExStoreValues>(key.to_vec())?;
+            self.tx.put::<ExExStoreChangeSets>(changeset_key, encode_previous(previous))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the key of the changeset entry of the key in the given block.
+///
+/// The block number is encoded in big-endian, so that the entries are ordered by block.
+fn changeset_key(block: BlockNumber, key: &[u8]) -> Vec<u8> {
+    let mut changeset_key = Vec::with_capacity(8 + key.len());
+    changeset_key.extend_from_slice(&block.to_be_bytes());
+    changeset_key.extend_from_slice(key);
+    changeset_key
+}
+
+/// Splits the key of a changeset entry into the block number and the key.
+fn split_changeset_key(changeset_key: &[u8]) -> eyre::Result<(BlockNumber, &[u8])> {
+    ensure!(changeset_key.len() >= 8, "invalid ExEx store changeset key");
+    let (block, key) = changeset_key.split_at(8);
+    Ok((BlockNumber::from_be_bytes(block.try_into()?), key))
+}
+
+/// Encodes a value that was overwritten by a block. Absent values are encoded as empty bytes and
+/// present values are prefixed with `1`, so that empty values can be told apart.
+fn encode_previous(previous: Option<Vec<u8>>) -> Vec<u8> {
+    previous.map_or_else(Vec::new, |value| [&[1], value.as_slice()].concat())
+}
+
+/// Decodes a value encoded with [`encode_previous`].
+fn decode_previous(mut previous: Vec<u8>) -> Option<Vec<u8>> {
+    (!previous.is_empty()).then(|| {
+        previous.remove(0);
+        previous
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::Chain;
+    use reth_testing_utils::generators::{self, random_block};
+    use std::sync::Arc;
+
+    const fn block(number: BlockNumber) -> BlockNumHash {
+        BlockNumHash { number, hash: B256::with_last_byte(number as u8) }
+    }
+
+    #[test]
+    fn reverts_block_writes() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let store = ExExStore::open(temp_dir.path())?;
+        assert_eq!(store.head()?, None);
+
+        let writer = store.block(block(1))?;
+        writer.put(b"a", b"1".to_vec())?;
+        writer.put(b"b", Vec::new())?;
+        assert_eq!(writer.commit()?, ExExEvent::FinishedHeight(1));
+
+        let writer = store.block(block(2))?;
+        writer.put(b"a", b"2".to_vec())?;
+        writer.put(b"a", b"3".to_vec())?;
+        writer.delete(b"b")?;
+        writer.put(b"c", b"2".to_vec())?;
+        writer.commit()?;
+
+        // Writes below the head are rejected
+        assert!(store.block(block(2)).is_err());
+        // Uncommitted writes are discarded
+        store.block(block(3))?.put(b"a", b"4".to_vec())?;
+
+        assert_eq!(store.head()?, Some(ExExHead { block: block(2) }));
+        assert_eq!(store.get(b"a")?, Some(b"3".to_vec()));
+        assert_eq!(store.get(b"b")?, None);
+        assert_eq!(store.get(b"c")?, Some(b"2".to_vec()));
+
+        // Revert the second block with a notification
+        let reverted_block = random_block(&mut generators::rng(), 2, None, Some(0), None);
+        let notification = ExExNotification::ChainReverted {
+            old: Arc::new(Chain::new(
+                [reverted_block.seal_with_senders().unwrap()],
+                Default::default(),
+                None,
+            )),
+        };
+        store.handle_notification(&notification)?;
+
+        assert_eq!(store.head()?, Some(ExExHead { block: block(1) }));
+        assert_eq!(store.finished_height()?, Some(ExExEvent::FinishedHeight(1)));
+        assert_eq!(store.get(b"a")?, Some(b"1".to_vec()));
+        assert_eq!(store.get(b"b")?, Some(Vec::new()));
+        assert_eq!(store.get(b"c")?, None);
+
+        // Pruned blocks can't be reverted anymore
+        store.prune(1)?;
+        assert_eq!(store.pruned_height()?, Some(1));
+        assert!(store.revert_to(0).is_err());
+        assert_eq!(store.head()?, Some(ExExHead { block: block(1) }));
+        assert_eq!(store.get(b"a")?, Some(b"1".to_vec()));
+        // Reverting to the pruned height itself is a no-op
+        store.revert_to(1)?;
+        assert_eq!(store.head()?, Some(ExExHead { block: block(1) }));
+
+        // The store is persisted
+        drop(store);
+        let store = ExExStore::open(temp_dir.path())?;
+        assert_eq!(store.get(b"a")?, Some(b"1".to_vec()));
+
+        Ok(())
+    }
+}
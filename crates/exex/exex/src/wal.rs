@@ -0,0 +1,449 @@
+//! Write-ahead log of [`ExExNotification`]s.
+
+use crate::ExExNotification;
+use eyre::WrapErr;
+use reth_primitives::BlockNumber;
+use reth_tracing::tracing::{debug, error};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{self, File},
+    io::{BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::mpsc,
+    task::{Context, Poll},
+};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+/// File extension of the notification files.
+const NOTIFICATION_EXTENSION: &str = "wal";
+
+/// Name of the file with the progress of the `ExEx`'s.
+const PROGRESS_FILE: &str = "progress.json";
+
+/// The progress of an `ExEx` through the notifications of the [`Wal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExExProgress {
+    /// The last height the `ExEx` reported as finished.
+    pub finished_height: BlockNumber,
+    /// The ID of the first notification the `ExEx` didn't finish.
+    pub next_id: u64,
+}
+
+/// Durable write-ahead log of the [`ExExNotification`]s sent to the `ExEx`'s.
+///
+/// Every notification is written to its own file in the log directory as it's received, named
+/// `<ID>-<HEIGHT>.wal`, where `ID` is monotonically increasing and `HEIGHT` is the block number of
+/// the chain tip after the notification, see [`notification_height`]. The log also keeps the
+/// [`ExExProgress`] of every `ExEx`, so that after a restart each of them can be sent the
+/// notifications it didn't finish before.
+///
+/// Notifications are removed from the front of the log with [`Wal::finalize`] once all `ExEx`'s
+/// have finished them. Since heights go down on reverts, notifications are always tracked by their
+/// ID.
+#[derive(Debug)]
+pub struct Wal {
+    /// The directory of the log.
+    directory: PathBuf,
+    /// The notifications in the log, keyed by their ID, with the height they lead to.
+    entries: BTreeMap<u64, BlockNumber>,
+    /// The ID of the next notification.
+    next_id: u64,
+    /// The progress of each `ExEx`, keyed by its ID.
+    progress: HashMap<String, ExExProgress>,
+}
+
+impl Wal {
+    /// Opens the log in the given directory, creating the directory if it doesn't exist.
+    pub fn new(directory: impl AsRef<Path>) -> eyre::Result<Self> {
+        let directory = directory.as_ref().to_path_buf();
+        fs::create_dir_all(&directory)
+            .wrap_err_with(|| format!("failed to create ExEx WAL directory {directory:?}"))?;
+
+        let mut entries = BTreeMap::new();
+        for entry in fs::read_dir(&directory)? {
+            let path = entry?.path();
+            if path.extension().and_then(|extension| extension.to_str()) !=
+                Some(NOTIFICATION_EXTENSION)
+            {
+                continue
+            }
+
+            let (id, height) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.split_once('-'))
+                .and_then(|(id, height)| Some((id.parse().ok()?, height.parse().ok()?)))
+                .ok_or_else(|| eyre::eyre!("invalid ExEx WAL file name {path:?}"))?;
+            entries.insert(id, height);
+        }
+        let next_id = entries.last_key_value().map_or(0, |(id, _)| id + 1);
+
+        let progress_path = directory.join(PROGRESS_FILE);
+        let progress = if progress_path.exists() {
+            serde_json::from_reader(BufReader::new(File::open(&progress_path)?))
+                .wrap_err_with(|| format!("failed to read {progress_path:?}"))?
+        } else {
+            HashMap::new()
+        };
+
+        debug!(target: "exex::wal", ?directory, notifications = entries.len(), "Opened WAL");
+        Ok(Self { directory, entries, next_id, progress })
+    }
+
+    /// Returns the ID of the first notification in the log, or of the next one if the log is
+    /// empty.
+    pub fn first_id(&self) -> u64 {
+        self.entries.first_key_value().map_or(self.next_id, |(id, _)| *id)
+    }
+
+    /// Returns the number of notifications in the log.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no notifications in the log.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Durably appends the notification to the log.
+    pub fn commit(&mut self, notification: &ExExNotification) -> eyre::Result<()> {
+        let id = self.next_id;
+        let height = notification_height(notification);
+
+        // Write to a temporary file first, so that a crash never leaves a partially written
+        // notification behind.
+        let path = self.notification_path(id, height);
+        let tmp_path = path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        serde_json::to_writer(&mut writer, notification)?;
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        fs::rename(&tmp_path, &path)?;
+        self.sync_directory()?;
+
+        debug!(target: "exex::wal", id, height, "Committed notification");
+        self.entries.insert(id, height);
+        self.next_id += 1;
+        Ok(())
+    }
+
+    /// Reads all notifications in the log, in the order they were committed.
+    pub fn notifications(&self) -> eyre::Result<Vec<ExExNotification>> {
+        self.entries
+            .iter()
+            .map(|(&id, &height)| {
+                let path = self.notification_path(id, height);
+                serde_json::from_reader(BufReader::new(File::open(&path)?))
+                    .wrap_err_with(|| format!("failed to read ExEx WAL file {path:?}"))
+            })
+            .collect()
+    }
+
+    /// Removes the notifications with an ID lower than the given one from the log.
+    ///
+    /// Returns the number of removed notifications.
+    pub fn finalize(&mut self, next_id: u64) -> eyre::Result<usize> {
+        let mut removed = 0;
+        while let Some((&id, &notification_height)) = self.entries.first_key_value() {
+            if id >= next_id {
+                break
+            }
+
+            fs::remove_file(self.notification_path(id, notification_height))?;
+            self.entries.remove(&id);
+            removed += 1;
+        }
+
+        if removed > 0 {
+            self.sync_directory()?;
+            debug!(target: "exex::wal", next_id, removed, "Finalized notifications");
+        }
+        Ok(removed)
+    }
+
+    /// Returns the progress of the `ExEx`, if it was ever recorded.
+    pub fn progress(&self, exex_id: &str) -> Option<ExExProgress> {
+        self.progress.get(exex_id).copied()
+    }
+
+    /// Records the progress of the `ExEx`.
+    pub fn set_progress(&mut self, exex_id: &str, progress: ExExProgress) -> eyre::Result<()> {
+        if self.progress.insert(exex_id.to_string(), progress) == Some(progress) {
+            return Ok(())
+        }
+
+        let path = self.directory.join(PROGRESS_FILE);
+        let tmp_path = path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        serde_json::to_writer(&mut writer, &self.progress)?;
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        fs::rename(&tmp_path, &path)?;
+        self.sync_directory()
+    }
+
+    /// Syncs the log directory, so that created, renamed and removed files survive a crash.
+    fn sync_directory(&self) -> eyre::Result<()> {
+        File::open(&self.directory)?.sync_all()?;
+        Ok(())
+    }
+
+    fn notification_path(&self, id: u64, height: BlockNumber) -> PathBuf {
+        self.directory.join(format!("{id}-{height}.{NOTIFICATION_EXTENSION}"))
+    }
+}
+
+/// An action performed on the [`Wal`] by the [`WalWriter`].
+#[derive(Debug)]
+enum WalAction {
+    /// See [`Wal::commit`].
+    Commit(ExExNotification),
+    /// See [`Wal::set_progress`].
+    SetProgress(String, ExExProgress),
+    /// See [`Wal::finalize`].
+    Finalize(u64),
+    /// Sends a message once all previous actions are done.
+    #[cfg(test)]
+    Flush(mpsc::SyncSender<()>),
+}
+
+/// Writes to a [`Wal`] on a dedicated thread, so that its IO doesn't block the
+/// [`ExExManager`](crate::ExExManager).
+///
+/// Writes are performed in the order they were requested, and every committed notification is
+/// reported back through [`WalWriter::poll_committed`], so that notifications are only sent to the
+/// `ExEx`'s once they are durable. If any write fails, the thread stops writing and reports the
+/// error instead, leaving the log as it was. Dropping the writer waits for the pending writes.
+#[derive(Debug)]
+pub(crate) struct WalWriter {
+    tx: Option<mpsc::Sender<WalAction>>,
+    thread: Option<std::thread::JoinHandle<()>>,
+    /// Receives `Ok` for every committed notification, and the error of the failed write.
+    results: UnboundedReceiver<eyre::Result<()>>,
+    /// The ID the log was last finalized at, to skip redundant finalizations.
+    finalized_id: Option<u64>,
+}
+
+impl WalWriter {
+    /// Spawns the thread writing to the log.
+    pub(crate) fn spawn(mut wal: Wal) -> eyre::Result<Self> {
+        let (tx, rx) = mpsc::channel();
+        let (results_tx, results) = unbounded_channel();
+        let thread = std::thread::Builder::new()
+            .name("ExEx WAL".to_string())
+            .spawn(move || Self::run(&mut wal, rx, results_tx))?;
+
+        Ok(Self { tx: Some(tx), thread: Some(thread), results, finalized_id: None })
+    }
+
+    /// Performs the requested actions until the writer is dropped or a write fails.
+    fn run(
+        wal: &mut Wal,
+        rx: mpsc::Receiver<WalAction>,
+        results: UnboundedSender<eyre::Result<()>>,
+    ) {
+        for action in rx {
+            let result = match action {
+                WalAction::Commit(notification) => {
+                    wal.commit(&notification).map(|()| drop(results.send(Ok(()))))
+                }
+                WalAction::SetProgress(exex_id, progress) => wal.set_progress(&exex_id, progress),
+                WalAction::Finalize(next_id) => wal.finalize(next_id).map(|_| ()),
+                #[cfg(test)]
+                WalAction::Flush(tx) => {
+                    let _ = tx.send(());
+                    Ok(())
+                }
+            };
+            if let Err(err) = result {
+                error!(target: "exex::wal", %err, "Failed to write to the WAL");
+                let _ = results.send(Err(err));
+                return
+            }
+        }
+    }
+
+    /// Appends the notification to the log.
+    ///
+    /// It is counted by [`WalWriter::poll_committed`] once it is durable.
+    pub(crate) fn commit(&self, notification: ExExNotification) {
+        self.send(WalAction::Commit(notification))
+    }
+
+    /// Records the progress of the `ExEx`.
+    pub(crate) fn set_progress(&self, exex_id: String, progress: ExExProgress) {
+        self.send(WalAction::SetProgress(exex_id, progress))
+    }
+
+    /// Removes the notifications with an ID lower than the given one from the log.
+    pub(crate) fn finalize(&mut self, next_id: u64) {
+        if self.finalized_id != Some(next_id) {
+            self.finalized_id = Some(next_id);
+            self.send(WalAction::Finalize(next_id))
+        }
+    }
+
+    /// Returns the number of notifications committed since the last call, or the error of the
+    /// failed write.
+    ///
+    /// Registers the waker of the context to be woken up once the next notification is committed.
+    pub(crate) fn poll_committed(&mut self, cx: &mut Context<'_>) -> eyre::Result<usize> {
+        let mut committed = 0;
+        loop {
+            match self.results.poll_recv(cx) {
+                Poll::Ready(Some(result)) => {
+                    result?;
+                    committed += 1;
+                }
+                Poll::Ready(None) => eyre::bail!("the ExEx WAL writer stopped"),
+                Poll::Pending => return Ok(committed),
+            }
+        }
+    }
+
+    /// Waits until all previously requested writes are done.
+    #[cfg(test)]
+    pub(crate) fn flush(&self) {
+        let (tx, rx) = mpsc::sync_channel(1);
+        self.send(WalAction::Flush(tx));
+        let _ = rx.recv();
+    }
+
+    fn send(&self, action: WalAction) {
+        // the thread only exits once the writer is dropped, or a write failed, which is reported
+        // by `poll_committed`
+        if let Some(tx) = &self.tx {
+            let _ = tx.send(action);
+        }
+    }
+}
+
+impl Drop for WalWriter {
+    fn drop(&mut self) {
+        // closing the channel stops the thread once the pending writes are done
+        self.tx.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Returns the block number of the chain tip after the notification: the tip of the committed
+/// chain, or the block preceding the reverted chain if nothing was committed.
+pub fn notification_height(notification: &ExExNotification) -> BlockNumber {
+    match notification.committed_chain() {
+        Some(new) => new.tip().number,
+        None => notification.reverted_chain().map_or(0, |old| old.first().number.saturating_sub(1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Address, B256, U256};
+    use reth_provider::{Chain, ExecutionOutcome};
+    use reth_revm::{db::BundleState, primitives::AccountInfo};
+    use reth_testing_utils::generators::{self, random_block_range};
+    use std::{ops::RangeInclusive, sync::Arc};
+
+    fn chain(rng: &mut impl rand::Rng, range: RangeInclusive<u64>) -> Arc<Chain> {
+        let first_block = *range.start();
+        let blocks = random_block_range(rng, range, B256::ZERO, 0..3)
+            .into_iter()
+            .map(|block| block.seal_with_senders().unwrap())
+            .collect::<Vec<_>>();
+        let execution_outcome = ExecutionOutcome::new(
+            BundleState::new(
+                vec![(
+                    Address::with_last_byte(1),
+                    None,
+                    Some(AccountInfo { balance: U256::from(10), ..Default::default() }),
+                    HashMap::from([(U256::from(1), (U256::ZERO, U256::from(2)))]),
+                )],
+                vec![vec![(Address::with_last_byte(1), None, vec![(U256::from(1), U256::ZERO)])]],
+                vec![],
+            ),
+            vec![vec![]; blocks.len()].into(),
+            first_block,
+            vec![],
+        );
+        Arc::new(Chain::new(blocks, execution_outcome, None))
+    }
+
+    #[test]
+    fn commit_reopen_finalize() {
+        let mut rng = generators::rng();
+        let directory = tempfile::tempdir().unwrap();
+
+        let committed = ExExNotification::ChainCommitted { new: chain(&mut rng, 1..=2) };
+        let reorged = ExExNotification::ChainReorged {
+            old: chain(&mut rng, 2..=2),
+            new: chain(&mut rng, 2..=3),
+        };
+        let reverted = ExExNotification::ChainReverted { old: chain(&mut rng, 3..=3) };
+
+        let mut wal = Wal::new(directory.path()).unwrap();
+        assert!(wal.is_empty());
+        for notification in [&committed, &reorged, &reverted] {
+            wal.commit(notification).unwrap();
+        }
+        let progress = ExExProgress { finished_height: 3, next_id: 2 };
+        wal.set_progress("exex", progress).unwrap();
+
+        // notifications and progress survive a restart
+        let mut wal = Wal::new(directory.path()).unwrap();
+        assert_eq!(
+            wal.notifications().unwrap(),
+            vec![committed, reorged.clone(), reverted.clone()]
+        );
+        assert_eq!(wal.progress("exex"), Some(progress));
+        assert_eq!(wal.progress("other"), None);
+
+        // the revert leads back to height 2, below the finished height, but is kept until its ID
+        // is finalized
+        assert_eq!(wal.finalize(2).unwrap(), 2);
+        assert_eq!(wal.notifications().unwrap(), vec![reverted]);
+        assert_eq!(wal.first_id(), 2);
+        assert_eq!(wal.finalize(3).unwrap(), 1);
+        assert!(wal.is_empty());
+        assert_eq!(wal.first_id(), 3);
+
+        // IDs keep increasing after the log was emptied
+        let committed = ExExNotification::ChainCommitted { new: chain(&mut rng, 3..=4) };
+        wal.commit(&committed).unwrap();
+        let wal = Wal::new(directory.path()).unwrap();
+        assert_eq!(wal.notifications().unwrap(), vec![committed]);
+        assert_eq!(wal.next_id, 4);
+    }
+
+    #[test]
+    fn writer_reports_committed_notifications_and_errors() {
+        let mut rng = generators::rng();
+        let directory = tempfile::tempdir().unwrap();
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let mut writer = WalWriter::spawn(Wal::new(directory.path()).unwrap()).unwrap();
+        let committed = ExExNotification::ChainCommitted { new: chain(&mut rng, 1..=1) };
+        writer.commit(committed.clone());
+        writer.flush();
+        assert_eq!(writer.poll_committed(&mut cx).unwrap(), 1);
+        assert_eq!(writer.poll_committed(&mut cx).unwrap(), 0);
+
+        // the next commit fails, because its temporary file can't be created
+        fs::create_dir(directory.path().join("1-2.tmp")).unwrap();
+        writer.commit(ExExNotification::ChainCommitted { new: chain(&mut rng, 2..=2) });
+        writer.flush();
+        assert!(writer.poll_committed(&mut cx).is_err());
+
+        // nothing is written after the failure, and the log is left as it was
+        writer.set_progress("exex".to_string(), ExExProgress { finished_height: 1, next_id: 1 });
+        writer.finalize(1);
+        drop(writer);
+        let wal = Wal::new(directory.path()).unwrap();
+        assert_eq!(wal.notifications().unwrap(), vec![committed]);
+        assert_eq!(wal.progress("exex"), None);
+    }
+}
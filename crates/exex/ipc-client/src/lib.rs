@@ -0,0 +1,140 @@
+//! Client for the IPC `ExEx` of reth.
+//!
+//! Allows an `ExEx` to run in its own process, receiving the [`ExExNotification`]s of the node
+//! over a local socket, split into one [`ExExIpcNotification`] per block. See
+//! [`ipc`](reth_exex_types::ipc) for the wire format.
+//!
+//! ```no_run
+//! # async fn t() -> Result<(), reth_exex_ipc_client::ExExIpcClientError> {
+//! use reth_exex_ipc_client::ExExIpcClient;
+//!
+//! let mut client = ExExIpcClient::connect("/tmp/reth-exex.ipc").await?;
+//! while let Some((id, notification)) = client.next_notification().await? {
+//!     // process the notification, then acknowledge it
+//!     client.ack(id).await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use futures::{SinkExt, StreamExt, TryFutureExt};
+use interprocess::local_socket::{
+    tokio::{prelude::*, Stream as LocalSocketStream},
+    GenericFilePath,
+};
+use reth_exex_types::ipc::{
+    ExExIpcClientMessage, ExExIpcServerMessage, EXEX_IPC_MAX_FRAME_LENGTH,
+    EXEX_IPC_PROTOCOL_VERSION,
+};
+use std::io;
+use tokio_util::codec::{Framed, LengthDelimitedCodec};
+
+pub use reth_exex_types::{
+    ipc::{self, ExExIpcNotification},
+    ExExNotification,
+};
+
+/// Errors of the [`ExExIpcClient`].
+#[derive(Debug, thiserror::Error)]
+pub enum ExExIpcClientError {
+    /// Failed to connect to the socket of the node.
+    #[error("failed to connect to {path}: {err}")]
+    FailedToConnect {
+        /// The path of the socket.
+        path: String,
+        /// The underlying error.
+        #[source]
+        err: io::Error,
+    },
+    /// The node rejected the connection.
+    #[error("connection rejected: {0}")]
+    Rejected(String),
+    /// The node closed the connection before the handshake was completed.
+    #[error("connection closed during handshake")]
+    Closed,
+    /// The node sent a message that is not valid at this point of the protocol.
+    #[error("unexpected message from the node: {0:?}")]
+    UnexpectedMessage(Box<ExExIpcServerMessage>),
+    /// IO error.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// Failed to encode or decode a message.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// Client of the IPC `ExEx` of reth.
+#[derive(Debug)]
+pub struct ExExIpcClient {
+    framed: Framed<LocalSocketStream, LengthDelimitedCodec>,
+}
+
+impl ExExIpcClient {
+    /// Connects to the IPC `ExEx` at the given socket path and performs the handshake.
+    pub async fn connect(path: &str) -> Result<Self, ExExIpcClientError> {
+        let stream = async { path.to_fs_name::<GenericFilePath>() }
+            .and_then(LocalSocketStream::connect)
+            .await
+            .map_err(|err| ExExIpcClientError::FailedToConnect { path: path.to_string(), err })?;
+
+        let mut client = Self {
+            framed: Framed::new(
+                stream,
+                LengthDelimitedCodec::builder()
+                    .max_frame_length(EXEX_IPC_MAX_FRAME_LENGTH)
+                    .new_codec(),
+            ),
+        };
+        client.send(&ExExIpcClientMessage::Hello { version: EXEX_IPC_PROTOCOL_VERSION }).await?;
+        match client.recv().await?.ok_or(ExExIpcClientError::Closed)? {
+            ExExIpcServerMessage::Welcome { .. } => Ok(client),
+            ExExIpcServerMessage::Rejected { reason } => Err(ExExIpcClientError::Rejected(reason)),
+            message => Err(ExExIpcClientError::UnexpectedMessage(Box::new(message))),
+        }
+    }
+
+    /// Waits for the next notification and returns it along with its ID.
+    ///
+    /// Returns `None` if the node closed the connection. Notifications that were not acknowledged
+    /// with [`ExExIpcClient::ack`] are sent again after reconnecting.
+    pub async fn next_notification(
+        &mut self,
+    ) -> Result<Option<(u64, ExExIpcNotification)>, ExExIpcClientError> {
+        match self.recv().await? {
+            Some(ExExIpcServerMessage::Notification { id, notification }) => {
+                Ok(Some((id, notification)))
+            }
+            Some(message) => Err(ExExIpcClientError::UnexpectedMessage(Box::new(message))),
+            None => Ok(None),
+        }
+    }
+
+    /// Acknowledges that the notification with the given ID and all notifications before it were
+    /// processed.
+    ///
+    /// The node only sends a limited number of notifications ahead of the acknowledgements, so
+    /// notifications must be acknowledged to keep receiving new ones.
+    pub async fn ack(&mut self, id: u64) -> Result<(), ExExIpcClientError> {
+        self.send(&ExExIpcClientMessage::Ack { id }).await
+    }
+
+    async fn send(&mut self, message: &ExExIpcClientMessage) -> Result<(), ExExIpcClientError> {
+        self.framed.send(serde_json::to_vec(message)?.into()).await?;
+        Ok(())
+    }
+
+    async fn recv(&mut self) -> Result<Option<ExExIpcServerMessage>, ExExIpcClientError> {
+        match self.framed.next().await {
+            Some(frame) => Ok(Some(serde_json::from_slice(&frame?)?)),
+            None => Ok(None),
+        }
+    }
+}
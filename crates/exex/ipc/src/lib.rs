@@ -0,0 +1,426 @@
+//! An `ExEx` serving [`ExExNotification`]s to an `ExEx` running in another process.
+//!
+//! The [`IpcExEx`] listens on a local socket and streams the notifications of the node to a single
+//! connected client, such as the `reth-exex-ipc-client` crate. A new client replaces the connected
+//! one. The client acknowledges every processed notification, and the [`IpcExEx`] only reports the
+//! finished height of acknowledged notifications to the node. See [`ipc`](reth_exex_types::ipc)
+//! for the wire format.
+//!
+//! [`ExExNotification`]: reth_exex::ExExNotification
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use eyre::OptionExt;
+use futures::{future, stream::FuturesUnordered, FutureExt, SinkExt, StreamExt, TryStreamExt};
+use interprocess::local_socket::{
+    tokio::{prelude::*, Listener, RecvHalf, SendHalf, Stream as LocalSocketStream},
+    GenericFilePath, ListenerOptions,
+};
+use reth_exex::{ExExContext, ExExEvent};
+use reth_exex_types::ipc::{
+    ExExIpcClientMessage, ExExIpcNotification, ExExIpcServerMessage,
+    EXEX_IPC_MAX_CLIENT_FRAME_LENGTH, EXEX_IPC_MAX_FRAME_LENGTH, EXEX_IPC_PROTOCOL_VERSION,
+};
+use reth_node_api::FullNodeComponents;
+use reth_tracing::tracing::{debug, info, warn};
+use std::{collections::VecDeque, time::Duration};
+use tokio_util::codec::{FramedRead, FramedWrite, LengthDelimitedCodec};
+
+/// Default number of notifications sent to the client ahead of its acknowledgements.
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 16;
+
+/// Time given to a client to complete the handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A client that completed the handshake.
+struct Client {
+    reader: FramedRead<RecvHalf, LengthDelimitedCodec>,
+    writer: FramedWrite<SendHalf, LengthDelimitedCodec>,
+    /// The number of queued notifications sent to the client.
+    sent: usize,
+}
+
+/// `ExEx` serving the notifications of the node over a local socket.
+///
+/// At most the maximum number of notifications are in flight, i.e. sent but not acknowledged, and
+/// notifications are only read from the node while fewer than that are queued, so a slow client
+/// applies backpressure to the node like any other `ExEx`. Notifications that were not
+/// acknowledged are sent again to the next client.
+///
+/// While no client is connected, notifications are queued until the queue is full, and then no
+/// more are read from the node, so no notification is ever dropped and the node only prunes the
+/// blocks a client acknowledged.
+pub struct IpcExEx<Node: FullNodeComponents> {
+    ctx: ExExContext<Node>,
+    listener: Listener,
+    /// The maximum number of notifications not acknowledged by the client.
+    max_in_flight: usize,
+    /// The notifications not acknowledged by the client yet, with their IDs.
+    queue: VecDeque<(u64, ExExIpcNotification)>,
+    /// The ID of the next notification.
+    next_id: u64,
+}
+
+impl<Node: FullNodeComponents> std::fmt::Debug for IpcExEx<Node> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IpcExEx")
+            .field("ctx", &self.ctx)
+            .field("max_in_flight", &self.max_in_flight)
+            .field("queue", &self.queue.len())
+            .field("next_id", &self.next_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Node: FullNodeComponents> IpcExEx<Node> {
+    /// Creates a new [`IpcExEx`] listening on the given socket path.
+    ///
+    /// On unix, a socket left at the path by a previous run is removed. Any other existing file at
+    /// the path is an error.
+    pub fn new(ctx: ExExContext<Node>, path: &str) -> eyre::Result<Self> {
+        #[cfg(unix)]
+        remove_stale_socket(path)?;
+
+        let listener = path
+            .to_fs_name::<GenericFilePath>()
+            .and_then(|name| ListenerOptions::new().name(name).create_tokio())?;
+        info!(target: "exex::ipc", path, "Listening for ExEx clients");
+
+        Ok(Self {
+            ctx,
+            listener,
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT,
+            queue: VecDeque::new(),
+            next_id: 0,
+        })
+    }
+
+    /// Sets the maximum number of notifications sent to the client ahead of its
+    /// acknowledgements.
+    pub const fn with_max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = max_in_flight;
+        self
+    }
+
+    /// Runs the `ExEx` until the node stops sending notifications.
+    pub async fn run(mut self) -> eyre::Result<()> {
+        let mut client: Option<Client> = None;
+        let mut handshakes = FuturesUnordered::new();
+
+        loop {
+            tokio::select! {
+                stream = self.listener.accept() => {
+                    match stream {
+                        Ok(stream) => handshakes.push(
+                            tokio::time::timeout(HANDSHAKE_TIMEOUT, handshake(stream)).boxed(),
+                        ),
+                        Err(err) => warn!(target: "exex::ipc", %err, "Failed to accept a client"),
+                    }
+                }
+                Some(handshake) = handshakes.next(), if !handshakes.is_empty() => {
+                    match handshake {
+                        Ok(Ok(new_client)) => {
+                            if client.replace(new_client).is_some() {
+                                info!(target: "exex::ipc", "ExEx client replaced by a new connection");
+                            }
+                            info!(target: "exex::ipc", queued = self.queue.len(), "ExEx client connected");
+                        }
+                        Ok(Err(err)) => debug!(target: "exex::ipc", %err, "Client handshake failed"),
+                        Err(_) => debug!(target: "exex::ipc", "Client handshake timed out"),
+                    }
+                }
+                notification = self.ctx.notifications.try_next(),
+                    if self.queue.len() < self.max_in_flight =>
+                {
+                    let Some(notification) = notification? else { return Ok(()) };
+
+                    for notification in ExExIpcNotification::from_exex_notification(&notification) {
+                        self.queue.push_back((self.next_id, notification));
+                        self.next_id += 1;
+                    }
+                }
+                message = recv(&mut client) => {
+                    match message {
+                        Ok(ExExIpcClientMessage::Ack { id }) => {
+                            let acked = self.ack(id)?;
+                            if let Some(client) = &mut client {
+                                client.sent = client.sent.saturating_sub(acked);
+                            }
+                        }
+                        Ok(message) => {
+                            warn!(target: "exex::ipc", ?message, "Unexpected message from the ExEx client, disconnecting");
+                            client = None;
+                        }
+                        Err(err) => {
+                            info!(target: "exex::ipc", %err, "ExEx client disconnected");
+                            client = None;
+                        }
+                    }
+                }
+            }
+
+            if let Some(connected) = &mut client {
+                if let Err(err) = self.send_queued(connected).await {
+                    info!(target: "exex::ipc", %err, "ExEx client disconnected");
+                    client = None;
+                }
+            }
+        }
+    }
+
+    /// Sends the queued notifications not sent to the client yet, keeping at most the maximum
+    /// number of notifications in flight.
+    async fn send_queued(&self, client: &mut Client) -> eyre::Result<()> {
+        for (id, notification) in self.queue.iter().take(self.max_in_flight).skip(client.sent) {
+            let message =
+                ExExIpcServerMessage::Notification { id: *id, notification: notification.clone() };
+            send(&mut client.writer, &message).await?;
+            client.sent += 1;
+        }
+        Ok(())
+    }
+
+    /// Removes the acknowledged notifications and reports the chain tip after the last of them as
+    /// the finished height.
+    ///
+    /// Returns the number of removed notifications.
+    fn ack(&mut self, id: u64) -> eyre::Result<usize> {
+        let mut acked = 0;
+        let mut finished_height = None;
+        while self.queue.front().is_some_and(|(notification_id, _)| *notification_id <= id) {
+            let (_, notification) = self.queue.pop_front().expect("not empty");
+            finished_height = Some(notification.tip_number());
+            acked += 1;
+        }
+
+        if let Some(height) = finished_height {
+            debug!(target: "exex::ipc", id, height, "ExEx client acknowledged notifications");
+            self.ctx.events.send(ExExEvent::FinishedHeight(height))?;
+        }
+        Ok(acked)
+    }
+}
+
+/// Removes the socket at the given path, left by a previous run.
+#[cfg(unix)]
+fn remove_stale_socket(path: &str) -> eyre::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => {
+            std::fs::remove_file(path)?;
+            debug!(target: "exex::ipc", path, "Removed existing IPC socket file");
+            Ok(())
+        }
+        Ok(_) => eyre::bail!("{path} exists and is not a socket"),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Waits for the hello of the client and accepts or rejects it based on its protocol version.
+async fn handshake(stream: LocalSocketStream) -> eyre::Result<Client> {
+    let (reader, writer) = stream.split();
+    let mut client = Client {
+        reader: FramedRead::new(
+            reader,
+            LengthDelimitedCodec::builder()
+                .max_frame_length(EXEX_IPC_MAX_CLIENT_FRAME_LENGTH)
+                .new_codec(),
+        ),
+        writer: FramedWrite::new(
+            writer,
+            LengthDelimitedCodec::builder().max_frame_length(EXEX_IPC_MAX_FRAME_LENGTH).new_codec(),
+        ),
+        sent: 0,
+    };
+
+    let message = client.reader.next().await.ok_or_eyre("connection closed")??;
+    let version = match serde_json::from_slice(&message)? {
+        ExExIpcClientMessage::Hello { version } => version,
+        message => eyre::bail!("expected hello, got {message:?}"),
+    };
+
+    if version != EXEX_IPC_PROTOCOL_VERSION {
+        let reason =
+            format!("unsupported protocol version {version}, expected {EXEX_IPC_PROTOCOL_VERSION}");
+        send(&mut client.writer, &ExExIpcServerMessage::Rejected { reason: reason.clone() })
+            .await?;
+        eyre::bail!(reason)
+    }
+
+    send(&mut client.writer, &ExExIpcServerMessage::Welcome { version: EXEX_IPC_PROTOCOL_VERSION })
+        .await?;
+    Ok(client)
+}
+
+async fn send(
+    writer: &mut FramedWrite<SendHalf, LengthDelimitedCodec>,
+    message: &ExExIpcServerMessage,
+) -> eyre::Result<()> {
+    writer.send(serde_json::to_vec(message)?.into()).await?;
+    Ok(())
+}
+
+/// Receives the next message from the client, or waits forever if there is none.
+async fn recv(client: &mut Option<Client>) -> eyre::Result<ExExIpcClientMessage> {
+    let Some(client) = client else { return future::pending().await };
+    let message = client.reader.next().await.ok_or_eyre("connection closed")??;
+    Ok(serde_json::from_slice(&message)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_exex_ipc_client::ExExIpcClient;
+    use reth_exex_test_utils::test_exex_context;
+    use reth_provider::Chain;
+    use tokio_util::codec::Framed;
+
+    fn socket_path() -> String {
+        let num: u64 = rand::random();
+        if cfg!(windows) {
+            format!(r"\\.\pipe\reth-exex-ipc-{num}")
+        } else {
+            format!("/tmp/reth-exex-ipc-{num}.ipc")
+        }
+    }
+
+    #[tokio::test]
+    async fn streams_and_acknowledges_notifications() -> eyre::Result<()> {
+        let (ctx, mut handle) = test_exex_context().await?;
+        let path = socket_path();
+        let exex = tokio::spawn(IpcExEx::new(ctx, &path)?.with_max_in_flight(1).run());
+
+        let mut client = ExExIpcClient::connect(&path).await?;
+        let genesis = Chain::from_block(handle.genesis.clone(), Default::default(), None);
+        handle.send_notification_chain_committed(genesis.clone()).await?;
+
+        let committed = ExExIpcNotification::from_exex_notification(
+            &reth_exex::ExExNotification::ChainCommitted { new: genesis.clone().into() },
+        );
+        let [committed @ ExExIpcNotification::BlockCommitted { .. }] = committed.as_slice() else {
+            panic!("expected a single committed block, got {committed:?}")
+        };
+        assert_eq!(client.next_notification().await?, Some((0, committed.clone())));
+
+        // a reconnecting client receives the notifications that weren't acknowledged again
+        drop(client);
+        let mut client = ExExIpcClient::connect(&path).await?;
+        assert_eq!(client.next_notification().await?, Some((0, committed.clone())));
+
+        // no more notifications are sent before the acknowledgement
+        handle.send_notification_chain_reverted(genesis).await?;
+        assert!(tokio::time::timeout(Duration::from_millis(100), client.next_notification())
+            .await
+            .is_err());
+        handle.assert_events_empty();
+
+        client.ack(0).await?;
+        assert_eq!(handle.events_rx.recv().await, Some(ExExEvent::FinishedHeight(0)));
+        let Some((1, ExExIpcNotification::BlockReverted { block })) =
+            client.next_notification().await?
+        else {
+            panic!("expected the reverted block")
+        };
+        assert_eq!(block.hash, handle.genesis.hash());
+
+        exex.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn queues_notifications_without_client() -> eyre::Result<()> {
+        let (ctx, mut handle) = test_exex_context().await?;
+        let path = socket_path();
+        let exex = tokio::spawn(IpcExEx::new(ctx, &path)?.with_max_in_flight(2).run());
+
+        let genesis = Chain::from_block(handle.genesis.clone(), Default::default(), None);
+        handle.send_notification_chain_committed(genesis.clone()).await?;
+        handle.send_notification_chain_reverted(genesis.clone()).await?;
+        handle.send_notification_chain_committed(genesis).await?;
+
+        // nothing is reported as finished without a client
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        handle.assert_events_empty();
+
+        // the client receives every notification, including the revert
+        let mut client = ExExIpcClient::connect(&path).await?;
+        let Some((0, ExExIpcNotification::BlockCommitted { .. })) =
+            client.next_notification().await?
+        else {
+            panic!("expected the committed block")
+        };
+        let Some((1, ExExIpcNotification::BlockReverted { .. })) =
+            client.next_notification().await?
+        else {
+            panic!("expected the reverted block")
+        };
+
+        // the revert leads back to the parent of the genesis block
+        client.ack(1).await?;
+        assert_eq!(handle.events_rx.recv().await, Some(ExExEvent::FinishedHeight(0)));
+        let Some((2, ExExIpcNotification::BlockCommitted { .. })) =
+            client.next_notification().await?
+        else {
+            panic!("expected the last committed block")
+        };
+
+        exex.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn slow_handshake_does_not_block_other_clients() -> eyre::Result<()> {
+        let (ctx, _handle) = test_exex_context().await?;
+        let path = socket_path();
+        let exex = tokio::spawn(IpcExEx::new(ctx, &path)?.run());
+
+        // connects without ever sending the hello
+        let _silent =
+            LocalSocketStream::connect(path.as_str().to_fs_name::<GenericFilePath>()?).await?;
+        tokio::time::timeout(Duration::from_secs(1), ExExIpcClient::connect(&path)).await??;
+
+        exex.abort();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rejects_unsupported_protocol_version() -> eyre::Result<()> {
+        let (ctx, _handle) = test_exex_context().await?;
+        let path = socket_path();
+        let exex = tokio::spawn(IpcExEx::new(ctx, &path)?.run());
+
+        let stream =
+            LocalSocketStream::connect(path.as_str().to_fs_name::<GenericFilePath>()?).await?;
+        let mut client = Framed::new(stream, LengthDelimitedCodec::new());
+        let hello = ExExIpcClientMessage::Hello { version: EXEX_IPC_PROTOCOL_VERSION + 1 };
+        client.send(serde_json::to_vec(&hello)?.into()).await?;
+
+        let message = client.next().await.unwrap()?;
+        assert!(matches!(serde_json::from_slice(&message)?, ExExIpcServerMessage::Rejected { .. }));
+
+        exex.abort();
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn refuses_to_replace_regular_file() -> eyre::Result<()> {
+        let (ctx, _handle) = test_exex_context().await?;
+        let path = socket_path();
+        std::fs::write(&path, b"not a socket")?;
+
+        assert!(IpcExEx::new(ctx, &path).is_err());
+        assert_eq!(std::fs::read(&path)?, b"not a socket");
+
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
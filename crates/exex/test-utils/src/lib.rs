@@ -16,7 +16,7 @@ use reth_db::{test_utils::TempDatabase, DatabaseEnv};
 use reth_db_common::init::init_genesis;
 use reth_evm::test_utils::MockExecutorProvider;
 use reth_execution_types::Chain;
-use reth_exex::{ExExContext, ExExEvent, ExExNotification};
+use reth_exex::{ExExContext, ExExEvent, ExExNotification, ExExNotifications};
 use reth_network::{config::SecretKey, NetworkConfigBuilder, NetworkManager};
 use reth_node_api::{FullNodeTypes, FullNodeTypesAdapter, NodeTypes};
 use reth_node_builder::{
@@ -48,6 +48,9 @@ use std::{
 use thiserror::Error;
 use tokio::sync::mpsc::{Sender, UnboundedReceiver};
 
+mod scenario;
+pub use scenario::*;
+
 /// A test [`PoolBuilder`] that builds a [`TestPool`].
 #[derive(Debug, Default, Clone, Copy)]
 #[non_exhaustive]
@@ -251,13 +254,13 @@ pub async fn test_exex_context_with_chain_spec(
         components: Components {
             transaction_pool,
             evm_config,
-            executor,
+            executor: executor.clone(),
             consensus,
             network,
             payload_builder,
         },
         task_executor,
-        provider,
+        provider: provider.clone(),
     };
 
     let genesis = provider_factory
@@ -279,11 +282,12 @@ pub async fn test_exex_context_with_chain_spec(
     let (notifications_tx, notifications_rx) = tokio::sync::mpsc::channel(1);
 
     let ctx = ExExContext {
+        id: "test-exex".to_string(),
         head,
         config: NodeConfig::test(),
         reth_config: reth_config::Config::default(),
         events: events_tx,
-        notifications: notifications_rx,
+        notifications: ExExNotifications::new(head, provider, executor, notifications_rx),
         components,
     };
 
@@ -0,0 +1,467 @@
+//! Scripted scenarios of notifications, reorgs and restarts for Execution Extensions.
+
+use crate::{test_exex_context_with_chain_spec, Adapter, PollOnceError, TestExExHandle};
+use futures_util::FutureExt;
+use reth_chainspec::{ChainSpec, MAINNET};
+use reth_execution_types::{Chain, ExecutionOutcome};
+use reth_exex::{
+    ExExContext, ExExHandle, ExExManager, ExExManagerHandle, ExExNotification, ExExNotifications,
+    Wal,
+};
+use reth_node_api::FullNodeComponents;
+use reth_node_core::{args::DatadirArgs, dirs::MaybePlatformPath, node_config::NodeConfig};
+use reth_primitives::{BlockNumber, Head, SealedBlockWithSenders};
+use reth_testing_utils::generators::{self, random_block_range};
+use std::{
+    fmt::Debug,
+    future::{poll_fn, Future},
+    pin::Pin,
+    sync::Arc,
+    task::Poll,
+    time::Duration,
+};
+use tempfile::TempDir;
+
+/// The ID of the Execution Extension in a scenario.
+const SCENARIO_EXEX_ID: &str = "test-exex";
+
+/// The capacity of the notification buffer of the manager in a scenario.
+const SCENARIO_MANAGER_CAPACITY: usize = 1024;
+
+/// The number of consecutive rounds without progress after which the Execution Extension and the
+/// manager are considered idle.
+const IDLE_ROUNDS: usize = 3;
+
+/// The maximum number of rounds to wait for the Execution Extension and the manager to become
+/// idle.
+const MAX_ROUNDS: usize = 10_000;
+
+/// The time to wait between rounds while notifications are buffered in the manager, which are
+/// only sent once the write-ahead log thread made them durable.
+const BUFFERED_ROUND_DELAY: Duration = Duration::from_millis(1);
+
+type ExExFuture = Pin<Box<dyn Future<Output = eyre::Result<()>> + Send>>;
+
+/// A step of a scripted [`ExExScenario`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScenarioStep {
+    /// Commits the given number of new blocks on top of the canonical chain.
+    Commit(u64),
+    /// Reverts the given number of blocks from the tip of the canonical chain.
+    Revert(u64),
+    /// Reverts `depth` blocks from the tip of the canonical chain and commits `blocks` new blocks
+    /// on top of the fork point in a single notification.
+    Reorg {
+        /// The number of reverted blocks.
+        depth: u64,
+        /// The number of new blocks.
+        blocks: u64,
+    },
+    /// Restarts the node, see [`ExExScenario::restart`].
+    Restart,
+    /// Asserts the last `FinishedHeight` emitted by the Execution Extension.
+    AssertFinishedHeight(Option<BlockNumber>),
+}
+
+/// A scripted scenario for testing an Execution Extension against the [`ExExManager`].
+///
+/// The Execution Extension is launched with a [`TestExExContext`](crate::TestExExContext) whose
+/// notifications are delivered by a real [`ExExManager`] with a write-ahead log in a temporary
+/// data directory, so that reorgs, reverts and restarts follow the same paths as in the node:
+/// - Blocks are generated on top of a canonical chain starting at the genesis block, and sent as
+///   [`ExExNotification`]s. They're not inserted into the database, so the head of the node stays
+///   at genesis.
+/// - [`ExExScenario::restart`] drops the Execution Extension and the manager without shutting them
+///   down, like a crash, and launches them again. The notifications the Execution Extension didn't
+///   emit a `FinishedHeight` for are replayed from the write-ahead log.
+///
+/// After every step, the Execution Extension and the manager are polled until neither of them
+/// makes progress.
+pub struct ExExScenario<L> {
+    launch: L,
+    exex: ExExFuture,
+    manager: ExExManager,
+    manager_handle: ExExManagerHandle,
+    /// The handle of the test context, with the genesis block and the database of the node.
+    handle: TestExExHandle,
+    /// The data directory of the node, holding the write-ahead log and the data of the Execution
+    /// Extension across restarts.
+    datadir: TempDir,
+    /// The canonical chain, starting at the genesis block.
+    canonical_chain: Vec<SealedBlockWithSenders>,
+    /// The parts of the context the Execution Extension is launched with.
+    context: ScenarioContext,
+}
+
+/// The parts of the [`ExExContext`] that are kept across restarts.
+#[derive(Clone)]
+struct ScenarioContext {
+    head: Head,
+    config: NodeConfig,
+    reth_config: reth_config::Config,
+    components: Adapter,
+}
+
+impl<L> Debug for ExExScenario<L> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExExScenario")
+            .field("launch", &"...")
+            .field("exex", &"...")
+            .field("manager", &self.manager)
+            .field("handle", &self.handle)
+            .field("datadir", &self.datadir)
+            .field("canonical_chain", &self.canonical_chain.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<L, F> ExExScenario<L>
+where
+    L: FnMut(ExExContext<Adapter>) -> F,
+    F: Future<Output = eyre::Result<()>> + Send + 'static,
+{
+    /// Creates a new scenario with the [`MAINNET`] chain spec, and launches the
+    /// Execution Extension.
+    pub async fn new(launch: L) -> eyre::Result<Self> {
+        Self::with_chain_spec(MAINNET.clone(), launch).await
+    }
+
+    /// Creates a new scenario with the given chain spec, and launches the Execution Extension.
+    pub async fn with_chain_spec(chain_spec: Arc<ChainSpec>, launch: L) -> eyre::Result<Self> {
+        let (context, handle) = test_exex_context_with_chain_spec(chain_spec).await?;
+        let datadir = tempfile::tempdir()?;
+        let context = ScenarioContext {
+            head: context.head,
+            config: context.config.with_datadir_args(DatadirArgs {
+                datadir: MaybePlatformPath::from(datadir.path().to_path_buf()),
+                ..Default::default()
+            }),
+            reth_config: context.reth_config,
+            components: context.components,
+        };
+
+        let canonical_chain = vec![handle.genesis.clone()];
+        let mut scenario = Self {
+            launch,
+            exex: Box::pin(std::future::pending()),
+            manager: ExExManager::new(Vec::new(), SCENARIO_MANAGER_CAPACITY),
+            manager_handle: ExExManagerHandle::empty(),
+            handle,
+            datadir,
+            canonical_chain,
+            context,
+        };
+        scenario.launch()?;
+        scenario.poll_until_idle().await?;
+
+        Ok(scenario)
+    }
+
+    /// Launches the Execution Extension and the manager with the write-ahead log in the data
+    /// directory.
+    fn launch(&mut self) -> eyre::Result<()> {
+        let ScenarioContext { head, config, reth_config, components } = self.context.clone();
+        let wal = Wal::new(config.datadir().exex_wal())?;
+
+        let (exex_handle, events, notifications) = ExExHandle::new(SCENARIO_EXEX_ID.to_string());
+        let notifications = ExExNotifications::new(
+            head,
+            components.provider().clone(),
+            components.block_executor().clone(),
+            notifications,
+        )
+        .with_wal_notifications(wal.notifications()?);
+        let context = ExExContext {
+            id: SCENARIO_EXEX_ID.to_string(),
+            head,
+            config,
+            reth_config,
+            events,
+            notifications,
+            components,
+        };
+
+        self.manager =
+            ExExManager::new(vec![exex_handle], SCENARIO_MANAGER_CAPACITY).with_wal(wal)?;
+        self.manager_handle = self.manager.handle();
+        self.exex = Box::pin((self.launch)(context));
+
+        Ok(())
+    }
+
+    /// Runs the given steps in order.
+    pub async fn run(&mut self, steps: impl IntoIterator<Item = ScenarioStep>) -> eyre::Result<()> {
+        for step in steps {
+            match step {
+                ScenarioStep::Commit(blocks) => {
+                    self.commit(blocks).await?;
+                }
+                ScenarioStep::Revert(depth) => {
+                    self.revert(depth).await?;
+                }
+                ScenarioStep::Reorg { depth, blocks } => {
+                    self.reorg(depth, blocks).await?;
+                }
+                ScenarioStep::Restart => self.restart().await?,
+                ScenarioStep::AssertFinishedHeight(height) => self.assert_finished_height(height),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Commits the given number of new blocks on top of the canonical chain, and returns the
+    /// committed chain.
+    pub async fn commit(&mut self, blocks: u64) -> eyre::Result<Arc<Chain>> {
+        let new = Arc::new(self.extend_canonical_chain(blocks));
+        self.send_notification(ExExNotification::ChainCommitted { new: new.clone() }).await?;
+        Ok(new)
+    }
+
+    /// Reverts the given number of blocks from the tip of the canonical chain, and returns the
+    /// reverted chain.
+    pub async fn revert(&mut self, depth: u64) -> eyre::Result<Arc<Chain>> {
+        let old = Arc::new(self.truncate_canonical_chain(depth)?);
+        self.send_notification(ExExNotification::ChainReverted { old: old.clone() }).await?;
+        Ok(old)
+    }
+
+    /// Reverts `depth` blocks from the tip of the canonical chain and commits `blocks` new blocks
+    /// on top of the fork point, and returns the reverted and the committed chains.
+    pub async fn reorg(
+        &mut self,
+        depth: u64,
+        blocks: u64,
+    ) -> eyre::Result<(Arc<Chain>, Arc<Chain>)> {
+        let old = Arc::new(self.truncate_canonical_chain(depth)?);
+        let new = Arc::new(self.extend_canonical_chain(blocks));
+        self.send_notification(ExExNotification::ChainReorged {
+            old: old.clone(),
+            new: new.clone(),
+        })
+        .await?;
+        Ok((old, new))
+    }
+
+    /// Restarts the node.
+    ///
+    /// The Execution Extension and the manager are dropped without being shut down, and launched
+    /// again with the write-ahead log left behind.
+    pub async fn restart(&mut self) -> eyre::Result<()> {
+        self.exex = Box::pin(std::future::pending());
+        self.manager = ExExManager::new(Vec::new(), SCENARIO_MANAGER_CAPACITY);
+        self.launch()?;
+        self.poll_until_idle().await
+    }
+
+    /// Returns the last `FinishedHeight` emitted by the Execution Extension, if any.
+    pub fn finished_height(&self) -> Option<BlockNumber> {
+        self.manager_handle.status().borrow().first().and_then(|status| status.finished_height)
+    }
+
+    /// Asserts the last `FinishedHeight` emitted by the Execution Extension.
+    #[track_caller]
+    pub fn assert_finished_height(&self, height: Option<BlockNumber>) {
+        assert_eq!(self.finished_height(), height);
+    }
+
+    /// Returns the canonical chain, starting at the genesis block.
+    pub fn canonical_chain(&self) -> &[SealedBlockWithSenders] {
+        &self.canonical_chain
+    }
+
+    /// Returns the tip of the canonical chain.
+    pub fn tip(&self) -> &SealedBlockWithSenders {
+        self.canonical_chain.last().expect("canonical chain has the genesis block")
+    }
+
+    /// Returns the handle of the test context, with the genesis block and the database of the
+    /// node.
+    pub const fn handle(&self) -> &TestExExHandle {
+        &self.handle
+    }
+
+    /// Generates the given number of blocks on top of the canonical chain.
+    fn extend_canonical_chain(&mut self, blocks: u64) -> Chain {
+        assert!(blocks > 0, "at least one block must be generated");
+
+        let tip = self.tip();
+        let first_block = tip.number + 1;
+        let blocks = random_block_range(
+            &mut generators::rng(),
+            first_block..=tip.number + blocks,
+            tip.hash(),
+            0..2,
+        )
+        .into_iter()
+        .map(|block| block.seal_with_senders().expect("failed to recover senders"))
+        .collect::<Vec<_>>();
+        self.canonical_chain.extend(blocks.iter().cloned());
+
+        Chain::new(blocks, ExecutionOutcome { first_block, ..Default::default() }, None)
+    }
+
+    /// Removes the given number of blocks from the tip of the canonical chain.
+    fn truncate_canonical_chain(&mut self, depth: u64) -> eyre::Result<Chain> {
+        eyre::ensure!(depth > 0, "at least one block must be reverted");
+        eyre::ensure!(
+            (depth as usize) < self.canonical_chain.len(),
+            "the genesis block can't be reverted"
+        );
+
+        let blocks = self.canonical_chain.split_off(self.canonical_chain.len() - depth as usize);
+        let first_block = blocks[0].number;
+        Ok(Chain::new(blocks, ExecutionOutcome { first_block, ..Default::default() }, None))
+    }
+
+    /// Sends the notification to the manager and polls until it's processed.
+    async fn send_notification(&mut self, notification: ExExNotification) -> eyre::Result<()> {
+        self.manager_handle.send(notification)?;
+        self.poll_until_idle().await
+    }
+
+    /// Polls the Execution Extension and the manager until the manager buffer is drained and
+    /// neither of them makes progress for [`IDLE_ROUNDS`] rounds.
+    ///
+    /// Progress is observed through the capacity of the manager buffer and the status of the
+    /// Execution Extension.
+    async fn poll_until_idle(&mut self) -> eyre::Result<()> {
+        let mut idle_rounds = 0;
+        let mut last_state = None;
+        for _ in 0..MAX_ROUNDS {
+            poll_fn(|cx| {
+                if let Poll::Ready(result) = self.manager.poll_unpin(cx) {
+                    return Poll::Ready(result.and(Err(eyre::eyre!("ExEx manager exited"))))
+                }
+                match self.exex.poll_unpin(cx) {
+                    Poll::Ready(Ok(())) => Poll::Ready(Err(PollOnceError::FutureIsReady.into())),
+                    Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+                    Poll::Pending => Poll::Ready(Ok(())),
+                }
+            })
+            .await?;
+
+            // buffered notifications are waiting for the write-ahead log, which makes progress
+            // on its own thread
+            let capacity = self.manager_handle.capacity();
+            if capacity < SCENARIO_MANAGER_CAPACITY {
+                idle_rounds = 0;
+                tokio::time::sleep(BUFFERED_ROUND_DELAY).await;
+                continue
+            }
+            tokio::task::yield_now().await;
+
+            let state = Some((capacity, self.manager_handle.status().borrow().clone()));
+            if state == last_state {
+                idle_rounds += 1;
+                if idle_rounds >= IDLE_ROUNDS {
+                    return Ok(())
+                }
+            } else {
+                idle_rounds = 0;
+                last_state = state;
+            }
+        }
+
+        eyre::bail!("ExEx didn't become idle after {MAX_ROUNDS} rounds")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use reth_exex::ExExEvent;
+    use std::sync::Mutex;
+
+    /// Stores the tip of every committed block, and emits the `FinishedHeight` of the store.
+    async fn store_exex(mut ctx: ExExContext<Adapter>) -> eyre::Result<()> {
+        let store = ctx.open_store()?;
+        if let Some(head) = store.head()? {
+            ctx.notifications.set_with_head(head);
+        }
+
+        while let Some(notification) = ctx.notifications.next().await {
+            let notification = notification?;
+            store.handle_notification(&notification)?;
+            if let Some(committed_chain) = notification.committed_chain() {
+                for block in committed_chain.blocks().values() {
+                    let writer = store.block(block.num_hash())?;
+                    writer.put(b"tip", block.number.to_be_bytes().to_vec())?;
+                    ctx.events.send(writer.commit()?)?;
+                }
+            }
+            if notification.committed_chain().is_none() {
+                if let Some(event) = store.finished_height()? {
+                    ctx.events.send(event)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the tip of every notification, and emits a `FinishedHeight` only for every second
+    /// notification.
+    async fn delayed_exex(
+        mut ctx: ExExContext<Adapter>,
+        received: Arc<Mutex<Vec<BlockNumber>>>,
+    ) -> eyre::Result<()> {
+        while let Some(notification) = ctx.notifications.next().await {
+            let tip = notification?.committed_chain().expect("only commits are sent").tip().number;
+            received.lock().unwrap().push(tip);
+            if tip % 2 == 0 {
+                ctx.events.send(ExExEvent::FinishedHeight(tip))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn reorgs_and_restarts() -> eyre::Result<()> {
+        let mut scenario = ExExScenario::new(store_exex).await?;
+
+        scenario
+            .run([
+                ScenarioStep::Commit(3),
+                ScenarioStep::AssertFinishedHeight(Some(3)),
+                ScenarioStep::Reorg { depth: 2, blocks: 3 },
+                ScenarioStep::AssertFinishedHeight(Some(4)),
+                ScenarioStep::Revert(3),
+                ScenarioStep::AssertFinishedHeight(Some(1)),
+                ScenarioStep::Restart,
+                ScenarioStep::AssertFinishedHeight(Some(1)),
+                ScenarioStep::Commit(2),
+                ScenarioStep::AssertFinishedHeight(Some(3)),
+            ])
+            .await?;
+        assert_eq!(scenario.tip().number, 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn replays_unfinished_notifications() -> eyre::Result<()> {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let mut scenario = ExExScenario::new({
+            let received = received.clone();
+            move |ctx| delayed_exex(ctx, received.clone())
+        })
+        .await?;
+
+        scenario.commit(1).await?;
+        scenario.assert_finished_height(None);
+        scenario.commit(1).await?;
+        scenario.assert_finished_height(Some(2));
+        scenario.commit(1).await?;
+        scenario.assert_finished_height(Some(2));
+
+        // The notification of the third block wasn't finished, so it's sent again
+        scenario.restart().await?;
+        assert_eq!(*received.lock().unwrap(), vec![1, 2, 3, 3]);
+        scenario.assert_finished_height(Some(2));
+
+        Ok(())
+    }
+}
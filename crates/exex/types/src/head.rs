@@ -0,0 +1,11 @@
+use alloy_eips::BlockNumHash;
+
+/// The head of an `ExEx`, i.e. the last block it has processed.
+///
+/// Declared by the `ExEx` on startup, so that the node can bring it up to date before sending it
+/// new notifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExExHead {
+    /// The number and hash of the head block.
+    pub block: BlockNumHash,
+}
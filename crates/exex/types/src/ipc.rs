@@ -0,0 +1,390 @@
+//! Wire format of the IPC `ExEx`, used to stream [`ExExNotification`]s to `ExEx`'s running in
+//! other processes.
+//!
+//! Messages are JSON objects keyed by the message name, e.g. `{"ack":{"id":1}}`, sent over a local
+//! socket in frames prefixed with their length as a 4-byte big-endian integer.
+//!
+//! 1. The client opens the connection with [`ExExIpcClientMessage::Hello`], carrying the protocol
+//!    version it speaks.
+//! 2. The node replies with [`ExExIpcServerMessage::Welcome`] if it supports the version, or with
+//!    [`ExExIpcServerMessage::Rejected`] and closes the connection.
+//! 3. The node sends [`ExExIpcServerMessage::Notification`]s with increasing IDs, and the client
+//!    acknowledges each processed notification with [`ExExIpcClientMessage::Ack`]. Only a limited
+//!    number of notifications are sent ahead of the acknowledgements, and notifications that were
+//!    not acknowledged are sent again to the next client after a disconnect.
+//!
+//! Every [`ExExNotification`] of the node is sent as one [`ExExIpcNotification`] per block: the
+//! blocks of a reverted chain from the highest to the lowest, followed by the blocks of a committed
+//! chain from the lowest to the highest. The messages only consist of the types defined here, which
+//! change together with [`EXEX_IPC_PROTOCOL_VERSION`].
+
+use crate::ExExNotification;
+use alloy_primitives::{Address, Bytes, B256, U256};
+use reth_provider::Chain;
+use revm::{
+    db::states::reverts::AccountInfoRevert,
+    primitives::{AccountInfo, HashMap},
+};
+
+/// Version of the wire format, bumped on every breaking change.
+pub const EXEX_IPC_PROTOCOL_VERSION: u32 = 1;
+
+/// Maximum length of a frame sent by the node, in bytes.
+///
+/// Every frame carries the notification of a single block.
+pub const EXEX_IPC_MAX_FRAME_LENGTH: usize = 64 * 1024 * 1024;
+
+/// Maximum length of a frame sent by a client, in bytes.
+pub const EXEX_IPC_MAX_CLIENT_FRAME_LENGTH: usize = 1024;
+
+/// Message sent from the node to a client.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExExIpcServerMessage {
+    /// The connection was accepted.
+    Welcome {
+        /// The protocol version of the node.
+        version: u32,
+    },
+    /// The connection was rejected and is closed by the node.
+    Rejected {
+        /// The reason of the rejection.
+        reason: String,
+    },
+    /// A notification for the client.
+    Notification {
+        /// The ID of the notification, used to acknowledge it.
+        id: u64,
+        /// The notification.
+        notification: ExExIpcNotification,
+    },
+}
+
+/// Message sent from a client to the node.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExExIpcClientMessage {
+    /// Opens the connection.
+    Hello {
+        /// The protocol version of the client.
+        version: u32,
+    },
+    /// Acknowledges that the notification with the given ID and all notifications before it were
+    /// processed.
+    ///
+    /// The node reports the last acknowledged committed block as the finished height of the
+    /// `ExEx`, allowing to prune the blocks below it.
+    Ack {
+        /// The ID of the last processed notification.
+        id: u64,
+    },
+}
+
+/// A change of the canonical chain by a single block.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExExIpcNotification {
+    /// The block was added to the canonical chain.
+    BlockCommitted {
+        /// The committed block.
+        block: ExExIpcBlock,
+    },
+    /// The block was removed from the canonical chain.
+    BlockReverted {
+        /// The reverted block.
+        block: ExExIpcBlock,
+    },
+}
+
+impl ExExIpcNotification {
+    /// Splits a notification of the node into the notifications of its blocks.
+    pub fn from_exex_notification(notification: &ExExNotification) -> Vec<Self> {
+        let mut notifications = Vec::new();
+        if let Some(reverted_chain) = notification.reverted_chain() {
+            notifications.extend(
+                ExExIpcBlock::from_chain(&reverted_chain)
+                    .into_iter()
+                    .rev()
+                    .map(|block| Self::BlockReverted { block }),
+            );
+        }
+        if let Some(committed_chain) = notification.committed_chain() {
+            notifications.extend(
+                ExExIpcBlock::from_chain(&committed_chain)
+                    .into_iter()
+                    .map(|block| Self::BlockCommitted { block }),
+            );
+        }
+        notifications
+    }
+
+    /// Returns the number of the chain tip after the notification, i.e. the committed block or
+    /// the parent of the reverted block.
+    pub const fn tip_number(&self) -> u64 {
+        match self {
+            Self::BlockCommitted { block } => block.number,
+            Self::BlockReverted { block } => block.number.saturating_sub(1),
+        }
+    }
+}
+
+/// A block with the results of its execution.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExExIpcBlock {
+    /// The number of the block.
+    pub number: u64,
+    /// The hash of the block.
+    pub hash: B256,
+    /// The RLP encoding of the block.
+    pub rlp: Bytes,
+    /// The senders of the transactions of the block.
+    pub senders: Vec<Address>,
+    /// The EIP-2718 encoded receipts of the transactions of the block.
+    pub receipts: Vec<Bytes>,
+    /// The accounts changed by the block, ordered by address.
+    pub state: Vec<ExExIpcAccountChange>,
+}
+
+impl ExExIpcBlock {
+    /// Returns the blocks of the chain with their state changes, from the lowest to the highest.
+    fn from_chain(chain: &Chain) -> Vec<Self> {
+        let outcome = chain.execution_outcome();
+        let bundle = &outcome.bundle;
+
+        // Walk the blocks from the tip down: the state after a block is the state before the block
+        // above it, starting with the state at the tip.
+        let mut state = bundle
+            .state
+            .iter()
+            .map(|(address, account)| {
+                let storage = account
+                    .storage
+                    .iter()
+                    .map(|(slot, value)| (*slot, value.present_value))
+                    .collect::<HashMap<_, _>>();
+                (*address, (account.info.clone(), storage))
+            })
+            .collect::<HashMap<_, _>>();
+
+        let mut blocks = chain
+            .blocks_iter()
+            .enumerate()
+            .map(|(index, block)| {
+                let mut receipts_rlp = Vec::new();
+                for receipt in
+                    outcome.receipts.receipt_vec.get(index).into_iter().flatten().flatten()
+                {
+                    let mut buf = Vec::new();
+                    receipt.with_bloom_ref().encode_inner(&mut buf, false);
+                    receipts_rlp.push(buf.into());
+                }
+                (
+                    index,
+                    Self {
+                        number: block.number,
+                        hash: block.hash(),
+                        rlp: alloy_rlp::encode(&block.block).into(),
+                        senders: block.senders.clone(),
+                        receipts: receipts_rlp,
+                        state: Vec::new(),
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        for (index, block) in blocks.iter_mut().rev() {
+            let Some(reverts) = bundle.reverts.get(*index) else { continue };
+            for (address, revert) in reverts {
+                let (info, storage) = state.entry(*address).or_default();
+
+                let after = info.clone();
+                let before = match &revert.account {
+                    AccountInfoRevert::DoNothing => after.clone(),
+                    AccountInfoRevert::DeleteIt => None,
+                    AccountInfoRevert::RevertTo(info) => Some(info.clone()),
+                };
+                let code = after
+                    .as_ref()
+                    .filter(|after| {
+                        before.as_ref().map_or(true, |before| before.code_hash != after.code_hash)
+                    })
+                    .and_then(|after| bundle.contracts.get(&after.code_hash))
+                    .map(|code| code.original_bytes());
+
+                let mut storage_changes = revert
+                    .storage
+                    .iter()
+                    .map(|(slot, previous)| ExExIpcStorageChange {
+                        slot: *slot,
+                        before: previous.to_previous_value(),
+                        after: storage.get(slot).copied().unwrap_or_default(),
+                    })
+                    .collect::<Vec<_>>();
+                storage_changes.sort_unstable_by_key(|change| change.slot);
+
+                *info = before.clone();
+                for change in &storage_changes {
+                    storage.insert(change.slot, change.before);
+                }
+
+                block.state.push(ExExIpcAccountChange {
+                    address: *address,
+                    before: before.map(Into::into),
+                    after: after.map(Into::into),
+                    code,
+                    storage_wiped: revert.wipe_storage,
+                    storage: storage_changes,
+                });
+            }
+            block.state.sort_unstable_by_key(|change| change.address);
+        }
+
+        blocks.into_iter().map(|(_, block)| block).collect()
+    }
+}
+
+/// The change of an account by a block.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExExIpcAccountChange {
+    /// The address of the account.
+    pub address: Address,
+    /// The account before the block, `None` if it did not exist.
+    pub before: Option<ExExIpcAccount>,
+    /// The account after the block, `None` if it was destroyed.
+    pub after: Option<ExExIpcAccount>,
+    /// The code of the account, if the block deployed it.
+    pub code: Option<Bytes>,
+    /// Whether the block wiped the storage of the account before changing the slots below.
+    pub storage_wiped: bool,
+    /// The storage slots changed by the block, ordered by slot.
+    pub storage: Vec<ExExIpcStorageChange>,
+}
+
+/// The state of an account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExExIpcAccount {
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The balance of the account.
+    pub balance: U256,
+    /// The hash of the code of the account.
+    pub code_hash: B256,
+}
+
+impl From<AccountInfo> for ExExIpcAccount {
+    fn from(info: AccountInfo) -> Self {
+        Self { nonce: info.nonce, balance: info.balance, code_hash: info.code_hash }
+    }
+}
+
+/// The change of a storage slot by a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExExIpcStorageChange {
+    /// The storage slot.
+    pub slot: U256,
+    /// The value before the block.
+    pub before: U256,
+    /// The value after the block.
+    pub after: U256,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_testing_utils::generators::{self, random_block};
+    use revm::{db::BundleState, primitives::Bytecode};
+    use std::sync::Arc;
+
+    #[test]
+    fn splits_chain_into_blocks_with_state_changes() {
+        let mut rng = generators::rng();
+        let address = Address::with_last_byte(1);
+        let code = Bytecode::new_raw(Bytes::from_static(&[0x60, 0x00]));
+        let first = AccountInfo {
+            nonce: 1,
+            balance: U256::from(10),
+            code_hash: code.hash_slow(),
+            code: None,
+        };
+        let second = AccountInfo { nonce: 2, balance: U256::from(20), ..first.clone() };
+
+        // the account is created with slot 1 set to 5 in block 1, and slot 1 is set to 7 in block 2
+        let bundle = BundleState::new(
+            [(
+                address,
+                None,
+                Some(second.clone()),
+                HashMap::from([(U256::from(1), (U256::ZERO, U256::from(7)))]),
+            )],
+            [
+                vec![(address, Some(None), vec![(U256::from(1), U256::ZERO)])],
+                vec![(address, Some(Some(first.clone())), vec![(U256::from(1), U256::from(5))])],
+            ],
+            [(code.hash_slow(), code.clone())],
+        );
+        let blocks = [1, 2].map(|number| {
+            random_block(&mut rng, number, None, Some(0), None).seal_with_senders().unwrap()
+        });
+        let chain = Chain::new(
+            blocks.clone(),
+            reth_provider::ExecutionOutcome::new(bundle, Default::default(), 1, Vec::new()),
+            None,
+        );
+
+        let notifications =
+            ExExIpcNotification::from_exex_notification(&ExExNotification::ChainReorged {
+                old: Arc::new(chain.clone()),
+                new: Arc::new(chain),
+            });
+        let numbers = notifications
+            .iter()
+            .map(|notification| match notification {
+                ExExIpcNotification::BlockCommitted { block } => (true, block.number),
+                ExExIpcNotification::BlockReverted { block } => (false, block.number),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, vec![(false, 2), (false, 1), (true, 1), (true, 2)]);
+
+        let [_, _, ExExIpcNotification::BlockCommitted { block: block_1 }, ExExIpcNotification::BlockCommitted { block: block_2 }] =
+            notifications.as_slice()
+        else {
+            unreachable!()
+        };
+        assert_eq!(block_1.hash, blocks[0].hash());
+        assert_eq!(block_1.rlp, Bytes::from(alloy_rlp::encode(&blocks[0].block)));
+        assert_eq!(
+            block_1.state,
+            vec![ExExIpcAccountChange {
+                address,
+                before: None,
+                after: Some(first.clone().into()),
+                code: Some(code.original_bytes()),
+                storage_wiped: false,
+                storage: vec![ExExIpcStorageChange {
+                    slot: U256::from(1),
+                    before: U256::ZERO,
+                    after: U256::from(5)
+                }],
+            }]
+        );
+        assert_eq!(
+            block_2.state,
+            vec![ExExIpcAccountChange {
+                address,
+                before: Some(first.into()),
+                after: Some(second.into()),
+                code: None,
+                storage_wiped: false,
+                storage: vec![ExExIpcStorageChange {
+                    slot: U256::from(1),
+                    before: U256::from(5),
+                    after: U256::from(7)
+                }],
+            }]
+        );
+    }
+}
@@ -9,7 +9,13 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 mod finished_height;
+mod head;
+#[cfg(feature = "serde")]
+pub mod ipc;
 mod notification;
+mod status;
 
 pub use finished_height::FinishedExExHeight;
+pub use head::ExExHead;
 pub use notification::ExExNotification;
+pub use status::{ExExHealth, ExExStatus};
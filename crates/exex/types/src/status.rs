@@ -0,0 +1,34 @@
+use alloy_primitives::BlockNumber;
+
+/// The status of an `ExEx`, as reported by the `ExEx` manager.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ExExStatus {
+    /// The ID of the `ExEx`.
+    pub id: String,
+    /// The last height the `ExEx` has finished, if it has emitted a `FinishedHeight` event.
+    pub finished_height: Option<BlockNumber>,
+    /// The number of blocks the finished height is behind the node, if both are known.
+    pub lag_blocks: Option<u64>,
+    /// The UNIX timestamp in seconds since which the `ExEx` has been behind the node without
+    /// finishing new blocks, if it is.
+    pub stalled_since: Option<u64>,
+    /// The health of the `ExEx` according to its lag policy.
+    pub health: ExExHealth,
+}
+
+/// The health of an `ExEx` according to its lag policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum ExExHealth {
+    /// The `ExEx` is within the maximum lag of its policy.
+    #[default]
+    Healthy,
+    /// The `ExEx` has exceeded the maximum lag of its policy.
+    Lagging,
+    /// The `ExEx` has exceeded the maximum lag of its policy and was detached from the node. It
+    /// doesn't receive notifications anymore, and pruning doesn't wait for it.
+    Detached,
+}
@@ -4,6 +4,7 @@ use std::marker::PhantomData;
 
 use reth_db_api::{
     database::Database,
+    database_compaction::DatabaseCompaction,
     database_metrics::{DatabaseMetadata, DatabaseMetrics},
 };
 use reth_evm::execute::BlockExecutorProvider;
@@ -60,7 +61,13 @@ where
 /// Its types are configured by node internally and are not intended to be user configurable.
 pub trait FullNodeTypes: NodeTypes + 'static {
     /// Underlying database type used by the node to store and retrieve data.
-    type DB: Database + DatabaseMetrics + DatabaseMetadata + Clone + Unpin + 'static;
+    type DB: Database
+        + DatabaseMetrics
+        + DatabaseMetadata
+        + DatabaseCompaction
+        + Clone
+        + Unpin
+        + 'static;
     /// The provider type used to interact with the node.
     type Provider: FullProvider<Self::DB>;
 }
@@ -109,7 +116,13 @@ impl<Types, DB, Provider> FullNodeTypes for FullNodeTypesAdapter<Types, DB, Prov
 where
     Types: NodeTypes,
     Provider: FullProvider<DB>,
-    DB: Database + DatabaseMetrics + DatabaseMetadata + Clone + Unpin + 'static,
+    DB: Database
+        + DatabaseMetrics
+        + DatabaseMetadata
+        + DatabaseCompaction
+        + Clone
+        + Unpin
+        + 'static,
 {
     type DB = DB;
     type Provider = Provider;
@@ -2,6 +2,7 @@
 
 use std::marker::PhantomData;
 
+use reth_exex::ExExNotificationFilter;
 use reth_node_api::{FullNodeComponents, NodeAddOns};
 
 use crate::{exex::BoxedLaunchExEx, hooks::NodeHooks, rpc::RpcHooks};
@@ -12,8 +13,8 @@ use crate::{exex::BoxedLaunchExEx, hooks::NodeHooks, rpc::RpcHooks};
 pub struct AddOns<Node: FullNodeComponents, AddOns: NodeAddOns<Node>> {
     /// Additional `NodeHooks` that are called at specific points in the node's launch lifecycle.
     pub hooks: NodeHooks<Node, AddOns>,
-    /// The `ExExs` (execution extensions) of the node.
-    pub exexs: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    /// The `ExExs` (execution extensions) of the node, with the filters of their notifications.
+    pub exexs: Vec<(String, ExExNotificationFilter, Box<dyn BoxedLaunchExEx<Node>>)>,
     /// Additional RPC add-ons.
     pub rpc: RpcAddOns<Node, AddOns::EthApi>,
 }
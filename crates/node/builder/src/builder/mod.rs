@@ -14,9 +14,10 @@ use reth_chainspec::ChainSpec;
 use reth_cli_util::get_secret_key;
 use reth_db_api::{
     database::Database,
+    database_compaction::DatabaseCompaction,
     database_metrics::{DatabaseMetadata, DatabaseMetrics},
 };
-use reth_exex::ExExContext;
+use reth_exex::{ExExContext, ExExNotificationFilter};
 use reth_network::{
     NetworkBuilder, NetworkConfig, NetworkConfigBuilder, NetworkHandle, NetworkManager,
 };
@@ -201,7 +202,13 @@ impl<DB> NodeBuilder<DB> {
 
 impl<DB> NodeBuilder<DB>
 where
-    DB: Database + DatabaseMetrics + DatabaseMetadata + Clone + Unpin + 'static,
+    DB: Database
+        + DatabaseMetrics
+        + DatabaseMetadata
+        + DatabaseCompaction
+        + Clone
+        + Unpin
+        + 'static,
 {
     /// Configures the types of the node.
     pub fn with_types<T>(self) -> NodeBuilderWithTypes<RethFullAdapter<DB, T>>
@@ -254,7 +261,13 @@ impl<Builder> WithLaunchContext<Builder> {
 
 impl<DB> WithLaunchContext<NodeBuilder<DB>>
 where
-    DB: Database + DatabaseMetrics + DatabaseMetadata + Clone + Unpin + 'static,
+    DB: Database
+        + DatabaseMetrics
+        + DatabaseMetadata
+        + DatabaseCompaction
+        + Clone
+        + Unpin
+        + 'static,
 {
     /// Returns a reference to the node builder's config.
     pub const fn config(&self) -> &NodeConfig {
@@ -440,6 +453,29 @@ where
         }
     }
 
+    /// Installs an `ExEx` (Execution Extension) in the node, that is only sent the notifications
+    /// matching the filter.
+    ///
+    /// # Note
+    ///
+    /// The `ExEx` ID must be unique.
+    pub fn install_exex_with_filter<F, R, E>(
+        self,
+        exex_id: impl Into<String>,
+        filter: ExExNotificationFilter,
+        exex: F,
+    ) -> Self
+    where
+        F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Send + 'static,
+        R: Future<Output = eyre::Result<E>> + Send,
+        E: Future<Output = eyre::Result<()>> + Send,
+    {
+        Self {
+            builder: self.builder.install_exex_with_filter(exex_id, filter, exex),
+            task_executor: self.task_executor,
+        }
+    }
+
     /// Launches the node with the given launcher.
     pub async fn launch_with<L>(self, launcher: L) -> eyre::Result<L::Node>
     where
@@ -466,7 +502,13 @@ where
 
 impl<T, DB, CB, AO> WithLaunchContext<NodeBuilderWithComponents<RethFullAdapter<DB, T>, CB, AO>>
 where
-    DB: Database + DatabaseMetrics + DatabaseMetadata + Clone + Unpin + 'static,
+    DB: Database
+        + DatabaseMetrics
+        + DatabaseMetadata
+        + DatabaseCompaction
+        + Clone
+        + Unpin
+        + 'static,
     T: NodeTypes,
     CB: NodeComponentsBuilder<RethFullAdapter<DB, T>>,
     AO: NodeAddOns<NodeAdapter<RethFullAdapter<DB, T>, CB::Components>>,
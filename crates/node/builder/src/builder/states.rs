@@ -7,7 +7,7 @@
 
 use std::{fmt, future::Future, marker::PhantomData};
 
-use reth_exex::ExExContext;
+use reth_exex::{ExExContext, ExExNotificationFilter};
 use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeAddOns, NodeTypes};
 use reth_node_core::{
     node_config::NodeConfig,
@@ -243,13 +243,33 @@ where
     /// # Note
     ///
     /// The `ExEx` ID must be unique.
-    pub fn install_exex<F, R, E>(mut self, exex_id: impl Into<String>, exex: F) -> Self
+    pub fn install_exex<F, R, E>(self, exex_id: impl Into<String>, exex: F) -> Self
     where
         F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Send + 'static,
         R: Future<Output = eyre::Result<E>> + Send,
         E: Future<Output = eyre::Result<()>> + Send,
     {
-        self.add_ons.exexs.push((exex_id.into(), Box::new(exex)));
+        self.install_exex_with_filter(exex_id, ExExNotificationFilter::All, exex)
+    }
+
+    /// Installs an `ExEx` (Execution Extension) in the node, that is only sent the notifications
+    /// matching the filter.
+    ///
+    /// # Note
+    ///
+    /// The `ExEx` ID must be unique.
+    pub fn install_exex_with_filter<F, R, E>(
+        mut self,
+        exex_id: impl Into<String>,
+        filter: ExExNotificationFilter,
+        exex: F,
+    ) -> Self
+    where
+        F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Send + 'static,
+        R: Future<Output = eyre::Result<E>> + Send,
+        E: Future<Output = eyre::Result<()>> + Send,
+    {
+        self.add_ons.exexs.push((exex_id.into(), filter, Box::new(exex)));
         self
     }
 
@@ -384,6 +384,16 @@ where
     /// between the database and static files. **It may execute a pipeline unwind if it fails this
     /// check.**
     pub async fn create_provider_factory(&self) -> eyre::Result<ProviderFactory<DB>> {
+        // Internal calls can only be indexed by re-executing the blocks in the pipeline, so the
+        // index could not follow the blocks inserted by the engine.
+        let index_address_transactions = self.toml_config().stages.index_address_transactions;
+        if index_address_transactions.enabled && index_address_transactions.internal_calls {
+            eyre::bail!(
+                "indexing the internal calls of address transactions is not supported by the node, \
+                 as blocks inserted by the engine can't be indexed with their internal calls"
+            )
+        }
+
         let factory = ProviderFactory::new(
             self.right().clone(),
             self.chain_spec(),
@@ -794,9 +804,20 @@ where
             .unwrap_or_default()
             .block_number;
 
+        // The log and address transactions index stages are optional, so they're only checked
+        // if enabled.
+        let stages_config = &self.toml_config().stages;
+        let optional_stages = [
+            stages_config.index_logs.enabled.then_some(StageId::IndexLogs),
+            stages_config
+                .index_address_transactions
+                .enabled
+                .then_some(StageId::IndexAddressTransactions),
+        ];
+
         // Skip the first stage as we've already retrieved it and comparing all other checkpoints
         // against it.
-        for stage_id in StageId::ALL.iter().skip(1) {
+        for stage_id in StageId::ALL.iter().skip(1).chain(optional_stages.iter().flatten()) {
             let stage_checkpoint = self
                 .blockchain_db()
                 .get_stage_checkpoint(*stage_id)?
@@ -2,7 +2,11 @@
 
 use crate::{common::WithConfigs, exex::BoxedLaunchExEx};
 use futures::future;
-use reth_exex::{ExExContext, ExExHandle, ExExManager, ExExManagerHandle};
+use reth_exex::{
+    ExExContext, ExExHandle, ExExManager, ExExManagerHandle, ExExNotificationFilter,
+    ExExNotifications, Wal,
+};
+use reth_exex_ipc::IpcExEx;
 use reth_node_api::FullNodeComponents;
 use reth_primitives::Head;
 use reth_provider::CanonStateSubscriptions;
@@ -12,7 +16,7 @@ use std::{fmt, fmt::Debug};
 /// Can launch execution extensions.
 pub struct ExExLauncher<Node: FullNodeComponents> {
     head: Head,
-    extensions: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    extensions: Vec<(String, ExExNotificationFilter, Box<dyn BoxedLaunchExEx<Node>>)>,
     components: Node,
     config_container: WithConfigs,
 }
@@ -22,7 +26,7 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
     pub const fn new(
         head: Head,
         components: Node,
-        extensions: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+        extensions: Vec<(String, ExExNotificationFilter, Box<dyn BoxedLaunchExEx<Node>>)>,
         config_container: WithConfigs,
     ) -> Self {
         Self { head, extensions, components, config_container }
@@ -32,24 +36,55 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
     ///
     /// Spawns all extensions and returns the handle to the exex manager if any extensions are
     /// installed.
-    pub async fn launch(self) -> Option<ExExManagerHandle> {
-        let Self { head, extensions, components, config_container } = self;
+    pub async fn launch(self) -> eyre::Result<Option<ExExManagerHandle>> {
+        let Self { head, mut extensions, components, config_container } = self;
+
+        // install the built-in ipc exex if configured
+        if let Some(path) = config_container.config.exex.ipc_path.clone() {
+            extensions.push((
+                "ipc".to_string(),
+                ExExNotificationFilter::All,
+                Box::new(move |ctx| async move { Ok(IpcExEx::new(ctx, &path)?.run()) }),
+            ));
+        }
 
         if extensions.is_empty() {
             // nothing to launch
-            return None
+            return Ok(None)
         }
 
+        // open the write-ahead log before the exexs are launched, so a broken log fails early
+        let wal = config_container
+            .config
+            .exex
+            .wal
+            .then(|| Wal::new(config_container.config.datadir().exex_wal()))
+            .transpose()?;
+        let wal_notifications =
+            wal.as_ref().map(Wal::notifications).transpose()?.unwrap_or_default();
+
         let mut exex_handles = Vec::with_capacity(extensions.len());
         let mut exexs = Vec::with_capacity(extensions.len());
 
-        for (id, exex) in extensions {
+        for (id, filter, exex) in extensions {
             // create a new exex handle
-            let (handle, events, notifications) = ExExHandle::new(id.clone());
+            let (mut handle, events, notifications) = ExExHandle::new(id.clone());
+            handle = handle.with_filter(filter);
+            if let Some(lag_policy) = config_container.toml_config.exex.lag_policies.get(&id) {
+                handle = handle.with_lag_policy(*lag_policy);
+            }
             exex_handles.push(handle);
+            let notifications = ExExNotifications::new(
+                head,
+                components.provider().clone(),
+                components.block_executor().clone(),
+                notifications,
+            )
+            .with_wal_notifications(wal_notifications.clone());
 
             // create the launch context for the exex
             let context = ExExContext {
+                id: id.clone(),
                 head,
                 config: config_container.config.clone(),
                 reth_config: config_container.toml_config.clone(),
@@ -83,7 +118,10 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
         // spawn exex manager
         debug!(target: "reth::cli", "spawning exex manager");
         // todo(onbjerg): rm magic number
-        let exex_manager = ExExManager::new(exex_handles, 1024);
+        let mut exex_manager = ExExManager::new(exex_handles, 1024);
+        if let Some(wal) = wal {
+            exex_manager = exex_manager.with_wal(wal)?;
+        }
         let exex_manager_handle = exex_manager.handle();
         components.task_executor().spawn_critical("exex manager", async move {
             exex_manager.await.expect("exex manager crashed");
@@ -106,7 +144,7 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
 
         info!(target: "reth::cli", "ExEx Manager started");
 
-        Some(exex_manager_handle)
+        Ok(Some(exex_manager_handle))
     }
 }
 
@@ -114,7 +152,7 @@ impl<Node: FullNodeComponents> Debug for ExExLauncher<Node> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ExExLauncher")
             .field("head", &self.head)
-            .field("extensions", &self.extensions.iter().map(|(id, _)| id).collect::<Vec<_>>())
+            .field("extensions", &self.extensions.iter().map(|(id, _, _)| id).collect::<Vec<_>>())
             .field("components", &"...")
             .field("config_container", &self.config_container)
             .finish()
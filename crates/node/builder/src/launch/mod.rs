@@ -172,7 +172,7 @@ where
             ctx.configs().clone(),
         )
         .launch()
-        .await;
+        .await?;
 
         // create pipeline
         let network_client = ctx.components().network().fetch_client().await?;
@@ -338,6 +338,7 @@ where
             Box::new(ctx.task_executor().clone()),
             client,
             EngineCapabilities::default(),
+            ctx.components().pool().clone(),
         );
         info!(target: "reth::cli", "Engine API handler initialized");
 
@@ -350,6 +351,7 @@ where
             engine_api,
             ctx.node_config(),
             jwt_secret,
+            exex_manager_handle.as_ref(),
             rpc,
         )
         .await?;
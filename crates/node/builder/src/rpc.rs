@@ -6,16 +6,24 @@ use std::{
 };
 
 use futures::TryFutureExt;
+use reth_exex::ExExManagerHandle;
 use reth_node_api::{BuilderProvider, FullNodeComponents};
 use reth_node_core::{
     node_config::NodeConfig,
-    rpc::{api::EngineApiServer, eth::FullEthApiServer},
+    rpc::{
+        api::{EngineApiServer, ExExApiServer},
+        eth::{
+            helpers::{EthSigner, EthTransactions},
+            FullEthApiServer,
+        },
+    },
 };
 use reth_payload_builder::PayloadBuilderHandle;
+use reth_rpc::{eth::RemoteSigner, ExExApi};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
 };
 use reth_rpc_layer::JwtSecret;
 use reth_tasks::TaskExecutor;
@@ -251,11 +259,18 @@ impl<'a, Node: FullNodeComponents, EthApi> RpcContext<'a, Node, EthApi> {
 }
 
 /// Launch the rpc servers.
+///
+/// The remote signer configured with `--rpc.signer` is added to the signers of the `eth` API
+/// before the servers are started.
+///
+/// If the `ExEx` manager handle is given, `reth_exexStatus` is served along with the `reth`
+/// namespace.
 pub async fn launch_rpc_servers<Node, Engine, EthApi>(
     node: Node,
     engine_api: Engine,
     config: &NodeConfig,
     jwt_secret: JwtSecret,
+    exex_manager_handle: Option<&ExExManagerHandle>,
     add_ons: RpcAddOns<Node, EthApi>,
 ) -> eyre::Result<(RethRpcServerHandles, RpcRegistry<Node, EthApi>)>
 where
@@ -276,7 +291,21 @@ where
         .with_evm_config(node.evm_config().clone())
         .build_with_auth_server(module_config, engine_api, EthApi::eth_api_builder());
 
+    if let Some(exex_manager_handle) = exex_manager_handle {
+        modules.merge_if_module_configured(
+            RethRpcModule::Reth,
+            ExExApi::new(exex_manager_handle.status()).into_rpc(),
+        )?;
+    }
+
     let mut registry = RpcRegistry { registry };
+
+    // connect the remote signer before the servers accept signing requests
+    if let Some(endpoint) = &config.rpc.rpc_signer {
+        let signer = RemoteSigner::connect(endpoint).await?;
+        info!(target: "reth::cli", accounts = ?signer.accounts(), "Connected to remote signer");
+        EthTransactions::signers(registry.eth_api()).write().push(Box::new(signer));
+    }
     let ctx = RpcContext {
         node: node.clone(),
         config,
@@ -14,7 +14,11 @@ use reth_network_p2p::{
 };
 use reth_node_core::primitives::{BlockNumber, B256};
 use reth_provider::ProviderFactory;
-use reth_stages::{prelude::DefaultStages, stages::ExecutionStage, Pipeline, StageSet};
+use reth_stages::{
+    prelude::DefaultStages,
+    stages::{ExecutionStage, IndexAddressTransactionsStage, InternalCallTracer},
+    Pipeline, StageSet,
+};
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::debug;
@@ -40,6 +44,46 @@ where
     DB: Database + Unpin + Clone + 'static,
     Client: BlockClient + 'static,
     Executor: BlockExecutorProvider,
+{
+    build_networked_pipeline_with_internal_call_tracer(
+        config,
+        client,
+        consensus,
+        provider_factory,
+        task_executor,
+        metrics_tx,
+        prune_config,
+        max_block,
+        static_file_producer,
+        executor,
+        exex_manager_handle,
+        None::<Box<dyn InternalCallTracer>>,
+    )
+}
+
+/// Constructs a [Pipeline] that's wired to the network, like [`build_networked_pipeline`], with
+/// the tracer the [`IndexAddressTransactionsStage`] uses if it's configured to index internal
+/// calls.
+#[allow(clippy::too_many_arguments)]
+pub fn build_networked_pipeline_with_internal_call_tracer<DB, Client, Executor, Tracer>(
+    config: &StageConfig,
+    client: Client,
+    consensus: Arc<dyn Consensus>,
+    provider_factory: ProviderFactory<DB>,
+    task_executor: &TaskExecutor,
+    metrics_tx: reth_stages::MetricEventsSender,
+    prune_config: Option<PruneConfig>,
+    max_block: Option<BlockNumber>,
+    static_file_producer: StaticFileProducer<DB>,
+    executor: Executor,
+    exex_manager_handle: ExExManagerHandle,
+    internal_call_tracer: Option<Tracer>,
+) -> eyre::Result<Pipeline<DB>>
+where
+    DB: Database + Unpin + Clone + 'static,
+    Client: BlockClient + 'static,
+    Executor: BlockExecutorProvider,
+    Tracer: InternalCallTracer + 'static,
 {
     // building network downloaders using the fetch client
     let header_downloader = ReverseHeadersDownloaderBuilder::new(config.headers)
@@ -50,7 +94,7 @@ where
         .build(client, Arc::clone(&consensus), provider_factory.clone())
         .into_task_with(task_executor);
 
-    let pipeline = build_pipeline(
+    let pipeline = build_pipeline_with_internal_call_tracer(
         provider_factory,
         config,
         header_downloader,
@@ -62,6 +106,7 @@ where
         static_file_producer,
         executor,
         exex_manager_handle,
+        internal_call_tracer,
     )?;
 
     Ok(pipeline)
@@ -87,6 +132,46 @@ where
     H: HeaderDownloader + 'static,
     B: BodyDownloader + 'static,
     Executor: BlockExecutorProvider,
+{
+    build_pipeline_with_internal_call_tracer(
+        provider_factory,
+        stage_config,
+        header_downloader,
+        body_downloader,
+        consensus,
+        max_block,
+        metrics_tx,
+        prune_config,
+        static_file_producer,
+        executor,
+        exex_manager_handle,
+        None::<Box<dyn InternalCallTracer>>,
+    )
+}
+
+/// Builds the [Pipeline] like [`build_pipeline`], with the tracer the
+/// [`IndexAddressTransactionsStage`] uses if it's configured to index internal calls.
+#[allow(clippy::too_many_arguments)]
+pub fn build_pipeline_with_internal_call_tracer<DB, H, B, Executor, Tracer>(
+    provider_factory: ProviderFactory<DB>,
+    stage_config: &StageConfig,
+    header_downloader: H,
+    body_downloader: B,
+    consensus: Arc<dyn Consensus>,
+    max_block: Option<u64>,
+    metrics_tx: reth_stages::MetricEventsSender,
+    prune_config: Option<PruneConfig>,
+    static_file_producer: StaticFileProducer<DB>,
+    executor: Executor,
+    exex_manager_handle: ExExManagerHandle,
+    internal_call_tracer: Option<Tracer>,
+) -> eyre::Result<Pipeline<DB>>
+where
+    DB: Database + Clone + 'static,
+    H: HeaderDownloader + 'static,
+    B: BodyDownloader + 'static,
+    Executor: BlockExecutorProvider,
+    Tracer: InternalCallTracer + 'static,
 {
     let mut builder = Pipeline::builder();
 
@@ -99,31 +184,46 @@ where
 
     let prune_modes = prune_config.map(|prune| prune.segments).unwrap_or_default();
 
+    let mut stages = DefaultStages::new(
+        provider_factory.clone(),
+        tip_rx,
+        Arc::clone(&consensus),
+        header_downloader,
+        body_downloader,
+        executor.clone(),
+        stage_config.clone(),
+        prune_modes.clone(),
+    )
+    .set(
+        ExecutionStage::new(
+            executor,
+            stage_config.execution.into(),
+            stage_config.execution_external_clean_threshold(),
+            prune_modes.clone(),
+            exex_manager_handle,
+        )
+        .with_metrics_tx(metrics_tx.clone()),
+    );
+
+    // Internal calls can only be indexed by re-executing the blocks with the tracer.
+    let index_address_transactions = stage_config.index_address_transactions;
+    if let Some(tracer) = internal_call_tracer
+        .filter(|_| index_address_transactions.enabled && index_address_transactions.internal_calls)
+    {
+        stages = stages.set(
+            IndexAddressTransactionsStage::new(
+                index_address_transactions,
+                stage_config.etl.clone(),
+                prune_modes.address_transactions,
+            )
+            .with_internal_call_tracer(tracer),
+        );
+    }
+
     let pipeline = builder
         .with_tip_sender(tip_tx)
-        .with_metrics_tx(metrics_tx.clone())
-        .add_stages(
-            DefaultStages::new(
-                provider_factory.clone(),
-                tip_rx,
-                Arc::clone(&consensus),
-                header_downloader,
-                body_downloader,
-                executor.clone(),
-                stage_config.clone(),
-                prune_modes.clone(),
-            )
-            .set(
-                ExecutionStage::new(
-                    executor,
-                    stage_config.execution.into(),
-                    stage_config.execution_external_clean_threshold(),
-                    prune_modes,
-                    exex_manager_handle,
-                )
-                .with_metrics_tx(metrics_tx),
-            ),
-        )
+        .with_metrics_tx(metrics_tx)
+        .add_stages(stages)
         .build(provider_factory, static_file_producer);
 
     Ok(pipeline)
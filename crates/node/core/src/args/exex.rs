@@ -0,0 +1,44 @@
+//! clap [Args](clap::Args) for execution extensions
+
+use clap::Args;
+
+/// Parameters for execution extensions
+#[derive(Debug, Clone, Args, PartialEq, Eq, Default)]
+#[command(next_help_heading = "ExEx")]
+pub struct ExExArgs {
+    /// Serve the notifications of the node to an `ExEx` running in another process, over the IPC
+    /// socket at the given path.
+    #[arg(long = "exex.ipcpath", value_name = "PATH")]
+    pub ipc_path: Option<String>,
+
+    /// Commit the notifications sent to the `ExEx`'s to a write-ahead log in the data directory
+    /// before sending them, and replay the unfinished ones after a restart.
+    #[arg(long = "exex.wal")]
+    pub wal: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_exex_args() {
+        let args = CommandParser::<ExExArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, ExExArgs::default());
+
+        let args =
+            CommandParser::<ExExArgs>::parse_from(["reth", "--exex.ipcpath", "/tmp/exex.ipc"]).args;
+        assert_eq!(args.ipc_path.as_deref(), Some("/tmp/exex.ipc"));
+
+        let args = CommandParser::<ExExArgs>::parse_from(["reth", "--exex.wal"]).args;
+        assert!(args.wal);
+    }
+}
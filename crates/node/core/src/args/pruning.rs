@@ -35,6 +35,8 @@ impl PruningArgs {
                     .or(Some(PruneMode::Full)),
                 account_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                 storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                log_index: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                address_transactions: None,
                 receipts_log_filter: ReceiptsLogPruneConfig(
                     chain_spec
                         .deposit_contract
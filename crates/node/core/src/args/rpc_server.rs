@@ -170,6 +170,13 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.proof-permits", alias = "rpc-proof-permits", value_name = "COUNT", default_value_t = constants::DEFAULT_PROOF_PERMITS)]
     pub rpc_proof_permits: usize,
 
+    /// Endpoint of an external signer, either an HTTP(S) URL or the path of an IPC socket, that
+    /// signs on behalf of its accounts for the `eth_` signing methods.
+    ///
+    /// Clef (`account_` namespace) and web3signer (`eth_` namespace) are supported.
+    #[arg(long = "rpc.signer", value_name = "ENDPOINT")]
+    pub rpc_signer: Option<String>,
+
     /// State cache configuration.
     #[command(flatten)]
     pub rpc_state_cache: RpcStateCacheArgs,
@@ -304,6 +311,7 @@ impl Default for RpcServerArgs {
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
+            rpc_signer: None,
         }
     }
 }
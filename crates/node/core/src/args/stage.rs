@@ -50,4 +50,12 @@ pub enum StageEnum {
     ///
     /// Manages historical data related to storage.
     StorageHistory,
+    /// The log index stage within the pipeline.
+    ///
+    /// Manages the index of blocks with logs of each address and topic.
+    Logs,
+    /// The address transactions index stage within the pipeline.
+    ///
+    /// Manages the index of transactions of each address.
+    AddressTransactions,
 }
@@ -323,6 +323,21 @@ impl<D> ChainPath<D> {
         self.data_dir().join("blobstore")
     }
 
+    /// Returns the path to the write-ahead log directory of the execution extensions.
+    ///
+    /// `<DIR>/<CHAIN_ID>/exex/wal`
+    pub fn exex_wal(&self) -> PathBuf {
+        self.data_dir().join("exex").join("wal")
+    }
+
+    /// Returns the path to the key/value store directory of the execution extension with the
+    /// given ID.
+    ///
+    /// `<DIR>/<CHAIN_ID>/exex/store/<ID>`
+    pub fn exex_store(&self, id: &str) -> PathBuf {
+        self.data_dir().join("exex").join("store").join(id)
+    }
+
     /// Returns the path to the local transactions backup file
     ///
     /// `<DIR>/<CHAIN_ID>/txpool-transactions-backup.rlp`
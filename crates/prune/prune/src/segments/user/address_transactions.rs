@@ -0,0 +1,233 @@
+use crate::{
+    segments::{user::history::prune_history_indices, PruneInput, Segment, SegmentOutput},
+    PrunerError,
+};
+use itertools::Itertools;
+use reth_db::tables;
+use reth_db_api::{database::Database, models::ShardedKey};
+use reth_provider::{DatabaseProviderRW, TransactionsProvider};
+use reth_prune_types::{
+    PruneInterruptReason, PruneMode, PruneProgress, PrunePurpose, PruneSegment,
+    SegmentOutputCheckpoint,
+};
+use rustc_hash::FxHashMap;
+use tracing::{instrument, trace};
+
+/// Number of address transactions tables to prune in one step.
+///
+/// Address transactions consist of two tables: [`tables::TransactionAddresses`] and
+/// [`tables::AddressTransactions`]. We want to prune them to the same transaction number.
+const ADDRESS_TRANSACTIONS_TABLES_TO_PRUNE: usize = 2;
+
+#[derive(Debug)]
+pub struct AddressTransactions {
+    mode: PruneMode,
+}
+
+impl AddressTransactions {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<DB: Database> Segment<DB> for AddressTransactions {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::AddressTransactions
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<SegmentOutput, PrunerError> {
+        let tx_range = match input.get_next_tx_num_range(provider)? {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No address transactions to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let tx_range_end = *tx_range.end();
+
+        let mut limiter = if let Some(limit) = input.limiter.deleted_entries_limit() {
+            input.limiter.set_deleted_entries_limit(limit / ADDRESS_TRANSACTIONS_TABLES_TO_PRUNE)
+        } else {
+            input.limiter
+        };
+        if limiter.is_limit_reached() {
+            return Ok(SegmentOutput::not_done(
+                PruneInterruptReason::new(&limiter),
+                input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint),
+            ))
+        }
+
+        let mut last_pruned_transaction = None;
+        // Deleted transaction addresses with the highest transaction number deleted for that
+        // address.
+        let mut highest_deleted_addresses = FxHashMap::default();
+        let (pruned_addresses, done) = provider
+            .prune_table_with_range::<tables::TransactionAddresses>(
+                tx_range,
+                &mut limiter,
+                |_| false,
+                |(tx_num, address)| {
+                    highest_deleted_addresses.insert(address, tx_num);
+                    last_pruned_transaction = Some(tx_num);
+                },
+            )?;
+        trace!(target: "pruner", pruned = %pruned_addresses, %done, "Pruned address transactions (transaction addresses)");
+
+        let last_pruned_transaction = last_pruned_transaction
+            // If there's more transaction addresses to prune, set the checkpoint transaction number
+            // to previous, so we could finish pruning its addresses on the next run.
+            .map(|tx_num| if done { tx_num } else { tx_num.saturating_sub(1) })
+            .unwrap_or(tx_range_end);
+
+        // Sort highest deleted transaction numbers by address and turn them into sharded keys.
+        // We did not use `BTreeMap` from the beginning, because it's inefficient for hashes.
+        let highest_sharded_keys = highest_deleted_addresses
+            .into_iter()
+            .sorted_unstable() // Unstable is fine because no equal keys exist in the map
+            .map(|(address, tx_num)| ShardedKey::new(address, tx_num));
+        let outcomes = prune_history_indices::<DB, tables::AddressTransactions, _>(
+            provider,
+            highest_sharded_keys,
+            |a, b| a.key == b.key,
+        )?;
+        trace!(target: "pruner", ?outcomes, %done, "Pruned address transactions (indices)");
+
+        let last_pruned_block = provider
+            .transaction_block(last_pruned_transaction)?
+            .ok_or(PrunerError::InconsistentData("Block for transaction is not found"))?
+            // If there's more transaction addresses to prune, set the checkpoint block number to
+            // previous, so we could finish pruning its transactions on the next run.
+            .checked_sub(if done { 0 } else { 1 });
+
+        let progress = PruneProgress::new(done, &limiter);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned: pruned_addresses + outcomes.deleted,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: last_pruned_block,
+                tx_number: Some(last_pruned_transaction),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{AddressTransactions, PruneInput, Segment};
+    use alloy_primitives::{Address, TxNumber, B256};
+    use reth_db::{tables, BlockNumberList};
+    use reth_db_api::models::ShardedKey;
+    use reth_provider::{HistoryWriter, PruneCheckpointReader};
+    use reth_prune_types::{PruneLimiter, PruneMode, PruneSegment};
+    use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_testing_utils::generators::{self, random_block_range};
+    use std::collections::{BTreeMap, BTreeSet};
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=100, B256::ZERO, 1..3);
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        // Index a few addresses for every transaction, like the `IndexAddressTransactions` stage
+        // does.
+        let tx_count = blocks.iter().map(|block| block.body.len() as TxNumber).sum::<TxNumber>();
+        let transaction_addresses = (0..tx_count)
+            .map(|tx_num| {
+                let addresses = BTreeSet::from([
+                    Address::with_last_byte((tx_num % 3) as u8),
+                    Address::with_last_byte(10 + (tx_num % 4) as u8),
+                ]);
+                (tx_num, addresses)
+            })
+            .collect::<BTreeMap<_, _>>();
+        let provider = db.factory.provider_rw().unwrap();
+        provider.insert_address_transactions(transaction_addresses.clone()).unwrap();
+        provider.commit().unwrap();
+
+        let to_block = 50;
+        let last_pruned_tx = blocks[..=to_block as usize]
+            .iter()
+            .map(|block| block.body.len() as TxNumber)
+            .sum::<TxNumber>() -
+            1;
+        let prune_mode = PruneMode::Before(to_block + 1);
+        let segment = AddressTransactions::new(prune_mode);
+
+        // Prune in small steps to also cover transactions with partially pruned addresses.
+        loop {
+            let input = PruneInput {
+                previous_checkpoint: db
+                    .factory
+                    .provider()
+                    .unwrap()
+                    .get_prune_checkpoint(PruneSegment::AddressTransactions)
+                    .unwrap(),
+                to_block,
+                limiter: PruneLimiter::default().set_deleted_entries_limit(10),
+            };
+
+            let provider = db.factory.provider_rw().unwrap();
+            let result = segment.prune(&provider, input).unwrap();
+            segment
+                .save_checkpoint(
+                    &provider,
+                    result.checkpoint.unwrap().as_prune_checkpoint(prune_mode),
+                )
+                .unwrap();
+            provider.commit().expect("commit");
+
+            if result.progress.is_finished() {
+                break
+            }
+        }
+
+        let mut expected_index = BTreeMap::<Address, Vec<TxNumber>>::new();
+        for (tx_num, addresses) in transaction_addresses.range(last_pruned_tx + 1..) {
+            for address in addresses {
+                expected_index.entry(*address).or_default().push(*tx_num);
+            }
+        }
+        let actual_index = db
+            .table::<tables::AddressTransactions>()
+            .unwrap()
+            .into_iter()
+            .map(|(key, list): (ShardedKey<Address>, BlockNumberList)| {
+                (key.key, list.iter().collect::<Vec<_>>())
+            })
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(actual_index, expected_index);
+
+        assert!(db
+            .table::<tables::TransactionAddresses>()
+            .unwrap()
+            .into_iter()
+            .all(|(tx_num, _)| tx_num > last_pruned_tx));
+
+        let checkpoint = db
+            .factory
+            .provider()
+            .unwrap()
+            .get_prune_checkpoint(PruneSegment::AddressTransactions)
+            .unwrap()
+            .unwrap();
+        assert_eq!(checkpoint.block_number, Some(to_block));
+        assert_eq!(checkpoint.tx_number, Some(last_pruned_tx));
+    }
+}
@@ -0,0 +1,233 @@
+use crate::{
+    segments::{user::history::prune_history_indices, PruneInput, Segment},
+    PrunerError,
+};
+use itertools::Itertools;
+use reth_db::tables;
+use reth_db_api::{database::Database, models::ShardedKey};
+use reth_provider::{BlockReader, DatabaseProviderRW, ReceiptProvider};
+use reth_prune_types::{
+    PruneInterruptReason, PruneMode, PruneProgress, PrunePurpose, PruneSegment, SegmentOutput,
+    SegmentOutputCheckpoint,
+};
+use rustc_hash::FxHashSet;
+use tracing::{instrument, trace};
+
+/// Prunes the log index tables [`tables::LogAddressIndex`] and [`tables::LogTopicIndex`].
+///
+/// The keys to prune are read from the receipts of the pruned blocks, so this segment has to run
+/// before the receipts are pruned. Shards of blocks whose receipts are already gone are left in
+/// place, but they are outside of the range reported by the log index and never read.
+#[derive(Debug)]
+pub struct LogIndex {
+    mode: PruneMode,
+}
+
+impl LogIndex {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+}
+
+impl<DB: Database> Segment<DB> for LogIndex {
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::LogIndex
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        input: PruneInput,
+    ) -> Result<SegmentOutput, PrunerError> {
+        let range = match input.get_next_block_range() {
+            Some(range) => range,
+            None => {
+                trace!(target: "pruner", "No log index to prune");
+                return Ok(SegmentOutput::done())
+            }
+        };
+        let range_end = *range.end();
+
+        let mut limiter = input.limiter;
+        if limiter.is_limit_reached() {
+            return Ok(SegmentOutput::not_done(
+                PruneInterruptReason::new(&limiter),
+                input.previous_checkpoint.map(SegmentOutputCheckpoint::from_prune_checkpoint),
+            ))
+        }
+
+        // Addresses and topics of the walked logs. Every walked log counts towards the deleted
+        // entries limit, as each of them can result in a shard update.
+        let mut addresses = FxHashSet::default();
+        let mut topics = FxHashSet::default();
+        let mut last_pruned_block = None;
+        let mut done = true;
+        for block_number in range {
+            if limiter.is_limit_reached() {
+                done = false;
+                break
+            }
+
+            if let Some(body_indices) = provider.block_body_indices(block_number)? {
+                for receipt in provider.receipts_by_tx_range(body_indices.tx_num_range())? {
+                    for log in &receipt.logs {
+                        addresses.insert(log.address);
+                        topics.extend(log.topics().iter().copied());
+                        limiter.increment_deleted_entries_count();
+                    }
+                }
+            }
+            last_pruned_block = Some(block_number);
+        }
+        let last_pruned_block = last_pruned_block.unwrap_or(range_end);
+
+        // Sort the keys and turn them into sharded keys.
+        // We did not use `BTreeSet` from the beginning, because it's inefficient for hashes.
+        let address_outcomes = prune_history_indices::<DB, tables::LogAddressIndex, _>(
+            provider,
+            addresses
+                .into_iter()
+                .sorted_unstable() // Unstable is fine because no equal keys exist in the set
+                .map(|address| ShardedKey::new(address, last_pruned_block)),
+            |a, b| a.key == b.key,
+        )?;
+        let topic_outcomes = prune_history_indices::<DB, tables::LogTopicIndex, _>(
+            provider,
+            topics
+                .into_iter()
+                .sorted_unstable()
+                .map(|topic| ShardedKey::new(topic, last_pruned_block)),
+            |a, b| a.key == b.key,
+        )?;
+        trace!(target: "pruner", ?address_outcomes, ?topic_outcomes, %done, "Pruned log index");
+
+        let progress = PruneProgress::new(done, &limiter);
+
+        Ok(SegmentOutput {
+            progress,
+            pruned: address_outcomes.deleted + topic_outcomes.deleted,
+            checkpoint: Some(SegmentOutputCheckpoint {
+                block_number: Some(last_pruned_block),
+                tx_number: None,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{LogIndex, PruneInput, Segment};
+    use alloy_primitives::B256;
+    use reth_db::{tables, BlockNumberList};
+    use reth_db_api::{models::ShardedKey, transaction::DbTxMut};
+    use reth_provider::PruneCheckpointReader;
+    use reth_prune_types::{PruneCheckpoint, PruneLimiter, PruneMode, PruneProgress, PruneSegment};
+    use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_testing_utils::generators::{self, random_block_range, random_receipt};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn prune() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=100, B256::ZERO, 1..3);
+        db.insert_blocks(blocks.iter(), StorageKind::Database(None)).expect("insert blocks");
+
+        // Index the logs of every block, like the `IndexLogs` stage does.
+        let mut receipts = Vec::new();
+        let mut addresses = BTreeMap::<_, Vec<u64>>::new();
+        let mut topics = BTreeMap::<_, Vec<u64>>::new();
+        for block in &blocks {
+            for transaction in &block.body {
+                let receipt = random_receipt(&mut rng, transaction, Some(1));
+                for log in &receipt.logs {
+                    let blocks = addresses.entry(log.address).or_default();
+                    if blocks.last() != Some(&block.number) {
+                        blocks.push(block.number);
+                    }
+                    for topic in log.topics() {
+                        let blocks = topics.entry(*topic).or_default();
+                        if blocks.last() != Some(&block.number) {
+                            blocks.push(block.number);
+                        }
+                    }
+                }
+                receipts.push((receipts.len() as u64, receipt));
+            }
+        }
+        db.insert_receipts(receipts).expect("insert receipts");
+        db.commit(|tx| {
+            for (address, blocks) in &addresses {
+                tx.put::<tables::LogAddressIndex>(
+                    ShardedKey::last(*address),
+                    BlockNumberList::new_pre_sorted(blocks),
+                )?;
+            }
+            for (topic, blocks) in &topics {
+                tx.put::<tables::LogTopicIndex>(
+                    ShardedKey::last(*topic),
+                    BlockNumberList::new_pre_sorted(blocks),
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap();
+
+        let to_block = 50;
+        let prune_mode = PruneMode::Before(to_block + 1);
+        let input = PruneInput {
+            previous_checkpoint: None,
+            to_block,
+            limiter: PruneLimiter::default().set_deleted_entries_limit(usize::MAX),
+        };
+        let segment = LogIndex::new(prune_mode);
+
+        let provider = db.factory.provider_rw().unwrap();
+        let result = segment.prune(&provider, input).unwrap();
+        assert_eq!(result.progress, PruneProgress::Finished);
+        segment
+            .save_checkpoint(&provider, result.checkpoint.unwrap().as_prune_checkpoint(prune_mode))
+            .unwrap();
+        provider.commit().expect("commit");
+
+        fn expected<K: Copy + Ord>(
+            index: &BTreeMap<K, Vec<u64>>,
+            to_block: u64,
+        ) -> BTreeMap<K, Vec<u64>> {
+            index
+                .iter()
+                .filter_map(|(key, blocks)| {
+                    let blocks = blocks.iter().copied().filter(|block| *block > to_block);
+                    let blocks = blocks.collect::<Vec<_>>();
+                    (!blocks.is_empty()).then_some((*key, blocks))
+                })
+                .collect()
+        }
+        fn actual<K: Ord>(entries: Vec<(ShardedKey<K>, BlockNumberList)>) -> BTreeMap<K, Vec<u64>> {
+            entries.into_iter().map(|(key, blocks)| (key.key, blocks.iter().collect())).collect()
+        }
+        assert_eq!(
+            actual(db.table::<tables::LogAddressIndex>().unwrap()),
+            expected(&addresses, to_block)
+        );
+        assert_eq!(
+            actual(db.table::<tables::LogTopicIndex>().unwrap()),
+            expected(&topics, to_block)
+        );
+
+        assert_eq!(
+            db.factory.provider().unwrap().get_prune_checkpoint(PruneSegment::LogIndex).unwrap(),
+            Some(PruneCheckpoint { block_number: Some(to_block), tx_number: None, prune_mode })
+        );
+    }
+}
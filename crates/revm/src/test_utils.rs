@@ -3,10 +3,11 @@ use reth_primitives::{
     keccak256, Account, Address, BlockNumber, Bytecode, Bytes, StorageKey, B256, U256,
 };
 use reth_storage_api::{
-    AccountReader, BlockHashReader, StateProofProvider, StateProvider, StateRootProvider,
+    AccountReader, BlockHashReader, HashedStateRange, StateProofProvider, StateProvider,
+    StateRangeProvider, StateRootProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
-use reth_trie::{updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage};
+use reth_trie::{updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, TrieAccount};
 
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
@@ -107,6 +108,42 @@ impl StateProofProvider for StateProviderTest {
     }
 }
 
+impl StateRangeProvider for StateProviderTest {
+    fn hashed_account_range(
+        &self,
+        _hashed_state: HashedPostState,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<HashedStateRange<TrieAccount>> {
+        unimplemented!("state iteration is not supported")
+    }
+
+    fn hashed_storage_range(
+        &self,
+        _hashed_state: HashedPostState,
+        _hashed_address: B256,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<HashedStateRange<U256>> {
+        unimplemented!("state iteration is not supported")
+    }
+
+    fn account_preimages(
+        &self,
+        _hashed_addresses: &[B256],
+    ) -> ProviderResult<HashMap<B256, Address>> {
+        unimplemented!("state iteration is not supported")
+    }
+
+    fn storage_preimages(
+        &self,
+        _address: Address,
+        _hashed_slots: &[B256],
+    ) -> ProviderResult<HashMap<B256, B256>> {
+        unimplemented!("state iteration is not supported")
+    }
+}
+
 impl StateProvider for StateProviderTest {
     fn storage(
         &self,
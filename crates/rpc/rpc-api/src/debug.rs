@@ -1,13 +1,11 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, B256};
 use reth_rpc_types::{
-    trace::geth::{
-        BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
-        TraceResult,
-    },
+    debug::{AccountRange, DebugSubscriptionKind, StorageRangeResult},
+    trace::geth::{GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult},
     Bundle, RichBlock, StateContext, TransactionRequest,
 };
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 
 /// Debug rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
@@ -39,14 +37,26 @@ pub trait DebugApi {
     #[method(name = "getBadBlocks")]
     async fn bad_blocks(&self) -> RpcResult<Vec<RichBlock>>;
 
-    /// Returns the structured logs created during the execution of EVM between two blocks
-    /// (excluding start) as a JSON object.
-    #[method(name = "traceChain")]
-    async fn debug_trace_chain(
+    /// Creates a subscription of the given kind, like geth's `debug_subscribe`.
+    ///
+    /// The only kind is `traceChain`, which emits the structured logs created during the
+    /// execution of EVM between two blocks (excluding start), one
+    /// [`BlockTraceResult`](reth_rpc_types::trace::geth::BlockTraceResult) per block.
+    ///
+    /// Blocks are traced in ascending order and the next block is only traced once the previous
+    /// result has been sent. For the last parameter see [`GethDebugTracingOptions`].
+    #[subscription(
+        name = "subscribe" => "subscription",
+        unsubscribe = "unsubscribe",
+        item = reth_rpc_types::trace::geth::BlockTraceResult
+    )]
+    async fn debug_subscribe(
         &self,
+        kind: DebugSubscriptionKind,
         start_exclusive: BlockNumberOrTag,
         end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
 
     /// The `debug_traceBlock` method will return a full stack trace of all invoked opcodes of all
     /// transaction that were included in this block.
@@ -152,10 +162,13 @@ pub trait DebugApi {
     async fn debug_backtrace_at(&self, location: &str) -> RpcResult<()>;
 
     /// Enumerates all accounts at a given block with paging capability. `maxResults` are returned
-    /// in the page and the items have keys that come after the `start` key (hashed address).
+    /// in the page and the items have hashed addresses that come after the `start` hashed address.
     ///
     /// If incompletes is false, then accounts for which the key preimage (i.e: the address) doesn't
     /// exist in db are skipped. NB: geth by default does not store preimages.
+    ///
+    /// reth does not store preimages either: addresses are only known for the accounts changed
+    /// since the requested block.
     #[method(name = "accountRange")]
     async fn debug_account_range(
         &self,
@@ -165,7 +178,7 @@ pub trait DebugApi {
         nocode: bool,
         nostorage: bool,
         incompletes: bool,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<AccountRange>;
 
     /// Turns on block profiling for the given duration and writes profile data to disk. It uses a
     /// profile rate of 1 for most accurate information. If a different rate is desired, set the
@@ -173,11 +186,6 @@ pub trait DebugApi {
     #[method(name = "blockProfile")]
     async fn debug_block_profile(&self, file: String, seconds: u64) -> RpcResult<()>;
 
-    /// Flattens the entire key-value database into a single level, removing all unused slots and
-    /// merging all keys.
-    #[method(name = "chaindbCompact")]
-    async fn debug_chaindb_compact(&self) -> RpcResult<()>;
-
     /// Returns leveldb properties of the key-value database.
     #[method(name = "chaindbProperty")]
     async fn debug_chaindb_property(&self, property: String) -> RpcResult<()>;
@@ -236,17 +244,18 @@ pub trait DebugApi {
     async fn debug_get_modified_accounts_by_hash(
         &self,
         start_hash: B256,
-        end_hash: B256,
-    ) -> RpcResult<()>;
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Returns all accounts that have changed between the two blocks specified. A change is defined
-    /// as a difference in nonce, balance, code hash or storage hash.
+    /// as a difference in nonce, balance, code hash or storage hash. With one parameter, returns
+    /// the list of accounts modified in the specified block.
     #[method(name = "getModifiedAccountsByNumber")]
     async fn debug_get_modified_accounts_by_number(
         &self,
         start_number: u64,
-        end_number: u64,
-    ) -> RpcResult<()>;
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Turns on Go runtime tracing for the given duration and writes trace data to disk.
     #[method(name = "goTrace")]
@@ -254,12 +263,14 @@ pub trait DebugApi {
 
     /// Executes a block (bad- or canon- or side-), and returns a list of intermediate roots: the
     /// stateroot after each transaction.
+    ///
+    /// The state and block overrides of `opts` are applied before the block is executed.
     #[method(name = "intermediateRoots")]
     async fn debug_intermediate_roots(
         &self,
         block_hash: B256,
         opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<Vec<B256>>;
 
     /// Returns detailed runtime memory statistics.
     #[method(name = "memStats")]
@@ -312,16 +323,19 @@ pub trait DebugApi {
     #[method(name = "stacks")]
     async fn debug_stacks(&self) -> RpcResult<()>;
 
-    /// Used to obtain info about a block.
+    /// This method is similar to `debug_standardTraceBlockToFile`, but can be used to obtain info
+    /// about a block which has been rejected as invalid (for some reason).
+    ///
+    /// Writes the EIP-3155 trace of each transaction of the bad block to a separate file and
+    /// returns the paths of the written files.
     #[method(name = "standardTraceBadBlockToFile")]
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        block: BlockNumberOrTag,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<String>>;
 
-    /// This method is similar to `debug_standardTraceBlockToFile`, but can be used to obtain info
-    /// about a block which has been rejected as invalid (for some reason).
+    /// Used to obtain info about a block.
     #[method(name = "standardTraceBlockToFile")]
     async fn debug_standard_trace_block_to_file(
         &self,
@@ -348,6 +362,9 @@ pub trait DebugApi {
     /// Returns the storage at the given block height and transaction index. The result can be
     /// paged by providing a `maxResult` to cap the number of storage slots returned as well as
     /// specifying the offset via `keyStart` (hash of storage key).
+    ///
+    /// The `key` of the returned entries is the unhashed storage slot, looked up in the plain
+    /// state.
     #[method(name = "storageRangeAt")]
     async fn debug_storage_range_at(
         &self,
@@ -356,7 +373,7 @@ pub trait DebugApi {
         contract_address: Address,
         key_start: B256,
         max_result: u64,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StorageRangeResult>;
 
     /// Returns the structured logs created during the execution of EVM against a block pulled
     /// from the pool of bad ones and returns them as a JSON object. For the second parameter see
@@ -365,8 +382,8 @@ pub trait DebugApi {
     async fn debug_trace_bad_block(
         &self,
         block_hash: B256,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>>;
 
     /// Sets the logging verbosity ceiling. Log messages with level up to and including the given
     /// level will be printed.
@@ -389,3 +406,20 @@ pub trait DebugApi {
     #[method(name = "writeMutexProfile")]
     async fn debug_write_mutex_profile(&self, file: String) -> RpcResult<()>;
 }
+
+/// Debug rpc interface for compacting the database.
+///
+/// This is separate from [`DebugApi`], because it only needs access to the database and is merged
+/// into the `debug` namespace.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
+pub trait DebugCompactApi {
+    /// Flattens the entire key-value database into a single level, removing all unused slots and
+    /// merging all keys.
+    ///
+    /// Reth writes a compacted copy of the database into the `compact` directory next to it,
+    /// replacing any previous copy, without interrupting the node, and returns the directory of
+    /// the copy. Use `reth db compact` to compact the database in place while the node is stopped.
+    #[method(name = "chaindbCompact")]
+    async fn debug_chaindb_compact(&self) -> RpcResult<PathBuf>;
+}
@@ -0,0 +1,11 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_exex_types::ExExStatus;
+
+/// Reth API namespace for the execution extensions installed on the node.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait ExExApi {
+    /// Returns the status of every `ExEx` installed on the node, sorted by their IDs.
+    #[method(name = "exexStatus")]
+    fn exex_status(&self) -> RpcResult<Vec<ExExStatus>>;
+}
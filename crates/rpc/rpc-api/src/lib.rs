@@ -18,6 +18,7 @@ mod admin;
 mod anvil;
 mod debug;
 mod engine;
+mod exex;
 mod ganache;
 mod hardhat;
 mod mev;
@@ -37,8 +38,9 @@ pub use servers::*;
 pub mod servers {
     pub use crate::{
         admin::AdminApiServer,
-        debug::DebugApiServer,
+        debug::{DebugApiServer, DebugCompactApiServer},
         engine::{EngineApiServer, EngineEthApiServer},
+        exex::ExExApiServer,
         mev::MevApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
@@ -65,8 +67,9 @@ pub mod clients {
     pub use crate::{
         admin::AdminApiClient,
         anvil::AnvilApiClient,
-        debug::DebugApiClient,
+        debug::{DebugApiClient, DebugCompactApiClient},
         engine::{EngineApiClient, EngineEthApiClient},
+        exex::ExExApiClient,
         ganache::GanacheApiClient,
         hardhat::HardhatApiClient,
         mev::MevApiClient,
@@ -1,5 +1,5 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::{Address, BlockId, U256};
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, B256, U256};
 use std::collections::HashMap;
 
 /// Reth API namespace for reth-specific methods
@@ -12,4 +12,16 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Returns the hashes of the transactions sent by or to the address in the block range, in
+    /// chain order.
+    ///
+    /// Requires the address transactions index to be enabled and to cover the block range.
+    #[method(name = "getTransactionsByAddress")]
+    async fn reth_get_transactions_by_address(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<Vec<B256>>;
 }
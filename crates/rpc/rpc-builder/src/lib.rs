@@ -159,13 +159,14 @@ use reth_provider::{
     EvmEnvProvider, FullRpcProvider, StateProviderFactory,
 };
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthBundle, NetApi, OtterscanApi, RPCApi, RethApi, TraceApi,
-    TxPoolApi, Web3Api,
+    AdminApi, DebugApi, DebugCompactApi, EngineEthApi, EthBundle, NetApi, OtterscanApi, RPCApi,
+    RethApi, TraceApi, TxPoolApi, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
     helpers::{
-        Call, EthApiSpec, EthTransactions, LoadPendingBlock, TraceExt, UpdateRawTxForwarder,
+        Call, EthApiSpec, EthState, EthTransactions, LoadPendingBlock, TraceExt,
+        UpdateRawTxForwarder,
     },
     EthApiServer, FullEthApiServer, RawTransactionForwarder,
 };
@@ -833,10 +834,11 @@ where
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn register_debug(&mut self) -> &mut Self
     where
-        EthApi: EthApiSpec + EthTransactions + TraceExt,
+        EthApi: EthApiSpec + EthState + EthTransactions + TraceExt,
     {
-        let debug_api = self.debug_api();
-        self.modules.insert(RethRpcModule::Debug, debug_api.into_rpc().into());
+        let mut debug_module = self.debug_api().into_rpc();
+        debug_module.merge(self.debug_compact_api().into_rpc()).expect("No conflicting methods");
+        self.modules.insert(RethRpcModule::Debug, debug_module.into());
         self
     }
 
@@ -917,12 +919,12 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
-    pub fn otterscan_api(&self) -> OtterscanApi<EthApi>
+    pub fn otterscan_api(&self) -> OtterscanApi<Provider, EthApi>
     where
         EthApi: EthApiServer,
     {
         let eth_api = self.eth_api().clone();
-        OtterscanApi::new(eth_api)
+        OtterscanApi::new(self.provider.clone(), eth_api)
     }
 
     /// Instantiates `DebugApi`
@@ -938,6 +940,11 @@ where
         DebugApi::new(self.provider.clone(), eth_api, self.blocking_pool_guard.clone())
     }
 
+    /// Instantiates `DebugCompactApi`
+    pub fn debug_compact_api(&self) -> DebugCompactApi<Provider> {
+        DebugCompactApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+    }
+
     /// Instantiates `NetApi`
     ///
     /// # Panics
@@ -1054,13 +1061,25 @@ where
                                 .into_rpc()
                                 .into()
                         }
-                        RethRpcModule::Debug => DebugApi::new(
-                            self.provider.clone(),
-                            eth_api.clone(),
-                            self.blocking_pool_guard.clone(),
-                        )
-                        .into_rpc()
-                        .into(),
+                        RethRpcModule::Debug => {
+                            let mut module = DebugApi::new(
+                                self.provider.clone(),
+                                eth_api.clone(),
+                                self.blocking_pool_guard.clone(),
+                            )
+                            .into_rpc();
+                            module
+                                .merge(
+                                    DebugCompactApi::new(
+                                        self.provider.clone(),
+                                        Box::new(self.executor.clone()),
+                                    )
+                                    .into_rpc(),
+                                )
+                                .expect("No conflicts");
+
+                            module.into()
+                        }
                         RethRpcModule::Eth => {
                             // merge all eth handlers
                             let mut module = eth_api.clone().into_rpc();
@@ -1091,7 +1110,11 @@ where
                         )
                         .into_rpc()
                         .into(),
-                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
+                        RethRpcModule::Ots => {
+                            OtterscanApi::new(self.provider.clone(), eth_api.clone())
+                                .into_rpc()
+                                .into()
+                        }
                         RethRpcModule::Reth => {
                             RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
                                 .into_rpc()
@@ -1690,6 +1713,28 @@ impl TransportRpcModules {
         Ok(false)
     }
 
+    /// Merge the given [Methods] in the methods of the transports the given module is configured
+    /// for.
+    ///
+    /// Fails if any of the methods in other is present already.
+    pub fn merge_if_module_configured(
+        &mut self,
+        module: RethRpcModule,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        if self.config.http().is_some_and(|http| http.contains(&module)) {
+            self.merge_http(other.clone())?;
+        }
+        if self.config.ws().is_some_and(|ws| ws.contains(&module)) {
+            self.merge_ws(other.clone())?;
+        }
+        if self.config.ipc().is_some_and(|ipc| ipc.contains(&module)) {
+            self.merge_ipc(other)?;
+        }
+        Ok(())
+    }
+
     /// Merge the given [Methods] in all configured methods.
     ///
     /// Fails if any of the methods in other is present already.
@@ -26,6 +26,7 @@ where
     EngineApiClient::exchange_transition_configuration(client, TransitionConfiguration::default())
         .await;
     EngineApiClient::exchange_capabilities(client, vec![]).await;
+    EngineApiClient::get_blobs_v1(client, vec![]).await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
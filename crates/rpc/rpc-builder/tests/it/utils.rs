@@ -46,6 +46,7 @@ pub async fn launch_auth(secret: JwtSecret) -> AuthServerHandle {
         Box::<TokioTaskExecutor>::default(),
         client,
         EngineCapabilities::default(),
+        TestPool::from(TestPoolBuilder::default()),
     );
     let module = AuthRpcModule::new(engine_api);
     module.start_server(config).await.unwrap()
@@ -17,17 +17,21 @@ use reth_primitives::{
 };
 use reth_rpc_api::EngineApiServer;
 use reth_rpc_types::engine::{
-    CancunPayloadFields, ClientVersionV1, ExecutionPayload, ExecutionPayloadBodiesV1,
-    ExecutionPayloadBodiesV2, ExecutionPayloadInputV2, ExecutionPayloadV1, ExecutionPayloadV3,
-    ExecutionPayloadV4, ForkchoiceState, ForkchoiceUpdated, PayloadId, PayloadStatus,
-    TransitionConfiguration,
+    BlobAndProofV1, CancunPayloadFields, ClientVersionV1, ExecutionPayload,
+    ExecutionPayloadBodiesV1, ExecutionPayloadBodiesV2, ExecutionPayloadInputV2,
+    ExecutionPayloadV1, ExecutionPayloadV3, ExecutionPayloadV4, ForkchoiceState, ForkchoiceUpdated,
+    PayloadId, PayloadStatus, TransitionConfiguration,
 };
 use reth_rpc_types_compat::engine::payload::{
     convert_payload_input_v2_to_payload, convert_to_payload_body_v1, convert_to_payload_body_v2,
 };
-use reth_storage_api::{BlockReader, HeaderProvider, StateProviderFactory};
+use reth_storage_api::{
+    errors::provider::{ProviderError, ProviderResult},
+    BlockReader, HeaderProvider, StateProviderFactory,
+};
 use reth_tasks::TaskSpawner;
-use std::{sync::Arc, time::Instant};
+use reth_transaction_pool::{BlobAndProof, TransactionPool};
+use std::{ops::RangeInclusive, sync::Arc, time::Instant};
 use tokio::sync::oneshot;
 use tracing::{trace, warn};
 
@@ -37,13 +41,51 @@ pub type EngineApiSender<Ok> = oneshot::Sender<EngineApiResult<Ok>>;
 /// The upper limit for payload bodies request.
 const MAX_PAYLOAD_BODIES_LIMIT: u64 = 1024;
 
+/// The upper limit of versioned hashes in a blobs request.
+const MAX_BLOB_LIMIT: usize = 128;
+
+/// The default number of blocks read at once when serving payload bodies by range.
+const DEFAULT_PAYLOAD_BODIES_CHUNK_SIZE: u64 = 64;
+
+/// The default limit of the total size of the transactions in a payload bodies response, 128MB.
+///
+/// Transactions are hex encoded in the response, so it stays below the maximum response size of
+/// the auth server.
+const DEFAULT_PAYLOAD_BODIES_MAX_RESPONSE_SIZE: usize = 128 * 1024 * 1024;
+
+/// Limits of the `engine_getPayloadBodiesByRange` and `engine_getPayloadBodiesByHash` responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadBodiesLimits {
+    /// The number of blocks read from the provider at once when serving a range.
+    ///
+    /// The transactions of a chunk are read in one pass over the static file `Transactions`
+    /// segment and converted to payload bodies chunk by chunk, so this bounds the memory used for
+    /// the blocks in addition to the response.
+    pub chunk_size: u64,
+    /// The maximum total size of the encoded transactions in a response.
+    ///
+    /// Requests whose response doesn't fit are rejected.
+    pub max_response_size: usize,
+}
+
+impl Default for PayloadBodiesLimits {
+    fn default() -> Self {
+        Self {
+            chunk_size: DEFAULT_PAYLOAD_BODIES_CHUNK_SIZE,
+            max_response_size: DEFAULT_PAYLOAD_BODIES_MAX_RESPONSE_SIZE,
+        }
+    }
+}
+
 /// The Engine API implementation that grants the Consensus layer access to data and
 /// functions in the Execution layer that are crucial for the consensus process.
-pub struct EngineApi<Provider, EngineT: EngineTypes> {
-    inner: Arc<EngineApiInner<Provider, EngineT>>,
+pub struct EngineApi<Provider, EngineT: EngineTypes, Pool> {
+    inner: Arc<EngineApiInner<Provider, EngineT, Pool>>,
+    /// Limits of the payload bodies responses.
+    payload_bodies_limits: PayloadBodiesLimits,
 }
 
-struct EngineApiInner<Provider, EngineT: EngineTypes> {
+struct EngineApiInner<Provider, EngineT: EngineTypes, Pool> {
     /// The provider to interact with the chain.
     provider: Provider,
     /// Consensus configuration
@@ -60,14 +102,18 @@ struct EngineApiInner<Provider, EngineT: EngineTypes> {
     client: ClientVersionV1,
     /// The list of all supported Engine capabilities available over the engine endpoint.
     capabilities: EngineCapabilities,
+    /// Transaction pool, whose blob store serves the blobs requested by the consensus client.
+    tx_pool: Pool,
 }
 
-impl<Provider, EngineT> EngineApi<Provider, EngineT>
+impl<Provider, EngineT, Pool> EngineApi<Provider, EngineT, Pool>
 where
     Provider: HeaderProvider + BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     EngineT: EngineTypes,
+    Pool: TransactionPool + 'static,
 {
     /// Create new instance of [`EngineApi`].
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         provider: Provider,
         chain_spec: Arc<ChainSpec>,
@@ -76,6 +122,7 @@ where
         task_spawner: Box<dyn TaskSpawner>,
         client: ClientVersionV1,
         capabilities: EngineCapabilities,
+        tx_pool: Pool,
     ) -> Self {
         let inner = Arc::new(EngineApiInner {
             provider,
@@ -86,8 +133,15 @@ where
             metrics: EngineApiMetrics::default(),
             client,
             capabilities,
+            tx_pool,
         });
-        Self { inner }
+        Self { inner, payload_bodies_limits: PayloadBodiesLimits::default() }
+    }
+
+    /// Sets the limits of the payload bodies responses.
+    pub const fn with_payload_bodies_limits(mut self, limits: PayloadBodiesLimits) -> Self {
+        self.payload_bodies_limits = limits;
+        self
     }
 
     /// Fetches the client version.
@@ -369,6 +423,11 @@ where
 
     /// Fetches all the blocks for the provided range starting at `start`, containing `count`
     /// blocks and returns the mapped payload bodies.
+    ///
+    /// The blocks are read in chunks of [`PayloadBodiesLimits::chunk_size`] blocks. Only the
+    /// headers, transactions, withdrawals and requests of the blocks are read, since the payload
+    /// bodies don't contain the ommers. Requests whose response is larger than
+    /// [`PayloadBodiesLimits::max_response_size`] are rejected.
     async fn get_payload_bodies_by_range_with<F, R>(
         &self,
         start: BlockNumber,
@@ -381,6 +440,7 @@ where
     {
         let (tx, rx) = oneshot::channel();
         let inner = self.inner.clone();
+        let limits = self.payload_bodies_limits;
 
         self.inner.task_spawner.spawn_blocking(Box::pin(async move {
             if count > MAX_PAYLOAD_BODIES_LIMIT {
@@ -393,32 +453,59 @@ where
                 return;
             }
 
-            let mut result = Vec::with_capacity(count as usize);
+            let metrics = inner.metrics.payload_bodies.by_range(count);
+            let result = (|| {
+                let mut result = Vec::with_capacity(count as usize);
+                let mut response_size = 0;
 
-            // -1 so range is inclusive
-            let mut end = start.saturating_add(count - 1);
+                // -1 so range is inclusive
+                let mut end = start.saturating_add(count - 1);
 
-            // > Client software MUST NOT return trailing null values if the request extends past the current latest known block.
-            // truncate the end if it's greater than the last block
-            if let Ok(best_block) = inner.provider.best_block_number() {
-                if end > best_block {
-                    end = best_block;
+                // > Client software MUST NOT return trailing null values if the request extends past the current latest known block.
+                // truncate the end if it's greater than the last block
+                if let Ok(best_block) = inner.provider.best_block_number() {
+                    if end > best_block {
+                        end = best_block;
+                    }
                 }
-            }
 
-            for num in start..=end {
-                let block_result = inner.provider.block(BlockHashOrNumber::Number(num));
-                match block_result {
-                    Ok(block) => {
+                let mut chunk_start = start;
+                while chunk_start <= end {
+                    let chunk_end =
+                        chunk_start.saturating_add(limits.chunk_size.max(1) - 1).min(end);
+                    let blocks = payload_body_blocks(&inner.provider, chunk_start..=chunk_end)
+                        .map_err(|err| EngineApiError::Internal(Box::new(err)))?;
+
+                    // Blocks that are missing in the provider are skipped in the chunk
+                    let mut blocks = blocks.into_iter().peekable();
+                    for num in chunk_start..=chunk_end {
+                        let block = blocks.next_if(|block| block.number == num);
+                        if let Some(block) = &block {
+                            response_size += transactions_size(block);
+                            if response_size > limits.max_response_size {
+                                return Err(EngineApiError::PayloadResponseTooLarge {
+                                    max_response_size: limits.max_response_size,
+                                })
+                            }
+                        }
                         result.push(block.map(&f));
                     }
-                    Err(err) => {
-                        tx.send(Err(EngineApiError::Internal(Box::new(err)))).ok();
-                        return;
+
+                    if chunk_end == end {
+                        break
                     }
-                };
+                    chunk_start = chunk_end + 1;
+                }
+
+                metrics.response_size.record(response_size as f64);
+                Ok(result)
+            })();
+
+            if matches!(result, Err(EngineApiError::PayloadResponseTooLarge { .. })) {
+                trace!(target: "rpc::engine", start, count, "Rejected payload bodies response");
+                metrics.rejected_responses.increment(1);
             }
-            tx.send(Ok(result)).ok();
+            tx.send(result).ok();
         }));
 
         rx.await.map_err(|err| EngineApiError::Internal(Box::new(err)))?
@@ -468,15 +555,25 @@ where
             return Err(EngineApiError::PayloadRequestTooLarge { len })
         }
 
+        let max_response_size = self.payload_bodies_limits.max_response_size;
         let mut result = Vec::with_capacity(hashes.len());
+        let mut response_size = 0;
         for hash in hashes {
             let block = self
                 .inner
                 .provider
                 .block(BlockHashOrNumber::Hash(hash))
                 .map_err(|err| EngineApiError::Internal(Box::new(err)))?;
+            if let Some(block) = &block {
+                response_size += transactions_size(block);
+                if response_size > max_response_size {
+                    self.inner.metrics.payload_bodies.by_hash(len).rejected_responses.increment(1);
+                    return Err(EngineApiError::PayloadResponseTooLarge { max_response_size })
+                }
+            }
             result.push(block.map(&f));
         }
+        self.inner.metrics.payload_bodies.by_hash(len).response_size.record(response_size as f64);
 
         Ok(result)
     }
@@ -499,6 +596,37 @@ where
         self.get_payload_bodies_by_hash_with(hashes, convert_to_payload_body_v2)
     }
 
+    /// Returns the blob and proof for each of the given versioned hashes from the blob store of the
+    /// transaction pool, or `None` if the blob is not available.
+    ///
+    /// Caution: This method does not check whether the hashes belong to a canonical block, it only
+    /// serves the blobs that are known to the pool.
+    pub fn get_blobs_v1(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> EngineApiResult<Vec<Option<BlobAndProofV1>>> {
+        if versioned_hashes.len() > MAX_BLOB_LIMIT {
+            return Err(EngineApiError::PayloadRequestTooLarge {
+                len: versioned_hashes.len() as u64,
+            })
+        }
+
+        let blobs = self
+            .inner
+            .tx_pool
+            .get_blobs_for_versioned_hashes(&versioned_hashes)
+            .map_err(|err| EngineApiError::Internal(Box::new(err)))?;
+
+        let hits = blobs.iter().filter(|blob| blob.is_some()).count();
+        self.inner.metrics.blobs.blob_hits.increment(hits as u64);
+        self.inner.metrics.blobs.blob_misses.increment((blobs.len() - hits) as u64);
+
+        Ok(blobs
+            .into_iter()
+            .map(|blob| blob.map(|BlobAndProof { blob, proof }| BlobAndProofV1 { blob, proof }))
+            .collect())
+    }
+
     /// Called to verify network configuration parameters and ensure that Consensus and Execution
     /// layers are using the latest configuration.
     pub fn exchange_transition_configuration(
@@ -609,10 +737,11 @@ where
 }
 
 #[async_trait]
-impl<Provider, EngineT> EngineApiServer<EngineT> for EngineApi<Provider, EngineT>
+impl<Provider, EngineT, Pool> EngineApiServer<EngineT> for EngineApi<Provider, EngineT, Pool>
 where
     Provider: HeaderProvider + BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     EngineT: EngineTypes,
+    Pool: TransactionPool + 'static,
 {
     /// Handler for `engine_newPayloadV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/paris.md#engine_newpayloadv1>
@@ -817,8 +946,10 @@ where
     ) -> RpcResult<ExecutionPayloadBodiesV1> {
         trace!(target: "rpc::engine", "Serving engine_getPayloadBodiesByHashV1");
         let start = Instant::now();
+        let len = block_hashes.len() as u64;
         let res = Self::get_payload_bodies_by_hash_v1(self, block_hashes);
         self.inner.metrics.latency.get_payload_bodies_by_hash_v1.record(start.elapsed());
+        self.inner.metrics.payload_bodies.by_hash(len).latency.record(start.elapsed());
         Ok(res?)
     }
 
@@ -828,8 +959,10 @@ where
     ) -> RpcResult<ExecutionPayloadBodiesV2> {
         trace!(target: "rpc::engine", "Serving engine_getPayloadBodiesByHashV2");
         let start = Instant::now();
+        let len = block_hashes.len() as u64;
         let res = Self::get_payload_bodies_by_hash_v2(self, block_hashes);
         self.inner.metrics.latency.get_payload_bodies_by_hash_v2.record(start.elapsed());
+        self.inner.metrics.payload_bodies.by_hash(len).latency.record(start.elapsed());
         Ok(res?)
     }
 
@@ -858,6 +991,7 @@ where
         let start_time = Instant::now();
         let res = Self::get_payload_bodies_by_range_v1(self, start.to(), count.to()).await;
         self.inner.metrics.latency.get_payload_bodies_by_range_v1.record(start_time.elapsed());
+        self.inner.metrics.payload_bodies.by_range(count.to()).latency.record(start_time.elapsed());
         Ok(res?)
     }
 
@@ -870,6 +1004,7 @@ where
         let start_time = Instant::now();
         let res = Self::get_payload_bodies_by_range_v2(self, start.to(), count.to()).await;
         self.inner.metrics.latency.get_payload_bodies_by_range_v2.record(start_time.elapsed());
+        self.inner.metrics.payload_bodies.by_range(count.to()).latency.record(start_time.elapsed());
         Ok(res?)
     }
 
@@ -904,9 +1039,55 @@ where
     async fn exchange_capabilities(&self, _capabilities: Vec<String>) -> RpcResult<Vec<String>> {
         Ok(self.inner.capabilities.list())
     }
+
+    /// Handler for `engine_getBlobsV1`
+    ///
+    /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#engine_getblobsv1>
+    async fn get_blobs_v1(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> RpcResult<Vec<Option<BlobAndProofV1>>> {
+        trace!(target: "rpc::engine", "Serving engine_getBlobsV1");
+        let start = Instant::now();
+        let res = Self::get_blobs_v1(self, versioned_hashes);
+        self.inner.metrics.latency.get_blobs_v1.record(start.elapsed());
+        Ok(res?)
+    }
+}
+
+/// Reads the blocks of the range that are known to the provider, without their ommers.
+///
+/// The transactions of the whole range are read at once, which streams them from the static file
+/// `Transactions` segment instead of looking up the transactions of each block.
+fn payload_body_blocks<Provider: BlockReader>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+) -> ProviderResult<Vec<Block>> {
+    let headers = provider.headers_range(range.clone())?;
+    let transactions = provider.transactions_by_block_range(range)?;
+    if let Some(header) = headers.get(transactions.len()) {
+        return Err(ProviderError::BlockBodyIndicesNotFound(header.number))
+    }
+
+    headers
+        .into_iter()
+        .zip(transactions)
+        .map(|(header, body)| {
+            let withdrawals =
+                provider.withdrawals_by_block(header.number.into(), header.timestamp)?;
+            let requests = provider.requests_by_block(header.number.into(), header.timestamp)?;
+            Ok(Block { header, body, ommers: Vec::new(), withdrawals, requests })
+        })
+        .collect()
 }
 
-impl<Provider, EngineT> std::fmt::Debug for EngineApi<Provider, EngineT>
+/// Returns the total size of the encoded transactions of the block, which make up most of its
+/// payload body.
+fn transactions_size(block: &Block) -> usize {
+    block.body.iter().map(|tx| tx.length_without_header()).sum()
+}
+
+impl<Provider, EngineT, Pool> std::fmt::Debug for EngineApi<Provider, EngineT, Pool>
 where
     EngineT: EngineTypes,
 {
@@ -923,18 +1104,25 @@ mod tests {
     use reth_ethereum_engine_primitives::EthEngineTypes;
     use reth_testing_utils::generators::random_block;
 
+    use alloy_eips::eip4844::{Blob, Bytes48};
     use reth_chainspec::MAINNET;
     use reth_payload_builder::test_utils::spawn_test_payload_service;
-    use reth_primitives::{SealedBlock, B256};
+    use reth_primitives::{BlobTransactionSidecar, SealedBlock, B256};
     use reth_provider::test_utils::MockEthProvider;
     use reth_rpc_types::engine::{ClientCode, ClientVersionV1};
     use reth_rpc_types_compat::engine::payload::execution_payload_from_sealed_block;
     use reth_tasks::TokioTaskExecutor;
     use reth_tokio_util::EventSender;
+    use reth_transaction_pool::{
+        blobstore::InMemoryBlobStore,
+        noop::MockTransactionValidator,
+        test_utils::{MockOrdering, TestPool},
+        BlobStore, Pool,
+    };
     use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
 
-    fn setup_engine_api() -> (EngineApiTestHandle, EngineApi<Arc<MockEthProvider>, EthEngineTypes>)
-    {
+    fn setup_engine_api(
+    ) -> (EngineApiTestHandle, EngineApi<Arc<MockEthProvider>, EthEngineTypes, TestPool>) {
         let client = ClientVersionV1 {
             code: ClientCode::RH,
             name: "Reth".to_string(),
@@ -948,6 +1136,13 @@ mod tests {
         let (to_engine, engine_rx) = unbounded_channel();
         let event_sender: EventSender<BeaconConsensusEngineEvent> = Default::default();
         let task_executor = Box::<TokioTaskExecutor>::default();
+        let blob_store = InMemoryBlobStore::default();
+        let tx_pool = Pool::new(
+            MockTransactionValidator::default(),
+            MockOrdering::default(),
+            blob_store.clone(),
+            Default::default(),
+        );
         let api = EngineApi::new(
             provider.clone(),
             chain_spec.clone(),
@@ -956,8 +1151,9 @@ mod tests {
             task_executor,
             client,
             EngineCapabilities::default(),
+            tx_pool,
         );
-        let handle = EngineApiTestHandle { chain_spec, provider, from_api: engine_rx };
+        let handle = EngineApiTestHandle { chain_spec, provider, blob_store, from_api: engine_rx };
         (handle, api)
     }
 
@@ -977,6 +1173,7 @@ mod tests {
     struct EngineApiTestHandle {
         chain_spec: Arc<ChainSpec>,
         provider: Arc<MockEthProvider>,
+        blob_store: InMemoryBlobStore,
         from_api: UnboundedReceiver<BeaconEngineMessage<EthEngineTypes>>,
     }
 
@@ -993,6 +1190,34 @@ mod tests {
     }
 
     // tests covering `engine_getPayloadBodiesByRange` and `engine_getPayloadBodiesByHash`
+    #[tokio::test]
+    async fn engine_get_blobs_v1() {
+        let (handle, api) = setup_engine_api();
+
+        let sidecar = BlobTransactionSidecar {
+            blobs: vec![Blob::repeat_byte(1)],
+            commitments: vec![Bytes48::repeat_byte(2)],
+            proofs: vec![Bytes48::repeat_byte(3)],
+        };
+        let versioned_hash = sidecar.versioned_hashes().next().unwrap();
+        handle.blob_store.insert(B256::random(), sidecar).unwrap();
+
+        let res = api.get_blobs_v1(vec![B256::random(), versioned_hash]).unwrap();
+        assert_eq!(
+            res,
+            vec![
+                None,
+                Some(BlobAndProofV1 {
+                    blob: Box::new(Blob::repeat_byte(1)),
+                    proof: Bytes48::repeat_byte(3),
+                }),
+            ]
+        );
+
+        let res = api.get_blobs_v1(vec![B256::random(); MAX_BLOB_LIMIT + 1]);
+        assert_matches!(res, Err(EngineApiError::PayloadRequestTooLarge { len }) if len == MAX_BLOB_LIMIT as u64 + 1);
+    }
+
     mod get_payload_bodies {
         use super::*;
         use reth_testing_utils::{generators, generators::random_block_range};
@@ -1104,6 +1329,45 @@ mod tests {
             let res = api.get_payload_bodies_by_hash_v1(hashes).unwrap();
             assert_eq!(res, expected);
         }
+
+        #[tokio::test]
+        async fn limits_payload_bodies_response_size() {
+            let mut rng = generators::rng();
+            let (handle, api) = setup_engine_api();
+
+            let (start, count) = (1, 10);
+            let blocks =
+                random_block_range(&mut rng, start..=start + count - 1, B256::default(), 1..2);
+            handle.provider.extend_blocks(blocks.iter().cloned().map(|b| (b.hash(), b.unseal())));
+
+            // Fit the transactions of all blocks, read two blocks at a time
+            let max_response_size =
+                blocks.iter().map(|b| transactions_size(&b.clone().unseal())).sum();
+            let api = api.with_payload_bodies_limits(PayloadBodiesLimits {
+                chunk_size: 2,
+                max_response_size,
+            });
+
+            let expected = blocks
+                .iter()
+                .cloned()
+                .map(|b| Some(convert_to_payload_body_v1(b.unseal())))
+                .collect::<Vec<_>>();
+            let res = api.get_payload_bodies_by_range_v1(start, count).await.unwrap();
+            assert_eq!(res, expected);
+
+            // Responses that don't fit are rejected instead of truncated
+            let api = api.with_payload_bodies_limits(PayloadBodiesLimits {
+                chunk_size: 2,
+                max_response_size: max_response_size - 1,
+            });
+            let res = api.get_payload_bodies_by_range_v1(start, count).await;
+            assert_matches!(res, Err(EngineApiError::PayloadResponseTooLarge { .. }));
+
+            let hashes = blocks.iter().map(|b| b.hash()).collect();
+            let res = api.get_payload_bodies_by_hash_v1(hashes);
+            assert_matches!(res, Err(EngineApiError::PayloadResponseTooLarge { .. }));
+        }
     }
 
     // https://github.com/ethereum/execution-apis/blob/main/src/engine/paris.md#specification-3
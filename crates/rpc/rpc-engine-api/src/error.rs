@@ -42,6 +42,12 @@ pub enum EngineApiError {
         /// The length that was requested.
         len: u64,
     },
+    /// The payload bodies response is larger than the configured limit.
+    #[error("payload bodies response larger than {max_response_size} bytes")]
+    PayloadResponseTooLarge {
+        /// The maximum size of the encoded transactions in a response.
+        max_response_size: usize,
+    },
     /// Thrown if `engine_getPayloadBodiesByRangeV1` contains an invalid range
     #[error("invalid start ({start}) or count ({count})")]
     InvalidBodiesRange {
@@ -147,7 +153,8 @@ impl From<EngineApiError> for jsonrpsee_types::error::ErrorObject<'static> {
                 error.to_string(),
                 None::<()>,
             ),
-            EngineApiError::PayloadRequestTooLarge { .. } => {
+            EngineApiError::PayloadRequestTooLarge { .. } |
+            EngineApiError::PayloadResponseTooLarge { .. } => {
                 jsonrpsee_types::error::ErrorObject::owned(
                     REQUEST_TOO_LARGE_CODE,
                     REQUEST_TOO_LARGE_MESSAGE,
@@ -222,6 +229,12 @@ mod tests {
             EngineApiError::PayloadRequestTooLarge { len: 0 },
         );
 
+        ensure_engine_rpc_error(
+            REQUEST_TOO_LARGE_CODE,
+            "Too large request",
+            EngineApiError::PayloadResponseTooLarge { max_response_size: 0 },
+        );
+
         ensure_engine_rpc_error(
             -38002,
             "Invalid forkchoice state",
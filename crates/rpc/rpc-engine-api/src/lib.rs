@@ -24,7 +24,7 @@ mod error;
 /// Engine API metrics.
 mod metrics;
 
-pub use engine_api::{EngineApi, EngineApiSender};
+pub use engine_api::{EngineApi, EngineApiSender, PayloadBodiesLimits};
 pub use error::*;
 pub use message::EngineApiMessageVersion;
 
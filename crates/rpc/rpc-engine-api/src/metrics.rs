@@ -14,6 +14,10 @@ pub(crate) struct EngineApiMetrics {
     pub(crate) fcu_response: ForkchoiceUpdatedResponseMetrics,
     /// Engine API newPayload response type metrics
     pub(crate) new_payload_response: NewPayloadStatusResponseMetrics,
+    /// Engine API getPayloadBodies metrics by request size
+    pub(crate) payload_bodies: PayloadBodiesMetrics,
+    /// Engine API getBlobs metrics
+    pub(crate) blobs: BlobMetrics,
 }
 
 /// Beacon consensus engine latency metrics.
@@ -52,6 +56,78 @@ pub(crate) struct EngineApiLatencyMetrics {
     pub(crate) get_payload_bodies_by_hash_v2: Histogram,
     /// Latency for `engine_exchangeTransitionConfigurationV1`
     pub(crate) exchange_transition_configuration: Histogram,
+    /// Latency for `engine_getBlobsV1`
+    pub(crate) get_blobs_v1: Histogram,
+}
+
+/// The upper bounds of the request size buckets of the payload bodies metrics, with their labels.
+const PAYLOAD_BODIES_REQUEST_SIZES: [(u64, &str); 4] =
+    [(32, "1-32"), (128, "33-128"), (512, "129-512"), (u64::MAX, "513+")];
+
+/// Metrics for engine API getPayloadBodiesByRange and getPayloadBodiesByHash requests, by the
+/// number of requested bodies.
+pub(crate) struct PayloadBodiesMetrics {
+    by_range: Vec<PayloadBodiesRequestMetrics>,
+    by_hash: Vec<PayloadBodiesRequestMetrics>,
+}
+
+impl PayloadBodiesMetrics {
+    /// Returns the metrics of getPayloadBodiesByRange requests for `count` bodies.
+    pub(crate) fn by_range(&self, count: u64) -> &PayloadBodiesRequestMetrics {
+        &self.by_range[Self::bucket(count)]
+    }
+
+    /// Returns the metrics of getPayloadBodiesByHash requests for `count` bodies.
+    pub(crate) fn by_hash(&self, count: u64) -> &PayloadBodiesRequestMetrics {
+        &self.by_hash[Self::bucket(count)]
+    }
+
+    fn bucket(count: u64) -> usize {
+        PAYLOAD_BODIES_REQUEST_SIZES
+            .iter()
+            .position(|(max, _)| count <= *max)
+            .unwrap_or(PAYLOAD_BODIES_REQUEST_SIZES.len() - 1)
+    }
+}
+
+impl Default for PayloadBodiesMetrics {
+    fn default() -> Self {
+        let metrics = |method: &'static str| {
+            PAYLOAD_BODIES_REQUEST_SIZES
+                .iter()
+                .map(|(_, size)| {
+                    PayloadBodiesRequestMetrics::new_with_labels(&[
+                        ("method", method),
+                        ("request_size", size),
+                    ])
+                })
+                .collect()
+        };
+        Self { by_range: metrics("by_range"), by_hash: metrics("by_hash") }
+    }
+}
+
+/// Metrics for engine API getPayloadBodies requests of a method and request size.
+#[derive(Metrics)]
+#[metrics(scope = "engine.rpc.payload_bodies")]
+pub(crate) struct PayloadBodiesRequestMetrics {
+    /// Latency of the requests
+    pub(crate) latency: Histogram,
+    /// Total size of the encoded transactions in the responses
+    pub(crate) response_size: Histogram,
+    /// The total count of requests rejected because their response exceeded the maximum response
+    /// size
+    pub(crate) rejected_responses: Counter,
+}
+
+/// Metrics for engine API getBlobs requests.
+#[derive(Metrics)]
+#[metrics(scope = "engine.rpc.blobs")]
+pub(crate) struct BlobMetrics {
+    /// The total count of blobs that were found in the blob store
+    pub(crate) blob_hits: Counter,
+    /// The total count of blobs that were not found in the blob store
+    pub(crate) blob_misses: Counter,
 }
 
 /// Metrics for engine API forkchoiceUpdated responses.
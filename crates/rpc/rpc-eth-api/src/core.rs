@@ -13,7 +13,7 @@ use reth_primitives::{
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use reth_rpc_types::{
     serde_helpers::JsonStorageKey,
-    simulate::{SimBlock, SimulatedBlock},
+    simulate::{SimulatePayload, SimulatedBlock},
     state::{EvmOverrides, StateOverride},
     AnyTransactionReceipt, BlockOverrides, Bundle, EIP1186AccountProofResponse, EthCallResponse,
     FeeHistory, Header, Index, RichBlock, StateContext, SyncStatus, Transaction,
@@ -197,7 +197,7 @@ pub trait EthApi {
     #[method(name = "simulateV1")]
     async fn simulate_v1(
         &self,
-        opts: SimBlock,
+        payload: SimulatePayload,
         block_number: Option<BlockId>,
     ) -> RpcResult<Vec<SimulatedBlock>>;
 
@@ -578,11 +578,11 @@ where
     /// Handler for: `eth_simulateV1`
     async fn simulate_v1(
         &self,
-        opts: SimBlock,
+        payload: SimulatePayload,
         block_number: Option<BlockId>,
     ) -> RpcResult<Vec<SimulatedBlock>> {
         trace!(target: "rpc::eth", ?block_number, "Serving eth_simulateV1");
-        Ok(EthCall::simulate_v1(self, opts, block_number).await?)
+        Ok(EthCall::simulate_v1(self, payload, block_number).await?)
     }
 
     /// Handler for: `eth_call`
@@ -650,10 +650,11 @@ where
     /// Handler for: `eth_getAccount`
     async fn get_account(
         &self,
-        _address: Address,
-        _block: BlockId,
+        address: Address,
+        block: BlockId,
     ) -> RpcResult<reth_rpc_types::Account> {
-        Err(internal_rpc_err("unimplemented"))
+        trace!(target: "rpc::eth", ?address, ?block, "Serving eth_getAccount");
+        Ok(EthState::get_account(self, address, block).await?)
     }
 
     /// Handler for: `eth_maxPriorityFeePerGas`
@@ -736,14 +737,15 @@ where
     }
 
     /// Handler for: `eth_signTransaction`
-    async fn sign_transaction(&self, _transaction: TransactionRequest) -> RpcResult<Bytes> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn sign_transaction(&self, request: TransactionRequest) -> RpcResult<Bytes> {
+        trace!(target: "rpc::eth", ?request, "Serving eth_signTransaction");
+        Ok(EthTransactions::sign_transaction(self, request).await?)
     }
 
     /// Handler for: `eth_signTypedData`
     async fn sign_typed_data(&self, address: Address, data: TypedData) -> RpcResult<Bytes> {
         trace!(target: "rpc::eth", ?address, ?data, "Serving eth_signTypedData");
-        Ok(EthTransactions::sign_typed_data(self, &data, address)?)
+        Ok(EthTransactions::sign_typed_data(self, &data, address).await?)
     }
 
     /// Handler for: `eth_getProof`
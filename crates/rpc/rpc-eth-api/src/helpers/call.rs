@@ -3,16 +3,19 @@
 
 use crate::{AsEthApiError, FromEthApiError, FromEvmError, IntoEthApiError};
 use futures::Future;
-use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
+use reth_evm::{system_calls::pre_block_beacon_root_contract_call, ConfigureEvm, ConfigureEvmEnv};
 use reth_primitives::{
     revm_primitives::{
-        BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ExecutionResult, HaltReason,
-        ResultAndState, TransactTo, TxEnv,
+        BlockEnv, CfgEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ExecutionResult, HaltReason,
+        ResultAndState, SpecId, TransactTo, TxEnv,
     },
     transaction::AccessListResult,
-    Bytes, TransactionSignedEcRecovered, TxKind, B256, U256,
+    Bytes, Signature, TransactionSigned, TransactionSignedEcRecovered, TxKind, B256, U256,
+};
+use reth_provider::{
+    BlockNumReader, BlockReaderIdExt, ChainSpecProvider, HeaderProvider, StateProvider,
+    StateRootProvider,
 };
-use reth_provider::{ChainSpecProvider, StateProvider};
 use reth_revm::{database::StateProviderDatabase, db::CacheDB, DatabaseRef};
 use reth_rpc_eth_types::{
     cache::db::{StateCacheDbRefMutWrapper, StateProviderTraitObjWrapper},
@@ -21,13 +24,17 @@ use reth_rpc_eth_types::{
         apply_block_overrides, apply_state_overrides, caller_gas_allowance,
         cap_tx_gas_limit_with_caller_allowance, get_precompiles, CallFees,
     },
+    simulate::{
+        build_simulated_block, cache_db_hashed_state, resolve_transaction, sanitize_blocks,
+        simulated_block_header, EthSimulateError, SimulatedCall, TransferInspector,
+    },
     EthApiError, RevertError, RpcInvalidTransactionError, StateCacheDb,
 };
 use reth_rpc_server_types::constants::gas_oracle::{
     CALL_STIPEND_GAS, ESTIMATE_GAS_ERROR_RATIO, MIN_TRANSACTION_GAS,
 };
 use reth_rpc_types::{
-    simulate::{SimBlock, SimulatedBlock},
+    simulate::{SimBlock, SimulatePayload, SimulatedBlock},
     state::{EvmOverrides, StateOverride},
     BlockId, Bundle, EthCallResponse, StateContext, TransactionInfo, TransactionRequest,
 };
@@ -35,7 +42,9 @@ use revm::{Database, DatabaseCommit};
 use revm_inspectors::access_list::AccessListInspector;
 use tracing::trace;
 
-use super::{LoadBlock, LoadPendingBlock, LoadState, LoadTransaction, SpawnBlocking, Trace};
+use super::{
+    EthState, LoadBlock, LoadPendingBlock, LoadState, LoadTransaction, SpawnBlocking, Trace,
+};
 
 /// Execution related functions for the [`EthApiServer`](crate::EthApiServer) trait in
 /// the `eth_` namespace.
@@ -53,13 +62,173 @@ pub trait EthCall: Call + LoadPendingBlock {
     /// `eth_simulateV1` executes an arbitrary number of transactions on top of the requested state.
     /// The transactions are packed into individual blocks. Overrides can be provided.
     ///
+    /// The gas of all calls is capped by [`Call::call_gas_limit`] as a whole. Since the state root
+    /// of every simulated block is computed, the requested block must be within
+    /// [`EthState::max_proof_window`].
+    ///
     /// See also: <https://github.com/ethereum/go-ethereum/pull/27720>
     fn simulate_v1(
         &self,
-        _opts: SimBlock,
-        _block_number: Option<BlockId>,
-    ) -> impl Future<Output = Result<Vec<SimulatedBlock>, Self::Error>> + Send {
-        async move { Err(EthApiError::Unsupported("eth_simulateV1 is not supported.").into()) }
+        payload: SimulatePayload,
+        block: Option<BlockId>,
+    ) -> impl Future<Output = Result<Vec<SimulatedBlock>, Self::Error>> + Send
+    where
+        Self: Trace + EthState,
+    {
+        async move {
+            let SimulatePayload {
+                block_state_calls,
+                trace_transfers,
+                validation,
+                return_full_transactions,
+            } = payload;
+            if block_state_calls.is_empty() {
+                return Err(EthApiError::InvalidParams(String::from("calls are empty.")).into())
+            }
+
+            let provider = LoadPendingBlock::provider(self);
+            let parent = provider
+                .sealed_header_by_id(block.unwrap_or_default())
+                .map_err(Self::Error::from_eth_err)?
+                .ok_or(EthApiError::UnknownBlockNumber)?;
+            let best_number = provider.best_block_number().map_err(Self::Error::from_eth_err)?;
+            if best_number.saturating_sub(parent.number) > self.max_proof_window() {
+                return Err(EthApiError::ExceedsMaxProofWindow.into())
+            }
+            let total_difficulty = provider
+                .header_td_by_number(parent.number)
+                .map_err(Self::Error::from_eth_err)?
+                .unwrap_or_default();
+            let blocks =
+                sanitize_blocks(block_state_calls, &parent).map_err(Self::Error::from_eth_err)?;
+
+            let this = self.clone();
+            self.spawn_with_state_at_block(parent.hash().into(), move |state| {
+                let chain_spec = LoadPendingBlock::provider(&this).chain_spec();
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                let mut parent = parent;
+                let mut simulated_blocks = Vec::with_capacity(blocks.len());
+                // the gas cap is shared by all calls of all simulated blocks
+                let mut gas_budget = this.call_gas_limit();
+
+                for block in blocks {
+                    let SimBlock { block_overrides, state_overrides, calls } = block;
+                    let mut block_overrides = block_overrides.unwrap_or_default();
+                    if let Some(block_hashes) = block_overrides.block_hash.take() {
+                        db.block_hashes.extend(
+                            block_hashes.into_iter().map(|(num, hash)| (U256::from(num), hash)),
+                        )
+                    }
+
+                    let mut header =
+                        simulated_block_header(&parent, &block_overrides, &chain_spec, validation);
+                    let mut cfg =
+                        CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), SpecId::LATEST);
+                    let mut block_env = BlockEnv::default();
+                    Call::evm_config(&this).fill_cfg_and_block_env(
+                        &mut cfg,
+                        &mut block_env,
+                        &chain_spec,
+                        &header,
+                        total_difficulty,
+                    );
+                    // without validation, calls are executed like `eth_call`
+                    cfg.disable_eip3607 = !validation;
+                    cfg.disable_base_fee = !validation;
+
+                    if let Some(state_overrides) = state_overrides {
+                        apply_state_overrides(state_overrides, &mut db)
+                            .map_err(Self::Error::from_eth_err)?;
+                    }
+
+                    pre_block_beacon_root_contract_call(
+                        &mut db,
+                        Call::evm_config(&this),
+                        &chain_spec,
+                        &cfg,
+                        &block_env,
+                        header.number,
+                        header.timestamp,
+                        header.parent_beacon_block_root,
+                    )
+                    .map_err(|err| Self::Error::from_eth_err(EthApiError::Internal(err.into())))?;
+
+                    let mut gas_used = 0;
+                    let mut executed_calls = Vec::with_capacity(calls.len());
+                    for mut request in calls {
+                        let from = *request.from.get_or_insert_with(Default::default);
+                        if request.nonce.is_none() {
+                            let account = db.basic_ref(from).map_err(Self::Error::from_eth_err)?;
+                            request.nonce = Some(account.unwrap_or_default().nonce);
+                        }
+                        let gas_limit = request
+                            .gas
+                            .map_or_else(
+                                || header.gas_limit.saturating_sub(gas_used),
+                                |gas| gas.try_into().unwrap_or(u64::MAX),
+                            )
+                            .min(gas_budget);
+                        if gas_used + gas_limit > header.gas_limit {
+                            return Err(Self::Error::from_eth_err(
+                                EthSimulateError::BlockGasLimitReached {
+                                    gas_used: gas_used + gas_limit,
+                                    gas_limit: header.gas_limit,
+                                },
+                            ))
+                        }
+                        request.gas = Some(gas_limit.into());
+                        request.chain_id.get_or_insert(chain_spec.chain().id());
+
+                        let mut env = this.build_call_evm_env(
+                            cfg.clone(),
+                            block_env.clone(),
+                            request.clone(),
+                        )?;
+                        if !validation {
+                            env.tx.nonce = None;
+                        }
+
+                        let (ResultAndState { result, state }, logs) = if trace_transfers {
+                            let mut inspector = TransferInspector::default();
+                            let (res, _) = this.inspect(&mut db, env, &mut inspector)?;
+                            (res, inspector.into_logs())
+                        } else {
+                            let (res, _) = this.transact(&mut db, env)?;
+                            let logs = res.result.logs().to_vec();
+                            (res, logs)
+                        };
+                        db.commit(state);
+                        gas_used += result.gas_used();
+                        gas_budget -= result.gas_used();
+
+                        let transaction =
+                            resolve_transaction(request).map_err(Self::Error::from_eth_err)?;
+                        let transaction = TransactionSigned::from_transaction_and_signature(
+                            transaction,
+                            Signature::default(),
+                        )
+                        .with_signer(from);
+                        executed_calls.push(SimulatedCall { transaction, result, logs });
+                    }
+
+                    header.state_root = db
+                        .db
+                        .hashed_state_root(cache_db_hashed_state(&db))
+                        .map_err(Self::Error::from_eth_err)?;
+                    let (header, simulated_block) =
+                        build_simulated_block(header, executed_calls, return_full_transactions)
+                            .map_err(Self::Error::from_eth_err)?;
+
+                    // make the block available to `BLOCKHASH` in the following blocks
+                    db.block_hashes.insert(U256::from(header.number), header.hash());
+                    simulated_blocks.push(simulated_block);
+                    parent = header;
+                }
+
+                Ok(simulated_blocks)
+            })
+            .await
+        }
     }
 
     /// Executes the call request (`eth_call`) and returns the output
@@ -26,14 +26,14 @@ pub trait EthSigner: Send + Sync + DynClone {
     async fn sign(&self, address: Address, message: &[u8]) -> Result<Signature>;
 
     /// signs a transaction request using the given account in request
-    fn sign_transaction(
+    async fn sign_transaction(
         &self,
         request: TypedTransactionRequest,
         address: &Address,
     ) -> Result<TransactionSigned>;
 
     /// Encodes and signs the typed data according EIP-712. Payload must implement Eip712 trait.
-    fn sign_typed_data(&self, address: Address, payload: &TypedData) -> Result<Signature>;
+    async fn sign_typed_data(&self, address: Address, payload: &TypedData) -> Result<Signature>;
 }
 
 dyn_clone::clone_trait_object!(EthSigner);
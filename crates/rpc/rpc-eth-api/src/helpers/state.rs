@@ -4,14 +4,18 @@
 use futures::Future;
 use reth_errors::RethError;
 use reth_evm::ConfigureEvmEnv;
-use reth_primitives::{Address, BlockId, Bytes, Header, B256, U256};
+use reth_primitives::{
+    constants::EMPTY_ROOT_HASH, Address, BlockId, Bytes, Header, B256, KECCAK_EMPTY, U256,
+};
 use reth_provider::{
-    BlockIdReader, ChainSpecProvider, StateProvider, StateProviderBox, StateProviderFactory,
+    BlockIdReader, BlockNumReader, ChainSpecProvider, StateProvider, StateProviderBox,
+    StateProviderFactory,
 };
 use reth_rpc_eth_types::{EthApiError, EthStateCache, PendingBlockEnv, RpcInvalidTransactionError};
-use reth_rpc_types::{serde_helpers::JsonStorageKey, EIP1186AccountProofResponse};
+use reth_rpc_types::{serde_helpers::JsonStorageKey, Account, EIP1186AccountProofResponse};
 use reth_rpc_types_compat::proof::from_primitive_account_proof;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
+use reth_trie::HashedStorage;
 use revm::db::BundleState;
 use revm_primitives::{BlockEnv, CfgEnvWithHandlerCfg, SpecId};
 
@@ -127,6 +131,60 @@ pub trait EthState: LoadState + SpawnBlocking {
             .await
         })
     }
+
+    /// Returns the balance, nonce, code hash and storage root of the given account at the given
+    /// block identifier.
+    ///
+    /// Computing the storage root is as expensive as generating a proof, hence the block must be
+    /// within the maximum configured proof window and the computation shares the proof permits.
+    fn get_account(
+        &self,
+        address: Address,
+        block_id: BlockId,
+    ) -> impl Future<Output = Result<Account, Self::Error>> + Send {
+        async move {
+            let provider = LoadState::provider(self);
+            let chain_info = provider.chain_info().map_err(Self::Error::from_eth_err)?;
+            let block_number = provider
+                .block_number_for_id(block_id)
+                .map_err(Self::Error::from_eth_err)?
+                .ok_or(EthApiError::UnknownBlockNumber)?;
+            let max_window = self.max_proof_window();
+            if chain_info.best_number.saturating_sub(block_number) > max_window {
+                return Err(EthApiError::ExceedsMaxProofWindow.into())
+            }
+
+            let _permit = self
+                .acquire_owned()
+                .await
+                .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+            self.spawn_blocking_io(move |this| {
+                let state = this.state_at_block_id(block_id)?;
+                let Some(account) =
+                    state.basic_account(address).map_err(Self::Error::from_eth_err)?
+                else {
+                    return Ok(Account {
+                        storage_root: EMPTY_ROOT_HASH,
+                        code_hash: KECCAK_EMPTY,
+                        ..Default::default()
+                    })
+                };
+
+                // the storage root of the account on top of the state without any changes
+                let storage_root = state
+                    .hashed_storage_root(address, HashedStorage::new(false))
+                    .map_err(Self::Error::from_eth_err)?;
+
+                Ok(Account {
+                    nonce: account.nonce,
+                    balance: account.balance,
+                    storage_root,
+                    code_hash: account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+                })
+            })
+            .await
+        }
+    }
 }
 
 /// Loads state from database.
@@ -277,8 +277,50 @@ pub trait EthTransactions: LoadTransaction {
     /// Returns the hash of the signed transaction.
     fn send_transaction(
         &self,
-        mut request: TransactionRequest,
+        request: TransactionRequest,
     ) -> impl Future<Output = Result<B256, Self::Error>> + Send
+    where
+        Self: EthApiSpec + LoadBlock + LoadPendingBlock + LoadFee + Call,
+    {
+        async move {
+            let signed_tx = self.build_and_sign_transaction(request).await?;
+
+            let recovered =
+                signed_tx.into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature)?;
+
+            let pool_transaction = match recovered.try_into() {
+                Ok(converted) => converted,
+                Err(_) => return Err(EthApiError::TransactionConversionError.into()),
+            };
+
+            // submit the transaction to the pool with a `Local` origin
+            let hash = LoadTransaction::pool(self)
+                .add_transaction(TransactionOrigin::Local, pool_transaction)
+                .await
+                .map_err(Self::Error::from_eth_err)?;
+
+            Ok(hash)
+        }
+    }
+
+    /// Signs transaction with a matching signer, if any, without submitting it. Returns the
+    /// EIP-2718 encoded signed transaction.
+    fn sign_transaction(
+        &self,
+        request: TransactionRequest,
+    ) -> impl Future<Output = Result<Bytes, Self::Error>> + Send
+    where
+        Self: EthApiSpec + LoadBlock + LoadPendingBlock + LoadFee + Call,
+    {
+        async move { Ok(self.build_and_sign_transaction(request).await?.envelope_encoded()) }
+    }
+
+    /// Fills the missing fields of the transaction request, i.e. nonce, gas limit and fees, and
+    /// signs it with the signer of its `from` account.
+    fn build_and_sign_transaction(
+        &self,
+        mut request: TransactionRequest,
+    ) -> impl Future<Output = Result<TransactionSigned, Self::Error>> + Send
     where
         Self: EthApiSpec + LoadBlock + LoadPendingBlock + LoadFee + Call,
     {
@@ -465,23 +507,7 @@ pub trait EthTransactions: LoadTransaction {
                 None => return Err(EthApiError::ConflictingFeeFieldsInRequest.into()),
             };
 
-            let signed_tx = self.sign_request(&from, transaction)?;
-
-            let recovered =
-                signed_tx.into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature)?;
-
-            let pool_transaction = match recovered.try_into() {
-                Ok(converted) => converted,
-                Err(_) => return Err(EthApiError::TransactionConversionError.into()),
-            };
-
-            // submit the transaction to the pool with a `Local` origin
-            let hash = LoadTransaction::pool(self)
-                .add_transaction(TransactionOrigin::Local, pool_transaction)
-                .await
-                .map_err(Self::Error::from_eth_err)?;
-
-            Ok(hash)
+            self.sign_request(&from, transaction).await
         }
     }
 
@@ -490,16 +516,13 @@ pub trait EthTransactions: LoadTransaction {
         &self,
         from: &Address,
         request: TypedTransactionRequest,
-    ) -> Result<TransactionSigned, Self::Error> {
-        for signer in self.signers().read().iter() {
-            if signer.is_signer_for(from) {
-                return match signer.sign_transaction(request, from) {
-                    Ok(tx) => Ok(tx),
-                    Err(e) => Err(e.into_eth_err()),
-                }
-            }
+    ) -> impl Future<Output = Result<TransactionSigned, Self::Error>> + Send {
+        async move {
+            self.find_signer(from)?
+                .sign_transaction(request, from)
+                .await
+                .map_err(Self::Error::from_eth_err)
         }
-        Err(EthApiError::InvalidTransactionSignature.into())
     }
 
     /// Signs given message. Returns the signature.
@@ -519,12 +542,19 @@ pub trait EthTransactions: LoadTransaction {
     }
 
     /// Encodes and signs the typed data according EIP-712. Payload must implement Eip712 trait.
-    fn sign_typed_data(&self, data: &TypedData, account: Address) -> Result<Bytes, Self::Error> {
-        Ok(self
-            .find_signer(&account)?
-            .sign_typed_data(account, data)
-            .map_err(Self::Error::from_eth_err)?
-            .to_hex_bytes())
+    fn sign_typed_data(
+        &self,
+        data: &TypedData,
+        account: Address,
+    ) -> impl Future<Output = Result<Bytes, Self::Error>> + Send {
+        async move {
+            Ok(self
+                .find_signer(&account)?
+                .sign_typed_data(account, data)
+                .await
+                .map_err(Self::Error::from_eth_err)?
+                .to_hex_bytes())
+        }
     }
 
     /// Returns the signer for the given account, if found in configured signers.
@@ -60,6 +60,42 @@ impl<'a> reth_storage_api::StateProofProvider for StateProviderTraitObjWrapper<'
     }
 }
 
+impl<'a> reth_storage_api::StateRangeProvider for StateProviderTraitObjWrapper<'a> {
+    fn hashed_account_range(
+        &self,
+        hashed_state: reth_trie::HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<reth_storage_api::HashedStateRange<reth_trie::TrieAccount>> {
+        self.0.hashed_account_range(hashed_state, start, limit)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_state: reth_trie::HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<reth_storage_api::HashedStateRange<U256>> {
+        self.0.hashed_storage_range(hashed_state, hashed_address, start, limit)
+    }
+
+    fn account_preimages(
+        &self,
+        hashed_addresses: &[B256],
+    ) -> ProviderResult<std::collections::HashMap<B256, revm_primitives::Address>> {
+        self.0.account_preimages(hashed_addresses)
+    }
+
+    fn storage_preimages(
+        &self,
+        address: revm_primitives::Address,
+        hashed_slots: &[B256],
+    ) -> ProviderResult<std::collections::HashMap<B256, B256>> {
+        self.0.storage_preimages(address, hashed_slots)
+    }
+}
+
 impl<'a> reth_storage_api::AccountReader for StateProviderTraitObjWrapper<'a> {
     fn basic_account(
         &self,
@@ -675,6 +675,9 @@ pub enum SignError {
     /// No chain ID was given.
     #[error("no chainid")]
     NoChainId,
+    /// The remote signer failed to sign.
+    #[error("remote signer: {0}")]
+    Remote(String),
 }
 
 /// Converts the evm [`ExecutionResult`] into a result where `Ok` variant is the output bytes if it
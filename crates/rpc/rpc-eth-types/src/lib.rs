@@ -18,6 +18,7 @@ pub mod logs_utils;
 pub mod pending_block;
 pub mod receipt;
 pub mod revm_utils;
+pub mod simulate;
 pub mod transaction;
 pub mod utils;
 
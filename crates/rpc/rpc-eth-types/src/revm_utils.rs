@@ -1,15 +1,15 @@
 //! utilities for working with revm
 
-use reth_primitives::{Address, B256, U256};
+use reth_primitives::{keccak256, Address, B256, U256};
 use reth_rpc_types::{
     state::{AccountOverride, StateOverride},
     BlockOverrides,
 };
 use revm::{
-    db::CacheDB,
+    db::{CacheDB, State},
     precompile::{PrecompileSpecId, Precompiles},
-    primitives::{db::DatabaseRef, Bytecode, SpecId, TxEnv},
-    Database,
+    primitives::{db::DatabaseRef, Account, Bytecode, EvmStorageSlot, HashMap, SpecId, TxEnv},
+    Database, DatabaseCommit,
 };
 use revm_primitives::BlockEnv;
 use std::cmp::min;
@@ -309,6 +309,57 @@ where
     Ok(())
 }
 
+/// Commits the given state overrides (a set of [`AccountOverride`]) to the [`State`], so that they
+/// are part of its bundle like the changes of executed transactions.
+pub fn commit_state_overrides<DB>(overrides: StateOverride, db: &mut State<DB>) -> EthResult<()>
+where
+    DB: Database,
+    EthApiError: From<<DB as Database>::Error>,
+{
+    for (address, account_override) in overrides {
+        // the account must be loaded into the cache before changes to it can be committed
+        let mut account = Account::from(db.basic(address)?.unwrap_or_default());
+        account.mark_touch();
+
+        if let Some(nonce) = account_override.nonce {
+            account.info.nonce = nonce;
+        }
+        if let Some(code) = account_override.code {
+            account.info.code_hash = keccak256(&code);
+            account.info.code = Some(Bytecode::new_raw(code));
+        }
+        if let Some(balance) = account_override.balance {
+            account.info.balance = balance;
+        }
+
+        // If state is set, the account is committed as newly created, which drops its old storage
+        match (account_override.state, account_override.state_diff) {
+            (Some(_), Some(_)) => return Err(EthApiError::BothStateAndStateDiffInOverride(address)),
+            (None, None) => {}
+            (Some(new_account_state), None) => {
+                account.mark_created();
+                for (slot, value) in new_account_state {
+                    account
+                        .storage
+                        .insert(slot.into(), EvmStorageSlot::new_changed(U256::ZERO, value.into()));
+                }
+            }
+            (None, Some(account_state_diff)) => {
+                for (slot, value) in account_state_diff {
+                    let slot = slot.into();
+                    let original = db.storage(address, slot)?;
+                    account
+                        .storage
+                        .insert(slot, EvmStorageSlot::new_changed(original, value.into()));
+                }
+            }
+        }
+
+        db.commit(HashMap::from([(address, account)]));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,601 @@
+//! Utilities for serving `eth_simulateV1`.
+
+use reth_chainspec::{ChainSpec, EthereumHardforks};
+use reth_primitives::{
+    b256,
+    constants::{BEACON_NONCE, EMPTY_ROOT_HASH},
+    keccak256, logs_bloom,
+    proofs::{calculate_receipt_root_no_memo, calculate_transaction_root},
+    revm_primitives::{ExecutionResult, Log},
+    Account, Address, Block, BlockWithSenders, Bytes, EthereumHardfork, Header, Receipt, Requests,
+    SealedHeader, Transaction, TransactionSignedEcRecovered, TxEip1559, TxEip2930, TxEip4844,
+    TxKind, TxLegacy, Withdrawals, B256, EMPTY_OMMER_ROOT_HASH, U256,
+};
+use reth_revm::db::{AccountState, CacheDB};
+use reth_rpc_server_types::result::rpc_err;
+use reth_rpc_types::{
+    error::EthRpcErrorCode,
+    simulate::{SimBlock, SimCallResult, SimulateError, SimulatedBlock, MAX_SIMULATE_BLOCKS},
+    BlockOverrides, BlockTransactionsKind, ToRpcError, TransactionRequest,
+};
+use reth_rpc_types_compat::block::from_block;
+use reth_trie::{HashedPostState, HashedStorage};
+use revm::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    Database, EvmContext, Inspector,
+};
+
+use crate::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError};
+
+/// Seconds between two simulated blocks, if the timestamp of a block isn't overridden.
+pub const SIMULATE_TIMESTAMP_INCREMENT: u64 = 12;
+
+/// Error code of a simulated call that halted.
+pub const SIMULATE_VM_ERROR_CODE: i32 = -32015;
+
+/// Address the synthetic ETH transfer logs are emitted from.
+pub const TRANSFER_LOG_EMITTER: Address = Address::new([0xee; 20]);
+
+/// Signature of the ERC-20 `Transfer(address,address,uint256)` event, used as the first topic of
+/// the synthetic ETH transfer logs.
+pub const TRANSFER_EVENT_SIGNATURE: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// Errors specific to `eth_simulateV1`.
+#[derive(Debug, thiserror::Error)]
+pub enum EthSimulateError {
+    /// The calls of a block use more gas than the block gas limit.
+    #[error("block gas limit reached: {gas_used} >= {gas_limit}")]
+    BlockGasLimitReached {
+        /// Gas used by the block, including the call that exceeds the limit.
+        gas_used: u64,
+        /// Gas limit of the block.
+        gas_limit: u64,
+    },
+    /// The block numbers are not strictly increasing.
+    #[error("block numbers must be in order: {number} <= {parent}")]
+    BlockNumberInvalid {
+        /// Number of the offending block.
+        number: u64,
+        /// Number of the previous block.
+        parent: u64,
+    },
+    /// The block timestamps are not strictly increasing.
+    #[error("block timestamps must be in order: {timestamp} <= {parent}")]
+    BlockTimestampInvalid {
+        /// Timestamp of the offending block.
+        timestamp: u64,
+        /// Timestamp of the previous block.
+        parent: u64,
+    },
+    /// More than [`MAX_SIMULATE_BLOCKS`] blocks are simulated.
+    #[error("too many blocks")]
+    TooManyBlocks,
+}
+
+impl EthSimulateError {
+    const fn error_code(&self) -> i32 {
+        match self {
+            Self::BlockGasLimitReached { .. } => -38015,
+            Self::BlockNumberInvalid { .. } => -38020,
+            Self::BlockTimestampInvalid { .. } => -38021,
+            Self::TooManyBlocks => -38026,
+        }
+    }
+}
+
+impl ToRpcError for EthSimulateError {
+    fn to_rpc_error(&self) -> jsonrpsee_types::ErrorObject<'static> {
+        rpc_err(self.error_code(), self.to_string(), None)
+    }
+}
+
+impl From<EthSimulateError> for EthApiError {
+    fn from(err: EthSimulateError) -> Self {
+        Self::other(err)
+    }
+}
+
+/// Assigns a number and a timestamp to every block that doesn't override them, and inserts empty
+/// blocks for gaps between block numbers.
+///
+/// Returns an error if block numbers or timestamps are not strictly increasing, or if the
+/// simulation would span more than [`MAX_SIMULATE_BLOCKS`] blocks.
+pub fn sanitize_blocks(
+    blocks: Vec<SimBlock>,
+    parent: &Header,
+) -> Result<Vec<SimBlock>, EthSimulateError> {
+    let mut sanitized = Vec::with_capacity(blocks.len());
+    let (mut prev_number, mut prev_timestamp) = (parent.number, parent.timestamp);
+
+    for mut block in blocks {
+        let overrides = block.block_overrides.get_or_insert_with(Default::default);
+
+        let number = overrides.number.map_or(prev_number + 1, |number| number.saturating_to());
+        if number <= prev_number {
+            return Err(EthSimulateError::BlockNumberInvalid { number, parent: prev_number })
+        }
+        if number - parent.number > MAX_SIMULATE_BLOCKS {
+            return Err(EthSimulateError::TooManyBlocks)
+        }
+
+        for gap in prev_number + 1..number {
+            prev_timestamp += SIMULATE_TIMESTAMP_INCREMENT;
+            sanitized.push(SimBlock {
+                block_overrides: Some(BlockOverrides {
+                    number: Some(U256::from(gap)),
+                    time: Some(prev_timestamp),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            });
+        }
+
+        let timestamp = overrides.time.unwrap_or(prev_timestamp + SIMULATE_TIMESTAMP_INCREMENT);
+        if timestamp <= prev_timestamp {
+            return Err(EthSimulateError::BlockTimestampInvalid {
+                timestamp,
+                parent: prev_timestamp,
+            })
+        }
+
+        overrides.number = Some(U256::from(number));
+        overrides.time = Some(timestamp);
+        (prev_number, prev_timestamp) = (number, timestamp);
+        sanitized.push(block);
+    }
+
+    Ok(sanitized)
+}
+
+/// Creates the header of a simulated block on top of `parent` with the given overrides applied.
+///
+/// Without validation, the base fee defaults to zero instead of being derived from the parent.
+///
+/// The fields that depend on the executed calls are set by [`build_simulated_block`].
+pub fn simulated_block_header(
+    parent: &SealedHeader,
+    overrides: &BlockOverrides,
+    chain_spec: &ChainSpec,
+    validation: bool,
+) -> Header {
+    let number = overrides.number.map_or(parent.number + 1, |number| number.saturating_to());
+    let timestamp = overrides.time.unwrap_or(parent.timestamp + SIMULATE_TIMESTAMP_INCREMENT);
+
+    let base_fee_per_gas =
+        chain_spec.fork(EthereumHardfork::London).active_at_block(number).then(|| {
+            if let Some(base_fee) = overrides.base_fee {
+                base_fee.saturating_to()
+            } else if validation {
+                parent
+                    .next_block_base_fee(chain_spec.base_fee_params_at_timestamp(timestamp))
+                    .unwrap_or_default()
+            } else {
+                0
+            }
+        });
+    let is_cancun = chain_spec.is_cancun_active_at_timestamp(timestamp);
+
+    Header {
+        parent_hash: parent.hash(),
+        ommers_hash: EMPTY_OMMER_ROOT_HASH,
+        beneficiary: overrides.coinbase.unwrap_or(parent.beneficiary),
+        withdrawals_root: chain_spec
+            .is_shanghai_active_at_timestamp(timestamp)
+            .then_some(EMPTY_ROOT_HASH),
+        difficulty: overrides.difficulty.unwrap_or(parent.difficulty),
+        number,
+        gas_limit: overrides.gas_limit.unwrap_or(parent.gas_limit),
+        timestamp,
+        mix_hash: overrides.random.unwrap_or_default(),
+        nonce: BEACON_NONCE,
+        base_fee_per_gas,
+        blob_gas_used: is_cancun.then_some(0),
+        excess_blob_gas: is_cancun.then(|| parent.next_block_excess_blob_gas().unwrap_or_default()),
+        parent_beacon_block_root: is_cancun.then_some(B256::ZERO),
+        requests_root: chain_spec
+            .is_prague_active_at_timestamp(timestamp)
+            .then_some(EMPTY_ROOT_HASH),
+        ..Default::default()
+    }
+}
+
+/// Builds the transaction included in the simulated block for a call.
+///
+/// The request's nonce, gas limit and chain id are expected to be set. The transaction type is
+/// derived from the fields that are set, fees that are not set default to zero.
+pub fn resolve_transaction(request: TransactionRequest) -> EthResult<Transaction> {
+    let TransactionRequest {
+        to,
+        gas_price,
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+        max_fee_per_blob_gas,
+        gas,
+        value,
+        input,
+        nonce,
+        chain_id,
+        access_list,
+        blob_versioned_hashes,
+        ..
+    } = request;
+
+    let nonce = nonce.unwrap_or_default();
+    let chain_id = chain_id.unwrap_or_default();
+    let gas_limit = gas
+        .unwrap_or_default()
+        .try_into()
+        .map_err(|_| RpcInvalidTransactionError::GasUintOverflow)?;
+    let to = to.unwrap_or(TxKind::Create);
+    let value = value.unwrap_or_default();
+    let input = input.try_into_unique_input()?.unwrap_or_default();
+
+    let transaction = if let Some(blob_versioned_hashes) = blob_versioned_hashes {
+        let TxKind::Call(to) = to else {
+            return Err(RpcInvalidTransactionError::BlobTransactionIsCreate.into())
+        };
+        Transaction::Eip4844(TxEip4844 {
+            chain_id,
+            nonce,
+            gas_limit,
+            max_fee_per_gas: max_fee_per_gas.unwrap_or_default(),
+            max_priority_fee_per_gas: max_priority_fee_per_gas.unwrap_or_default(),
+            placeholder: Some(()),
+            to,
+            value,
+            access_list: access_list.unwrap_or_default(),
+            blob_versioned_hashes,
+            max_fee_per_blob_gas: max_fee_per_blob_gas.unwrap_or_default(),
+            input,
+        })
+    } else if gas_price.is_none() {
+        Transaction::Eip1559(TxEip1559 {
+            chain_id,
+            nonce,
+            gas_limit,
+            max_fee_per_gas: max_fee_per_gas.unwrap_or_default(),
+            max_priority_fee_per_gas: max_priority_fee_per_gas.unwrap_or_default(),
+            to,
+            value,
+            access_list: access_list.unwrap_or_default(),
+            input,
+        })
+    } else if let Some(access_list) = access_list {
+        Transaction::Eip2930(TxEip2930 {
+            chain_id,
+            nonce,
+            gas_price: gas_price.unwrap_or_default(),
+            gas_limit,
+            to,
+            value,
+            access_list,
+            input,
+        })
+    } else {
+        Transaction::Legacy(TxLegacy {
+            chain_id: Some(chain_id),
+            nonce,
+            gas_price: gas_price.unwrap_or_default(),
+            gas_limit,
+            to,
+            value,
+            input,
+        })
+    };
+
+    Ok(transaction)
+}
+
+/// Returns the changes the simulation made to the state, relative to the state the [`CacheDB`]
+/// wraps.
+///
+/// Accounts that don't exist or are empty are reported as destroyed.
+pub fn cache_db_hashed_state<DB>(db: &CacheDB<DB>) -> HashedPostState {
+    let mut hashed_state = HashedPostState::default();
+    for (address, account) in &db.accounts {
+        let hashed_address = keccak256(address);
+        let destroyed =
+            matches!(account.account_state, AccountState::NotExisting) || account.info.is_empty();
+
+        let info = (!destroyed).then(|| Account::from(account.info.clone()));
+        hashed_state.accounts.insert(hashed_address, info);
+
+        let wiped = destroyed || account.account_state.is_storage_cleared();
+        if wiped || !account.storage.is_empty() {
+            let mut hashed_storage = HashedStorage::new(wiped);
+            if !destroyed {
+                hashed_storage.storage.extend(
+                    account
+                        .storage
+                        .iter()
+                        .map(|(slot, value)| (keccak256(B256::from(*slot)), *value)),
+                );
+            }
+            hashed_state.storages.insert(hashed_address, hashed_storage);
+        }
+    }
+    hashed_state
+}
+
+/// A call executed in a simulated block.
+#[derive(Debug)]
+pub struct SimulatedCall {
+    /// The transaction built for the call.
+    pub transaction: TransactionSignedEcRecovered,
+    /// The result of executing the call.
+    pub result: ExecutionResult,
+    /// The logs to report for the call, including synthetic transfer logs if these are traced.
+    pub logs: Vec<Log>,
+}
+
+/// Completes the header of a simulated block with the executed calls and assembles the block.
+///
+/// The receipts, and therefore the receipts root and logs bloom, are built from the same logs that
+/// are reported for the calls. The header's state root is expected to be set.
+pub fn build_simulated_block(
+    mut header: Header,
+    mut calls: Vec<SimulatedCall>,
+    full_transactions: bool,
+) -> EthResult<(SealedHeader, SimulatedBlock)> {
+    let mut receipts = Vec::with_capacity(calls.len());
+    let mut cumulative_gas_used = 0;
+    let mut blob_gas_used = 0;
+    for call in &mut calls {
+        // failed calls don't emit any logs, not even transfer logs
+        if !call.result.is_success() {
+            call.logs.clear();
+        }
+        cumulative_gas_used += call.result.gas_used();
+        blob_gas_used += call.transaction.as_eip4844().map_or(0, |tx| tx.blob_gas());
+        #[allow(clippy::needless_update)]
+        receipts.push(Receipt {
+            tx_type: call.transaction.tx_type(),
+            success: call.result.is_success(),
+            cumulative_gas_used,
+            logs: call.logs.clone(),
+            ..Default::default()
+        });
+    }
+
+    let (transactions, senders): (Vec<_>, Vec<_>) =
+        calls.iter().map(|call| call.transaction.clone().to_components()).unzip();
+
+    header.gas_used = cumulative_gas_used;
+    header.blob_gas_used = header.blob_gas_used.map(|_| blob_gas_used);
+    header.transactions_root = calculate_transaction_root(&transactions);
+    header.receipts_root = calculate_receipt_root_no_memo(&receipts.iter().collect::<Vec<_>>());
+    header.logs_bloom = logs_bloom(receipts.iter().flat_map(|receipt| &receipt.logs));
+    let header = header.seal_slow();
+
+    let mut log_index = 0;
+    let results = calls
+        .into_iter()
+        .enumerate()
+        .map(|(index, SimulatedCall { transaction, result, logs })| {
+            let logs = logs
+                .into_iter()
+                .map(|inner| {
+                    let log = reth_rpc_types::Log {
+                        inner,
+                        block_hash: Some(header.hash()),
+                        block_number: Some(header.number),
+                        block_timestamp: Some(header.timestamp),
+                        transaction_hash: Some(transaction.hash()),
+                        transaction_index: Some(index as u64),
+                        log_index: Some(log_index),
+                        removed: false,
+                    };
+                    log_index += 1;
+                    log
+                })
+                .collect();
+            sim_call_result(result, logs, transaction.gas_limit())
+        })
+        .collect();
+
+    let block = Block {
+        header: header.header().clone(),
+        body: transactions,
+        ommers: Vec::new(),
+        withdrawals: header.withdrawals_root.map(|_| Withdrawals::default()),
+        requests: header.requests_root.map(|_| Requests::default()),
+    };
+    let kind =
+        if full_transactions { BlockTransactionsKind::Full } else { BlockTransactionsKind::Hashes };
+    let mut inner =
+        from_block(BlockWithSenders { block, senders }, U256::ZERO, kind, Some(header.hash()))?;
+    // simulated blocks are not part of the chain
+    inner.header.total_difficulty = None;
+
+    Ok((header, SimulatedBlock { inner, calls: results }))
+}
+
+/// Converts the result of a simulated call into the result reported by `eth_simulateV1`.
+fn sim_call_result(
+    result: ExecutionResult,
+    logs: Vec<reth_rpc_types::Log>,
+    gas_limit: u64,
+) -> SimCallResult {
+    let gas_used = result.gas_used();
+    match result {
+        ExecutionResult::Success { output, .. } => SimCallResult {
+            return_data: output.into_data(),
+            logs,
+            gas_used,
+            status: 1,
+            error: None,
+        },
+        ExecutionResult::Revert { output, .. } => SimCallResult {
+            return_data: output.clone(),
+            logs,
+            gas_used,
+            status: 0,
+            error: Some(SimulateError {
+                code: EthRpcErrorCode::ExecutionError.code(),
+                message: RevertError::new(output.clone()).to_string(),
+                data: Some(output),
+            }),
+        },
+        ExecutionResult::Halt { reason, .. } => SimCallResult {
+            return_data: Bytes::new(),
+            logs,
+            gas_used,
+            status: 0,
+            error: Some(SimulateError {
+                code: SIMULATE_VM_ERROR_CODE,
+                message: RpcInvalidTransactionError::halt(reason, gas_limit).to_string(),
+                data: None,
+            }),
+        },
+    }
+}
+
+/// An [`Inspector`] that records the logs of a transaction together with a synthetic log for
+/// every ETH transfer, in execution order.
+///
+/// The transfer logs mimic ERC-20 `Transfer` events emitted by [`TRANSFER_LOG_EMITTER`]. Logs of
+/// reverted call frames are discarded.
+#[derive(Debug, Default)]
+pub struct TransferInspector {
+    logs: Vec<Log>,
+    /// Number of logs recorded before each of the active call frames was entered.
+    checkpoints: Vec<usize>,
+}
+
+impl TransferInspector {
+    /// Consumes the inspector and returns the recorded logs.
+    pub fn into_logs(self) -> Vec<Log> {
+        self.logs
+    }
+
+    fn transfer_log(from: Address, to: Address, value: U256) -> Log {
+        Log::new_unchecked(
+            TRANSFER_LOG_EMITTER,
+            vec![TRANSFER_EVENT_SIGNATURE, from.into_word(), to.into_word()],
+            value.to_be_bytes_vec().into(),
+        )
+    }
+
+    /// Pops the checkpoint of the current call frame, discarding its logs if it failed, and
+    /// returns it.
+    fn exit_frame(&mut self, success: bool) -> usize {
+        let checkpoint = self.checkpoints.pop().unwrap_or_default();
+        if !success {
+            self.logs.truncate(checkpoint);
+        }
+        checkpoint
+    }
+}
+
+impl<DB: Database> Inspector<DB> for TransferInspector {
+    fn log(&mut self, _interp: &mut Interpreter, _context: &mut EvmContext<DB>, log: &Log) {
+        self.logs.push(log.clone());
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.checkpoints.push(self.logs.len());
+        if inputs.transfers_value() {
+            self.logs.push(Self::transfer_log(
+                inputs.transfer_from(),
+                inputs.transfer_to(),
+                inputs.call_value(),
+            ));
+        }
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.exit_frame(outcome.result.is_ok());
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.checkpoints.push(self.logs.len());
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        let checkpoint = self.exit_frame(outcome.result.is_ok());
+        // the address of the new contract is only known now, but the transfer happened before
+        // the constructor emitted any logs
+        if let Some(address) = outcome.address.filter(|_| outcome.result.is_ok()) {
+            if !inputs.value.is_zero() {
+                self.logs
+                    .insert(checkpoint, Self::transfer_log(inputs.caller, address, inputs.value));
+            }
+        }
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        if !value.is_zero() {
+            self.logs.push(Self::transfer_log(contract, target, value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(number: Option<u64>, time: Option<u64>) -> SimBlock {
+        SimBlock {
+            block_overrides: Some(BlockOverrides {
+                number: number.map(U256::from),
+                time,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sanitize_fills_gaps() {
+        let parent = Header { number: 10, timestamp: 100, ..Default::default() };
+        let blocks =
+            sanitize_blocks(vec![SimBlock::default(), block(Some(14), None)], &parent).unwrap();
+
+        let numbers_and_times = blocks
+            .iter()
+            .map(|block| {
+                let overrides = block.block_overrides.as_ref().unwrap();
+                (overrides.number.unwrap().to::<u64>(), overrides.time.unwrap())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(numbers_and_times, vec![(11, 112), (12, 124), (13, 136), (14, 148)]);
+    }
+
+    #[test]
+    fn sanitize_rejects_invalid_blocks() {
+        let parent = Header { number: 10, timestamp: 100, ..Default::default() };
+
+        let err = sanitize_blocks(vec![block(Some(10), None)], &parent).unwrap_err();
+        assert!(matches!(err, EthSimulateError::BlockNumberInvalid { number: 10, parent: 10 }));
+
+        let err = sanitize_blocks(vec![block(None, Some(100))], &parent).unwrap_err();
+        assert!(matches!(err, EthSimulateError::BlockTimestampInvalid { .. }));
+
+        let err = sanitize_blocks(vec![block(Some(10 + MAX_SIMULATE_BLOCKS + 1), None)], &parent)
+            .unwrap_err();
+        assert!(matches!(err, EthSimulateError::TooManyBlocks));
+    }
+}
@@ -117,18 +117,25 @@ fn fill(
 
 /// Convert [`TransactionSignedEcRecovered`] to [`TransactionRequest`]
 pub fn transaction_to_call_request(tx: TransactionSignedEcRecovered) -> TransactionRequest {
-    let from = tx.signer();
-    let to = Some(tx.transaction.to().into());
-    let gas = tx.transaction.gas_limit();
-    let value = tx.transaction.value();
-    let input = tx.transaction.input().clone();
-    let nonce = tx.transaction.nonce();
-    let chain_id = tx.transaction.chain_id();
-    let access_list = tx.transaction.access_list().cloned();
-    let max_fee_per_blob_gas = tx.transaction.max_fee_per_blob_gas();
-    let _authorization_list = tx.transaction.authorization_list();
-    let blob_versioned_hashes = tx.transaction.blob_versioned_hashes();
-    let tx_type = tx.transaction.tx_type();
+    unsigned_transaction_to_request(tx.signer(), &tx.transaction)
+}
+
+/// Convert an unsigned [`reth_primitives::Transaction`] sent by `from` to [`TransactionRequest`]
+pub fn unsigned_transaction_to_request(
+    from: Address,
+    tx: &reth_primitives::Transaction,
+) -> TransactionRequest {
+    let to = Some(tx.to().into());
+    let gas = tx.gas_limit();
+    let value = tx.value();
+    let input = tx.input().clone();
+    let nonce = tx.nonce();
+    let chain_id = tx.chain_id();
+    let access_list = tx.access_list().cloned();
+    let max_fee_per_blob_gas = tx.max_fee_per_blob_gas();
+    let _authorization_list = tx.authorization_list();
+    let blob_versioned_hashes = tx.blob_versioned_hashes();
+    let tx_type = tx.tx_type();
 
     // fees depending on the transaction type
     let (gas_price, max_fee_per_gas) = if tx.is_dynamic_fee() {
@@ -136,7 +143,7 @@ pub fn transaction_to_call_request(tx: TransactionSignedEcRecovered) -> Transact
     } else {
         (Some(tx.max_fee_per_gas()), None)
     };
-    let max_priority_fee_per_gas = tx.transaction.max_priority_fee_per_gas();
+    let max_priority_fee_per_gas = tx.max_priority_fee_per_gas();
 
     TransactionRequest {
         from: Some(from),
@@ -0,0 +1,73 @@
+//! Types for the `debug` namespace that are not covered by alloy.
+
+use alloy_primitives::{Address, Bytes, B256, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Response of `debug_accountRange`.
+///
+/// Mirrors geth's state dump: a page of accounts ordered by their hashed address.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountRange {
+    /// State root of the block the accounts were read at.
+    pub root: B256,
+    /// The accounts of the page, keyed by address, or by `pre(<hashed address>)` if the address is
+    /// unknown.
+    pub accounts: BTreeMap<String, DumpAccount>,
+    /// Hashed address of the first account of the next page, if there are more accounts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<B256>,
+}
+
+/// An account as returned by `debug_accountRange`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpAccount {
+    /// Account balance as a decimal string.
+    pub balance: String,
+    /// Account nonce.
+    pub nonce: u64,
+    /// Storage root of the account.
+    pub root: B256,
+    /// Hash of the account's bytecode.
+    pub code_hash: B256,
+    /// Bytecode of the account, omitted if code was not requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// Storage of the account keyed by hashed slot, omitted if storage was not requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<B256, U256>>,
+    /// Address of the account, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    /// Hashed address of the account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<B256>,
+}
+
+/// Response of `debug_storageRangeAt`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeResult {
+    /// The storage slots of the page, keyed by hashed slot.
+    pub storage: BTreeMap<B256, StorageRangeEntry>,
+    /// Hashed slot of the first entry of the next page, `null` if there are no more slots.
+    pub next_key: Option<B256>,
+}
+
+/// A storage slot as returned by `debug_storageRangeAt`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageRangeEntry {
+    /// The unhashed storage slot, `null` if the preimage is unknown.
+    pub key: Option<B256>,
+    /// The value of the storage slot.
+    pub value: B256,
+}
+
+/// Subscription kinds of `debug_subscribe`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DebugSubscriptionKind {
+    /// Traces all blocks of a range, see `debug_subscribe`.
+    TraceChain,
+}
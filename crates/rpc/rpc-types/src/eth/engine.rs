@@ -0,0 +1,20 @@
+//! Engine API types.
+//!
+//! Re-exports the alloy engine types and adds the ones alloy doesn't provide yet.
+
+pub use alloy_rpc_types_engine::*;
+
+use alloy_eips::eip4844::{Blob, Bytes48};
+use serde::{Deserialize, Serialize};
+
+/// A blob and its proof, as returned by `engine_getBlobsV1`.
+///
+/// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#blobandproofv1>
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobAndProofV1 {
+    /// The blob data.
+    pub blob: Box<Blob>,
+    /// The KZG proof for the blob.
+    pub proof: Bytes48,
+}
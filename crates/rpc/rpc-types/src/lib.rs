@@ -9,8 +9,10 @@
 )]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
+pub mod debug;
 #[allow(hidden_glob_reexports)]
 mod eth;
+pub mod simulate;
 
 /// Alias for a peer identifier
 pub type PeerId = B512;
@@ -0,0 +1,84 @@
+//! Types for `eth_simulateV1`: <https://github.com/ethereum/execution-apis/pull/484>
+//!
+//! These replace the alloy types of the same name, which don't model optional overrides and don't
+//! return full blocks.
+
+use crate::{state::StateOverride, Block, BlockOverrides, Log, TransactionRequest};
+use alloy_primitives::Bytes;
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of blocks that can be simulated in a single request.
+pub const MAX_SIMULATE_BLOCKS: u64 = 256;
+
+/// Request of `eth_simulateV1`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// The blocks to simulate, in order.
+    pub block_state_calls: Vec<SimBlock>,
+    /// Whether to add a synthetic log for every ETH transfer.
+    #[serde(default)]
+    pub trace_transfers: bool,
+    /// Whether to run the nonce, base fee and sender checks of regular block execution.
+    #[serde(default)]
+    pub validation: bool,
+    /// Whether to return full transactions instead of transaction hashes.
+    #[serde(default)]
+    pub return_full_transactions: bool,
+}
+
+/// A block to simulate, with the overrides applied before its calls are executed.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimBlock {
+    /// Overrides of the block's header fields.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_overrides: Option<BlockOverrides>,
+    /// Overrides of the state the block is executed on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+    /// The calls to execute in the block.
+    #[serde(default)]
+    pub calls: Vec<TransactionRequest>,
+}
+
+/// A simulated block, with the results of its calls.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SimulatedBlock {
+    /// The simulated block.
+    #[serde(flatten)]
+    pub inner: Block,
+    /// The results of the block's calls, one per transaction.
+    pub calls: Vec<SimCallResult>,
+}
+
+/// Result of a simulated call.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimCallResult {
+    /// The data returned by the call, or the revert data.
+    pub return_data: Bytes,
+    /// The logs emitted by the call, empty if it failed.
+    pub logs: Vec<Log>,
+    /// The gas used by the call.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// `1` if the call succeeded, `0` otherwise.
+    #[serde(with = "alloy_serde::quantity")]
+    pub status: u64,
+    /// The reason the call failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulateError>,
+}
+
+/// Error of a failed simulated call.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulateError {
+    /// `3` if the call reverted, `-32015` if it halted.
+    pub code: i32,
+    /// Error message.
+    pub message: String,
+    /// The revert data, if the call reverted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+}
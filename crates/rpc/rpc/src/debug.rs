@@ -1,24 +1,40 @@
+use alloy_primitives::hex;
 use alloy_rlp::{Decodable, Encodable};
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionResult},
+    server::SubscriptionMessage,
+    types::ErrorObjectOwned,
+    PendingSubscriptionSink, SubscriptionSink,
+};
 use reth_chainspec::EthereumHardforks;
+use reth_errors::RethError;
 use reth_evm::{system_calls::pre_block_beacon_root_contract_call, ConfigureEvmEnv};
 use reth_primitives::{
-    Address, Block, BlockId, BlockNumberOrTag, Bytes, TransactionSignedEcRecovered, B256, U256,
+    Address, Block, BlockId, BlockNumberOrTag, BlockWithSenders, Bytes, SealedBlock,
+    TransactionSignedEcRecovered, B256, KECCAK_EMPTY, U256,
 };
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, HeaderProvider, StateProofProvider,
-    StateProviderFactory, TransactionVariant,
+    BadBlockReader, BlockReaderIdExt, ChainSpecProvider, DatabaseCompactor, EvmEnvProvider,
+    HeaderProvider, HistoryReader, ProviderError, PruneCheckpointReader, StateProofProvider,
+    StateProvider, StateProviderFactory, StateRangeProvider, StateRootProvider, TransactionVariant,
 };
+use reth_prune_types::PruneSegment;
 use reth_revm::database::StateProviderDatabase;
-use reth_rpc_api::DebugApiServer;
+use reth_rpc_api::{DebugApiServer, DebugCompactApiServer};
 use reth_rpc_eth_api::{
-    helpers::{Call, EthApiSpec, EthTransactions, TraceExt},
+    helpers::{Call, EthApiSpec, EthState, EthTransactions, TraceExt},
     EthApiTypes, FromEthApiError,
 };
-use reth_rpc_eth_types::{EthApiError, StateCacheDb};
+use reth_rpc_eth_types::{
+    revm_utils::{apply_block_overrides, commit_state_overrides},
+    EthApiError, EthResult, StateCacheDb,
+};
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use reth_rpc_types::{
+    debug::{
+        AccountRange, DebugSubscriptionKind, DumpAccount, StorageRangeEntry, StorageRangeResult,
+    },
     state::EvmOverrides,
     trace::geth::{
         BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
@@ -26,10 +42,12 @@ use reth_rpc_types::{
     },
     BlockError, Bundle, RichBlock, StateContext, TransactionRequest,
 };
-use reth_tasks::pool::BlockingTaskGuard;
+use reth_rpc_types_compat::block::{from_block_full, from_block_with_tx_hashes};
+use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner};
 use reth_trie::{HashedPostState, HashedStorage};
 use revm::{
     db::{states::bundle_state::BundleRetention, CacheDB},
+    inspectors::TracerEip3155,
     primitives::{db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg},
     StateBuilder,
 };
@@ -38,8 +56,29 @@ use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig,
 };
 use revm_primitives::{keccak256, HashMap};
-use std::sync::Arc;
-use tokio::sync::{AcquireError, OwnedSemaphorePermit};
+use std::{
+    collections::BTreeMap, fs::File, io::BufWriter, ops::RangeInclusive, path::PathBuf, sync::Arc,
+};
+use tokio::sync::{oneshot, AcquireError, OwnedSemaphorePermit};
+use tracing::info;
+
+/// The maximum number of accounts returned by a single `debug_accountRange` call.
+const ACCOUNT_RANGE_MAX_RESULTS: u64 = 256;
+
+/// The maximum number of accounts scanned by a single `debug_accountRange` call that skips the
+/// accounts with an unknown address. The page ends early once reached.
+const ACCOUNT_RANGE_MAX_SCANNED: usize = 8192;
+
+/// The maximum number of storage slots returned per account by a single `debug_accountRange` call.
+/// Storage beyond this is omitted from the dump.
+const ACCOUNT_RANGE_MAX_STORAGE_RESULTS: usize = 1024;
+
+/// The maximum number of storage slots returned by a single `debug_storageRangeAt` call.
+const STORAGE_RANGE_MAX_RESULTS: usize = 1024;
+
+/// The maximum number of blocks whose changesets are read by a single
+/// `debug_getModifiedAccountsByNumber` call.
+const MODIFIED_ACCOUNTS_MAX_BLOCKS: u64 = 10_000;
 
 /// `debug` API implementation.
 ///
@@ -72,6 +111,9 @@ where
         + ChainSpecProvider
         + StateProviderFactory
         + EvmEnvProvider
+        + BadBlockReader
+        + HistoryReader
+        + PruneCheckpointReader
         + 'static,
     Eth: EthApiTypes + TraceExt + 'static,
 {
@@ -151,35 +193,313 @@ where
             .map_err(BlockError::RlpDecodeRawBlock)
             .map_err(Eth::Error::from_eth_err)?;
 
+        self.trace_block_on_parent(block, opts).await
+    }
+
+    /// Replays the given block, which is not necessarily part of the chain, on top of its parent
+    /// block and returns the trace of each transaction.
+    ///
+    /// Note, the parent of this block must be present, or it will fail.
+    async fn trace_block_on_parent(
+        &self,
+        block: Block,
+        opts: GethDebugTracingOptions,
+    ) -> Result<Vec<TraceResult>, Eth::Error> {
         let (cfg, block_env) = self.eth_api().evm_env_for_raw_block(&block.header).await?;
         // we trace on top the block's parent block
         let parent = block.parent_hash;
+        let transactions = self.recover_block_transactions(block)?;
+
+        self.trace_block(parent.into(), transactions, cfg, block_env, opts).await
+    }
 
+    /// Recovers the signers of the block's transactions.
+    fn recover_block_transactions(
+        &self,
+        block: Block,
+    ) -> Result<Vec<TransactionSignedEcRecovered>, Eth::Error> {
         // Depending on EIP-2 we need to recover the transactions differently
-        let transactions =
-            if self.inner.provider.chain_spec().is_homestead_active_at_block(block.number) {
-                block
-                    .body
-                    .into_iter()
-                    .map(|tx| {
-                        tx.into_ecrecovered()
-                            .ok_or_else(|| EthApiError::InvalidTransactionSignature)
-                            .map_err(Eth::Error::from_eth_err)
-                    })
-                    .collect::<Result<Vec<_>, Eth::Error>>()?
-            } else {
-                block
-                    .body
+        if self.inner.provider.chain_spec().is_homestead_active_at_block(block.number) {
+            block
+                .body
+                .into_iter()
+                .map(|tx| {
+                    tx.into_ecrecovered()
+                        .ok_or_else(|| EthApiError::InvalidTransactionSignature)
+                        .map_err(Eth::Error::from_eth_err)
+                })
+                .collect()
+        } else {
+            block
+                .body
+                .into_iter()
+                .map(|tx| {
+                    tx.into_ecrecovered_unchecked()
+                        .ok_or_else(|| EthApiError::InvalidTransactionSignature)
+                        .map_err(Eth::Error::from_eth_err)
+                })
+                .collect()
+        }
+    }
+
+    /// Returns the tracked bad block with the given hash.
+    fn bad_block(&self, block_hash: B256) -> Result<SealedBlock, Eth::Error> {
+        self.inner
+            .provider
+            .bad_block_by_hash(block_hash)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or_else(|| {
+                Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                    "bad block {block_hash} not found"
+                )))
+            })
+    }
+
+    /// Replays the bad block with the given hash on top of its parent block and returns the trace
+    /// of each transaction.
+    pub async fn debug_trace_bad_block(
+        &self,
+        block_hash: B256,
+        opts: GethDebugTracingOptions,
+    ) -> Result<Vec<TraceResult>, Eth::Error> {
+        let block = self.bad_block(block_hash)?;
+        self.trace_block_on_parent(block.unseal(), opts).await
+    }
+
+    /// Replays the bad block with the given hash on top of its parent block and writes the
+    /// EIP-3155 trace of each transaction to a separate file in the temp directory.
+    ///
+    /// Returns the paths of the written files.
+    pub async fn debug_standard_trace_bad_block_to_file(
+        &self,
+        block_hash: B256,
+        opts: GethDebugTracingOptions,
+    ) -> Result<Vec<String>, Eth::Error> {
+        let block = self.bad_block(block_hash)?.unseal();
+        let (cfg, block_env) = self.eth_api().evm_env_for_raw_block(&block.header).await?;
+        let parent = block.parent_hash;
+        let transactions = self.recover_block_transactions(block)?;
+        let enable_memory = opts.config.enable_memory.unwrap_or_default();
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(parent.into(), move |state| {
+                let mut files = Vec::with_capacity(transactions.len());
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                let mut transactions = transactions.into_iter().enumerate().peekable();
+                while let Some((index, tx)) = transactions.next() {
+                    let path = std::env::temp_dir().join(format!(
+                        "block_{}-{index}-{}.jsonl",
+                        hex::encode_prefixed(&block_hash[..4]),
+                        hex::encode_prefixed(&tx.hash[..4]),
+                    ));
+                    let file = File::create(&path)
+                        .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+                    let mut inspector = TracerEip3155::new(Box::new(BufWriter::new(file)));
+                    if enable_memory {
+                        inspector = inspector.with_memory();
+                    }
+
+                    let env = EnvWithHandlerCfg {
+                        env: Env::boxed(
+                            cfg.cfg_env.clone(),
+                            block_env.clone(),
+                            Call::evm_config(this.eth_api()).tx_env(&tx),
+                        ),
+                        handler_cfg: cfg.handler_cfg,
+                    };
+                    let (res, _) = this.eth_api().inspect(&mut db, env, &mut inspector)?;
+                    files.push(path.display().to_string());
+
+                    if transactions.peek().is_some() {
+                        // need to apply the state changes of this transaction before executing the
+                        // next transaction
+                        db.commit(res.state)
+                    }
+                }
+
+                Ok(files)
+            })
+            .await
+    }
+
+    /// Executes the given block, which may be a bad block, on top of its parent and returns the
+    /// state root after each transaction.
+    ///
+    /// The block and state overrides of `opts` are applied before the block is executed, its
+    /// tracing options have no effect since no tracer is run. Like proofs, the roots can only be
+    /// computed within the maximum proof window of the tip.
+    pub async fn debug_intermediate_roots(
+        &self,
+        block_hash: B256,
+        opts: Option<GethDebugTracingCallOptions>,
+    ) -> Result<Vec<B256>, Eth::Error>
+    where
+        Eth: EthState,
+    {
+        // bad blocks are not part of the chain, so look them up first
+        let bad_block =
+            self.inner.provider.bad_block_by_hash(block_hash).map_err(Eth::Error::from_eth_err)?;
+        let block = match bad_block {
+            Some(block) => block.unseal(),
+            None => self
+                .inner
+                .provider
+                .block_by_hash(block_hash)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or(EthApiError::UnknownBlockNumber)?,
+        };
+
+        // computing the roots on top of historical state requires reverting it from the tip, which
+        // is as costly as computing a proof
+        let best_number =
+            self.inner.provider.best_block_number().map_err(Eth::Error::from_eth_err)?;
+        if best_number.saturating_sub(block.number) > self.inner.eth_api.max_proof_window() {
+            return Err(EthApiError::ExceedsMaxProofWindow.into())
+        }
+
+        let (cfg, mut block_env) = self.eth_api().evm_env_for_raw_block(&block.header).await?;
+        let GethDebugTracingCallOptions { state_overrides, block_overrides, .. } =
+            opts.unwrap_or_default();
+        if let Some(block_overrides) = block_overrides {
+            apply_block_overrides(block_overrides, &mut block_env);
+        }
+        let parent = block.parent_hash;
+        let (timestamp, number, parent_beacon_block_root) =
+            (block.timestamp, block.number, block.parent_beacon_block_root);
+        let transactions = self.recover_block_transactions(block)?;
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(parent.into(), move |state| {
+                let evm_config = Call::evm_config(this.eth_api()).clone();
+                let mut db = StateBuilder::new()
+                    .with_database(StateProviderDatabase::new(state))
+                    .with_bundle_update()
+                    .build();
+                if let Some(state_overrides) = state_overrides {
+                    commit_state_overrides(state_overrides, &mut db)?;
+                }
+
+                pre_block_beacon_root_contract_call(
+                    &mut db,
+                    &evm_config,
+                    &this.inner.provider.chain_spec(),
+                    &cfg,
+                    &block_env,
+                    number,
+                    timestamp,
+                    parent_beacon_block_root,
+                )
+                .map_err(|err| EthApiError::Internal(err.into()))?;
+
+                let mut roots = Vec::with_capacity(transactions.len());
+                for tx in transactions {
+                    let env = EnvWithHandlerCfg {
+                        env: Env::boxed(
+                            cfg.cfg_env.clone(),
+                            block_env.clone(),
+                            evm_config.tx_env(&tx),
+                        ),
+                        handler_cfg: cfg.handler_cfg,
+                    };
+                    let (res, _) = this.eth_api().transact(&mut db, env)?;
+                    db.commit(res.state);
+
+                    // compute the root of all changes so far on top of the parent state
+                    db.merge_transitions(BundleRetention::PlainState);
+                    let hashed_state = HashedPostState::from_bundle_state(&db.bundle_state.state);
+                    roots.push(
+                        db.database
+                            .hashed_state_root(hashed_state)
+                            .map_err(Eth::Error::from_eth_err)?,
+                    );
+                }
+
+                Ok(roots)
+            })
+            .await
+    }
+
+    /// Returns the addresses of all accounts that changed in the blocks after `start` up to and
+    /// including `end`, or in the block `start` only if no `end` is given.
+    ///
+    /// At most [`MODIFIED_ACCOUNTS_MAX_BLOCKS`] blocks can be queried at once.
+    pub async fn debug_get_modified_accounts_by_number(
+        &self,
+        start: u64,
+        end: Option<u64>,
+    ) -> Result<Vec<Address>, Eth::Error> {
+        let range = match end {
+            Some(end) if start >= end => {
+                return Err(EthApiError::InvalidParams(format!(
+                    "start block height ({start}) must be less than end block height ({end})"
+                ))
+                .into())
+            }
+            Some(end) if end - start > MODIFIED_ACCOUNTS_MAX_BLOCKS => {
+                return Err(EthApiError::InvalidParams(format!(
+                    "block range ({start}, {end}] exceeds the maximum of \
+                     {MODIFIED_ACCOUNTS_MAX_BLOCKS} blocks"
+                ))
+                .into())
+            }
+            Some(end) => start + 1..=end,
+            None => start..=start,
+        };
+
+        let best_number =
+            self.inner.provider.best_block_number().map_err(Eth::Error::from_eth_err)?;
+        if *range.end() > best_number {
+            return Err(EthApiError::UnknownBlockNumber.into())
+        }
+
+        // changesets are pruned together with the history indices
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            let pruned_until = self
+                .inner
+                .provider
+                .get_prune_checkpoint(segment)
+                .map_err(Eth::Error::from_eth_err)?
+                .and_then(|checkpoint| checkpoint.block_number);
+            if pruned_until.is_some_and(|pruned_until| *range.start() <= pruned_until) {
+                return Err(Eth::Error::from_eth_err(ProviderError::StateAtBlockPruned(
+                    *range.start(),
+                )))
+            }
+        }
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                Ok(this
+                    .inner
+                    .provider
+                    .changed_accounts_in_range(range)
+                    .map_err(Eth::Error::from_eth_err)?
                     .into_iter()
-                    .map(|tx| {
-                        tx.into_ecrecovered_unchecked()
-                            .ok_or_else(|| EthApiError::InvalidTransactionSignature)
-                            .map_err(Eth::Error::from_eth_err)
-                    })
-                    .collect::<Result<Vec<_>, Eth::Error>>()?
-            };
+                    .collect())
+            })
+            .await
+    }
 
-        self.trace_block(parent.into(), transactions, cfg, block_env, opts).await
+    /// Returns the addresses of all accounts that changed in the blocks after `start` up to and
+    /// including `end`, or in the block `start` only if no `end` is given.
+    pub async fn debug_get_modified_accounts_by_hash(
+        &self,
+        start: B256,
+        end: Option<B256>,
+    ) -> Result<Vec<Address>, Eth::Error> {
+        let block_number = |hash| -> Result<u64, Eth::Error> {
+            self.inner
+                .provider
+                .block_number(hash)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or_else(|| EthApiError::UnknownBlockNumber.into())
+        };
+        let start = block_number(start)?;
+        let end = end.map(block_number).transpose()?;
+        self.debug_get_modified_accounts_by_number(start, end).await
     }
 
     /// Replays a block and returns the trace of each transaction.
@@ -215,6 +535,335 @@ where
         .await
     }
 
+    /// Resolves the range `(start_exclusive, end_inclusive]` of blocks traced by a `traceChain`
+    /// subscription, failing if the range is empty or reaches beyond the chain.
+    pub fn trace_chain_range(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+    ) -> Result<RangeInclusive<u64>, Eth::Error> {
+        let start = self.block_number_for_tag(start_exclusive)?;
+        let end = self.block_number_for_tag(end_inclusive)?;
+        if end <= start {
+            return Err(EthApiError::InvalidParams(format!(
+                "end block #{end} must be higher than start block #{start}"
+            ))
+            .into())
+        }
+        let best_number =
+            self.inner.provider.best_block_number().map_err(Eth::Error::from_eth_err)?;
+        if end > best_number {
+            return Err(EthApiError::InvalidParams(format!(
+                "end block #{end} is higher than the latest block #{best_number}"
+            ))
+            .into())
+        }
+        Ok(start + 1..=end)
+    }
+
+    /// Replays all blocks of the range, as returned by [`Self::trace_chain_range`], and sends the
+    /// traces of each block to the given subscription sink.
+    ///
+    /// Blocks are traced one at a time, each with its own tracing permit, and the next block is
+    /// only traced once the sink accepted the previous result. Tracing stops early if the
+    /// subscription is closed.
+    pub async fn debug_trace_chain(
+        &self,
+        sink: SubscriptionSink,
+        blocks: RangeInclusive<u64>,
+        opts: GethDebugTracingOptions,
+    ) -> Result<(), Eth::Error> {
+        for number in blocks {
+            if sink.is_closed() {
+                break
+            }
+
+            let hash = self
+                .inner
+                .provider
+                .block_hash(number)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+            let traces = {
+                let _permit = self.acquire_trace_permit().await;
+                self.debug_trace_block(hash.into(), opts.clone()).await?
+            };
+
+            let result = BlockTraceResult { block: U256::from(number), hash, traces };
+            let msg = SubscriptionMessage::from_json(&result)
+                .map_err(|err| EthApiError::Internal(RethError::other(err)))?;
+            if sink.send(msg).await.is_err() {
+                // subscription closed
+                break
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resolves the block number of the given tag, failing if the block is unknown.
+    fn block_number_for_tag(&self, tag: BlockNumberOrTag) -> Result<u64, Eth::Error> {
+        self.inner
+            .provider
+            .convert_block_number(tag)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber.into())
+    }
+
+    /// Returns a page of the accounts of the state at the end of the given block, ordered by their
+    /// hashed address and starting at the hashed address `start`.
+    ///
+    /// reth does not store address preimages, so addresses are only known for the accounts changed
+    /// since the block. Accounts with an unknown address are keyed by `pre(<hashed address>)` if
+    /// `incompletes` is set and skipped otherwise, in which case at most
+    /// [`ACCOUNT_RANGE_MAX_SCANNED`] accounts are scanned before the page ends. At most
+    /// [`ACCOUNT_RANGE_MAX_STORAGE_RESULTS`] storage slots are returned per account.
+    ///
+    /// Like proofs, the range can only be taken within the maximum proof window of the tip.
+    pub async fn debug_account_range(
+        &self,
+        block_number: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> Result<AccountRange, Eth::Error>
+    where
+        Eth: EthState,
+    {
+        if start.len() > B256::len_bytes() {
+            return Err(EthApiError::InvalidParams(format!(
+                "start key must be at most {} bytes long",
+                B256::len_bytes()
+            ))
+            .into())
+        }
+
+        let header = self
+            .inner
+            .provider
+            .header_by_number_or_tag(block_number)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        let root = header.state_root;
+
+        // reading historical state requires reverting it from the tip, which is as costly as
+        // computing a proof
+        let best_number =
+            self.inner.provider.best_block_number().map_err(Eth::Error::from_eth_err)?;
+        if best_number.saturating_sub(header.number) > self.inner.eth_api.max_proof_window() {
+            return Err(EthApiError::ExceedsMaxProofWindow.into())
+        }
+
+        let start = B256::right_padding_from(&start);
+        let limit = if max_results == 0 || max_results > ACCOUNT_RANGE_MAX_RESULTS {
+            ACCOUNT_RANGE_MAX_RESULTS
+        } else {
+            max_results
+        } as usize;
+
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(header.number.into(), move |state| {
+                let mut accounts = BTreeMap::new();
+                let mut next = Some(start);
+                let mut scanned = 0;
+                while let Some(start) = next {
+                    if accounts.len() >= limit || scanned >= ACCOUNT_RANGE_MAX_SCANNED {
+                        break
+                    }
+
+                    let range = state
+                        .hashed_account_range(
+                            HashedPostState::default(),
+                            start,
+                            limit - accounts.len(),
+                        )
+                        .map_err(Eth::Error::from_eth_err)?;
+                    scanned += range.entries.len();
+                    next = range.next_key;
+
+                    let hashed_addresses = range
+                        .entries
+                        .iter()
+                        .map(|(hashed_address, _)| *hashed_address)
+                        .collect::<Vec<_>>();
+                    let preimages = state
+                        .account_preimages(&hashed_addresses)
+                        .map_err(Eth::Error::from_eth_err)?;
+
+                    for (hashed_address, account) in range.entries {
+                        let address = preimages.get(&hashed_address).copied();
+                        if address.is_none() && !incompletes {
+                            continue
+                        }
+
+                        let code = if nocode || account.code_hash == KECCAK_EMPTY {
+                            None
+                        } else {
+                            state
+                                .bytecode_by_hash(account.code_hash)
+                                .map_err(Eth::Error::from_eth_err)?
+                                .map(|code| code.original_bytes())
+                        };
+                        let storage = if nostorage {
+                            None
+                        } else {
+                            let storage = state
+                                .hashed_storage_range(
+                                    HashedPostState::default(),
+                                    hashed_address,
+                                    B256::ZERO,
+                                    ACCOUNT_RANGE_MAX_STORAGE_RESULTS,
+                                )
+                                .map_err(Eth::Error::from_eth_err)?;
+                            Some(storage.entries.into_iter().collect())
+                        };
+
+                        let key = address.map_or_else(
+                            || format!("pre({hashed_address})"),
+                            |address| address.to_string(),
+                        );
+                        accounts.insert(
+                            key,
+                            DumpAccount {
+                                balance: account.balance.to_string(),
+                                nonce: account.nonce,
+                                root: account.storage_root,
+                                code_hash: account.code_hash,
+                                code,
+                                storage,
+                                address,
+                                key: Some(hashed_address),
+                            },
+                        );
+                    }
+                }
+
+                Ok(AccountRange { root, accounts, next })
+            })
+            .await
+    }
+
+    /// Returns a page of the storage of the given contract, ordered by hashed slot and starting at
+    /// the hashed slot `key_start`, as it was before the transaction at index `tx_idx` of the
+    /// given block was executed.
+    ///
+    /// At most [`STORAGE_RANGE_MAX_RESULTS`] slots are returned, along with their unhashed slot
+    /// that is looked up in the plain state and the storage changes since the block.
+    ///
+    /// Like proofs, the range can only be taken within the maximum proof window of the tip.
+    pub async fn debug_storage_range_at(
+        &self,
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> Result<StorageRangeResult, Eth::Error>
+    where
+        Eth: EthState,
+    {
+        let ((cfg, block_env, _), block) = futures::try_join!(
+            self.inner.eth_api.evm_env_at(block_hash.into()),
+            self.inner.eth_api.block_with_senders(block_hash.into()),
+        )?;
+        let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        if tx_idx > block.body.len() {
+            return Err(EthApiError::InvalidParams(format!(
+                "transaction index {tx_idx} out of range for block with {} transactions",
+                block.body.len()
+            ))
+            .into())
+        }
+
+        // reading historical state requires reverting it from the tip, which is as costly as
+        // computing a proof
+        let best_number =
+            self.inner.provider.best_block_number().map_err(Eth::Error::from_eth_err)?;
+        if best_number.saturating_sub(block.number) > self.inner.eth_api.max_proof_window() {
+            return Err(EthApiError::ExceedsMaxProofWindow.into())
+        }
+        let limit = (max_result as usize).min(STORAGE_RANGE_MAX_RESULTS);
+
+        let this = self.clone();
+        self.inner
+            .eth_api
+            .spawn_with_state_at_block(block.parent_hash.into(), move |state| {
+                let evm_config = Call::evm_config(this.eth_api()).clone();
+                let mut db = StateBuilder::new()
+                    .with_database(StateProviderDatabase::new(state))
+                    .with_bundle_update()
+                    .build();
+
+                pre_block_beacon_root_contract_call(
+                    &mut db,
+                    &evm_config,
+                    &this.inner.provider.chain_spec(),
+                    &cfg,
+                    &block_env,
+                    block.number,
+                    block.timestamp,
+                    block.parent_beacon_block_root,
+                )
+                .map_err(|err| EthApiError::Internal(err.into()))?;
+
+                // replay all transactions prior to the targeted transaction
+                for tx in block.into_transactions_ecrecovered().take(tx_idx) {
+                    let env = EnvWithHandlerCfg {
+                        env: Env::boxed(
+                            cfg.cfg_env.clone(),
+                            block_env.clone(),
+                            evm_config.tx_env(&tx),
+                        ),
+                        handler_cfg: cfg.handler_cfg,
+                    };
+                    let (res, _) = this.inner.eth_api.transact(&mut db, env)?;
+                    db.commit(res.state);
+                }
+
+                db.merge_transitions(BundleRetention::PlainState);
+                let bundle = db.take_bundle();
+                let hashed_state = HashedPostState::from_bundle_state(&bundle.state);
+
+                let state = db.database.into_inner();
+                let range = state
+                    .hashed_storage_range(
+                        hashed_state,
+                        keccak256(contract_address),
+                        key_start,
+                        limit,
+                    )
+                    .map_err(Eth::Error::from_eth_err)?;
+
+                let hashed_slots =
+                    range.entries.iter().map(|(hashed_slot, _)| *hashed_slot).collect::<Vec<_>>();
+                let mut preimages = state
+                    .storage_preimages(contract_address, &hashed_slots)
+                    .map_err(Eth::Error::from_eth_err)?;
+                // slots set by the replayed transactions may be missing from the state
+                if let Some(account) = bundle.state.get(&contract_address) {
+                    preimages.extend(account.storage.keys().map(|slot| {
+                        let slot = B256::from(*slot);
+                        (keccak256(slot), slot)
+                    }));
+                }
+
+                let storage = range
+                    .entries
+                    .into_iter()
+                    .map(|(hashed_slot, value)| {
+                        let key = preimages.get(&hashed_slot).copied();
+                        (hashed_slot, StorageRangeEntry { key, value: value.into() })
+                    })
+                    .collect();
+
+                Ok(StorageRangeResult { storage, next_key: range.next_key })
+            })
+            .await
+    }
+
     /// Trace the transaction according to the provided options.
     ///
     /// Ref: <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers>
@@ -583,8 +1232,8 @@ where
                     &this.inner.provider.chain_spec(),
                     &cfg,
                     &block_env,
-                    block.timestamp,
                     block.number,
+                    block.timestamp,
                     block.parent_beacon_block_root,
                 )
                 .map_err(|err| EthApiError::Internal(err.into()))?;
@@ -770,8 +1419,11 @@ where
         + ChainSpecProvider
         + StateProviderFactory
         + EvmEnvProvider
+        + BadBlockReader
+        + HistoryReader
+        + PruneCheckpointReader
         + 'static,
-    Eth: EthApiSpec + EthTransactions + TraceExt + 'static,
+    Eth: EthApiSpec + EthState + EthTransactions + TraceExt + 'static,
 {
     /// Handler for `debug_getRawHeader`
     async fn raw_header(&self, block_id: BlockId) -> RpcResult<Bytes> {
@@ -845,16 +1497,63 @@ where
 
     /// Handler for `debug_getBadBlocks`
     async fn bad_blocks(&self) -> RpcResult<Vec<RichBlock>> {
-        Err(internal_rpc_err("unimplemented"))
+        self.inner
+            .provider
+            .bad_blocks()
+            .to_rpc_result()?
+            .into_iter()
+            .map(|block| {
+                // the total difficulty is only known if the parent block is known
+                let total_difficulty = self
+                    .inner
+                    .provider
+                    .header_td(&block.parent_hash)
+                    .to_rpc_result()?
+                    .map(|td| td + block.difficulty)
+                    .unwrap_or_default();
+                let hash = block.hash();
+                let block = block.unseal();
+
+                // the signatures of a bad block may be invalid, in which case only the transaction
+                // hashes are returned
+                let block = match block.clone().with_recovered_senders() {
+                    Some(block) => from_block_full(block, total_difficulty, Some(hash))
+                        .map_err(EthApiError::from)?,
+                    None => from_block_with_tx_hashes(
+                        BlockWithSenders { block, senders: Vec::new() },
+                        total_difficulty,
+                        Some(hash),
+                    ),
+                };
+                Ok(block.into())
+            })
+            .collect()
     }
 
-    /// Handler for `debug_traceChain`
-    async fn debug_trace_chain(
+    /// Handler for `debug_subscribe`
+    async fn debug_subscribe(
         &self,
-        _start_exclusive: BlockNumberOrTag,
-        _end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        pending: PendingSubscriptionSink,
+        kind: DebugSubscriptionKind,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> SubscriptionResult {
+        // `traceChain` is the only kind of subscription, like in geth
+        let DebugSubscriptionKind::TraceChain = kind;
+        let blocks = match self.trace_chain_range(start_exclusive, end_inclusive) {
+            Ok(blocks) => blocks,
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(())
+            }
+        };
+
+        let sink = pending.accept().await?;
+        Self::debug_trace_chain(self, sink, blocks, opts.unwrap_or_default())
+            .await
+            .map_err(|err| -> ErrorObjectOwned { err.into() })?;
+        Ok(())
     }
 
     /// Handler for `debug_traceBlock`
@@ -941,26 +1640,34 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_accountRange`
     async fn debug_account_range(
         &self,
-        _block_number: BlockNumberOrTag,
-        _start: Bytes,
-        _max_results: u64,
-        _nocode: bool,
-        _nostorage: bool,
-        _incompletes: bool,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_number: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+        incompletes: bool,
+    ) -> RpcResult<AccountRange> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_account_range(
+            self,
+            block_number,
+            start,
+            max_results,
+            nocode,
+            nostorage,
+            incompletes,
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn debug_block_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
         Ok(())
     }
 
-    async fn debug_chaindb_compact(&self) -> RpcResult<()> {
-        Ok(())
-    }
-
     async fn debug_chaindb_property(&self, _property: String) -> RpcResult<()> {
         Ok(())
     }
@@ -1005,32 +1712,40 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_getModifiedAccountsByHash`
     async fn debug_get_modified_accounts_by_hash(
         &self,
-        _start_hash: B256,
-        _end_hash: B256,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>> {
+        Self::debug_get_modified_accounts_by_hash(self, start_hash, end_hash)
+            .await
+            .map_err(Into::into)
     }
 
+    /// Handler for `debug_getModifiedAccountsByNumber`
     async fn debug_get_modified_accounts_by_number(
         &self,
-        _start_number: u64,
-        _end_number: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>> {
+        Self::debug_get_modified_accounts_by_number(self, start_number, end_number)
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_go_trace(&self, _file: String, _seconds: u64) -> RpcResult<()> {
         Ok(())
     }
 
+    /// Handler for `debug_intermediateRoots`
     async fn debug_intermediate_roots(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingCallOptions>,
+    ) -> RpcResult<Vec<B256>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_intermediate_roots(self, block_hash, opts).await.map_err(Into::into)
     }
 
     async fn debug_mem_stats(&self) -> RpcResult<()> {
@@ -1077,12 +1792,16 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_standardTraceBadBlockToFile`
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<String>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_standard_trace_bad_block_to_file(self, block_hash, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_standard_trace_block_to_file(
@@ -1109,23 +1828,38 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_storageRangeAt`
     async fn debug_storage_range_at(
         &self,
-        _block_hash: B256,
-        _tx_idx: usize,
-        _contract_address: Address,
-        _key_start: B256,
-        _max_result: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> RpcResult<StorageRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_storage_range_at(
+            self,
+            block_hash,
+            tx_idx,
+            contract_address,
+            key_start,
+            max_result,
+        )
+        .await
+        .map_err(Into::into)
     }
 
+    /// Handler for `debug_traceBadBlock`
     async fn debug_trace_bad_block(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_trace_bad_block(self, block_hash, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_verbosity(&self, _level: usize) -> RpcResult<()> {
@@ -1169,3 +1903,397 @@ struct DebugApiInner<Provider, Eth> {
     // restrict the number of concurrent calls to blocking calls
     blocking_task_guard: BlockingTaskGuard,
 }
+
+/// `debug_chaindbCompact` implementation.
+///
+/// This is separate from [`DebugApi`], because it only needs access to the database and is merged
+/// into the `debug` namespace.
+pub struct DebugCompactApi<Compactor> {
+    /// Writes the compacted copy of the database.
+    compactor: Compactor,
+    /// The type that can spawn the blocking task writing the copy.
+    task_spawner: Box<dyn TaskSpawner>,
+}
+
+impl<Compactor> DebugCompactApi<Compactor>
+where
+    Compactor: DatabaseCompactor + Clone + 'static,
+{
+    /// Creates a new instance of the [`DebugCompactApi`].
+    pub fn new(compactor: Compactor, task_spawner: Box<dyn TaskSpawner>) -> Self {
+        Self { compactor, task_spawner }
+    }
+
+    /// Writes a compacted copy of the database next to it, replacing any previous copy, and returns
+    /// the directory of the copy.
+    ///
+    /// The copy is taken from a read-only snapshot, so the node keeps running while it is written.
+    pub async fn debug_chaindb_compact(&self) -> EthResult<PathBuf> {
+        let (tx, rx) = oneshot::channel();
+        let compactor = self.compactor.clone();
+        self.task_spawner.spawn_blocking(Box::pin(async move {
+            let _ = tx.send(compactor.compact_database().map_err(EthApiError::from));
+        }));
+        rx.await.map_err(|_| EthApiError::InternalEthError)?
+    }
+}
+
+#[async_trait]
+impl<Compactor> DebugCompactApiServer for DebugCompactApi<Compactor>
+where
+    Compactor: DatabaseCompactor + Clone + 'static,
+{
+    /// Handler for `debug_chaindbCompact`
+    async fn debug_chaindb_compact(&self) -> RpcResult<PathBuf> {
+        let path = Self::debug_chaindb_compact(self).await?;
+        info!(target: "rpc::debug", ?path, "Wrote compacted copy of the database");
+        Ok(path)
+    }
+}
+
+impl<Compactor> std::fmt::Debug for DebugCompactApi<Compactor> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DebugCompactApi").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{TestChain, TestDb, TestEthApi};
+    use alloy_eips::eip4788::{BEACON_ROOTS_ADDRESS, BEACON_ROOTS_CODE};
+    use reth_chainspec::{EthereumHardfork, ForkCondition};
+    use reth_primitives::{
+        constants::ETHEREUM_BLOCK_GAS_LIMIT, GenesisAccount, Header, TransactionSigned, Withdrawals,
+    };
+    use reth_provider::providers::BlockchainProvider2;
+    use reth_rpc_server_types::constants::{DEFAULT_ETH_PROOF_WINDOW, MAX_ETH_PROOF_WINDOW};
+    use reth_rpc_types::state::{AccountOverride, StateOverride};
+
+    /// Timestamp at which Cancun activates, different from any block number used in the tests.
+    const CANCUN_TIMESTAMP: u64 = 1_000;
+
+    /// Recipient of the ETH transferred in the first block.
+    const RECIPIENT: Address = Address::repeat_byte(0x42);
+
+    /// Returns a Cancun block with the given transactions, `n` slots after the activation.
+    fn cancun_block(n: u64, body: Vec<TransactionSigned>) -> Block {
+        Block {
+            header: Header {
+                timestamp: CANCUN_TIMESTAMP + 12 * n,
+                gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
+                base_fee_per_gas: Some(7),
+                withdrawals_root: Some(Default::default()),
+                blob_gas_used: Some(0),
+                excess_blob_gas: Some(0),
+                parent_beacon_block_root: Some(B256::with_last_byte(n as u8)),
+                ..Default::default()
+            },
+            body,
+            withdrawals: Some(Withdrawals::default()),
+            ..Default::default()
+        }
+    }
+
+    /// Creates a Cancun chain with one block that transfers some ETH, and returns it together with
+    /// that block.
+    fn cancun_chain() -> (TestChain, SealedBlock) {
+        let beacon_roots = GenesisAccount {
+            nonce: Some(1),
+            code: Some(BEACON_ROOTS_CODE.clone()),
+            ..Default::default()
+        };
+        let mut chain = TestChain::new([(BEACON_ROOTS_ADDRESS, beacon_roots)], |spec| {
+            spec.shanghai_activated()
+                .with_fork(EthereumHardfork::Cancun, ForkCondition::Timestamp(CANCUN_TIMESTAMP))
+        });
+
+        let transfer = chain.transaction(RECIPIENT, U256::from(1_000), 21_000);
+        let block = chain.append_block(cancun_block(1, vec![transfer]));
+        (chain, block)
+    }
+
+    /// Creates a debug API on top of the chain.
+    fn debug_api(
+        chain: &TestChain,
+        max_proof_window: u64,
+    ) -> DebugApi<BlockchainProvider2<TestDb>, TestEthApi> {
+        let (provider, eth_api) = chain.eth_api(max_proof_window);
+        DebugApi::new(provider, eth_api, BlockingTaskGuard::new(4))
+    }
+
+    /// Creates a debug API on top of [`cancun_chain`], and returns it together with its block.
+    fn debug_api_with_cancun_block(
+        max_proof_window: u64,
+    ) -> (DebugApi<BlockchainProvider2<TestDb>, TestEthApi>, SealedBlock) {
+        let (chain, block) = cancun_chain();
+        (debug_api(&chain, max_proof_window), block)
+    }
+
+    #[tokio::test]
+    async fn intermediate_roots_after_cancun() {
+        let (debug_api, block) = debug_api_with_cancun_block(DEFAULT_ETH_PROOF_WINDOW);
+
+        let roots = debug_api.debug_intermediate_roots(block.hash(), None).await.unwrap();
+
+        // the beacon root contract call is part of the state of the first transaction
+        assert_eq!(roots, vec![block.state_root]);
+    }
+
+    #[tokio::test]
+    async fn intermediate_roots_with_state_overrides() {
+        let (debug_api, block) = debug_api_with_cancun_block(DEFAULT_ETH_PROOF_WINDOW);
+        let roots_with = |account_override| {
+            let opts = GethDebugTracingCallOptions {
+                state_overrides: Some(StateOverride::from_iter([(RECIPIENT, account_override)])),
+                ..Default::default()
+            };
+            debug_api.debug_intermediate_roots(block.hash(), Some(opts))
+        };
+
+        // overriding an account with its own state doesn't change the roots
+        let roots =
+            roots_with(AccountOverride { nonce: Some(0), ..Default::default() }).await.unwrap();
+        assert_eq!(roots, vec![block.state_root]);
+
+        let roots = roots_with(AccountOverride {
+            balance: Some(U256::from(1)),
+            state_diff: Some(HashMap::from_iter([(B256::ZERO, B256::with_last_byte(1))])),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_ne!(roots, vec![block.state_root]);
+
+        let err = roots_with(AccountOverride {
+            state: Some(Default::default()),
+            state_diff: Some(Default::default()),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+        assert!(matches!(err, EthApiError::BothStateAndStateDiffInOverride(RECIPIENT)));
+    }
+
+    #[tokio::test]
+    async fn intermediate_roots_of_known_blocks_within_proof_window() {
+        let (mut chain, first) = cancun_chain();
+        let second = chain.append_block(cancun_block(2, Vec::new()));
+        let debug_api = debug_api(&chain, 0);
+
+        assert!(debug_api.debug_intermediate_roots(second.hash(), None).await.is_ok());
+        let err = debug_api.debug_intermediate_roots(first.hash(), None).await.unwrap_err();
+        assert!(matches!(err, EthApiError::ExceedsMaxProofWindow));
+        let err = debug_api.debug_intermediate_roots(B256::random(), None).await.unwrap_err();
+        assert!(matches!(err, EthApiError::UnknownBlockNumber));
+    }
+
+    #[tokio::test]
+    async fn modified_accounts_range_is_bounded() {
+        let (debug_api, block) = debug_api_with_cancun_block(DEFAULT_ETH_PROOF_WINDOW);
+
+        let accounts =
+            debug_api.debug_get_modified_accounts_by_number(0, Some(block.number)).await.unwrap();
+        assert!(accounts.contains(&RECIPIENT));
+
+        let err = debug_api
+            .debug_get_modified_accounts_by_number(0, Some(MODIFIED_ACCOUNTS_MAX_BLOCKS + 1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EthApiError::InvalidParams(_)));
+        let err =
+            debug_api.debug_get_modified_accounts_by_hash(B256::random(), None).await.unwrap_err();
+        assert!(matches!(err, EthApiError::UnknownBlockNumber));
+    }
+
+    #[tokio::test]
+    async fn storage_range_after_cancun() {
+        let (debug_api, block) = debug_api_with_cancun_block(DEFAULT_ETH_PROOF_WINDOW);
+
+        // the pre-state of the first transaction contains the beacon root contract writes, whose
+        // slots are not in the state of the parent block
+        let range = debug_api
+            .debug_storage_range_at(block.hash(), 0, BEACON_ROOTS_ADDRESS, B256::ZERO, u64::MAX)
+            .await
+            .unwrap();
+        let timestamp = B256::from(U256::from(block.timestamp));
+        let root = block.parent_beacon_block_root.unwrap();
+        let mut entries = range
+            .storage
+            .iter()
+            .map(|(hashed_slot, entry)| {
+                let key = entry.key.unwrap();
+                assert_eq!(keccak256(key), *hashed_slot);
+                (key, entry.value)
+            })
+            .collect::<Vec<_>>();
+        entries.sort();
+        let slot = U256::from(block.timestamp % 8191);
+        assert_eq!(
+            entries,
+            vec![(slot.into(), timestamp), ((slot + U256::from(8191)).into(), root)]
+        );
+        assert_eq!(range.next_key, None);
+    }
+
+    #[tokio::test]
+    async fn storage_range_keys_of_historical_slots() {
+        let (mut chain, _) = cancun_chain();
+        let second = chain.append_block(cancun_block(2, Vec::new()));
+        chain.append_block(cancun_block(3, Vec::new()));
+        let debug_api = debug_api(&chain, MAX_ETH_PROOF_WINDOW);
+
+        // the slots written by the first block are read from the historical state, the ones of the
+        // second block from its beacon root contract call
+        let range = debug_api
+            .debug_storage_range_at(second.hash(), 0, BEACON_ROOTS_ADDRESS, B256::ZERO, 10)
+            .await
+            .unwrap();
+        assert_eq!(range.storage.len(), 4);
+        for (hashed_slot, entry) in &range.storage {
+            assert_eq!(entry.key.map(keccak256), Some(*hashed_slot));
+        }
+
+        let page = debug_api
+            .debug_storage_range_at(second.hash(), 0, BEACON_ROOTS_ADDRESS, B256::ZERO, 1)
+            .await
+            .unwrap();
+        assert_eq!(page.storage.len(), 1);
+        assert_eq!(page.next_key, range.storage.keys().nth(1).copied());
+    }
+
+    #[tokio::test]
+    async fn storage_range_within_proof_window() {
+        let (mut chain, first) = cancun_chain();
+        let second = chain.append_block(cancun_block(2, Vec::new()));
+        let debug_api = debug_api(&chain, 0);
+
+        assert!(debug_api
+            .debug_storage_range_at(second.hash(), 0, BEACON_ROOTS_ADDRESS, B256::ZERO, 10)
+            .await
+            .is_ok());
+        let err = debug_api
+            .debug_storage_range_at(first.hash(), 0, BEACON_ROOTS_ADDRESS, B256::ZERO, 10)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EthApiError::ExceedsMaxProofWindow));
+    }
+
+    #[tokio::test]
+    async fn account_range_within_proof_window() {
+        let (debug_api, block) = debug_api_with_cancun_block(0);
+
+        let range = debug_api
+            .debug_account_range(block.number.into(), Bytes::new(), 0, true, false, true)
+            .await
+            .unwrap();
+        assert_eq!(range.root, block.state_root);
+        assert!(!range.accounts.is_empty());
+
+        let err = debug_api
+            .debug_account_range(BlockNumberOrTag::Earliest, Bytes::new(), 0, true, false, false)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EthApiError::ExceedsMaxProofWindow));
+    }
+
+    #[tokio::test]
+    async fn account_range_pages_hashed_accounts() {
+        let (mut chain, first) = cancun_chain();
+        chain.append_block(cancun_block(2, Vec::new()));
+        let debug_api = debug_api(&chain, MAX_ETH_PROOF_WINDOW);
+        let recipient_key = keccak256(RECIPIENT);
+
+        // the recipient only exists after the first block
+        let at = |number: u64, start: B256, max_results, incompletes| {
+            debug_api.debug_account_range(
+                number.into(),
+                start.to_vec().into(),
+                max_results,
+                false,
+                false,
+                incompletes,
+            )
+        };
+        let genesis = at(0, B256::ZERO, 0, true).await.unwrap();
+        assert!(genesis.accounts.values().all(|account| account.key != Some(recipient_key)));
+        assert_eq!(genesis.next, None);
+
+        // the recipient didn't change after the first block, so its address is unknown
+        let range = at(first.number, B256::ZERO, 0, true).await.unwrap();
+        let recipient = &range.accounts[&format!("pre({recipient_key})")];
+        assert_eq!(recipient.balance, "1000");
+        assert_eq!(recipient.address, None);
+        assert_eq!(recipient.key, Some(recipient_key));
+        assert_eq!(range.accounts.len(), genesis.accounts.len() + 1);
+
+        // accounts with an unknown address are skipped unless incompletes are requested
+        let complete = at(first.number, B256::ZERO, 0, false).await.unwrap();
+        assert!(complete.accounts.len() < range.accounts.len());
+        for (key, account) in &complete.accounts {
+            let address = account.address.unwrap();
+            assert_eq!(key, &address.to_string());
+            assert_eq!(account.key, Some(keccak256(address)));
+        }
+
+        // pages continue at the next hashed address
+        let page = at(first.number, B256::ZERO, 1, true).await.unwrap();
+        assert_eq!(page.accounts.len(), 1);
+        let next = page.next.unwrap();
+        assert!(page.accounts.values().next().unwrap().key < Some(next));
+        let page = at(first.number, next, 1, true).await.unwrap();
+        assert_eq!(page.accounts.values().next().unwrap().key, Some(next));
+    }
+
+    /// Returns the `debug_subscribe` params of a `traceChain` subscription for the given range of
+    /// blocks.
+    fn trace_chain(start: u64, end: u64) -> (&'static str, BlockNumberOrTag, BlockNumberOrTag) {
+        ("traceChain", start.into(), end.into())
+    }
+
+    #[tokio::test]
+    async fn trace_chain_validates_range() {
+        let (debug_api, block) = debug_api_with_cancun_block(DEFAULT_ETH_PROOF_WINDOW);
+        let module = DebugApiServer::into_rpc(debug_api);
+
+        let mut subscription =
+            module.subscribe_unbounded("debug_subscribe", trace_chain(0, 1)).await.unwrap();
+        let (result, _) = subscription.next::<BlockTraceResult>().await.unwrap().unwrap();
+        assert_eq!(result.hash, block.hash());
+
+        // inverted and empty ranges are rejected before the subscription is accepted
+        assert!(module.subscribe_unbounded("debug_subscribe", trace_chain(1, 0)).await.is_err());
+        assert!(module.subscribe_unbounded("debug_subscribe", trace_chain(1, 1)).await.is_err());
+
+        // so are ranges that reach beyond the chain
+        assert!(module.subscribe_unbounded("debug_subscribe", trace_chain(0, 5)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn trace_chain_traces_blocks_in_order() {
+        let (mut chain, first) = cancun_chain();
+        let transfer = chain.transaction(RECIPIENT, U256::from(1_000), 21_000);
+        let second = chain.append_block(cancun_block(2, vec![transfer]));
+        let module = DebugApiServer::into_rpc(debug_api(&chain, DEFAULT_ETH_PROOF_WINDOW));
+
+        let mut subscription =
+            module.subscribe_unbounded("debug_subscribe", trace_chain(0, 2)).await.unwrap();
+        for block in [first, second] {
+            let (result, _) = subscription.next::<BlockTraceResult>().await.unwrap().unwrap();
+            assert_eq!((result.block, result.hash), (U256::from(block.number), block.hash()));
+            assert_eq!(result.traces.len(), block.body.len());
+        }
+
+        // the subscription is closed once the whole range is traced
+        assert!(subscription.next::<BlockTraceResult>().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn subscribe_rejects_unknown_kinds() {
+        let (debug_api, _) = debug_api_with_cancun_block(DEFAULT_ETH_PROOF_WINDOW);
+        let module = DebugApiServer::into_rpc(debug_api);
+
+        let params = ("traceBlocks", BlockNumberOrTag::from(0), BlockNumberOrTag::from(1));
+        assert!(module.subscribe_unbounded("debug_subscribe", params).await.is_err());
+    }
+}
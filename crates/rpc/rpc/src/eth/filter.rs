@@ -1,7 +1,7 @@
 //! `eth_` `Filter` RPC handler implementation
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     fmt,
     iter::StepBy,
     ops::RangeInclusive,
@@ -13,7 +13,7 @@ use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, server::IdProvider};
 use reth_chainspec::ChainInfo;
 use reth_primitives::{IntoRecoveredTransaction, TxHash};
-use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, ProviderError};
+use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, LogIndexReader, ProviderError};
 use reth_rpc_eth_api::EthFilterApiServer;
 use reth_rpc_eth_types::{
     logs_utils::{self, append_matching_block_logs},
@@ -124,7 +124,7 @@ where
 
 impl<Provider, Pool> EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexReader + 'static,
     Pool: TransactionPool + 'static,
     <Pool as TransactionPool>::Transaction: 'static,
 {
@@ -221,7 +221,7 @@ where
 #[async_trait]
 impl<Provider, Pool> EthFilterApiServer for EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexReader + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Handler for `eth_newFilter`
@@ -340,7 +340,7 @@ struct EthFilterInner<Provider, Pool> {
 
 impl<Provider, Pool> EthFilterInner<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexReader + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Returns logs matching given filter object.
@@ -416,6 +416,9 @@ where
 
     /// Returns all logs in the given _inclusive_ range that match the filter
     ///
+    /// Blocks covered by the log index are looked up in it, the rest of the range is scanned with
+    /// the header blooms.
+    ///
     /// Returns an error if:
     ///  - underlying database error
     ///  - amount of matches exceeds configured limit
@@ -433,7 +436,11 @@ where
             return Err(EthFilterError::InvalidBlockRangeParams)
         }
 
-        if to_block - from_block > self.max_blocks_per_filter {
+        // blocks covered by the log index are cheap to query, so only the rest of the range
+        // counts towards the limit
+        let indexed = self.indexed_range(filter, from_block, to_block)?;
+        let indexed_blocks = indexed.as_ref().map_or(0, |range| range.end() - range.start() + 1);
+        if (to_block - from_block).saturating_sub(indexed_blocks) > self.max_blocks_per_filter {
             return Err(EthFilterError::QueryExceedsMaxBlocks(self.max_blocks_per_filter))
         }
 
@@ -461,15 +468,129 @@ where
             return Ok(all_logs)
         }
 
+        // size check but only if range is multiple blocks, so we always return all logs of a
+        // single block
+        let is_multi_block_range = from_block != to_block;
+
+        let Some(indexed) = indexed else {
+            self.append_logs_by_bloom(
+                &mut all_logs,
+                filter,
+                &filter_params,
+                from_block..=to_block,
+                is_multi_block_range,
+            )
+            .await?;
+            return Ok(all_logs)
+        };
+
+        if from_block < *indexed.start() {
+            self.append_logs_by_bloom(
+                &mut all_logs,
+                filter,
+                &filter_params,
+                from_block..=*indexed.start() - 1,
+                is_multi_block_range,
+            )
+            .await?;
+        }
+
+        for block_number in self.indexed_blocks(filter, indexed.clone())? {
+            let Some(header) = self.provider.sealed_header(block_number)? else { continue };
+            self.append_block_logs(
+                &mut all_logs,
+                &filter_params,
+                BlockNumHash::new(header.number, header.hash()),
+                header.timestamp,
+                is_multi_block_range,
+            )
+            .await?;
+        }
+
+        if *indexed.end() < to_block {
+            self.append_logs_by_bloom(
+                &mut all_logs,
+                filter,
+                &filter_params,
+                *indexed.end() + 1..=to_block,
+                is_multi_block_range,
+            )
+            .await?;
+        }
+
+        Ok(all_logs)
+    }
+
+    /// Returns the part of the range covered by the log index, or `None` if the log index isn't
+    /// maintained or the filter matches logs of any address and with any topics, in which case
+    /// the index can't narrow down the blocks to read.
+    fn indexed_range(
+        &self,
+        filter: &Filter,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Option<RangeInclusive<u64>>, EthFilterError> {
+        if filter.address.is_empty() && filter.topics.iter().all(|topics| topics.is_empty()) {
+            return Ok(None)
+        }
+
+        let Some(index_range) = self.provider.log_index_range()? else { return Ok(None) };
+        let start = from_block.max(*index_range.start());
+        let end = to_block.min(*index_range.end());
+        Ok((start <= end).then_some(start..=end))
+    }
+
+    /// Returns the blocks of the range that may contain logs matching the filter, according to
+    /// the log index.
+    ///
+    /// The index doesn't record the position of topics, so this is a superset of the blocks with
+    /// matching logs.
+    fn indexed_blocks(
+        &self,
+        filter: &Filter,
+        range: RangeInclusive<u64>,
+    ) -> Result<Vec<u64>, EthFilterError> {
+        let mut candidates = Vec::<BTreeSet<u64>>::new();
+        if !filter.address.is_empty() {
+            let mut blocks = BTreeSet::new();
+            for address in filter.address.iter() {
+                blocks.extend(self.provider.log_address_blocks(*address, range.clone())?);
+            }
+            candidates.push(blocks);
+        }
+        for topics in filter.topics.iter().filter(|topics| !topics.is_empty()) {
+            let mut blocks = BTreeSet::new();
+            for topic in topics.iter() {
+                blocks.extend(self.provider.log_topic_blocks(*topic, range.clone())?);
+            }
+            candidates.push(blocks);
+        }
+
+        Ok(candidates
+            .into_iter()
+            .reduce(|all, blocks| all.intersection(&blocks).copied().collect())
+            .unwrap_or_default()
+            .into_iter()
+            .collect())
+    }
+
+    /// Appends the logs of the range that match the filter, only reading the receipts of blocks
+    /// whose header bloom matches.
+    async fn append_logs_by_bloom(
+        &self,
+        all_logs: &mut Vec<Log>,
+        filter: &Filter,
+        filter_params: &FilteredParams,
+        range: RangeInclusive<u64>,
+        is_multi_block_range: bool,
+    ) -> Result<(), EthFilterError> {
         // derive bloom filters from filter input, so we can check headers for matching logs
         let address_filter = FilteredParams::address_filter(&filter.address);
         let topics_filter = FilteredParams::topics_filter(&filter.topics);
 
         // loop over the range of new blocks and check logs if the filter matches the log's bloom
         // filter
-        for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
-        {
+        for (from, to) in BlockRangeInclusiveIter::new(range, self.max_headers_range) {
             let headers = self.provider.headers_range(from..=to)?;
 
             for (idx, header) in headers.iter().enumerate() {
@@ -487,31 +608,47 @@ where
                             .ok_or(ProviderError::HeaderNotFound(header.number.into()))?,
                     };
 
-                    if let Some(receipts) = self.eth_cache.get_receipts(block_hash).await? {
-                        append_matching_block_logs(
-                            &mut all_logs,
-                            &self.provider,
-                            &filter_params,
-                            BlockNumHash::new(header.number, block_hash),
-                            &receipts,
-                            false,
-                            header.timestamp,
-                        )?;
-
-                        // size check but only if range is multiple blocks, so we always return all
-                        // logs of a single block
-                        let is_multi_block_range = from_block != to_block;
-                        if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
-                            return Err(EthFilterError::QueryExceedsMaxResults(
-                                self.max_logs_per_response,
-                            ))
-                        }
-                    }
+                    self.append_block_logs(
+                        all_logs,
+                        filter_params,
+                        BlockNumHash::new(header.number, block_hash),
+                        header.timestamp,
+                        is_multi_block_range,
+                    )
+                    .await?;
                 }
             }
         }
 
-        Ok(all_logs)
+        Ok(())
+    }
+
+    /// Appends the logs of the block that match the filter.
+    async fn append_block_logs(
+        &self,
+        all_logs: &mut Vec<Log>,
+        filter_params: &FilteredParams,
+        block: BlockNumHash,
+        timestamp: u64,
+        is_multi_block_range: bool,
+    ) -> Result<(), EthFilterError> {
+        if let Some(receipts) = self.eth_cache.get_receipts(block.hash).await? {
+            append_matching_block_logs(
+                all_logs,
+                &self.provider,
+                filter_params,
+                block,
+                &receipts,
+                false,
+                timestamp,
+            )?;
+
+            if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
+                return Err(EthFilterError::QueryExceedsMaxResults(self.max_logs_per_response))
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -25,3 +25,177 @@ where
         self.inner.evm_config()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{
+        constants::ETHEREUM_BLOCK_GAS_LIMIT, Address, Block, BloomInput, Header, TxKind, B256, U256,
+    };
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_rpc_eth_types::{
+        simulate::TRANSFER_LOG_EMITTER, EthApiError, EthStateCache, FeeHistoryCache,
+        FeeHistoryCacheConfig, GasPriceOracle,
+    };
+    use reth_rpc_server_types::constants::{DEFAULT_ETH_PROOF_WINDOW, DEFAULT_PROOF_PERMITS};
+    use reth_rpc_types::{
+        simulate::{SimBlock, SimulatePayload},
+        BlockOverrides, TransactionRequest,
+    };
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    /// Creates an eth API on top of the given mock provider with the given gas cap.
+    fn mock_eth_api(
+        mock_provider: MockEthProvider,
+        gas_cap: u64,
+    ) -> EthApi<MockEthProvider, TestPool, (), EthEvmConfig> {
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(mock_provider.clone(), Default::default(), evm_config);
+        EthApi::new(
+            mock_provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(mock_provider, Default::default(), cache.clone()),
+            gas_cap,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            None,
+            DEFAULT_PROOF_PERMITS,
+        )
+    }
+
+    /// Returns a transfer call request.
+    fn transfer(from: Address, to: Address, value: u64) -> TransactionRequest {
+        TransactionRequest {
+            from: Some(from),
+            to: Some(TxKind::Call(to)),
+            value: Some(U256::from(value)),
+            ..Default::default()
+        }
+    }
+
+    /// Adds a parent block to the given mock provider.
+    fn add_parent(mock_provider: &MockEthProvider) {
+        add_block(mock_provider, 20_000_000);
+    }
+
+    /// Adds a block with the given number to the given mock provider and returns its hash.
+    fn add_block(mock_provider: &MockEthProvider, number: u64) -> B256 {
+        let header = Header {
+            number,
+            timestamp: 1_720_000_000,
+            gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
+            base_fee_per_gas: Some(1_000_000_000),
+            ..Default::default()
+        };
+        let hash = header.hash_slow();
+        mock_provider.add_block(hash, Block { header, ..Default::default() });
+        hash
+    }
+
+    #[tokio::test]
+    async fn test_simulate_v1() {
+        let mock_provider = MockEthProvider::default();
+        add_parent(&mock_provider);
+
+        let sender = Address::random();
+        let recipient = Address::random();
+        mock_provider.add_account(sender, ExtendedAccount::new(0, U256::from(1_000)));
+
+        let eth_api = mock_eth_api(mock_provider, ETHEREUM_BLOCK_GAS_LIMIT);
+
+        let payload = SimulatePayload {
+            block_state_calls: vec![
+                SimBlock { calls: vec![transfer(sender, recipient, 100)], ..Default::default() },
+                SimBlock {
+                    block_overrides: Some(BlockOverrides {
+                        number: Some(U256::from(20_000_003)),
+                        ..Default::default()
+                    }),
+                    calls: vec![transfer(recipient, sender, 50)],
+                    ..Default::default()
+                },
+            ],
+            trace_transfers: true,
+            ..Default::default()
+        };
+
+        let blocks = EthCall::simulate_v1(&eth_api, payload.clone(), None).await.unwrap();
+
+        // the gap between the two blocks is filled with an empty block
+        assert_eq!(
+            blocks.iter().map(|block| block.inner.header.number).collect::<Vec<_>>(),
+            vec![Some(20_000_001), Some(20_000_002), Some(20_000_003)]
+        );
+        assert_eq!(blocks[1].inner.header.timestamp, 1_720_000_024);
+        assert_eq!(blocks[1].inner.header.parent_hash, blocks[0].inner.header.hash.unwrap());
+        assert!(blocks[1].calls.is_empty());
+
+        let call = &blocks[0].calls[0];
+        assert_eq!(call.status, 1);
+        assert_eq!(call.gas_used, 21_000);
+        assert_eq!(call.logs.len(), 1);
+        assert_eq!(call.logs[0].address(), TRANSFER_LOG_EMITTER);
+        assert_eq!(call.logs[0].topics()[2], recipient.into_word());
+
+        // the block commits to the same logs that are reported for its calls
+        let bloom = blocks[0].inner.header.logs_bloom;
+        assert!(bloom.contains_input(BloomInput::Raw(TRANSFER_LOG_EMITTER.as_slice())));
+
+        // the recipient spends the funds it received in the first block
+        assert_eq!(blocks[2].calls[0].status, 1);
+
+        // with validation, the calls must pay the base fee
+        let payload = SimulatePayload { validation: true, ..payload };
+        assert!(EthCall::simulate_v1(&eth_api, payload, None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_simulate_v1_shares_gas_cap() {
+        let mock_provider = MockEthProvider::default();
+        add_parent(&mock_provider);
+        let sender = Address::random();
+        let recipient = Address::random();
+        mock_provider.add_account(sender, ExtendedAccount::new(0, U256::from(1_000)));
+        let eth_api = mock_eth_api(mock_provider, 50_000);
+
+        // two transfers fit into the gas cap
+        let block = SimBlock { calls: vec![transfer(sender, recipient, 1)], ..Default::default() };
+        let payload = SimulatePayload {
+            block_state_calls: vec![block.clone(), block.clone()],
+            ..Default::default()
+        };
+        assert!(EthCall::simulate_v1(&eth_api, payload, None).await.is_ok());
+
+        // a third one only gets the remaining gas, even though it is in a separate block
+        let payload = SimulatePayload {
+            block_state_calls: vec![block.clone(), block.clone(), block],
+            ..Default::default()
+        };
+        assert!(EthCall::simulate_v1(&eth_api, payload, None).await.is_err());
+    }
+    #[tokio::test]
+    async fn test_simulate_v1_within_proof_window() {
+        let mock_provider = MockEthProvider::default();
+        let parent = add_block(&mock_provider, 20_000_000);
+        add_block(&mock_provider, 20_000_001);
+        let sender = Address::random();
+        mock_provider.add_account(sender, ExtendedAccount::new(0, U256::from(1_000)));
+        let eth_api = mock_eth_api(mock_provider, ETHEREUM_BLOCK_GAS_LIMIT);
+
+        // the state roots of blocks simulated on top of older blocks are not computed
+        let block =
+            SimBlock { calls: vec![transfer(sender, Address::random(), 1)], ..Default::default() };
+        let payload = SimulatePayload { block_state_calls: vec![block], ..Default::default() };
+        let err =
+            EthCall::simulate_v1(&eth_api, payload.clone(), Some(parent.into())).await.unwrap_err();
+        assert!(matches!(err, EthApiError::ExceedsMaxProofWindow));
+
+        assert!(EthCall::simulate_v1(&eth_api, payload, None).await.is_ok());
+    }
+}
@@ -1,16 +1,29 @@
 //! An abstraction over ethereum signers.
 
-use std::collections::HashMap;
+use std::{collections::HashMap, result, sync::Arc};
 
 use alloy_dyn_abi::TypedData;
+use jsonrpsee::{
+    core::{
+        client::{Client, ClientT, Error as ClientError},
+        params::ArrayParams,
+    },
+    http_client::{HttpClient, HttpClientBuilder},
+    rpc_params,
+    types::error::METHOD_NOT_FOUND_CODE,
+};
+use reth_ipc::client::{IpcClientBuilder, IpcError};
 use reth_primitives::{
-    eip191_hash_message, sign_message, Address, Signature, TransactionSigned, B256,
+    eip191_hash_message, sign_message, Address, Bytes, Signature, TransactionSigned, B256, U256,
 };
 use reth_rpc_eth_api::helpers::{signer::Result, AddDevSigners, EthSigner};
 use reth_rpc_eth_types::SignError;
 use reth_rpc_types::TypedTransactionRequest;
-use reth_rpc_types_compat::transaction::to_primitive_transaction;
+use reth_rpc_types_compat::transaction::{
+    to_primitive_transaction, unsigned_transaction_to_request,
+};
 use secp256k1::SecretKey;
+use serde::{de::DeserializeOwned, Deserialize};
 
 use crate::EthApi;
 
@@ -80,7 +93,7 @@ impl EthSigner for DevSigner {
         self.sign_hash(hash, address)
     }
 
-    fn sign_transaction(
+    async fn sign_transaction(
         &self,
         request: TypedTransactionRequest,
         address: &Address,
@@ -94,17 +107,187 @@ impl EthSigner for DevSigner {
         Ok(TransactionSigned::from_transaction_and_signature(transaction, signature))
     }
 
-    fn sign_typed_data(&self, address: Address, payload: &TypedData) -> Result<Signature> {
+    async fn sign_typed_data(&self, address: Address, payload: &TypedData) -> Result<Signature> {
         let encoded = payload.eip712_signing_hash().map_err(|_| SignError::InvalidTypedData)?;
         self.sign_hash(encoded, address)
     }
 }
 
+/// The JSON-RPC namespace served by the external signer of a [`RemoteSigner`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RemoteSignerApi {
+    /// The `account_` namespace, as served by Clef.
+    Account,
+    /// The `eth_` namespace, as served by web3signer.
+    Eth,
+}
+
+/// The transport used to talk to the external signer of a [`RemoteSigner`].
+#[derive(Debug, Clone)]
+enum RemoteSignerClient {
+    Http(HttpClient),
+    Ipc(Arc<Client>),
+}
+
+/// Errors returned when connecting to a [`RemoteSigner`].
+#[derive(Debug, thiserror::Error)]
+pub enum RemoteSignerError {
+    /// Failed to connect to the IPC socket of the signer.
+    #[error(transparent)]
+    Ipc(#[from] IpcError),
+    /// The signer returned an error.
+    #[error(transparent)]
+    Rpc(#[from] ClientError),
+}
+
+/// A signer that delegates to an external signer, reachable either over HTTP or over a local IPC
+/// socket.
+///
+/// Both the `account_` namespace of Clef and the `eth_` namespace of web3signer are supported.
+/// Keys never enter the node process: accounts are fetched once on connect and every signing
+/// request is forwarded to the external signer.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    client: RemoteSignerClient,
+    api: RemoteSignerApi,
+    addresses: Vec<Address>,
+}
+
+impl RemoteSigner {
+    /// Connects to the external signer at the given endpoint, which is either an HTTP(S) URL or
+    /// the path of an IPC socket, and fetches the accounts it manages.
+    pub async fn connect(endpoint: &str) -> result::Result<Self, RemoteSignerError> {
+        let client = if endpoint.starts_with("http://") || endpoint.starts_with("https://") {
+            RemoteSignerClient::Http(HttpClientBuilder::default().build(endpoint)?)
+        } else {
+            RemoteSignerClient::Ipc(Arc::new(IpcClientBuilder::default().build(endpoint).await?))
+        };
+
+        // Clef only serves the `account_` namespace, web3signer only the `eth_` namespace
+        let (api, addresses) = match client.request("account_list", rpc_params![]).await {
+            Ok(addresses) => (RemoteSignerApi::Account, addresses),
+            Err(ClientError::Call(err)) if err.code() == METHOD_NOT_FOUND_CODE => {
+                (RemoteSignerApi::Eth, client.request("eth_accounts", rpc_params![]).await?)
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        Ok(Self { client, api, addresses })
+    }
+
+    async fn request<R: DeserializeOwned>(&self, method: &str, params: ArrayParams) -> Result<R> {
+        self.client.request(method, params).await.map_err(|err| SignError::Remote(err.to_string()))
+    }
+}
+
+impl RemoteSignerClient {
+    async fn request<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: ArrayParams,
+    ) -> result::Result<R, ClientError> {
+        match self {
+            Self::Http(client) => client.request(method, params).await,
+            Self::Ipc(client) => client.request(method, params).await,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EthSigner for RemoteSigner {
+    fn accounts(&self) -> Vec<Address> {
+        self.addresses.clone()
+    }
+
+    async fn sign(&self, address: Address, message: &[u8]) -> Result<Signature> {
+        let message = Bytes::copy_from_slice(message);
+        let signature: Bytes = match self.api {
+            RemoteSignerApi::Account => {
+                self.request("account_signData", rpc_params!["text/plain", address, message])
+                    .await?
+            }
+            RemoteSignerApi::Eth => self.request("eth_sign", rpc_params![address, message]).await?,
+        };
+        signature_from_bytes(&signature)
+    }
+
+    async fn sign_transaction(
+        &self,
+        request: TypedTransactionRequest,
+        address: &Address,
+    ) -> Result<TransactionSigned> {
+        let transaction =
+            to_primitive_transaction(request).ok_or(SignError::InvalidTransactionRequest)?;
+        let request = unsigned_transaction_to_request(*address, &transaction);
+
+        let raw: Bytes = match self.api {
+            RemoteSignerApi::Account => {
+                /// The response of `account_signTransaction`.
+                #[derive(Deserialize)]
+                struct SignTransactionResult {
+                    raw: Bytes,
+                }
+
+                self.request::<SignTransactionResult>(
+                    "account_signTransaction",
+                    rpc_params![request],
+                )
+                .await?
+                .raw
+            }
+            RemoteSignerApi::Eth => {
+                self.request("eth_signTransaction", rpc_params![request]).await?
+            }
+        };
+
+        let signed = TransactionSigned::decode_enveloped(&mut raw.as_ref())
+            .map_err(|err| SignError::Remote(err.to_string()))?;
+        // make sure the signer signed what was requested, with the requested account
+        if signed.transaction != transaction || signed.recover_signer() != Some(*address) {
+            return Err(SignError::CouldNotSign)
+        }
+        Ok(signed)
+    }
+
+    async fn sign_typed_data(&self, address: Address, payload: &TypedData) -> Result<Signature> {
+        let method = match self.api {
+            RemoteSignerApi::Account => "account_signTypedData",
+            RemoteSignerApi::Eth => "eth_signTypedData",
+        };
+        let signature: Bytes = self.request(method, rpc_params![address, payload]).await?;
+        signature_from_bytes(&signature)
+    }
+}
+
+/// Decodes a signature in its 65 bytes `r || s || v` representation, where `v` is either the
+/// parity or the parity offset by 27.
+fn signature_from_bytes(bytes: &[u8]) -> Result<Signature> {
+    if bytes.len() != 65 {
+        return Err(SignError::Remote(format!("invalid signature length {}", bytes.len())))
+    }
+    let odd_y_parity = match bytes[64] {
+        0 | 27 => false,
+        1 | 28 => true,
+        v => return Err(SignError::Remote(format!("invalid signature v value {v}"))),
+    };
+    Ok(Signature {
+        r: U256::from_be_slice(&bytes[..32]),
+        s: U256::from_be_slice(&bytes[32..64]),
+        odd_y_parity,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
-    use reth_primitives::U256;
+    use jsonrpsee::{
+        core::RpcResult,
+        server::{ServerBuilder, ServerHandle},
+        RpcModule,
+    };
+    use reth_primitives::TxKind;
+    use reth_rpc_types::{transaction::LegacyTransactionRequest, TransactionRequest};
 
     use super::*;
 
@@ -185,7 +368,7 @@ mod tests {
         }"#;
         let data: TypedData = serde_json::from_str(eip_712_example).unwrap();
         let signer = build_signer();
-        let sig = signer.sign_typed_data(Address::default(), &data).unwrap();
+        let sig = signer.sign_typed_data(Address::default(), &data).await.unwrap();
         let expected = Signature {
             r: U256::from_str_radix(
                 "5318aee9942b84885761bb20e768372b76e7ee454fc4d39b59ce07338d15a06c",
@@ -222,4 +405,94 @@ mod tests {
         };
         assert_eq!(sig, expected)
     }
+
+    /// Serves the `account_` namespace of Clef, or the `eth_` namespace of web3signer, backed by
+    /// a [`DevSigner`] that only signs the given transaction.
+    async fn spawn_remote_signer(
+        api: RemoteSignerApi,
+        tx: TypedTransactionRequest,
+    ) -> (ServerHandle, String, Address) {
+        let secret =
+            SecretKey::from_str("4646464646464646464646464646464646464646464646464646464646464646")
+                .unwrap();
+        let address =
+            reth_primitives::public_key_to_address(secret.public_key(secp256k1::SECP256K1));
+        let signer =
+            DevSigner { addresses: vec![address], accounts: HashMap::from([(address, secret)]) };
+
+        let (list, sign, sign_tx) = match api {
+            RemoteSignerApi::Account => {
+                ("account_list", "account_signData", "account_signTransaction")
+            }
+            RemoteSignerApi::Eth => ("eth_accounts", "eth_sign", "eth_signTransaction"),
+        };
+
+        let mut module = RpcModule::new((signer, tx));
+        module.register_method(list, |_, (signer, _), _| signer.accounts()).unwrap();
+        module
+            .register_async_method(sign, move |params, ctx, _| async move {
+                let (address, message): (Address, Bytes) = if api == RemoteSignerApi::Account {
+                    let (_, address, message): (String, _, _) = params.parse()?;
+                    (address, message)
+                } else {
+                    params.parse()?
+                };
+                let signature = ctx.0.sign(address, &message).await.unwrap();
+                RpcResult::Ok(signature.to_hex_bytes())
+            })
+            .unwrap();
+        module
+            .register_async_method(sign_tx, move |params, ctx, _| async move {
+                let (request,): (TransactionRequest,) = params.parse()?;
+                let (signer, tx) = &*ctx;
+                let address = request.from.unwrap();
+                let signed = signer.sign_transaction(tx.clone(), &address).await.unwrap();
+                let raw = signed.envelope_encoded();
+                RpcResult::Ok(if api == RemoteSignerApi::Account {
+                    serde_json::json!({ "raw": raw, "tx": request })
+                } else {
+                    serde_json::json!(raw)
+                })
+            })
+            .unwrap();
+
+        let server = ServerBuilder::default().build("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", server.local_addr().unwrap());
+        (server.start(module), url, address)
+    }
+
+    fn legacy_transaction_request(gas_price: u64) -> TypedTransactionRequest {
+        TypedTransactionRequest::Legacy(LegacyTransactionRequest {
+            nonce: 1,
+            gas_price: U256::from(gas_price),
+            gas_limit: U256::from(21_000),
+            kind: TxKind::Call(Address::random()),
+            value: U256::from(1),
+            input: Bytes::default(),
+            chain_id: Some(1),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer() {
+        for api in [RemoteSignerApi::Account, RemoteSignerApi::Eth] {
+            let tx = legacy_transaction_request(1);
+            let (_server, url, address) = spawn_remote_signer(api, tx.clone()).await;
+
+            let signer = RemoteSigner::connect(&url).await.unwrap();
+            assert_eq!(signer.api, api);
+            assert_eq!(signer.accounts(), vec![address]);
+
+            let message = b"Test message";
+            let signature = signer.sign(address, message).await.unwrap();
+            assert_eq!(signature.recover_signer(eip191_hash_message(message)), Some(address));
+
+            let signed = signer.sign_transaction(tx, &address).await.unwrap();
+            assert_eq!(signed.recover_signer(), Some(address));
+
+            // the remote signer signs a different transaction than the requested one
+            let err = signer.sign_transaction(legacy_transaction_request(2), &address).await;
+            assert!(matches!(err, Err(SignError::CouldNotSign)));
+        }
+    }
 }
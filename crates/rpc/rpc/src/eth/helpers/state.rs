@@ -44,16 +44,17 @@ mod tests {
     use super::*;
     use reth_evm_ethereum::EthEvmConfig;
     use reth_primitives::{
-        constants::ETHEREUM_BLOCK_GAS_LIMIT, Address, StorageKey, StorageValue, U256,
+        constants::{EMPTY_ROOT_HASH, ETHEREUM_BLOCK_GAS_LIMIT},
+        Address, Block, BlockId, StorageKey, StorageValue, B256, KECCAK_EMPTY, U256,
     };
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider, NoopProvider};
     use reth_rpc_eth_api::helpers::EthState;
     use reth_rpc_eth_types::{
-        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+        EthApiError, EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
     };
     use reth_rpc_server_types::constants::{DEFAULT_ETH_PROOF_WINDOW, DEFAULT_PROOF_PERMITS};
     use reth_tasks::pool::BlockingTaskPool;
-    use reth_transaction_pool::test_utils::testing_pool;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
     use std::collections::HashMap;
 
     #[tokio::test]
@@ -109,4 +110,72 @@ mod tests {
         let storage = eth_api.storage_at(address, storage_key.into(), None).await.unwrap();
         assert_eq!(storage, storage_value.to_be_bytes());
     }
+
+    /// Creates an eth API on top of the given mock provider with the given proof window.
+    fn mock_eth_api(
+        mock_provider: MockEthProvider,
+        max_proof_window: u64,
+    ) -> EthApi<MockEthProvider, TestPool, (), EthEvmConfig> {
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(mock_provider.clone(), Default::default(), evm_config);
+        EthApi::new(
+            mock_provider.clone(),
+            testing_pool(),
+            (),
+            cache.clone(),
+            GasPriceOracle::new(mock_provider, Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            max_proof_window,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            None,
+            DEFAULT_PROOF_PERMITS,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_get_account() {
+        let mock_provider = MockEthProvider::default();
+        let address = Address::random();
+        mock_provider.add_account(address, ExtendedAccount::new(5, U256::from(1337)));
+        mock_provider.add_block(B256::random(), Block::default());
+        let eth_api = mock_eth_api(mock_provider, DEFAULT_ETH_PROOF_WINDOW);
+
+        let account = eth_api.get_account(address, BlockId::latest()).await.unwrap();
+        assert_eq!(account.nonce, 5);
+        assert_eq!(account.balance, U256::from(1337));
+        assert_eq!(account.code_hash, KECCAK_EMPTY);
+    }
+
+    #[tokio::test]
+    async fn test_get_missing_account() {
+        let mock_provider = MockEthProvider::default();
+        mock_provider.add_block(B256::random(), Block::default());
+        let eth_api = mock_eth_api(mock_provider, DEFAULT_ETH_PROOF_WINDOW);
+
+        let account = eth_api.get_account(Address::random(), BlockId::latest()).await.unwrap();
+        assert_eq!(account.nonce, 0);
+        assert_eq!(account.balance, U256::ZERO);
+        assert_eq!(account.storage_root, EMPTY_ROOT_HASH);
+        assert_eq!(account.code_hash, KECCAK_EMPTY);
+    }
+
+    #[tokio::test]
+    async fn test_get_account_outside_proof_window() {
+        let mock_provider = MockEthProvider::default();
+        let address = Address::random();
+        mock_provider.add_account(address, ExtendedAccount::new(5, U256::from(1337)));
+        mock_provider.add_block(B256::random(), Block::default());
+        let mut tip = Block::default();
+        tip.header.number = 10;
+        mock_provider.add_block(B256::random(), tip);
+        let eth_api = mock_eth_api(mock_provider, 5);
+
+        let err = eth_api.get_account(address, BlockId::number(0)).await.unwrap_err();
+        assert!(matches!(err, EthApiError::ExceedsMaxProofWindow));
+
+        let account = eth_api.get_account(address, BlockId::number(5)).await.unwrap();
+        assert_eq!(account.nonce, 5);
+    }
 }
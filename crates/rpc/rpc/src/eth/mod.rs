@@ -12,6 +12,6 @@ pub use core::EthApi;
 pub use filter::EthFilter;
 pub use pubsub::EthPubSub;
 
-pub use helpers::signer::DevSigner;
+pub use helpers::signer::{DevSigner, RemoteSigner, RemoteSignerError};
 
 pub use reth_rpc_eth_api::{EthApiServer, RawTransactionForwarder};
@@ -0,0 +1,27 @@
+use jsonrpsee::core::RpcResult;
+use reth_exex_types::ExExStatus;
+use reth_rpc_api::ExExApiServer;
+use tokio::sync::watch;
+
+/// `reth` API implementation for the execution extensions.
+///
+/// This type provides the status of the `ExEx`'s reported by the `ExEx` manager.
+#[derive(Debug)]
+pub struct ExExApi {
+    /// The status of all `ExEx`'s.
+    status: watch::Receiver<Vec<ExExStatus>>,
+}
+
+impl ExExApi {
+    /// Creates a new instance of `ExExApi` from the status channel of the `ExEx` manager.
+    pub const fn new(status: watch::Receiver<Vec<ExExStatus>>) -> Self {
+        Self { status }
+    }
+}
+
+impl ExExApiServer for ExExApi {
+    /// Handler for `reth_exexStatus`
+    fn exex_status(&self) -> RpcResult<Vec<ExExStatus>> {
+        Ok(self.status.borrow().clone())
+    }
+}
@@ -36,17 +36,21 @@ mod admin;
 mod debug;
 mod engine;
 pub mod eth;
+mod exex;
 mod net;
 mod otterscan;
 mod reth;
 mod rpc;
+#[cfg(test)]
+mod test_utils;
 mod trace;
 mod txpool;
 mod web3;
 pub use admin::AdminApi;
-pub use debug::DebugApi;
+pub use debug::{DebugApi, DebugCompactApi};
 pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{EthApi, EthBundle, EthFilter, EthPubSub};
+pub use exex::ExExApi;
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
 pub use reth::RethApi;
@@ -1,7 +1,9 @@
 use alloy_primitives::Bytes;
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
-use reth_primitives::{Address, BlockNumberOrTag, TxHash, B256, U256};
+use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
+use reth_primitives::{Address, BlockNumber, BlockNumberOrTag, TxHash, B256, U256};
+use reth_provider::{HistoryReader, PruneCheckpointReader, StageCheckpointReader};
+use reth_prune_types::PruneSegment;
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_eth_api::helpers::TraceExt;
 use reth_rpc_eth_types::EthApiError;
@@ -14,27 +16,33 @@ use reth_rpc_types::{
         },
         parity::{Action, CreateAction, CreateOutput, TraceOutput},
     },
-    AnyTransactionReceipt, BlockTransactions, Header, RichBlock,
+    AnyTransactionReceipt, BlockTransactions, Header, RichBlock, Transaction,
 };
+use reth_stages_types::StageId;
 use revm_inspectors::{
     tracing::{types::CallTraceNode, TracingInspectorConfig},
     transfer::{TransferInspector, TransferKind},
 };
 use revm_primitives::ExecutionResult;
-use std::future::Future;
+use std::{future::Future, ops::RangeInclusive};
 
 const API_LEVEL: u64 = 8;
 
+/// The number of blocks that are looked up in the history indices at once when searching for the
+/// transactions of an address.
+const HISTORY_SEARCH_WINDOW: u64 = 100_000;
+
 /// Otterscan API.
 #[derive(Debug)]
-pub struct OtterscanApi<Eth> {
+pub struct OtterscanApi<Provider, Eth> {
+    provider: Provider,
     eth: Eth,
 }
 
-impl<Eth> OtterscanApi<Eth> {
+impl<Provider, Eth> OtterscanApi<Provider, Eth> {
     /// Creates a new instance of `Otterscan`.
-    pub const fn new(eth: Eth) -> Self {
-        Self { eth }
+    pub const fn new(provider: Provider, eth: Eth) -> Self {
+        Self { provider, eth }
     }
 
     /// Constructs a `BlockDetails` from a block and its receipts.
@@ -56,9 +64,188 @@ impl<Eth> OtterscanApi<Eth> {
     }
 }
 
+impl<Provider, Eth> OtterscanApi<Provider, Eth>
+where
+    Provider: HistoryReader + PruneCheckpointReader + StageCheckpointReader + 'static,
+    Eth: EthApiServer + TraceExt + 'static,
+{
+    /// Returns the lowest block from which on both the account and the storage history indices
+    /// are available, taking the prune configuration into account.
+    fn lowest_available_history_block(&self) -> RpcResult<BlockNumber> {
+        let mut lowest = 0;
+        for segment in [PruneSegment::AccountHistory, PruneSegment::StorageHistory] {
+            let checkpoint =
+                self.provider.get_prune_checkpoint(segment).map_err(EthApiError::from)?;
+            if let Some(block_number) = checkpoint.and_then(|checkpoint| checkpoint.block_number) {
+                lowest = lowest.max(block_number + 1);
+            }
+        }
+        Ok(lowest)
+    }
+
+    /// Returns the highest block up to which both the account and the storage history indices are
+    /// built.
+    ///
+    /// The blocks above it, e.g. the ones that are only held in memory until they are persisted,
+    /// are not indexed yet.
+    fn highest_indexed_history_block(&self) -> RpcResult<BlockNumber> {
+        let mut highest = BlockNumber::MAX;
+        for stage in [StageId::IndexAccountHistory, StageId::IndexStorageHistory] {
+            let checkpoint =
+                self.provider.get_stage_checkpoint(stage).map_err(EthApiError::from)?;
+            highest = highest.min(checkpoint.unwrap_or_default().block_number);
+        }
+        Ok(highest)
+    }
+
+    /// Returns the transactions of the given block that involve the address, together with their
+    /// receipts.
+    ///
+    /// A transaction involves the address if the address is the sender or the recipient of the
+    /// transaction, or of any internal call, create or selfdestruct of its execution.
+    async fn block_transactions_of_address(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+    ) -> RpcResult<(Vec<Transaction>, Vec<OtsTransactionReceipt>)> {
+        let touched = self
+            .eth
+            .trace_block_with(
+                block_number.into(),
+                TracingInspectorConfig::none(),
+                move |_tx_info, inspector, _, _, _| {
+                    Ok(inspector.traces().nodes().iter().any(|CallTraceNode { trace, .. }| {
+                        trace.caller == address ||
+                            trace.address == address ||
+                            trace.selfdestruct_refund_target == Some(address)
+                    }))
+                },
+            )
+            .await
+            .map_err(Into::into)?
+            .unwrap_or_default();
+
+        if !touched.contains(&true) {
+            return Ok(Default::default())
+        }
+
+        let block = self.eth.block_by_number(block_number.into(), true);
+        let receipts = self.eth.block_receipts(block_number.into());
+        let (block, receipts) = futures::try_join!(block, receipts)?;
+
+        let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+        let receipts = receipts.ok_or_else(|| internal_rpc_err("receipts not found"))?;
+        let timestamp = Some(block.header.timestamp);
+
+        let BlockTransactions::Full(transactions) = block.inner.transactions else {
+            return Err(internal_rpc_err("block is not full"));
+        };
+
+        if transactions.len() != receipts.len() || transactions.len() != touched.len() {
+            return Err(internal_rpc_err(
+                "the number of transactions does not match the number of receipts",
+            ))
+        }
+
+        Ok(transactions
+            .into_iter()
+            .zip(receipts)
+            .zip(touched)
+            .filter(|(_, touched)| *touched)
+            .map(|((tx, receipt), _)| (tx, ots_receipt(receipt, timestamp)))
+            .unzip())
+    }
+
+    /// Collects the transactions that involve the address from the blocks of the given range, in
+    /// which the account or its storage was changed according to the history indices.
+    ///
+    /// The blocks above the indexed history are all traced, so that the transactions of the blocks
+    /// that are not persisted yet are found as well. Below it, transactions that leave the state of
+    /// the address unchanged, e.g. a `STATICCALL` to it, are not found.
+    ///
+    /// Blocks are visited in descending order if `descending` is set and in ascending order
+    /// otherwise, and the transactions are returned in the order they were visited. Collection
+    /// stops once at least `page_size` transactions were found; transactions of a block are
+    /// never split across pages.
+    ///
+    /// Returns the collected transactions and whether the entire range was searched.
+    async fn search_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        descending: bool,
+        page_size: usize,
+    ) -> RpcResult<(Vec<Transaction>, Vec<OtsTransactionReceipt>, bool)> {
+        let (mut txs, mut receipts) = (Vec::new(), Vec::new());
+        if range.is_empty() {
+            return Ok((txs, receipts, true))
+        }
+
+        let (start, end) = range.into_inner();
+        let highest_indexed = self.highest_indexed_history_block()?;
+        let mut window_start = start;
+        let mut window_end = end;
+        loop {
+            if descending {
+                window_start = window_end.saturating_sub(HISTORY_SEARCH_WINDOW - 1).max(start);
+            } else {
+                window_end = window_start.saturating_add(HISTORY_SEARCH_WINDOW - 1).min(end);
+            }
+
+            let mut blocks = self
+                .provider
+                .account_history_blocks(address, window_start..=window_end)
+                .map_err(EthApiError::from)?;
+            blocks.extend(
+                self.provider
+                    .storage_history_blocks(address, window_start..=window_end)
+                    .map_err(EthApiError::from)?,
+            );
+            blocks.extend(window_start.max(highest_indexed.saturating_add(1))..=window_end);
+            blocks.sort_unstable();
+            blocks.dedup();
+            if descending {
+                blocks.reverse();
+            }
+
+            for block_number in blocks {
+                let (mut block_txs, mut block_receipts) =
+                    self.block_transactions_of_address(address, block_number).await?;
+                if descending {
+                    block_txs.reverse();
+                    block_receipts.reverse();
+                }
+                txs.extend(block_txs);
+                receipts.extend(block_receipts);
+
+                if txs.len() >= page_size {
+                    let exhausted =
+                        if descending { block_number == start } else { block_number == end };
+                    return Ok((txs, receipts, exhausted))
+                }
+            }
+
+            if descending {
+                if window_start == start {
+                    break
+                }
+                window_end = window_start - 1;
+            } else {
+                if window_end == end {
+                    break
+                }
+                window_start = window_end + 1;
+            }
+        }
+
+        Ok((txs, receipts, true))
+    }
+}
+
 #[async_trait]
-impl<Eth> OtterscanServer for OtterscanApi<Eth>
+impl<Provider, Eth> OtterscanServer for OtterscanApi<Provider, Eth>
 where
+    Provider: HistoryReader + PruneCheckpointReader + StageCheckpointReader + 'static,
     Eth: EthApiServer + TraceExt + 'static,
 {
     /// Handler for `{ots,erigon}_getHeaderByNumber`
@@ -216,22 +403,7 @@ where
         let timestamp = Some(block.header.timestamp);
         let receipts = receipts
             .drain(page_start..page_end)
-            .map(|receipt| {
-                let receipt = receipt.inner.map_inner(|receipt| OtsReceipt {
-                    status: receipt
-                        .inner
-                        .receipt
-                        .status
-                        .as_eip658()
-                        .expect("ETH API returned pre-EIP-658 status"),
-                    cumulative_gas_used: receipt.inner.receipt.cumulative_gas_used as u64,
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: receipt.r#type,
-                });
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|receipt| ots_receipt(receipt, timestamp))
             .collect();
         Ok(OtsBlockTransactions { fullblock: block.inner.into(), receipts })
     }
@@ -239,21 +411,59 @@ where
     /// Handler for `searchTransactionsBefore`
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: u64,
-        _page_size: usize,
+        address: Address,
+        block_number: u64,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        // Block number 0 requests the most recent transactions.
+        let first_page = block_number == 0;
+        let end =
+            if first_page { self.eth.block_number()?.saturating_to() } else { block_number - 1 };
+
+        // The page can't be served if it starts in pruned history. Otherwise, the last page ends
+        // at the lowest available block.
+        let lowest = self.lowest_available_history_block()?;
+        if end < lowest {
+            return Err(history_pruned_err(address, lowest))
+        }
+
+        let (txs, receipts, exhausted) =
+            self.search_transactions(address, lowest..=end, true, page_size).await?;
+
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page: exhausted })
     }
 
     /// Handler for `searchTransactionsAfter`
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: u64,
-        _page_size: usize,
+        address: Address,
+        block_number: u64,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        // Block number 0 requests the oldest transactions, which start at the lowest available
+        // block. Other pages can't be served if they start in pruned history.
+        let last_page = block_number == 0;
+        let lowest = self.lowest_available_history_block()?;
+        let start = if last_page {
+            lowest
+        } else {
+            block_number.checked_add(1).ok_or_else(|| {
+                EthApiError::InvalidParams(format!("block number {block_number} is out of range"))
+            })?
+        };
+        if start < lowest {
+            return Err(history_pruned_err(address, lowest))
+        }
+
+        let end = self.eth.block_number()?.saturating_to();
+        let (mut txs, mut receipts, exhausted) =
+            self.search_transactions(address, start..=end, false, page_size).await?;
+
+        // Results are always returned from the most recent to the oldest transaction.
+        txs.reverse();
+        receipts.reverse();
+
+        Ok(TransactionsWithReceipts { txs, receipts, first_page: exhausted, last_page })
     }
 
     /// Handler for `getTransactionBySenderAndNonce`
@@ -364,6 +574,31 @@ where
     }
 }
 
+/// Converts a receipt into an [`OtsTransactionReceipt`], which omits the logs of the receipt.
+fn ots_receipt(receipt: AnyTransactionReceipt, timestamp: Option<u64>) -> OtsTransactionReceipt {
+    let receipt = receipt.inner.map_inner(|receipt| OtsReceipt {
+        status: receipt
+            .inner
+            .receipt
+            .status
+            .as_eip658()
+            .expect("ETH API returned pre-EIP-658 status"),
+        cumulative_gas_used: receipt.inner.receipt.cumulative_gas_used as u64,
+        logs: None,
+        logs_bloom: None,
+        r#type: receipt.r#type,
+    });
+
+    OtsTransactionReceipt { receipt, timestamp }
+}
+
+/// Returns the error for a search that requires account history which has been pruned.
+fn history_pruned_err(address: Address, lowest_available_block: BlockNumber) -> ErrorObjectOwned {
+    internal_rpc_err(format!(
+        "transaction history of {address} is pruned, it is only available from block #{lowest_available_block}"
+    ))
+}
+
 /// Performs a binary search within a given block range to find the desired block number.
 ///
 /// The binary search is performed by calling the provided asynchronous `check` closure on the
@@ -403,6 +638,151 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::TestChain;
+    use reth_primitives::{constants::ETHEREUM_BLOCK_GAS_LIMIT, Block, GenesisAccount, Header};
+    use reth_provider::{PruneCheckpointWriter, StageCheckpointWriter};
+    use reth_prune_types::{PruneCheckpoint, PruneMode};
+    use reth_rpc_server_types::constants::DEFAULT_ETH_PROOF_WINDOW;
+    use reth_stages_types::StageCheckpoint;
+
+    /// The address of a contract that stores the block number in its first slot when called.
+    const CONTRACT: Address = Address::with_last_byte(0xc0);
+
+    /// The number of blocks of the test chain, each of them calling [`CONTRACT`] once.
+    const BLOCKS: u64 = 5;
+
+    /// Creates a chain with [`BLOCKS`] blocks, in which only the storage of [`CONTRACT`] changes.
+    /// The last `in_memory` blocks are not persisted.
+    fn test_chain(in_memory: u64) -> TestChain {
+        let contract = GenesisAccount {
+            // NUMBER PUSH1 0 SSTORE STOP
+            code: Some(Bytes::from_static(&[0x43, 0x60, 0x00, 0x55, 0x00])),
+            ..Default::default()
+        };
+        let mut chain = TestChain::new([(CONTRACT, contract)], |spec| spec.london_activated());
+        for number in 1..=BLOCKS {
+            let body = vec![chain.transaction(CONTRACT, U256::ZERO, 100_000)];
+            let block = Block {
+                header: Header {
+                    timestamp: number * 12,
+                    gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
+                    base_fee_per_gas: Some(7),
+                    ..Default::default()
+                },
+                body,
+                ..Default::default()
+            };
+            if number + in_memory > BLOCKS {
+                chain.append_block_in_memory(block);
+            } else {
+                chain.append_block(block);
+            }
+        }
+        chain
+    }
+
+    /// Creates an Otterscan API on top of the chain.
+    fn otterscan_api(
+        chain: &TestChain,
+    ) -> OtterscanApi<
+        impl HistoryReader + PruneCheckpointReader + StageCheckpointReader,
+        impl EthApiServer + TraceExt,
+    > {
+        let (provider, eth_api) = chain.eth_api(DEFAULT_ETH_PROOF_WINDOW);
+        OtterscanApi::new(provider, eth_api)
+    }
+
+    /// Returns the block numbers of the transactions of the page.
+    fn page_blocks(page: &TransactionsWithReceipts) -> Vec<u64> {
+        page.txs.iter().map(|tx| tx.block_number.unwrap()).collect()
+    }
+
+    #[tokio::test]
+    async fn search_transactions_pages() {
+        let api = otterscan_api(&test_chain(0));
+
+        // the contract's account never changes, only its storage does
+        let page = api.search_transactions_before(CONTRACT, 0, 2).await.unwrap();
+        assert_eq!(page_blocks(&page), vec![5, 4]);
+        assert!(page.first_page && !page.last_page);
+        let page = api.search_transactions_before(CONTRACT, 4, 2).await.unwrap();
+        assert_eq!(page_blocks(&page), vec![3, 2]);
+        assert!(!page.first_page && !page.last_page);
+        let page = api.search_transactions_before(CONTRACT, 2, 2).await.unwrap();
+        assert_eq!(page_blocks(&page), vec![1]);
+        assert!(!page.first_page && page.last_page);
+
+        let page = api.search_transactions_after(CONTRACT, 0, 2).await.unwrap();
+        assert_eq!(page_blocks(&page), vec![2, 1]);
+        assert!(!page.first_page && page.last_page);
+        let page = api.search_transactions_after(CONTRACT, 2, 2).await.unwrap();
+        assert_eq!(page_blocks(&page), vec![4, 3]);
+        assert!(!page.first_page && !page.last_page);
+        let page = api.search_transactions_after(CONTRACT, 4, 2).await.unwrap();
+        assert_eq!(page_blocks(&page), vec![5]);
+        assert!(page.first_page && !page.last_page);
+        let err = api.search_transactions_after(CONTRACT, u64::MAX, 2).await.unwrap_err();
+        assert_eq!(err.code(), jsonrpsee::types::error::INVALID_PARAMS_CODE);
+
+        // an address without any transactions has a single empty page
+        let page = api.search_transactions_before(Address::with_last_byte(1), 0, 2).await.unwrap();
+        assert!(page.txs.is_empty());
+        assert!(page.first_page && page.last_page);
+    }
+
+    #[tokio::test]
+    async fn search_transactions_pruned_history() {
+        let chain = test_chain(0);
+        let provider_rw = chain.factory.provider_rw().unwrap();
+        provider_rw
+            .save_prune_checkpoint(
+                PruneSegment::AccountHistory,
+                PruneCheckpoint {
+                    block_number: Some(2),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(3),
+                },
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+        let api = otterscan_api(&chain);
+
+        // pages above the pruned history are served, the last one ends at the lowest block
+        let page = api.search_transactions_before(CONTRACT, 0, 10).await.unwrap();
+        assert_eq!(page_blocks(&page), vec![5, 4, 3]);
+        assert!(page.first_page && page.last_page);
+        let page = api.search_transactions_after(CONTRACT, 3, 10).await.unwrap();
+        assert_eq!(page_blocks(&page), vec![5, 4]);
+        let page = api.search_transactions_before(Address::with_last_byte(1), 0, 2).await.unwrap();
+        assert!(page.txs.is_empty());
+
+        // the oldest page starts at the lowest block
+        let page = api.search_transactions_after(CONTRACT, 0, 2).await.unwrap();
+        assert_eq!(page_blocks(&page), vec![4, 3]);
+        assert!(!page.first_page && page.last_page);
+
+        // other pages that start in the pruned history are rejected
+        assert!(api.search_transactions_before(CONTRACT, 3, 10).await.is_err());
+        assert!(api.search_transactions_after(CONTRACT, 1, 10).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn search_transactions_above_indexed_history() {
+        // the history of the last two blocks isn't indexed, since they are only held in memory
+        let chain = test_chain(2);
+        let provider_rw = chain.factory.provider_rw().unwrap();
+        for stage in [StageId::IndexAccountHistory, StageId::IndexStorageHistory] {
+            provider_rw.save_stage_checkpoint(stage, StageCheckpoint::new(3)).unwrap();
+        }
+        provider_rw.commit().unwrap();
+        let api = otterscan_api(&chain);
+
+        // the blocks above the indexed history are traced
+        let page = api.search_transactions_before(CONTRACT, 0, 10).await.unwrap();
+        assert_eq!(page_blocks(&page), vec![5, 4, 3, 2, 1]);
+        let page = api.search_transactions_after(CONTRACT, 2, 10).await.unwrap();
+        assert_eq!(page_blocks(&page), vec![5, 4, 3]);
+    }
 
     #[tokio::test]
     async fn test_binary_search() {
@@ -3,13 +3,18 @@ use std::{collections::HashMap, future::Future, sync::Arc};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_errors::RethResult;
-use reth_primitives::{Address, BlockId, U256};
-use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, B256, U256};
+use reth_provider::{
+    AddressTransactionsReader, BlockReaderIdExt, ChangeSetReader, StateProviderFactory,
+};
 use reth_rpc_api::RethApiServer;
 use reth_rpc_eth_types::{EthApiError, EthResult};
 use reth_tasks::TaskSpawner;
 use tokio::sync::oneshot;
 
+/// The maximum number of transactions returned by `reth_getTransactionsByAddress`.
+const MAX_TRANSACTIONS_BY_ADDRESS: usize = 10_000;
+
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
@@ -34,7 +39,11 @@ impl<Provider> RethApi<Provider> {
 
 impl<Provider> RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + AddressTransactionsReader
+        + StateProviderFactory
+        + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -82,12 +91,86 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the hashes of the transactions of the address in the block range.
+    pub async fn transactions_by_address(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> EthResult<Vec<B256>> {
+        self.on_blocking_task(|this| async move {
+            this.try_transactions_by_address(address, from_block, to_block)
+        })
+        .await
+    }
+
+    fn try_transactions_by_address(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> EthResult<Vec<B256>> {
+        let Some(from_block) = self.provider().convert_block_number(from_block)? else {
+            return Err(EthApiError::UnknownBlockNumber)
+        };
+        let Some(to_block) = self.provider().convert_block_number(to_block)? else {
+            return Err(EthApiError::UnknownBlockNumber)
+        };
+        if from_block > to_block {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+
+        let Some(index_range) = self.provider().address_transactions_range()? else {
+            return Err(EthApiError::Unsupported("address transactions index is not enabled"))
+        };
+        if !index_range.contains(&from_block) || !index_range.contains(&to_block) {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range {from_block}..={to_block} is not covered by the address transactions index {index_range:?}"
+            )))
+        }
+
+        let Some(from_indices) = self.provider().block_body_indices(from_block)? else {
+            return Err(EthApiError::UnknownBlockNumber)
+        };
+        let Some(to_indices) = self.provider().block_body_indices(to_block)? else {
+            return Err(EthApiError::UnknownBlockNumber)
+        };
+        if to_indices.next_tx_num() == from_indices.first_tx_num() {
+            return Ok(Vec::new())
+        }
+
+        let tx_numbers = self.provider().address_transactions(
+            address,
+            from_indices.first_tx_num()..=to_indices.next_tx_num() - 1,
+        )?;
+        if tx_numbers.len() > MAX_TRANSACTIONS_BY_ADDRESS {
+            return Err(EthApiError::InvalidParams(format!(
+                "query exceeds max results {MAX_TRANSACTIONS_BY_ADDRESS}, retry with a smaller block range"
+            )))
+        }
+
+        tx_numbers
+            .into_iter()
+            .map(|tx_number| {
+                let transaction = self
+                    .provider()
+                    .transaction_by_id(tx_number)?
+                    .ok_or(EthApiError::TransactionNotFound)?;
+                Ok(transaction.hash())
+            })
+            .collect()
+    }
 }
 
 #[async_trait]
 impl<Provider> RethApiServer for RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + AddressTransactionsReader
+        + StateProviderFactory
+        + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
@@ -96,6 +179,16 @@ where
     ) -> RpcResult<HashMap<Address, U256>> {
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_getTransactionsByAddress`
+    async fn reth_get_transactions_by_address(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<Vec<B256>> {
+        Ok(Self::transactions_by_address(self, address, from_block, to_block).await?)
+    }
 }
 
 impl<Provider> std::fmt::Debug for RethApi<Provider> {
@@ -0,0 +1,203 @@
+//! Chain fixture shared by the tests of the RPC handlers.
+
+use crate::EthApi;
+use reth_chain_state::{ExecutedBlock, MemoryOverlayStateProvider, NewCanonicalChain};
+use reth_chainspec::{ChainSpec, ChainSpecBuilder, MAINNET};
+use reth_db::{test_utils::TempDatabase, DatabaseEnv};
+use reth_db_common::init::init_genesis;
+use reth_evm::execute::{BlockExecutionInput, BlockExecutorProvider, Executor};
+use reth_evm_ethereum::{execute::EthExecutorProvider, EthEvmConfig};
+use reth_network_api::noop::NoopNetwork;
+use reth_primitives::{
+    constants::{ETHEREUM_BLOCK_GAS_LIMIT, ETH_TO_WEI},
+    public_key_to_address, Address, Block, Genesis, GenesisAccount, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, Transaction, TransactionSigned, TxEip1559, TxKind, U256,
+};
+use reth_provider::{
+    providers::BlockchainProvider2, test_utils::create_test_provider_factory_with_chain_spec,
+    BlockWriter, ExecutionOutcome, ProviderFactory, StateRootProvider,
+};
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle};
+use reth_rpc_server_types::constants::DEFAULT_PROOF_PERMITS;
+use reth_tasks::pool::BlockingTaskPool;
+use reth_testing_utils::generators::{self, sign_tx_with_key_pair};
+use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+use reth_trie::HashedPostState;
+use secp256k1::Keypair;
+use std::sync::Arc;
+
+/// The database of a [`TestChain`].
+pub(crate) type TestDb = Arc<TempDatabase<DatabaseEnv>>;
+
+/// The `eth` API on top of a [`TestChain`].
+pub(crate) type TestEthApi =
+    EthApi<BlockchainProvider2<TestDb>, TestPool, NoopNetwork, EthEvmConfig>;
+
+/// A chain of executed blocks, whose genesis funds the signer of its transactions with 1 ETH.
+///
+/// Blocks are persisted to the database, or held in memory on top of it like the blocks of the
+/// engine that are not persisted yet.
+pub(crate) struct TestChain {
+    pub(crate) chain_spec: Arc<ChainSpec>,
+    pub(crate) factory: ProviderFactory<TestDb>,
+    key_pair: Keypair,
+    nonce: u64,
+    tip: SealedHeader,
+    /// Blocks above the database tip, in ascending order.
+    in_memory: Vec<ExecutedBlock>,
+}
+
+impl TestChain {
+    /// Initializes a chain from the genesis with the given accounts, and the forks activated by
+    /// `forks` on top of a mainnet chain spec.
+    pub(crate) fn new(
+        alloc: impl IntoIterator<Item = (Address, GenesisAccount)>,
+        forks: impl FnOnce(ChainSpecBuilder) -> ChainSpecBuilder,
+    ) -> Self {
+        let key_pair = generators::generate_keys(&mut generators::rng(), 1)[0];
+        let sender = public_key_to_address(key_pair.public_key());
+        let genesis = Genesis {
+            alloc: alloc
+                .into_iter()
+                .chain([(
+                    sender,
+                    GenesisAccount { balance: U256::from(ETH_TO_WEI), ..Default::default() },
+                )])
+                .collect(),
+            gas_limit: ETHEREUM_BLOCK_GAS_LIMIT.into(),
+            ..MAINNET.genesis.clone()
+        };
+        let chain_spec = Arc::new(
+            forks(ChainSpecBuilder::default().chain(MAINNET.chain).genesis(genesis)).build(),
+        );
+
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        init_genesis(factory.clone()).unwrap();
+
+        let tip = chain_spec.sealed_genesis_header();
+        Self { chain_spec, factory, key_pair, nonce: 0, tip, in_memory: Vec::new() }
+    }
+
+    /// Signs an EIP-1559 transaction of the sender with the next nonce.
+    pub(crate) fn transaction(
+        &mut self,
+        to: Address,
+        value: U256,
+        gas_limit: u64,
+    ) -> TransactionSigned {
+        let tx = Transaction::Eip1559(TxEip1559 {
+            chain_id: self.chain_spec.chain.id(),
+            nonce: self.nonce,
+            gas_limit,
+            max_fee_per_gas: 1_000,
+            to: TxKind::Call(to),
+            value,
+            ..Default::default()
+        });
+        self.nonce += 1;
+        sign_tx_with_key_pair(self.key_pair, tx)
+    }
+
+    /// Executes the block on top of the tip, and persists it with its state.
+    ///
+    /// The parent hash, number, gas used and state root of the block are filled in, the other
+    /// header fields are taken as they are.
+    pub(crate) fn append_block(&mut self, block: Block) -> SealedBlock {
+        assert!(self.in_memory.is_empty(), "blocks can't be persisted above in-memory blocks");
+        let executed = self.execute(block);
+
+        let provider_rw = self.factory.provider_rw().unwrap();
+        provider_rw
+            .append_blocks_with_state(
+                vec![SealedBlockWithSenders::new(
+                    executed.block().clone(),
+                    executed.senders().clone(),
+                )
+                .unwrap()],
+                executed.execution_outcome().clone(),
+                executed.hashed_state().clone().into_sorted(),
+                executed.trie_updates().clone(),
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        executed.block().clone()
+    }
+
+    /// Executes the block on top of the tip like [`Self::append_block`], but only holds it in
+    /// memory.
+    pub(crate) fn append_block_in_memory(&mut self, block: Block) -> SealedBlock {
+        let executed = self.execute(block);
+        self.in_memory.push(executed.clone());
+        executed.block().clone()
+    }
+
+    /// Executes the block on top of the tip, fills in its header and makes it the new tip.
+    fn execute(&mut self, mut block: Block) -> ExecutedBlock {
+        block.header.parent_hash = self.tip.hash();
+        block.header.number = self.tip.number + 1;
+        let mut block = block.with_recovered_senders().unwrap();
+
+        let state = MemoryOverlayStateProvider::new(
+            self.in_memory.iter().rev().cloned().collect(),
+            self.factory.latest().unwrap(),
+        );
+        let output = EthExecutorProvider::ethereum(self.chain_spec.clone())
+            .executor(StateProviderDatabase::new(&state))
+            .execute(BlockExecutionInput { block: &block, total_difficulty: U256::ZERO })
+            .unwrap();
+        let hashed_state = HashedPostState::from_bundle_state(&output.state.state);
+        let (state_root, trie_updates) =
+            state.hashed_state_root_with_updates(hashed_state.clone()).unwrap();
+        block.block.header.state_root = state_root;
+        block.block.header.gas_used = output.gas_used;
+
+        let senders = block.senders.clone();
+        let block = block.block.seal_slow();
+        self.tip = block.header.clone();
+        ExecutedBlock::new(
+            Arc::new(block.clone()),
+            Arc::new(senders),
+            Arc::new(ExecutionOutcome {
+                bundle: output.state,
+                receipts: output.receipts.into(),
+                first_block: block.number,
+                requests: vec![Default::default()],
+            }),
+            Arc::new(hashed_state),
+            Arc::new(trie_updates),
+        )
+    }
+
+    /// Returns a provider of the chain and an `eth` API on top of it, serving proofs and state
+    /// roots up to `max_proof_window` blocks behind the tip.
+    pub(crate) fn eth_api(
+        &self,
+        max_proof_window: u64,
+    ) -> (BlockchainProvider2<TestDb>, TestEthApi) {
+        let provider = BlockchainProvider2::new(self.factory.clone()).unwrap();
+        if !self.in_memory.is_empty() {
+            let in_memory_state = provider.canonical_in_memory_state();
+            in_memory_state.update_chain(NewCanonicalChain::Commit { new: self.in_memory.clone() });
+            in_memory_state.set_canonical_head(self.tip.clone());
+        }
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config);
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider.clone(), Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            max_proof_window,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            None,
+            DEFAULT_PROOF_PERMITS,
+        );
+        (provider, eth_api)
+    }
+}
@@ -0,0 +1,643 @@
+use super::{flush_index_cache, load_history_indices, push_index, DEFAULT_CACHE_THRESHOLD};
+use reth_chainspec::{ChainSpec, EthereumHardforks};
+use reth_config::config::{EtlConfig, IndexAddressTransactionsConfig};
+use reth_db::{tables, BlockNumberList};
+use reth_db_api::{
+    cursor::DbDupCursorRW, database::Database, models::ShardedKey, table::Decode,
+    transaction::DbTxMut,
+};
+use reth_etl::Collector;
+use reth_evm::{
+    execute::{BlockExecutionError, BlockValidationError},
+    system_calls::apply_beacon_root_contract_call,
+    ConfigureEvm,
+};
+use reth_primitives::{Address, BlockNumber, BlockWithSenders, U256};
+use reth_provider::{
+    providers::transaction_addresses, BlockReader, DatabaseProviderRW, HeaderProvider,
+    HistoricalStateProviderRef, HistoryWriter, LowestAvailableBlocks, PruneCheckpointReader,
+    PruneCheckpointWriter, StateProvider, TransactionVariant,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
+use reth_revm::{
+    database::StateProviderDatabase,
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
+    primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg},
+    state_change::apply_blockhashes_update,
+    Database as EvmDatabase, DatabaseCommit, EvmContext, Inspector, State,
+};
+use reth_stages_api::{
+    BlockErrorKind, ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId,
+    UnwindInput, UnwindOutput,
+};
+use reth_storage_errors::provider::ProviderError;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    mem,
+    ops::RangeInclusive,
+    sync::Arc,
+};
+use tracing::info;
+
+/// Stage is indexing the transactions of each address, so that `reth_getTransactionsByAddress`
+/// can look up the transactions an address was involved in without scanning the chain.
+///
+/// Every transaction is indexed by its sender and its recipient, or the created contract. If
+/// [`IndexAddressTransactionsConfig::internal_calls`] is enabled, the blocks are re-executed with
+/// an [`InternalCallTracer`] and the addresses called, created or self-destructed by internal calls
+/// are indexed as well.
+///
+/// The transaction numbers are written to [`tables::AddressTransactions`], sharded like the
+/// account history, and the addresses of each transaction to [`tables::TransactionAddresses`], so
+/// that the index can be unwound and pruned without re-executing the blocks.
+///
+/// The stage is optional and only added to the pipeline if enabled in
+/// [`IndexAddressTransactionsConfig`].
+#[derive(Debug)]
+pub struct IndexAddressTransactionsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Whether to index the addresses touched by internal calls.
+    pub internal_calls: bool,
+    /// Pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+    /// Tracer of the internal calls, required if `internal_calls` is enabled.
+    internal_call_tracer: Option<Box<dyn InternalCallTracer>>,
+}
+
+impl IndexAddressTransactionsStage {
+    /// Create new instance of [`IndexAddressTransactionsStage`].
+    pub const fn new(
+        config: IndexAddressTransactionsConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self {
+            commit_threshold: config.commit_threshold,
+            internal_calls: config.internal_calls,
+            prune_mode,
+            etl_config,
+            internal_call_tracer: None,
+        }
+    }
+
+    /// Sets the tracer used to collect the addresses touched by internal calls.
+    pub fn with_internal_call_tracer(mut self, tracer: impl InternalCallTracer + 'static) -> Self {
+        self.internal_call_tracer = Some(Box::new(tracer));
+        self
+    }
+}
+
+impl Default for IndexAddressTransactionsStage {
+    fn default() -> Self {
+        Self {
+            commit_threshold: 100_000,
+            internal_calls: false,
+            prune_mode: None,
+            etl_config: EtlConfig::default(),
+            internal_call_tracer: None,
+        }
+    }
+}
+
+impl<DB: Database> Stage<DB> for IndexAddressTransactionsStage {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexAddressTransactions
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if let Some((target_prunable_block, prune_mode)) = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(
+                    input.target(),
+                    PruneSegment::AddressTransactions,
+                    PrunePurpose::User,
+                )
+            })
+            .transpose()?
+            .flatten()
+        {
+            if target_prunable_block > input.checkpoint().block_number {
+                input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+
+                // Save prune checkpoint only if we don't have one already.
+                // Otherwise, pruner may skip the unpruned range of blocks.
+                if provider.get_prune_checkpoint(PruneSegment::AddressTransactions)?.is_none() {
+                    let target_prunable_tx_number = provider
+                        .block_body_indices(target_prunable_block)?
+                        .ok_or(ProviderError::BlockBodyIndicesNotFound(target_prunable_block))?
+                        .last_tx_num();
+
+                    provider.save_prune_checkpoint(
+                        PruneSegment::AddressTransactions,
+                        PruneCheckpoint {
+                            block_number: Some(target_prunable_block),
+                            tx_number: Some(target_prunable_tx_number),
+                            prune_mode,
+                        },
+                    )?;
+                }
+            }
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let tracer = if self.internal_calls {
+            Some(self.internal_call_tracer.as_deref().ok_or_else(|| {
+                StageError::Fatal("indexing internal calls requires an internal call tracer".into())
+            })?)
+        } else {
+            None
+        };
+
+        let (mut range, is_final_range) =
+            input.next_block_range_with_threshold(self.commit_threshold);
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync we might have transactions coming from genesis. We clear the tables since
+        // it's faster to rebuild from scratch.
+        if first_sync {
+            provider.tx_ref().clear::<tables::AddressTransactions>()?;
+            provider.tx_ref().clear::<tables::TransactionAddresses>()?;
+            range = 0..=*range.end();
+        }
+
+        info!(target: "sync::stages::index_address_transactions::exec", ?first_sync, ?range, internal_calls = self.internal_calls, "Collecting indices");
+        let collector =
+            collect_address_transactions(provider, range.clone(), &self.etl_config, tracer)?;
+
+        info!(target: "sync::stages::index_address_transactions::exec", "Loading indices into database");
+        load_history_indices::<_, tables::AddressTransactions, _>(
+            provider,
+            collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_address_transactions(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+/// Collects the addresses of the transactions in the block range, writes them to
+/// [`tables::TransactionAddresses`] and stores the transaction numbers of each address in a
+/// [`Collector`].
+///
+/// Works like [`collect_history_indices`](super::collect_history_indices), but walks the block
+/// transactions instead of a changeset table.
+fn collect_address_transactions<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    range: RangeInclusive<BlockNumber>,
+    etl_config: &EtlConfig,
+    tracer: Option<&dyn InternalCallTracer>,
+) -> Result<Collector<ShardedKey<Address>, BlockNumberList>, StageError> {
+    let mut collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut cache = HashMap::<Address, Vec<u64>>::new();
+
+    // Tracing the internal calls of blocks with pruned account or storage history fails, the
+    // historical state is not available for them.
+    let lowest_available_blocks = LowestAvailableBlocks {
+        account_history_block_number: provider
+            .get_prune_checkpoint(PruneSegment::AccountHistory)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map(|block_number| block_number + 1),
+        storage_history_block_number: provider
+            .get_prune_checkpoint(PruneSegment::StorageHistory)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map(|block_number| block_number + 1),
+    };
+
+    // observability
+    let total_blocks = range.end() - range.start() + 1;
+    let interval = (total_blocks / 100).max(1);
+
+    let mut transaction_addresses_cursor =
+        provider.tx_ref().cursor_dup_write::<tables::TransactionAddresses>()?;
+    for (idx, block_number) in range.enumerate() {
+        let body_indices = provider
+            .block_body_indices(block_number)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?;
+        let block = provider
+            .block_with_senders(block_number.into(), TransactionVariant::NoHash)?
+            .ok_or(ProviderError::HeaderNotFound(block_number.into()))?;
+
+        let mut internal_calls = match tracer {
+            Some(tracer) => {
+                let state = HistoricalStateProviderRef::new_with_lowest_available_blocks(
+                    provider.tx_ref(),
+                    block_number,
+                    lowest_available_blocks,
+                    provider.static_file_provider().clone(),
+                );
+                let total_difficulty = provider
+                    .header_td_by_number(block_number)?
+                    .ok_or(ProviderError::TotalDifficultyNotFound(block_number))?;
+                tracer.trace_block(&state, &block, total_difficulty).map_err(|error| {
+                    StageError::Block {
+                        block: Box::new(block.header.clone().seal_slow()),
+                        error: BlockErrorKind::Execution(error),
+                    }
+                })?
+            }
+            None => Vec::new(),
+        }
+        .into_iter();
+
+        for ((sender, transaction), tx_num) in
+            block.transactions_with_sender().zip(body_indices.tx_num_range())
+        {
+            let mut addresses = internal_calls.next().unwrap_or_default();
+            addresses.extend(transaction_addresses(*sender, transaction));
+
+            let mut addresses = addresses.into_iter().collect::<Vec<_>>();
+            addresses.sort_unstable();
+            for address in addresses {
+                transaction_addresses_cursor.append_dup(tx_num, address)?;
+                push_index(&mut cache, address, tx_num);
+            }
+        }
+
+        let idx = idx as u64;
+        if idx > 0 && idx % interval == 0 && total_blocks > 100 {
+            info!(target: "sync::stages::index_address_transactions", progress = %format!("{:.2}%", (idx as f64 / total_blocks as f64) * 100.0), "Collecting indices");
+        }
+
+        if (idx + 1) % DEFAULT_CACHE_THRESHOLD == 0 {
+            flush_index_cache(&mut cache, &mut collector, ShardedKey::new)?;
+        }
+    }
+    flush_index_cache(&mut cache, &mut collector, ShardedKey::new)?;
+
+    Ok(collector)
+}
+
+/// Traces the internal calls of a block for the [`IndexAddressTransactionsStage`].
+pub trait InternalCallTracer: Debug + Send + Sync {
+    /// Executes the block on top of the given state, which is the state before the block, and
+    /// returns the addresses touched by the internal calls of each transaction.
+    fn trace_block(
+        &self,
+        state: &dyn StateProvider,
+        block: &BlockWithSenders,
+        total_difficulty: U256,
+    ) -> Result<Vec<HashSet<Address>>, BlockExecutionError>;
+}
+
+impl<T: InternalCallTracer + ?Sized> InternalCallTracer for Box<T> {
+    fn trace_block(
+        &self,
+        state: &dyn StateProvider,
+        block: &BlockWithSenders,
+        total_difficulty: U256,
+    ) -> Result<Vec<HashSet<Address>>, BlockExecutionError> {
+        (**self).trace_block(state, block, total_difficulty)
+    }
+}
+
+/// [`InternalCallTracer`] executing the blocks with the configured EVM.
+#[derive(Clone)]
+pub struct EvmInternalCallTracer<EvmConfig> {
+    evm_config: EvmConfig,
+    chain_spec: Arc<ChainSpec>,
+}
+
+impl<EvmConfig> EvmInternalCallTracer<EvmConfig> {
+    /// Create new instance of [`EvmInternalCallTracer`].
+    pub const fn new(evm_config: EvmConfig, chain_spec: Arc<ChainSpec>) -> Self {
+        Self { evm_config, chain_spec }
+    }
+}
+
+impl<EvmConfig> Debug for EvmInternalCallTracer<EvmConfig> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EvmInternalCallTracer").field("chain_spec", &self.chain_spec).finish()
+    }
+}
+
+impl<EvmConfig> InternalCallTracer for EvmInternalCallTracer<EvmConfig>
+where
+    EvmConfig: ConfigureEvm,
+{
+    fn trace_block(
+        &self,
+        state: &dyn StateProvider,
+        block: &BlockWithSenders,
+        total_difficulty: U256,
+    ) -> Result<Vec<HashSet<Address>>, BlockExecutionError> {
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(state))
+            .without_state_clear()
+            .build();
+        db.set_state_clear_flag(self.chain_spec.is_spurious_dragon_active_at_block(block.number));
+
+        let mut cfg = CfgEnvWithHandlerCfg::new(Default::default(), Default::default());
+        let mut block_env = BlockEnv::default();
+        self.evm_config.fill_cfg_and_block_env(
+            &mut cfg,
+            &mut block_env,
+            &self.chain_spec,
+            &block.header,
+            total_difficulty,
+        );
+        let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, Default::default());
+        let mut evm = self.evm_config.evm_with_env_and_inspector(
+            &mut db,
+            env,
+            InternalCallInspector::default(),
+        );
+
+        apply_beacon_root_contract_call(
+            &self.evm_config,
+            &self.chain_spec,
+            block.timestamp,
+            block.number,
+            block.parent_beacon_block_root,
+            &mut evm,
+        )?;
+        apply_blockhashes_update(
+            evm.db_mut(),
+            &self.chain_spec,
+            block.timestamp,
+            block.number,
+            block.parent_hash,
+        )?;
+        // System calls are not part of any transaction.
+        evm.context.external.addresses.clear();
+
+        let mut addresses = Vec::with_capacity(block.body.len());
+        for (sender, transaction) in block.transactions_with_sender() {
+            self.evm_config.fill_tx_env(evm.tx_mut(), transaction, *sender);
+            let result = evm.transact().map_err(|error| BlockValidationError::EVM {
+                hash: transaction.recalculate_hash(),
+                error: Box::new(error),
+            })?;
+            evm.db_mut().commit(result.state);
+
+            addresses.push(mem::take(&mut evm.context.external.addresses));
+        }
+
+        Ok(addresses)
+    }
+}
+
+/// [`Inspector`] recording the addresses called, created and self-destructed during execution.
+#[derive(Debug, Default)]
+struct InternalCallInspector {
+    addresses: HashSet<Address>,
+}
+
+impl<DB: EvmDatabase> Inspector<DB> for InternalCallInspector {
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.addresses.insert(inputs.target_address);
+        self.addresses.insert(inputs.bytecode_address);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        if let Some(address) = outcome.address {
+            self.addresses.insert(address);
+        }
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, _value: U256) {
+        self.addresses.insert(contract);
+        self.addresses.insert(target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::{
+        address, hex_literal::hex, keccak256, Account, Block, Bytecode, Header, SealedBlock,
+        Signature, Transaction, TransactionSigned, TxKind, TxLegacy, TxNumber, B256,
+    };
+    use reth_provider::LatestStateProviderRef;
+    use reth_testing_utils::generators::{self, random_block_range};
+    use std::collections::BTreeMap;
+
+    fn run(db: &TestStageDB, run_to: u64, input_checkpoint: Option<BlockNumber>) {
+        let input = ExecInput {
+            target: Some(run_to),
+            checkpoint: input_checkpoint.map(StageCheckpoint::new),
+        };
+        let mut stage = IndexAddressTransactionsStage::default();
+        let provider = db.factory.provider_rw().unwrap();
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(run_to), done: true });
+        provider.commit().unwrap();
+    }
+
+    fn unwind(db: &TestStageDB, unwind_from: u64, unwind_to: u64) {
+        let input = UnwindInput {
+            checkpoint: StageCheckpoint::new(unwind_from),
+            unwind_to,
+            ..Default::default()
+        };
+        let mut stage = IndexAddressTransactionsStage::default();
+        let provider = db.factory.provider_rw().unwrap();
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(unwind_to) });
+        provider.commit().unwrap();
+    }
+
+    /// Returns the expected addresses of the transactions in the blocks up to the given block.
+    fn expected_addresses(
+        blocks: &[SealedBlock],
+        to_block: BlockNumber,
+    ) -> BTreeMap<TxNumber, Vec<Address>> {
+        blocks
+            .iter()
+            .flat_map(|block| block.body.iter().map(move |transaction| (block.number, transaction)))
+            .enumerate()
+            .filter(|(_, (number, _))| *number <= to_block)
+            .map(|(tx_num, (_, transaction))| {
+                let sender = transaction.recover_signer().unwrap();
+                let mut addresses = transaction_addresses(sender, transaction).to_vec();
+                addresses.sort_unstable();
+                addresses.dedup();
+                (tx_num as TxNumber, addresses)
+            })
+            .collect()
+    }
+
+    fn assert_tables(db: &TestStageDB, blocks: &[SealedBlock], to_block: BlockNumber) {
+        let addresses = expected_addresses(blocks, to_block);
+        let mut index = BTreeMap::<Address, Vec<TxNumber>>::new();
+        for (tx_num, addresses) in &addresses {
+            for address in addresses {
+                index.entry(*address).or_default().push(*tx_num);
+            }
+        }
+
+        let mut actual_addresses = BTreeMap::<TxNumber, Vec<Address>>::new();
+        for (tx_num, address) in db.table::<tables::TransactionAddresses>().unwrap() {
+            actual_addresses.entry(tx_num).or_default().push(address);
+        }
+        assert_eq!(actual_addresses, addresses);
+
+        let actual_index = db
+            .table::<tables::AddressTransactions>()
+            .unwrap()
+            .into_iter()
+            .map(|(key, list)| {
+                assert_eq!(key.highest_block_number, u64::MAX);
+                (key.key, list.iter().collect::<Vec<_>>())
+            })
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(actual_index, index);
+    }
+
+    #[test]
+    fn index_and_unwind_address_transactions() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=10, B256::ZERO, 0..3);
+        db.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+        db.insert_transaction_senders(
+            blocks.iter().flat_map(|block| block.body.iter()).enumerate().map(
+                |(tx_num, transaction)| (tx_num as TxNumber, transaction.recover_signer().unwrap()),
+            ),
+        )
+        .unwrap();
+
+        // index the first blocks, then the rest on top of the existing shards
+        run(&db, 5, None);
+        assert_tables(&db, &blocks, 5);
+
+        run(&db, 10, Some(5));
+        assert!(!expected_addresses(&blocks, 10).is_empty());
+        assert_tables(&db, &blocks, 10);
+
+        // unwinding removes the transactions above the unwind target
+        unwind(&db, 10, 3);
+        assert_tables(&db, &blocks, 3);
+    }
+
+    #[test]
+    fn internal_calls_require_tracer() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=2, B256::ZERO, 0..3);
+        db.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+
+        let mut stage = IndexAddressTransactionsStage {
+            internal_calls: true,
+            ..IndexAddressTransactionsStage::default()
+        };
+        let provider = db.factory.provider_rw().unwrap();
+        let result = stage.execute(&provider, ExecInput { target: Some(2), checkpoint: None });
+        assert!(matches!(result, Err(StageError::Fatal(_))));
+    }
+
+    #[test]
+    fn trace_internal_calls() {
+        let db = TestStageDB::default();
+
+        // `caller` calls `callee` with all the remaining gas
+        let sender = address!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+        let caller = address!("1000000000000000000000000000000000000000");
+        let callee = address!("2000000000000000000000000000000000000000");
+        let code =
+            hex!("6000600060006000600073" "2000000000000000000000000000000000000000" "5af100");
+        let code_hash = keccak256(code);
+
+        let provider = db.factory.provider_rw().unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::PlainAccountState>(
+                sender,
+                Account {
+                    nonce: 0,
+                    balance: U256::from(10).pow(U256::from(18)),
+                    bytecode_hash: None,
+                },
+            )
+            .unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::PlainAccountState>(
+                caller,
+                Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) },
+            )
+            .unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.to_vec().into()))
+            .unwrap();
+        provider.commit().unwrap();
+
+        let transaction = TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy {
+                nonce: 0,
+                gas_price: 1,
+                gas_limit: 100_000,
+                to: TxKind::Call(caller),
+                ..Default::default()
+            }),
+            Signature::default(),
+        );
+        let block = BlockWithSenders::new(
+            Block {
+                header: Header { number: 1, gas_limit: 1_000_000, ..Default::default() },
+                body: vec![transaction],
+                ..Default::default()
+            },
+            vec![sender],
+        )
+        .unwrap();
+
+        let tracer = EvmInternalCallTracer::new(
+            EthEvmConfig::default(),
+            Arc::new(ChainSpecBuilder::mainnet().berlin_activated().build()),
+        );
+        let provider = db.factory.provider().unwrap();
+        let state =
+            LatestStateProviderRef::new(provider.tx_ref(), provider.static_file_provider().clone());
+        let addresses = tracer.trace_block(&state, &block, U256::ZERO).unwrap();
+        assert_eq!(addresses, vec![HashSet::from([caller, callee])]);
+    }
+}
@@ -0,0 +1,318 @@
+use super::{flush_index_cache, load_history_indices, push_index, DEFAULT_CACHE_THRESHOLD};
+use reth_config::config::{EtlConfig, IndexLogsConfig};
+use reth_db::{tables, BlockNumberList};
+use reth_db_api::{
+    cursor::DbCursorRO,
+    database::Database,
+    models::ShardedKey,
+    table::Decode,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_etl::Collector;
+use reth_primitives::{Address, BlockNumber, B256};
+use reth_provider::{
+    DatabaseProviderRW, HistoryWriter, PruneCheckpointReader, PruneCheckpointWriter,
+    ReceiptProvider,
+};
+use reth_prune_types::{PruneCheckpoint, PruneMode, PrunePurpose, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::{collections::HashMap, ops::RangeInclusive};
+use tracing::info;
+
+/// Stage is indexing the logs of the receipts generated in
+/// [`ExecutionStage`][crate::stages::ExecutionStage], so that `eth_getLogs` can jump straight to
+/// the blocks with logs of an address or topic. For more information on index sharding take a
+/// look at [`tables::LogAddressIndex`] and [`tables::LogTopicIndex`].
+///
+/// The stage is optional and only added to the pipeline if enabled in [`IndexLogsConfig`].
+#[derive(Debug)]
+pub struct IndexLogsStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Pruning configuration.
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexLogsStage {
+    /// Create new instance of [`IndexLogsStage`].
+    pub const fn new(
+        config: IndexLogsConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config, prune_mode }
+    }
+}
+
+impl Default for IndexLogsStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000, prune_mode: None, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<DB: Database> Stage<DB> for IndexLogsStage {
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexLogs
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        if let Some((target_prunable_block, prune_mode)) = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(input.target(), PruneSegment::LogIndex, PrunePurpose::User)
+            })
+            .transpose()?
+            .flatten()
+        {
+            if target_prunable_block > input.checkpoint().block_number {
+                input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+
+                // Save prune checkpoint only if we don't have one already.
+                // Otherwise, pruner may skip the unpruned range of blocks.
+                if provider.get_prune_checkpoint(PruneSegment::LogIndex)?.is_none() {
+                    provider.save_prune_checkpoint(
+                        PruneSegment::LogIndex,
+                        PruneCheckpoint {
+                            block_number: Some(target_prunable_block),
+                            tx_number: None,
+                            prune_mode,
+                        },
+                    )?;
+                }
+            }
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (mut range, is_final_range) =
+            input.next_block_range_with_threshold(self.commit_threshold);
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync we might have logs coming from genesis. We clear the tables since it's
+        // faster to rebuild from scratch.
+        if first_sync {
+            provider.tx_ref().clear::<tables::LogAddressIndex>()?;
+            provider.tx_ref().clear::<tables::LogTopicIndex>()?;
+            range = 0..=*range.end();
+        }
+
+        info!(target: "sync::stages::index_logs::exec", ?first_sync, ?range, "Collecting indices");
+        let (address_collector, topic_collector) =
+            collect_log_indices(provider, range.clone(), &self.etl_config)?;
+
+        info!(target: "sync::stages::index_logs::exec", "Loading indices into database");
+        load_history_indices::<_, tables::LogAddressIndex, _>(
+            provider,
+            address_collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<Address>::decode,
+            |key| key.key,
+        )?;
+        load_history_indices::<_, tables::LogTopicIndex, _>(
+            provider,
+            topic_collector,
+            first_sync,
+            ShardedKey::new,
+            ShardedKey::<B256>::decode,
+            |key| key.key,
+        )?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &DatabaseProviderRW<DB>,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_log_index(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+/// [`Collector`]s of the log indices, keyed by the log address and by the log topic.
+type LogIndexCollectors =
+    (Collector<ShardedKey<Address>, BlockNumberList>, Collector<ShardedKey<B256>, BlockNumberList>);
+
+/// Collects the log indices of the receipts in the block range and stores them in two
+/// [`Collector`]s, one keyed by the log address and one by the log topic.
+///
+/// Works like [`collect_history_indices`](super::collect_history_indices), but walks the block
+/// receipts instead of a changeset table.
+fn collect_log_indices<DB: Database>(
+    provider: &DatabaseProviderRW<DB>,
+    range: RangeInclusive<BlockNumber>,
+    etl_config: &EtlConfig,
+) -> Result<LogIndexCollectors, StageError> {
+    let mut address_collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut topic_collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut address_cache = HashMap::<Address, Vec<u64>>::new();
+    let mut topic_cache = HashMap::<B256, Vec<u64>>::new();
+
+    // observability
+    let total_blocks = range.end() - range.start() + 1;
+    let interval = (total_blocks / 100).max(1);
+
+    let mut body_indices_cursor = provider.tx_ref().cursor_read::<tables::BlockBodyIndices>()?;
+    for (idx, entry) in body_indices_cursor.walk_range(range)?.enumerate() {
+        let (block_number, body_indices) = entry?;
+
+        for receipt in provider.receipts_by_tx_range(body_indices.tx_num_range())? {
+            for log in receipt.logs {
+                push_index(&mut address_cache, log.address, block_number);
+                for topic in log.topics() {
+                    push_index(&mut topic_cache, *topic, block_number);
+                }
+            }
+        }
+
+        let idx = idx as u64;
+        if idx > 0 && idx % interval == 0 && total_blocks > 100 {
+            info!(target: "sync::stages::index_logs", progress = %format!("{:.2}%", (idx as f64 / total_blocks as f64) * 100.0), "Collecting indices");
+        }
+
+        if (idx + 1) % DEFAULT_CACHE_THRESHOLD == 0 {
+            flush_index_cache(&mut address_cache, &mut address_collector, ShardedKey::new)?;
+            flush_index_cache(&mut topic_cache, &mut topic_collector, ShardedKey::new)?;
+        }
+    }
+    flush_index_cache(&mut address_cache, &mut address_collector, ShardedKey::new)?;
+    flush_index_cache(&mut topic_cache, &mut topic_collector, ShardedKey::new)?;
+
+    Ok((address_collector, topic_collector))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use reth_primitives::Receipt;
+    use reth_testing_utils::generators::{self, random_block_range, random_receipt};
+    use std::collections::BTreeMap;
+
+    fn run(db: &TestStageDB, run_to: u64, input_checkpoint: Option<BlockNumber>) {
+        let input = ExecInput {
+            target: Some(run_to),
+            checkpoint: input_checkpoint.map(StageCheckpoint::new),
+        };
+        let mut stage = IndexLogsStage::default();
+        let provider = db.factory.provider_rw().unwrap();
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(run_to), done: true });
+        provider.commit().unwrap();
+    }
+
+    fn unwind(db: &TestStageDB, unwind_from: u64, unwind_to: u64) {
+        let input = UnwindInput {
+            checkpoint: StageCheckpoint::new(unwind_from),
+            unwind_to,
+            ..Default::default()
+        };
+        let mut stage = IndexLogsStage::default();
+        let provider = db.factory.provider_rw().unwrap();
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(unwind_to) });
+        provider.commit().unwrap();
+    }
+
+    /// Returns the expected address and topic indices of the receipts up to the given block.
+    fn expected_indices(
+        receipts: &[(BlockNumber, Vec<Receipt>)],
+        to_block: BlockNumber,
+    ) -> (BTreeMap<Address, Vec<u64>>, BTreeMap<B256, Vec<u64>>) {
+        let mut addresses = HashMap::new();
+        let mut topics = HashMap::new();
+        for (block_number, receipts) in receipts.iter().filter(|(number, _)| *number <= to_block) {
+            for log in receipts.iter().flat_map(|receipt| &receipt.logs) {
+                push_index(&mut addresses, log.address, *block_number);
+                for topic in log.topics() {
+                    push_index(&mut topics, *topic, *block_number);
+                }
+            }
+        }
+        (addresses.into_iter().collect(), topics.into_iter().collect())
+    }
+
+    fn table<P: Ord>(entries: Vec<(ShardedKey<P>, BlockNumberList)>) -> BTreeMap<P, Vec<u64>> {
+        entries
+            .into_iter()
+            .map(|(key, list)| {
+                assert_eq!(key.highest_block_number, u64::MAX);
+                (key.key, list.iter().collect())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn index_and_unwind_logs() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+
+        let blocks = random_block_range(&mut rng, 0..=10, B256::ZERO, 0..3);
+        db.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+
+        let mut tx_num = 0u64;
+        let receipts = blocks
+            .iter()
+            .map(|block| {
+                let receipts = block
+                    .body
+                    .iter()
+                    .map(|transaction| random_receipt(&mut rng, transaction, Some(2)))
+                    .collect::<Vec<_>>();
+                (block.number, receipts)
+            })
+            .collect::<Vec<_>>();
+        db.insert_receipts_by_block(
+            receipts.iter().map(|(block_number, receipts)| {
+                let receipts = receipts
+                    .iter()
+                    .map(|receipt| {
+                        tx_num += 1;
+                        (tx_num - 1, receipt.clone())
+                    })
+                    .collect::<Vec<_>>();
+                (*block_number, receipts)
+            }),
+            StorageKind::Static,
+        )
+        .unwrap();
+
+        // index the first blocks, then the rest on top of the existing shards
+        run(&db, 5, None);
+        let (addresses, topics) = expected_indices(&receipts, 5);
+        assert_eq!(table(db.table::<tables::LogAddressIndex>().unwrap()), addresses);
+        assert_eq!(table(db.table::<tables::LogTopicIndex>().unwrap()), topics);
+
+        run(&db, 10, Some(5));
+        let (addresses, topics) = expected_indices(&receipts, 10);
+        assert!(!addresses.is_empty());
+        assert_eq!(table(db.table::<tables::LogAddressIndex>().unwrap()), addresses);
+        assert_eq!(table(db.table::<tables::LogTopicIndex>().unwrap()), topics);
+
+        // unwinding removes the blocks above the unwind target
+        unwind(&db, 10, 3);
+        let (addresses, topics) = expected_indices(&receipts, 3);
+        assert_eq!(table(db.table::<tables::LogAddressIndex>().unwrap()), addresses);
+        assert_eq!(table(db.table::<tables::LogTopicIndex>().unwrap()), topics);
+    }
+}
@@ -3,8 +3,8 @@ use reth_config::config::EtlConfig;
 use reth_db::{BlockNumberList, Database};
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
-    models::sharded_key::NUM_OF_INDICES_IN_SHARD,
-    table::{Decompress, Table},
+    models::{sharded_key::NUM_OF_INDICES_IN_SHARD, ShardedKey},
+    table::{Decompress, Key, Table},
     transaction::{DbTx, DbTxMut},
     DatabaseError,
 };
@@ -16,7 +16,7 @@ use std::{collections::HashMap, hash::Hash, ops::RangeBounds};
 use tracing::info;
 
 /// Number of blocks before pushing indices from cache to [`Collector`]
-const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
+pub(crate) const DEFAULT_CACHE_THRESHOLD: u64 = 100_000;
 
 /// Collects all history (`H`) indices for a range of changesets (`CS`) and stores them in a
 /// [`Collector`].
@@ -186,6 +186,32 @@ where
     Ok(())
 }
 
+/// Adds the block (or transaction) number to the key's cached indices, unless it's already the
+/// last one.
+pub(crate) fn push_index<P: Eq + Hash>(cache: &mut HashMap<P, Vec<u64>>, key: P, number: u64) {
+    let indices = cache.entry(key).or_default();
+    if indices.last() != Some(&number) {
+        indices.push(number);
+    }
+}
+
+/// Moves the cached indices into the [`Collector`], keyed by the highest number in each list.
+pub(crate) fn flush_index_cache<P: Copy + Eq + Hash>(
+    cache: &mut HashMap<P, Vec<u64>>,
+    collector: &mut Collector<ShardedKey<P>, BlockNumberList>,
+    sharded_key_factory: impl Fn(P, BlockNumber) -> ShardedKey<P>,
+) -> Result<(), StageError>
+where
+    ShardedKey<P>: Key,
+{
+    for (key, indice_list) in cache.drain() {
+        let last = *indice_list.last().expect("qed");
+        collector
+            .insert(sharded_key_factory(key, last), BlockNumberList::new_pre_sorted(indice_list))?;
+    }
+    Ok(())
+}
+
 /// Shard and insert the indices list according to [`LoadMode`] and its length.
 pub(crate) fn load_indices<H, C, P>(
     cursor: &mut C,
@@ -66,12 +66,18 @@ pub struct StaticFileTargets {
     headers: Option<RangeInclusive<BlockNumber>>,
     receipts: Option<RangeInclusive<BlockNumber>>,
     transactions: Option<RangeInclusive<BlockNumber>>,
+    account_changesets: Option<RangeInclusive<BlockNumber>>,
+    storage_changesets: Option<RangeInclusive<BlockNumber>>,
 }
 
 impl StaticFileTargets {
     /// Returns `true` if any of the targets are [Some].
     pub const fn any(&self) -> bool {
-        self.headers.is_some() || self.receipts.is_some() || self.transactions.is_some()
+        self.headers.is_some() ||
+            self.receipts.is_some() ||
+            self.transactions.is_some() ||
+            self.account_changesets.is_some() ||
+            self.storage_changesets.is_some()
     }
 
     // Returns `true` if all targets are either [`None`] or has beginning of the range equal to the
@@ -81,6 +87,8 @@ impl StaticFileTargets {
             (self.headers.as_ref(), static_files.headers),
             (self.receipts.as_ref(), static_files.receipts),
             (self.transactions.as_ref(), static_files.transactions),
+            (self.account_changesets.as_ref(), static_files.account_changesets),
+            (self.storage_changesets.as_ref(), static_files.storage_changesets),
         ]
         .iter()
         .all(|(target_block_range, highest_static_fileted_block)| {
@@ -139,6 +147,12 @@ impl<DB: Database> StaticFileProducerInner<DB> {
         if let Some(block_range) = targets.receipts.clone() {
             segments.push((Box::new(segments::Receipts), block_range));
         }
+        if let Some(block_range) = targets.account_changesets.clone() {
+            segments.push((Box::new(segments::AccountChangeSets), block_range));
+        }
+        if let Some(block_range) = targets.storage_changesets.clone() {
+            segments.push((Box::new(segments::StorageChangeSets), block_range));
+        }
 
         segments.par_iter().try_for_each(|(segment, block_range)| -> ProviderResult<()> {
             debug!(target: "static_file", segment = %segment.segment(), ?block_range, "StaticFileProducer segment");
@@ -174,6 +188,9 @@ impl<DB: Database> StaticFileProducerInner<DB> {
     /// Copies data from database to static files according to
     /// [stage checkpoints](reth_stages_types::StageCheckpoint).
     ///
+    /// Changesets are left in the database, since blocks at the tip of the pipeline can still be
+    /// unwound. They are only moved once finalized, see [`Self::get_static_file_targets`].
+    ///
     /// Returns highest block numbers for all static file segments.
     pub fn copy_to_static_files(&self) -> ProviderResult<HighestStaticFiles> {
         let provider = self.provider_factory.provider()?;
@@ -186,6 +203,8 @@ impl<DB: Database> StaticFileProducerInner<DB> {
             headers: stages_checkpoints[0],
             receipts: stages_checkpoints[1],
             transactions: stages_checkpoints[2],
+            account_changesets: None,
+            storage_changesets: None,
         };
         let targets = self.get_static_file_targets(highest_static_files)?;
         self.run(targets)?;
@@ -196,6 +215,9 @@ impl<DB: Database> StaticFileProducerInner<DB> {
     /// Returns a static file targets at the provided finalized block numbers per segment.
     /// The target is determined by the check against highest `static_files` using
     /// [`reth_provider::providers::StaticFileProvider::get_highest_static_files`].
+    ///
+    /// Changesets are additionally capped by the checkpoints of the stages that still read them
+    /// from the database, and are not moved at all if history pruning is configured.
     pub fn get_static_file_targets(
         &self,
         finalized_block_numbers: HighestStaticFiles,
@@ -226,6 +248,24 @@ impl<DB: Database> StaticFileProducerInner<DB> {
                     finalized_block_number,
                 )
             }),
+            account_changesets: if self.prune_modes.account_history.is_none() {
+                self.get_change_set_target(
+                    highest_static_files.account_changesets,
+                    finalized_block_numbers.account_changesets,
+                    [StageId::AccountHashing, StageId::MerkleExecute, StageId::IndexAccountHistory],
+                )?
+            } else {
+                None
+            },
+            storage_changesets: if self.prune_modes.storage_history.is_none() {
+                self.get_change_set_target(
+                    highest_static_files.storage_changesets,
+                    finalized_block_numbers.storage_changesets,
+                    [StageId::StorageHashing, StageId::MerkleExecute, StageId::IndexStorageHistory],
+                )?
+            } else {
+                None
+            },
         };
 
         trace!(
@@ -240,6 +280,26 @@ impl<DB: Database> StaticFileProducerInner<DB> {
         Ok(targets)
     }
 
+    /// Returns the changeset target up to the finalized block, but no further than the lowest
+    /// checkpoint of the given stages, which read changesets from the database.
+    fn get_change_set_target(
+        &self,
+        highest_static_file: Option<BlockNumber>,
+        finalized_block_number: Option<BlockNumber>,
+        stages: [StageId; 3],
+    ) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        let Some(finalized_block_number) = finalized_block_number else { return Ok(None) };
+
+        let provider = self.provider_factory.provider()?;
+        let mut target = finalized_block_number;
+        for stage in stages {
+            let Some(checkpoint) = provider.get_stage_checkpoint(stage)? else { return Ok(None) };
+            target = target.min(checkpoint.block_number);
+        }
+
+        Ok(self.get_static_file_target(highest_static_file, target))
+    }
+
     fn get_static_file_target(
         &self,
         highest_static_file: Option<BlockNumber>,
@@ -257,17 +317,25 @@ mod tests {
     };
     use alloy_primitives::{B256, U256};
     use assert_matches::assert_matches;
-    use reth_db::{test_utils::TempDatabase, DatabaseEnv};
-    use reth_db_api::{database::Database, transaction::DbTx};
+    use reth_db::{tables, test_utils::TempDatabase, DatabaseEnv};
+    use reth_db_api::{
+        database::Database,
+        models::{AccountBeforeTx, AccountChangeSet},
+        transaction::{DbTx, DbTxMut},
+    };
     use reth_provider::{
-        providers::StaticFileWriter, ProviderError, ProviderFactory, StaticFileProviderFactory,
+        providers::StaticFileWriter, ProviderError, ProviderFactory, StageCheckpointWriter,
+        StaticFileProviderFactory,
     };
     use reth_prune_types::PruneModes;
     use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_stages_types::{StageCheckpoint, StageId};
     use reth_static_file_types::{HighestStaticFiles, StaticFileSegment};
     use reth_testing_utils::{
         generators,
-        generators::{random_block_range, random_receipt},
+        generators::{
+            random_block_range, random_changeset_range, random_eoa_accounts, random_receipt,
+        },
     };
     use std::{
         sync::{mpsc::channel, Arc},
@@ -322,6 +390,7 @@ mod tests {
                 headers: Some(1),
                 receipts: Some(1),
                 transactions: Some(1),
+                ..Default::default()
             })
             .expect("get static file targets");
         assert_eq!(
@@ -329,13 +398,20 @@ mod tests {
             StaticFileTargets {
                 headers: Some(0..=1),
                 receipts: Some(0..=1),
-                transactions: Some(0..=1)
+                transactions: Some(0..=1),
+                account_changesets: None,
+                storage_changesets: None,
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(1), receipts: Some(1), transactions: Some(1) }
+            HighestStaticFiles {
+                headers: Some(1),
+                receipts: Some(1),
+                transactions: Some(1),
+                ..Default::default()
+            }
         );
 
         let targets = static_file_producer
@@ -343,6 +419,7 @@ mod tests {
                 headers: Some(3),
                 receipts: Some(3),
                 transactions: Some(3),
+                ..Default::default()
             })
             .expect("get static file targets");
         assert_eq!(
@@ -350,13 +427,20 @@ mod tests {
             StaticFileTargets {
                 headers: Some(2..=3),
                 receipts: Some(2..=3),
-                transactions: Some(2..=3)
+                transactions: Some(2..=3),
+                account_changesets: None,
+                storage_changesets: None,
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(3), receipts: Some(3), transactions: Some(3) }
+            HighestStaticFiles {
+                headers: Some(3),
+                receipts: Some(3),
+                transactions: Some(3),
+                ..Default::default()
+            }
         );
 
         let targets = static_file_producer
@@ -364,6 +448,7 @@ mod tests {
                 headers: Some(4),
                 receipts: Some(4),
                 transactions: Some(4),
+                ..Default::default()
             })
             .expect("get static file targets");
         assert_eq!(
@@ -371,7 +456,9 @@ mod tests {
             StaticFileTargets {
                 headers: Some(4..=4),
                 receipts: Some(4..=4),
-                transactions: Some(4..=4)
+                transactions: Some(4..=4),
+                account_changesets: None,
+                storage_changesets: None,
             }
         );
         assert_matches!(
@@ -380,8 +467,96 @@ mod tests {
         );
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(3), receipts: Some(3), transactions: Some(3) }
+            HighestStaticFiles {
+                headers: Some(3),
+                receipts: Some(3),
+                transactions: Some(3),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn run_change_sets() {
+        let (provider_factory, _temp_static_files_dir) = setup();
+
+        let mut rng = generators::rng();
+        let blocks = random_block_range(&mut rng, 0..=3, B256::ZERO, 0..1);
+        let accounts = random_eoa_accounts(&mut rng, 3)
+            .into_iter()
+            .map(|(address, account)| (address, (account, Vec::new())))
+            .collect::<Vec<_>>();
+        let (changesets, _) = random_changeset_range(&mut rng, blocks.iter(), accounts, 1..3, 0..8);
+
+        let provider_rw = provider_factory.provider_rw().expect("provider rw");
+        for (block, changeset) in changesets.iter().enumerate() {
+            for (address, old_account, old_storage) in changeset {
+                provider_rw
+                    .tx_ref()
+                    .put::<tables::AccountChangeSets>(
+                        block as u64,
+                        AccountBeforeTx { address: *address, info: Some(*old_account) },
+                    )
+                    .expect("insert account changeset");
+                for entry in old_storage {
+                    provider_rw
+                        .tx_ref()
+                        .put::<tables::StorageChangeSets>((block as u64, *address).into(), *entry)
+                        .expect("insert storage changeset");
+                }
+            }
+        }
+        for stage in [
+            StageId::AccountHashing,
+            StageId::StorageHashing,
+            StageId::MerkleExecute,
+            StageId::IndexAccountHistory,
+        ] {
+            provider_rw
+                .save_stage_checkpoint(stage, StageCheckpoint::new(2))
+                .expect("save checkpoint");
+        }
+        provider_rw.commit().expect("commit");
+
+        let static_file_producer =
+            StaticFileProducerInner::new(provider_factory.clone(), PruneModes::default());
+
+        // Storage changesets are held back until the storage history index catches up, and
+        // account changesets are capped by their lowest checkpoint.
+        let finalized = HighestStaticFiles {
+            account_changesets: Some(3),
+            storage_changesets: Some(3),
+            ..Default::default()
+        };
+        let targets = static_file_producer.get_static_file_targets(finalized).expect("get targets");
+        assert_eq!(
+            targets,
+            StaticFileTargets {
+                headers: None,
+                receipts: None,
+                transactions: None,
+                account_changesets: Some(0..=2),
+                storage_changesets: None,
+            }
         );
+        assert_matches!(static_file_producer.run(targets), Ok(_));
+
+        let static_file_provider = provider_factory.static_file_provider();
+        assert_eq!(static_file_provider.get_highest_static_files().account_changesets, Some(2));
+        for (block, changeset) in changesets.iter().enumerate().take(3) {
+            let expected = changeset
+                .iter()
+                .map(|(address, old_account, _)| AccountBeforeTx {
+                    address: *address,
+                    info: Some(*old_account),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                static_file_provider.account_changeset(block as u64).expect("read changeset"),
+                Some(AccountChangeSet { changes: expected })
+            );
+        }
+        assert_eq!(static_file_provider.account_changeset(3).expect("read changeset"), None);
     }
 
     /// Tests that a cloneable [`StaticFileProducer`] type is not susceptible to any race condition.
@@ -408,6 +583,7 @@ mod tests {
                         headers: Some(1),
                         receipts: Some(1),
                         transactions: Some(1),
+                        ..Default::default()
                     })
                     .expect("get static file targets");
                 assert_matches!(locked_producer.run(targets.clone()), Ok(_));
@@ -0,0 +1,18 @@
+use crate::DatabaseError;
+use std::{path::PathBuf, sync::Arc};
+
+/// Represents a database that can write a compacted copy of itself while it is in use.
+pub trait DatabaseCompaction {
+    /// Writes a compacted copy of the database next to it, replacing any previous copy, and returns
+    /// the directory of the copy.
+    ///
+    /// The copy is made within a read-only transaction, so it is consistent and the database can
+    /// be written to in the meantime. The database itself is left untouched.
+    fn compact(&self) -> Result<PathBuf, DatabaseError>;
+}
+
+impl<DB: DatabaseCompaction> DatabaseCompaction for Arc<DB> {
+    fn compact(&self) -> Result<PathBuf, DatabaseError> {
+        <DB as DatabaseCompaction>::compact(self)
+    }
+}
@@ -65,6 +65,8 @@ pub mod common;
 pub mod cursor;
 /// Database traits.
 pub mod database;
+/// Database compaction trait extension.
+pub mod database_compaction;
 /// Database metrics trait extensions.
 pub mod database_metrics;
 pub mod mock;
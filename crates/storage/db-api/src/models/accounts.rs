@@ -7,15 +7,15 @@ use crate::{
     table::{Decode, Encode},
     DatabaseError,
 };
-use reth_codecs::{derive_arbitrary, Compact};
-use reth_primitives::{Account, Address, BlockNumber, Buf, StorageKey};
+use reth_codecs::{derive_arbitrary, reth_codec, Compact};
+use reth_primitives::{Account, Address, BlockNumber, Buf, StorageEntry, StorageKey};
 use serde::{Deserialize, Serialize};
 
 /// Account as it is saved in the database.
 ///
 /// [`Address`] is the subkey.
 #[derive_arbitrary(compact)]
-#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize)]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct AccountBeforeTx {
     /// Address for the account. Acts as `DupSort::SubKey`.
     pub address: Address,
@@ -57,6 +57,81 @@ impl Compact for AccountBeforeTx {
     }
 }
 
+/// Storage slot as it was before a block changed it.
+///
+/// This is the flattened form of a `StorageChangeSets` entry, with the [`Address`] taken out of
+/// the table key.
+#[derive_arbitrary(compact)]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StorageBeforeTx {
+    /// Address of the account owning the slot.
+    pub address: Address,
+    /// Storage slot and its value before the change.
+    pub entry: StorageEntry,
+}
+
+impl Compact for StorageBeforeTx {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(self.address.as_slice());
+        self.entry.to_compact(buf) + 20
+    }
+
+    fn from_compact(mut buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let address = Address::from_slice(&buf[..20]);
+        buf.advance(20);
+
+        let (entry, buf) = StorageEntry::from_compact(buf, len - 20);
+        (Self { address, entry }, buf)
+    }
+}
+
+/// All account changes of a single block.
+///
+/// Stored as one row of the `AccountChangeSets` static file segment.
+#[reth_codec]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AccountChangeSet {
+    /// Accounts as they were before the block was executed.
+    pub changes: Vec<AccountBeforeTx>,
+}
+
+impl AccountChangeSet {
+    /// Returns the change of the given account, if it was changed in this block.
+    ///
+    /// Changes are sorted by address, in the same order as in the database.
+    pub fn get(&self, address: Address) -> Option<&AccountBeforeTx> {
+        self.changes
+            .binary_search_by_key(&address, |change| change.address)
+            .ok()
+            .map(|index| &self.changes[index])
+    }
+}
+
+/// All storage changes of a single block.
+///
+/// Stored as one row of the `StorageChangeSets` static file segment.
+#[reth_codec]
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StorageChangeSet {
+    /// Storage slots as they were before the block was executed.
+    pub changes: Vec<StorageBeforeTx>,
+}
+
+impl StorageChangeSet {
+    /// Returns the change of the given storage slot, if it was changed in this block.
+    ///
+    /// Changes are sorted by address and then by slot, in the same order as in the database.
+    pub fn get(&self, address: Address, key: StorageKey) -> Option<&StorageEntry> {
+        self.changes
+            .binary_search_by(|change| (change.address, change.entry.key).cmp(&(address, key)))
+            .ok()
+            .map(|index| &self.changes[index].entry)
+    }
+}
+
 /// [`BlockNumber`] concatenated with [`Address`].
 ///
 /// Since it's used as a key, it isn't compressed when encoding it.
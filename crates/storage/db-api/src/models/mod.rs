@@ -217,6 +217,8 @@ impl_compression_for_compact!(
     StoredBlockWithdrawals,
     Bytecode,
     AccountBeforeTx,
+    AccountChangeSet,
+    StorageChangeSet,
     TransactionSignedNoHash,
     CompactU256,
     StageCheckpoint,
@@ -5,6 +5,7 @@ use crate::{
     metrics::DatabaseEnvMetrics,
     tables::{self, TableType, Tables},
     utils::default_page_size,
+    version::create_db_version_file,
     DatabaseError,
 };
 use eyre::Context;
@@ -12,27 +13,37 @@ use metrics::{gauge, Label};
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
     database::Database,
+    database_compaction::DatabaseCompaction,
     database_metrics::{DatabaseMetadata, DatabaseMetadataValue, DatabaseMetrics},
     models::client_version::ClientVersion,
+    table::Table,
     transaction::{DbTx, DbTxMut},
 };
 use reth_libmdbx::{
-    ffi, DatabaseFlags, Environment, EnvironmentFlags, Geometry, HandleSlowReadersReturnCode,
-    MaxReadTransactionDuration, Mode, PageSize, SyncMode, RO, RW,
+    ffi, CopyFlags, DatabaseFlags, Environment, EnvironmentFlags, Geometry,
+    HandleSlowReadersReturnCode, MaxReadTransactionDuration, Mode, PageSize, SyncMode, RO, RW,
 };
 use reth_storage_errors::db::LogLevel;
-use reth_tracing::tracing::error;
+use reth_tracing::tracing::{error, warn};
 use std::{
     ops::Deref,
-    path::Path,
-    sync::Arc,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard, TryLockError},
     time::{SystemTime, UNIX_EPOCH},
 };
+use sysinfo::Disks;
 use tx::Tx;
 
 pub mod cursor;
 pub mod tx;
 
+/// Name of the MDBX data file in the database directory.
+pub const DATA_FILE_NAME: &str = "mdbx.dat";
+
+/// Name of the directory inside the database directory that [`DatabaseEnv::compact`] writes the
+/// compacted copy of the database to.
+pub const COMPACT_DIR_NAME: &str = "compact";
+
 const GIGABYTE: usize = 1024 * 1024 * 1024;
 const TERABYTE: usize = GIGABYTE * 1024;
 
@@ -138,6 +149,9 @@ pub struct DatabaseEnv {
     metrics: Option<Arc<DatabaseEnvMetrics>>,
     /// Write lock for when dealing with a read-write environment.
     _lock_file: Option<StorageLock>,
+    /// Held while a compacted copy of the database is written, so that only one copy is written
+    /// at a time.
+    compaction_lock: Mutex<()>,
 }
 
 impl Database for DatabaseEnv {
@@ -246,6 +260,12 @@ impl DatabaseMetadata for DatabaseEnv {
     }
 }
 
+impl DatabaseCompaction for DatabaseEnv {
+    fn compact(&self) -> Result<PathBuf, DatabaseError> {
+        Self::compact(self)
+    }
+}
+
 impl DatabaseEnv {
     /// Opens the database at the specified path with the given `EnvKind`.
     ///
@@ -406,11 +426,88 @@ impl DatabaseEnv {
             inner: inner_env.open(path).map_err(|e| DatabaseError::Open(e.into()))?,
             metrics: None,
             _lock_file,
+            compaction_lock: Mutex::new(()),
         };
 
         Ok(env)
     }
 
+    /// Writes a compacted copy of the database into the [`COMPACT_DIR_NAME`] directory next to it
+    /// and returns the directory of the copy.
+    ///
+    /// The copy is made within a read-only transaction, so it is consistent and the database can
+    /// be written to in the meantime. The database itself is left untouched. A copy written by a
+    /// previous call, complete or not, is replaced.
+    ///
+    /// Returns an error without writing anything if another copy is being written at the same
+    /// time, or if there is not enough free disk space for the copy.
+    pub fn compact(&self) -> Result<PathBuf, DatabaseError> {
+        let _guard = self.lock_compaction()?;
+
+        let path =
+            self.inner.path().map_err(|e| DatabaseError::Copy(e.into()))?.join(COMPACT_DIR_NAME);
+        if path.exists() {
+            reth_fs_util::remove_dir_all(&path)
+                .map_err(|err| DatabaseError::Other(err.to_string()))?;
+        }
+        self.write_compacted_copy(&path)?;
+
+        Ok(path)
+    }
+
+    /// Writes a compacted copy of the database to the directory `dest`.
+    ///
+    /// The copy includes the database version file, so it can be opened like the database itself.
+    ///
+    /// Returns an error without writing anything if `dest` already exists, if another copy is
+    /// being written at the same time, or if there is not enough free disk space for the copy.
+    pub fn compact_to(&self, dest: &Path) -> Result<(), DatabaseError> {
+        let _guard = self.lock_compaction()?;
+
+        if dest.exists() {
+            return Err(DatabaseError::Other(format!("{} already exists", dest.display())))
+        }
+        self.write_compacted_copy(dest)
+    }
+
+    /// Acquires the compaction lock, failing if another compacted copy is being written.
+    fn lock_compaction(&self) -> Result<MutexGuard<'_, ()>, DatabaseError> {
+        match self.compaction_lock.try_lock() {
+            Ok(guard) => Ok(guard),
+            Err(TryLockError::Poisoned(err)) => Ok(err.into_inner()),
+            Err(TryLockError::WouldBlock) => Err(DatabaseError::Other(
+                "another compacted copy of the database is being written".to_string(),
+            )),
+        }
+    }
+
+    /// Writes a compacted copy of the database to the directory `dest`, which must not exist.
+    ///
+    /// Must be called with the compaction lock held.
+    fn write_compacted_copy(&self, dest: &Path) -> Result<(), DatabaseError> {
+        ensure_available_space(dest, self.used_size()?)?;
+
+        reth_fs_util::create_dir_all(dest).map_err(|err| DatabaseError::Other(err.to_string()))?;
+        self.inner
+            .copy(&dest.join(DATA_FILE_NAME), CopyFlags::COMPACT)
+            .map_err(|e| DatabaseError::Copy(e.into()))?;
+        create_db_version_file(dest).map_err(|err| DatabaseError::Other(err.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Returns the size of the pages in use, which is the upper bound of the size of a compacted
+    /// copy of the database.
+    fn used_size(&self) -> Result<u64, DatabaseError> {
+        let info = self.inner.info().map_err(|e| DatabaseError::Stats(e.into()))?;
+        let stat = self.inner.stat().map_err(|e| DatabaseError::Stats(e.into()))?;
+        let freelist = self.inner.freelist().map_err(|e| DatabaseError::Stats(e.into()))?;
+
+        // `last_pgno` is 0 based.
+        let used_pages = (info.last_pgno() + 1).saturating_sub(freelist);
+        Ok(used_pages as u64 * stat.page_size() as u64)
+    }
+
     /// Enables metrics on the database.
     pub fn with_metrics(mut self) -> Self {
         self.metrics = Some(DatabaseEnvMetrics::new().into());
@@ -422,12 +519,7 @@ impl DatabaseEnv {
         let tx = self.inner.begin_rw_txn().map_err(|e| DatabaseError::InitTx(e.into()))?;
 
         for table in Tables::ALL {
-            let flags = match table.table_type() {
-                TableType::Table => DatabaseFlags::default(),
-                TableType::DupSort => DatabaseFlags::DUP_SORT,
-            };
-
-            tx.create_db(Some(table.name()), flags)
+            tx.create_db(Some(table.name()), table_flags(table.table_type()))
                 .map_err(|e| DatabaseError::CreateTable(e.into()))?;
         }
 
@@ -436,6 +528,20 @@ impl DatabaseEnv {
         Ok(())
     }
 
+    /// Creates a table that is not defined in [`Tables`], if necessary.
+    ///
+    /// Allows other components to keep their own tables in a separate database.
+    pub fn create_table<T: Table>(&self, table_type: TableType) -> Result<(), DatabaseError> {
+        let tx = self.inner.begin_rw_txn().map_err(|e| DatabaseError::InitTx(e.into()))?;
+
+        tx.create_db(Some(T::NAME), table_flags(table_type))
+            .map_err(|e| DatabaseError::CreateTable(e.into()))?;
+
+        tx.commit().map_err(|e| DatabaseError::Commit(e.into()))?;
+
+        Ok(())
+    }
+
     /// Records version that accesses the database with write privileges.
     pub fn record_client_version(&self, version: ClientVersion) -> Result<(), DatabaseError> {
         if version.is_empty() {
@@ -458,6 +564,14 @@ impl DatabaseEnv {
     }
 }
 
+/// Returns the MDBX flags of a table of the given type.
+const fn table_flags(table_type: TableType) -> DatabaseFlags {
+    match table_type {
+        TableType::Table => DatabaseFlags::empty(),
+        TableType::DupSort => DatabaseFlags::DUP_SORT,
+    }
+}
+
 impl Deref for DatabaseEnv {
     type Target = Environment;
 
@@ -466,6 +580,38 @@ impl Deref for DatabaseEnv {
     }
 }
 
+/// Returns an error if the disk that `path` would be created on has less than `required` bytes of
+/// free space.
+///
+/// The check is skipped if the disk can't be determined.
+fn ensure_available_space(path: &Path, required: u64) -> Result<(), DatabaseError> {
+    let Some(available) = available_space(path) else {
+        warn!(target: "storage::db::mdbx", ?path, "Failed to determine the free disk space");
+        return Ok(())
+    };
+
+    if available < required {
+        return Err(DatabaseError::Other(format!(
+            "not enough free disk space for {}: {available} bytes available, {required} bytes required",
+            path.display()
+        )))
+    }
+
+    Ok(())
+}
+
+/// Returns the free space of the disk that `path` would be created on, if it can be determined.
+fn available_space(path: &Path) -> Option<u64> {
+    let existing = path.ancestors().find(|path| path.exists())?.canonicalize().ok()?;
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| existing.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| disk.available_space())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -539,6 +685,57 @@ mod tests {
         tx.commit().expect(ERROR_COMMIT);
     }
 
+    #[test]
+    fn db_compact() {
+        let env = create_test_db(DatabaseEnvKind::RW);
+
+        let tx = env.tx_mut().expect(ERROR_INIT_TX);
+        for key in 0..10u64 {
+            tx.put::<Headers>(key, Header { number: key, ..Default::default() }).expect(ERROR_PUT);
+        }
+        tx.commit().expect(ERROR_COMMIT);
+
+        // No copy is written while another one is being written.
+        {
+            let _guard = env.compaction_lock.lock().unwrap();
+            assert!(env.compact().is_err());
+        }
+
+        let path = env.compact().unwrap();
+        assert_eq!(path.file_name().unwrap(), COMPACT_DIR_NAME);
+
+        let copy = crate::open_db_read_only(&path, DatabaseArguments::default()).unwrap();
+        let tx = copy.tx().expect(ERROR_INIT_TX);
+        assert_eq!(tx.entries::<Headers>().unwrap(), 10);
+        assert_eq!(tx.get::<Headers>(9).expect(ERROR_GET).map(|header| header.number), Some(9));
+        drop(tx);
+
+        drop(copy);
+
+        // A copy is not written into an existing directory.
+        assert!(env.compact_to(&path).is_err());
+
+        // But a previous copy is replaced.
+        let tx = env.tx_mut().expect(ERROR_INIT_TX);
+        tx.put::<Headers>(10, Header { number: 10, ..Default::default() }).expect(ERROR_PUT);
+        tx.commit().expect(ERROR_COMMIT);
+        assert_eq!(env.compact().unwrap(), path);
+
+        let copy = crate::open_db_read_only(&path, DatabaseArguments::default()).unwrap();
+        assert_eq!(copy.tx().expect(ERROR_INIT_TX).entries::<Headers>().unwrap(), 11);
+    }
+
+    #[test]
+    fn db_compact_requires_free_space() {
+        let dir = TempDir::new().expect(ERROR_TEMPDIR);
+        let dest = dir.path().join(COMPACT_DIR_NAME);
+
+        assert!(ensure_available_space(&dest, 0).is_ok());
+        if available_space(&dest).is_some() {
+            assert!(ensure_available_space(&dest, u64::MAX).is_err());
+        }
+    }
+
     #[test]
     fn db_dup_cursor_delete_first() {
         let db: Arc<DatabaseEnv> = create_test_db(DatabaseEnvKind::RW);
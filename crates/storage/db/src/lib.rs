@@ -45,6 +45,7 @@ pub mod test_utils {
     use crate::mdbx::DatabaseArguments;
     use reth_db_api::{
         database::Database,
+        database_compaction::DatabaseCompaction,
         database_metrics::{DatabaseMetadata, DatabaseMetadataValue, DatabaseMetrics},
         models::ClientVersion,
     };
@@ -124,6 +125,12 @@ pub mod test_utils {
         }
     }
 
+    impl<DB: DatabaseCompaction> DatabaseCompaction for TempDatabase<DB> {
+        fn compact(&self) -> Result<PathBuf, DatabaseError> {
+            self.db().compact()
+        }
+    }
+
     /// Create `static_files` path for testing
     pub fn create_test_static_files_dir() -> (TempDir, PathBuf) {
         let temp_dir = TempDir::with_prefix("reth-test-static-").expect(ERROR_TEMPDIR);
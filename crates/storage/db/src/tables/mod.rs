@@ -359,6 +359,31 @@ tables! {
     /// Code example can be found in `reth_provider::HistoricalStateProviderRef`
     table StoragesHistory<Key = StorageShardedKey, Value = BlockNumberList>;
 
+    /// Stores the numbers of the blocks with logs emitted by each address.
+    ///
+    /// Only populated if the log index is enabled. Sharded like [`AccountsHistory`], with the last
+    /// shard of each address keyed by `u64::MAX`.
+    table LogAddressIndex<Key = ShardedKey<Address>, Value = BlockNumberList>;
+
+    /// Stores the numbers of the blocks with logs containing each topic, in any position.
+    ///
+    /// Only populated if the log index is enabled. Sharded like [`AccountsHistory`], with the last
+    /// shard of each topic keyed by `u64::MAX`.
+    table LogTopicIndex<Key = ShardedKey<B256>, Value = BlockNumberList>;
+
+    /// Stores the numbers of the transactions sent by, sent to or, optionally, internally calling
+    /// each address.
+    ///
+    /// Only populated if the address transactions index is enabled. Sharded like
+    /// [`AccountsHistory`], but by transaction number, with the last shard of each address keyed by
+    /// `u64::MAX`.
+    table AddressTransactions<Key = ShardedKey<Address>, Value = BlockNumberList>;
+
+    /// Stores the addresses indexed in [`AddressTransactions`] for each transaction.
+    ///
+    /// Used to unwind and prune [`AddressTransactions`] without re-executing the transactions.
+    table TransactionAddresses<Key = TxNumber, Value = Address, SubKey = Address>;
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -409,6 +434,9 @@ tables! {
 
     /// Stores generic chain state info, like the last finalized block.
     table ChainState<Key = ChainStateKey, Value = BlockNumber>;
+
+    /// Stores the RLP encoded most recent blocks that failed validation, keyed by their hash.
+    table BadBlocks<Key = BlockHash, Value = Vec<u8>>;
 }
 
 /// Keys for the `ChainState` table.
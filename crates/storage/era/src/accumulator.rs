@@ -0,0 +1,78 @@
+use crate::MAX_BLOCKS_PER_ERA1;
+use reth_primitives::{B256, U256};
+use sha2::{Digest, Sha256};
+
+/// Depth of the merkle tree of an epoch accumulator, `log2(MAX_BLOCKS_PER_ERA1)`.
+const ACCUMULATOR_DEPTH: usize = MAX_BLOCKS_PER_ERA1.trailing_zeros() as usize;
+
+/// Accumulator entry of a single block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderRecord {
+    /// Hash of the block.
+    pub block_hash: B256,
+    /// Total difficulty of the chain up to and including the block.
+    pub total_difficulty: U256,
+}
+
+impl HeaderRecord {
+    /// Returns the SSZ hash tree root of the record.
+    pub fn hash_tree_root(&self) -> B256 {
+        sha256_pair(&self.block_hash, &B256::from(self.total_difficulty.to_le_bytes::<32>()))
+    }
+}
+
+/// Returns the epoch accumulator root, the SSZ hash tree root of
+/// `List[HeaderRecord, MAX_BLOCKS_PER_ERA1]`.
+///
+/// # Panics
+///
+/// If more than [`MAX_BLOCKS_PER_ERA1`] records are passed.
+pub fn accumulator_root(records: &[HeaderRecord]) -> B256 {
+    assert!(records.len() as u64 <= MAX_BLOCKS_PER_ERA1, "too many accumulator records");
+
+    let mut zero_hash = B256::ZERO;
+    let mut layer = records.iter().map(HeaderRecord::hash_tree_root).collect::<Vec<_>>();
+    for _ in 0..ACCUMULATOR_DEPTH {
+        if layer.len() % 2 == 1 {
+            layer.push(zero_hash);
+        }
+        layer = layer.chunks_exact(2).map(|pair| sha256_pair(&pair[0], &pair[1])).collect();
+        zero_hash = sha256_pair(&zero_hash, &zero_hash);
+    }
+    let root = layer.first().copied().unwrap_or(zero_hash);
+
+    // Mix in the length of the list.
+    sha256_pair(&root, &B256::from(U256::from(records.len()).to_le_bytes::<32>()))
+}
+
+fn sha256_pair(left: &B256, right: &B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_accumulator() {
+        let mut zero_hash = B256::ZERO;
+        for _ in 0..ACCUMULATOR_DEPTH {
+            zero_hash = sha256_pair(&zero_hash, &zero_hash);
+        }
+        assert_eq!(accumulator_root(&[]), sha256_pair(&zero_hash, &B256::ZERO));
+    }
+
+    #[test]
+    fn accumulator_depends_on_records() {
+        let record =
+            HeaderRecord { block_hash: B256::repeat_byte(1), total_difficulty: U256::from(1) };
+        let other = HeaderRecord { total_difficulty: U256::from(2), ..record };
+
+        assert_ne!(accumulator_root(&[record]), accumulator_root(&[other]));
+        assert_ne!(accumulator_root(&[record]), accumulator_root(&[record, record]));
+        assert_ne!(accumulator_root(&[record, other]), accumulator_root(&[other, record]));
+    }
+}
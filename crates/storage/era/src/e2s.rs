@@ -0,0 +1,121 @@
+//! Minimal e2store (`.e2s`) entry reader and writer.
+//!
+//! Every entry starts with an 8 byte header: a 2 byte type, a 4 byte little-endian data length and
+//! 2 reserved bytes that must be zero.
+
+use crate::Era1Error;
+use std::io::{self, Read, Write};
+
+/// Size of an entry header.
+pub const HEADER_SIZE: u64 = 8;
+
+/// Entry type of the version entry, which starts every e2store stream.
+pub const VERSION: u16 = 0x3265;
+/// Entry type of a snappy compressed RLP header.
+pub const COMPRESSED_HEADER: u16 = 0x03;
+/// Entry type of a snappy compressed RLP body.
+pub const COMPRESSED_BODY: u16 = 0x04;
+/// Entry type of snappy compressed RLP receipts.
+pub const COMPRESSED_RECEIPTS: u16 = 0x05;
+/// Entry type of a little-endian 256 bit total difficulty.
+pub const TOTAL_DIFFICULTY: u16 = 0x06;
+/// Entry type of the epoch accumulator root.
+pub const ACCUMULATOR: u16 = 0x07;
+/// Entry type of the block index.
+pub const BLOCK_INDEX: u16 = 0x3266;
+
+/// A single e2store entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Type of the entry.
+    pub entry_type: u16,
+    /// Entry data.
+    pub data: Vec<u8>,
+}
+
+/// Reads [`Entry`]s from an e2store stream, keeping track of their offsets.
+#[derive(Debug)]
+pub struct E2StoreReader<R> {
+    reader: R,
+    offset: u64,
+}
+
+impl<R: Read> E2StoreReader<R> {
+    /// Creates a new reader at the start of an e2store stream.
+    pub const fn new(reader: R) -> Self {
+        Self { reader, offset: 0 }
+    }
+
+    /// Returns the offset of the next entry.
+    pub const fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Reads the next entry, returning [`None`] once the stream is exhausted.
+    pub fn next_entry(&mut self) -> Result<Option<Entry>, Era1Error> {
+        let mut header = [0u8; HEADER_SIZE as usize];
+        let mut read = 0;
+        while read < header.len() {
+            match self.reader.read(&mut header[read..]) {
+                Ok(0) if read == 0 => return Ok(None),
+                Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+                Ok(n) => read += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        let entry_type = u16::from_le_bytes([header[0], header[1]]);
+        let len = u32::from_le_bytes([header[2], header[3], header[4], header[5]]);
+        if header[6..] != [0, 0] {
+            return Err(Era1Error::InvalidReservedBytes(self.offset))
+        }
+
+        let mut data = vec![0u8; len as usize];
+        self.reader.read_exact(&mut data)?;
+        self.offset += HEADER_SIZE + len as u64;
+
+        Ok(Some(Entry { entry_type, data }))
+    }
+}
+
+/// Writes [`Entry`]s to an e2store stream, keeping track of their offsets.
+#[derive(Debug)]
+pub struct E2StoreWriter<W> {
+    writer: W,
+    offset: u64,
+}
+
+impl<W: Write> E2StoreWriter<W> {
+    /// Creates a new writer at the start of an e2store stream.
+    pub const fn new(writer: W) -> Self {
+        Self { writer, offset: 0 }
+    }
+
+    /// Returns the offset of the next entry.
+    pub const fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Writes an entry and returns its offset.
+    pub fn write_entry(&mut self, entry_type: u16, data: &[u8]) -> Result<u64, Era1Error> {
+        let len = u32::try_from(data.len())
+            .map_err(|_| Era1Error::InvalidEntryLength { entry_type, len: data.len() })?;
+
+        let mut header = [0u8; HEADER_SIZE as usize];
+        header[..2].copy_from_slice(&entry_type.to_le_bytes());
+        header[2..6].copy_from_slice(&len.to_le_bytes());
+        self.writer.write_all(&header)?;
+        self.writer.write_all(data)?;
+
+        let offset = self.offset;
+        self.offset += HEADER_SIZE + len as u64;
+        Ok(offset)
+    }
+
+    /// Flushes and returns the underlying writer.
+    pub fn into_inner(mut self) -> Result<W, Era1Error> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{Era1Error, Era1Summary};
+use reth_primitives::{GotExpected, B256};
+use std::str::FromStr;
+
+/// The canonical epoch accumulator roots of the pre-merge history of a chain, indexed by epoch.
+///
+/// An era1 file is only part of the canonical chain if its accumulator root is the root of its
+/// epoch. The roots are read from a list with one hex encoded root per line, in epoch order, like
+/// the published pre-merge accumulators. Empty lines and lines starting with `#` are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EpochAccumulators {
+    roots: Vec<B256>,
+}
+
+impl EpochAccumulators {
+    /// Creates the list from the roots of the epochs, starting at epoch zero.
+    pub const fn new(roots: Vec<B256>) -> Self {
+        Self { roots }
+    }
+
+    /// Returns the canonical accumulator root of the epoch, if known.
+    pub fn root(&self, epoch: u64) -> Option<B256> {
+        self.roots.get(usize::try_from(epoch).ok()?).copied()
+    }
+
+    /// Ensures that the file described by the summary is the canonical file of its epoch.
+    pub fn verify(&self, summary: &Era1Summary) -> Result<(), Era1Error> {
+        let epoch = summary.epoch();
+        let expected = self.root(epoch).ok_or(Era1Error::UnknownEpoch(epoch))?;
+        if summary.accumulator_root != expected {
+            return Err(Era1Error::NonCanonicalAccumulator {
+                epoch,
+                root: GotExpected { got: summary.accumulator_root, expected },
+            })
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for EpochAccumulators {
+    type Err = Era1Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let roots = s
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(line_number, line)| {
+                line.parse().map_err(|_| Era1Error::InvalidEpochAccumulator(line_number))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(roots))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MAX_BLOCKS_PER_ERA1;
+
+    const fn summary(epoch: u64, accumulator_root: B256) -> Era1Summary {
+        Era1Summary {
+            start_block: epoch * MAX_BLOCKS_PER_ERA1,
+            block_count: MAX_BLOCKS_PER_ERA1,
+            accumulator_root,
+        }
+    }
+
+    #[test]
+    fn parse_epoch_accumulators() {
+        let list = format!("# roots\n{}\n\n{}\n", B256::repeat_byte(1), B256::repeat_byte(2));
+        let accumulators = list.parse::<EpochAccumulators>().unwrap();
+        assert_eq!(
+            accumulators,
+            EpochAccumulators::new(vec![B256::repeat_byte(1), B256::repeat_byte(2)])
+        );
+
+        let err = format!("{}\nnot a root\n", B256::ZERO).parse::<EpochAccumulators>().unwrap_err();
+        assert!(matches!(err, Era1Error::InvalidEpochAccumulator(2)));
+    }
+
+    #[test]
+    fn verify_epoch_accumulators() {
+        let accumulators = EpochAccumulators::new(vec![B256::repeat_byte(1), B256::repeat_byte(2)]);
+
+        accumulators.verify(&summary(1, B256::repeat_byte(2))).unwrap();
+        assert!(matches!(
+            accumulators.verify(&summary(0, B256::repeat_byte(2))),
+            Err(Era1Error::NonCanonicalAccumulator { epoch: 0, .. })
+        ));
+        assert!(matches!(
+            accumulators.verify(&summary(2, B256::repeat_byte(3))),
+            Err(Era1Error::UnknownEpoch(2))
+        ));
+    }
+}
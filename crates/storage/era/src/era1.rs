@@ -0,0 +1,511 @@
+use crate::{
+    accumulator_root,
+    e2s::{
+        E2StoreReader, E2StoreWriter, Entry, ACCUMULATOR, BLOCK_INDEX, COMPRESSED_BODY,
+        COMPRESSED_HEADER, COMPRESSED_RECEIPTS, TOTAL_DIFFICULTY, VERSION,
+    },
+    Era1Error, HeaderRecord,
+};
+use alloy_rlp::{Decodable, RlpDecodable, RlpEncodable};
+use reth_primitives::{
+    hex, proofs, BlockNumber, GotExpected, Header, ReceiptWithBloom, TransactionSigned, B256, U256,
+};
+use std::{
+    io::{Read, Write},
+    ops::RangeInclusive,
+};
+
+/// Maximum number of blocks of an era1 file, which is the number of blocks of an epoch.
+pub const MAX_BLOCKS_PER_ERA1: u64 = 8192;
+
+/// Returns the canonical file name of an era1 file, `<network>-<epoch>-<short root>.era1`.
+///
+/// The short root is the hex encoding of the first 4 bytes of the accumulator root.
+pub fn era1_file_name(network: &str, epoch: u64, accumulator_root: B256) -> String {
+    format!("{network}-{epoch:05}-{}.era1", hex::encode(&accumulator_root[..4]))
+}
+
+/// Body of a pre-merge block as stored in era1 files.
+#[derive(Debug, Clone, Default, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct Era1Body {
+    /// Transactions of the block.
+    pub transactions: Vec<TransactionSigned>,
+    /// Ommer headers of the block.
+    pub ommers: Vec<Header>,
+}
+
+/// A block with its receipts and total difficulty, as stored in era1 files.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Era1Block {
+    /// Block header.
+    pub header: Header,
+    /// Block body.
+    pub body: Era1Body,
+    /// Receipts of the block transactions.
+    pub receipts: Vec<ReceiptWithBloom>,
+    /// Total difficulty of the chain up to and including this block.
+    pub total_difficulty: U256,
+}
+
+impl Era1Block {
+    /// Validates the body and receipts against the roots committed to in the header.
+    pub fn validate(&self) -> Result<(), Era1Error> {
+        let block = self.header.number;
+
+        let transactions_root = proofs::calculate_transaction_root(&self.body.transactions);
+        if transactions_root != self.header.transactions_root {
+            return Err(Era1Error::TransactionsRootMismatch {
+                block,
+                root: GotExpected {
+                    got: transactions_root,
+                    expected: self.header.transactions_root,
+                },
+            })
+        }
+
+        let ommers_hash = proofs::calculate_ommers_root(&self.body.ommers);
+        if ommers_hash != self.header.ommers_hash {
+            return Err(Era1Error::OmmersHashMismatch {
+                block,
+                hash: GotExpected { got: ommers_hash, expected: self.header.ommers_hash },
+            })
+        }
+
+        if self.receipts.len() != self.body.transactions.len() {
+            return Err(Era1Error::ReceiptsCountMismatch {
+                block,
+                receipts: self.receipts.len(),
+                transactions: self.body.transactions.len(),
+            })
+        }
+
+        let receipts_root = proofs::calculate_receipt_root(&self.receipts);
+        if receipts_root != self.header.receipts_root {
+            return Err(Era1Error::ReceiptsRootMismatch {
+                block,
+                root: GotExpected { got: receipts_root, expected: self.header.receipts_root },
+            })
+        }
+
+        Ok(())
+    }
+}
+
+/// Summary of a fully read or written era1 file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Era1Summary {
+    /// Number of the first block.
+    pub start_block: BlockNumber,
+    /// Number of blocks.
+    pub block_count: u64,
+    /// Epoch accumulator root.
+    pub accumulator_root: B256,
+}
+
+impl Era1Summary {
+    /// Returns the range of blocks of the file.
+    pub const fn block_range(&self) -> RangeInclusive<BlockNumber> {
+        self.start_block..=self.start_block + self.block_count - 1
+    }
+
+    /// Returns the epoch of the file.
+    pub const fn epoch(&self) -> u64 {
+        self.start_block / MAX_BLOCKS_PER_ERA1
+    }
+}
+
+/// Parsed block index entry.
+#[derive(Debug)]
+struct BlockIndex {
+    /// Offset of the block index entry.
+    offset: u64,
+    start_block: BlockNumber,
+    /// Offsets of the header entries, relative to the block index entry.
+    header_offsets: Vec<i64>,
+}
+
+/// Reads and verifies an era1 file block by block.
+///
+/// Every block is checked against its header roots and the previous block of the file. The
+/// accumulator and block index are checked by [`Era1Reader::finish`] once all blocks are read.
+#[derive(Debug)]
+pub struct Era1Reader<R> {
+    inner: E2StoreReader<R>,
+    records: Vec<HeaderRecord>,
+    header_offsets: Vec<u64>,
+    start_block: Option<BlockNumber>,
+    accumulator: Option<B256>,
+    block_index: Option<BlockIndex>,
+}
+
+impl<R: Read> Era1Reader<R> {
+    /// Creates a new reader, reading the version entry of the file.
+    pub fn new(reader: R) -> Result<Self, Era1Error> {
+        let mut inner = E2StoreReader::new(reader);
+        match inner.next_entry()? {
+            Some(Entry { entry_type: VERSION, data }) if data.is_empty() => {}
+            _ => return Err(Era1Error::MissingVersion),
+        }
+
+        Ok(Self {
+            inner,
+            records: Vec::new(),
+            header_offsets: Vec::new(),
+            start_block: None,
+            accumulator: None,
+            block_index: None,
+        })
+    }
+
+    /// Reads the next block, returning [`None`] once all blocks are read.
+    pub fn next_block(&mut self) -> Result<Option<Era1Block>, Era1Error> {
+        loop {
+            let offset = self.inner.offset();
+            let Some(entry) = self.inner.next_entry()? else { return Ok(None) };
+
+            if self.block_index.is_some() {
+                return Err(Era1Error::TrailingData)
+            }
+
+            match entry.entry_type {
+                COMPRESSED_HEADER if self.accumulator.is_none() => {
+                    let block = self.read_block(offset, &entry.data)?;
+                    return Ok(Some(block))
+                }
+                ACCUMULATOR => {
+                    if entry.data.len() != 32 {
+                        return Err(Era1Error::InvalidEntryLength {
+                            entry_type: ACCUMULATOR,
+                            len: entry.data.len(),
+                        })
+                    }
+                    self.accumulator = Some(B256::from_slice(&entry.data));
+                }
+                BLOCK_INDEX => self.block_index = Some(parse_block_index(offset, &entry.data)?),
+                COMPRESSED_HEADER | COMPRESSED_BODY | COMPRESSED_RECEIPTS | TOTAL_DIFFICULTY => {
+                    return Err(Era1Error::UnexpectedEntry {
+                        found: entry.entry_type,
+                        expected: if self.accumulator.is_none() {
+                            COMPRESSED_HEADER
+                        } else {
+                            BLOCK_INDEX
+                        },
+                    })
+                }
+                // Other entries are allowed between the block tuples and the accumulator.
+                _ => {}
+            }
+        }
+    }
+
+    /// Reads the remaining blocks and verifies the accumulator and block index of the file.
+    pub fn finish(mut self) -> Result<Era1Summary, Era1Error> {
+        while self.next_block()?.is_some() {}
+
+        let Some(start_block) = self.start_block else { return Err(Era1Error::Empty) };
+        let expected = self.accumulator.ok_or(Era1Error::MissingAccumulator)?;
+        let index = self.block_index.ok_or(Era1Error::MissingBlockIndex)?;
+
+        if index.start_block != start_block {
+            return Err(Era1Error::InvalidBlockIndex("starting block number mismatch"))
+        }
+        if index.header_offsets.len() != self.header_offsets.len() {
+            return Err(Era1Error::InvalidBlockIndex("block count mismatch"))
+        }
+        for (relative, absolute) in index.header_offsets.iter().zip(&self.header_offsets) {
+            if index.offset.checked_add_signed(*relative) != Some(*absolute) {
+                return Err(Era1Error::InvalidBlockIndex("header offset mismatch"))
+            }
+        }
+
+        let computed = accumulator_root(&self.records);
+        if computed != expected {
+            return Err(Era1Error::AccumulatorMismatch(GotExpected { got: computed, expected }))
+        }
+
+        Ok(Era1Summary {
+            start_block,
+            block_count: self.records.len() as u64,
+            accumulator_root: computed,
+        })
+    }
+
+    fn read_block(&mut self, offset: u64, header: &[u8]) -> Result<Era1Block, Era1Error> {
+        let header = Header::decode(&mut decompress(header)?.as_slice())?;
+        let body = Era1Body::decode(&mut self.expect_entry(COMPRESSED_BODY)?.as_slice())?;
+        let receipts = Vec::<ReceiptWithBloom>::decode(
+            &mut self.expect_entry(COMPRESSED_RECEIPTS)?.as_slice(),
+        )?;
+
+        let total_difficulty = self
+            .inner
+            .next_entry()?
+            .ok_or(Era1Error::UnexpectedEntry { found: 0, expected: TOTAL_DIFFICULTY })?;
+        if total_difficulty.entry_type != TOTAL_DIFFICULTY {
+            return Err(Era1Error::UnexpectedEntry {
+                found: total_difficulty.entry_type,
+                expected: TOTAL_DIFFICULTY,
+            })
+        }
+        if total_difficulty.data.len() != 32 {
+            return Err(Era1Error::InvalidEntryLength {
+                entry_type: TOTAL_DIFFICULTY,
+                len: total_difficulty.data.len(),
+            })
+        }
+        let total_difficulty = U256::from_le_slice(&total_difficulty.data);
+
+        let block = Era1Block { header, body, receipts, total_difficulty };
+        let number = block.header.number;
+
+        if self.records.len() as u64 == MAX_BLOCKS_PER_ERA1 {
+            return Err(Era1Error::TooManyBlocks)
+        }
+        let start_block = *self.start_block.get_or_insert(number);
+        let expected = start_block + self.records.len() as u64;
+        if number != expected {
+            return Err(Era1Error::NonSequentialBlock(GotExpected { got: number, expected }))
+        }
+
+        if let Some(parent) = self.records.last() {
+            if block.header.parent_hash != parent.block_hash {
+                return Err(Era1Error::ParentHashMismatch {
+                    block: number,
+                    hash: GotExpected {
+                        got: block.header.parent_hash,
+                        expected: parent.block_hash,
+                    },
+                })
+            }
+            let expected = parent.total_difficulty + block.header.difficulty;
+            if total_difficulty != expected {
+                return Err(Era1Error::TotalDifficultyMismatch {
+                    block: number,
+                    difficulty: GotExpected { got: total_difficulty, expected },
+                })
+            }
+        }
+
+        block.validate()?;
+
+        self.records.push(HeaderRecord { block_hash: block.header.hash_slow(), total_difficulty });
+        self.header_offsets.push(offset);
+
+        Ok(block)
+    }
+
+    /// Reads the next entry, which must be of the given type, and returns its decompressed data.
+    fn expect_entry(&mut self, entry_type: u16) -> Result<Vec<u8>, Era1Error> {
+        match self.inner.next_entry()? {
+            Some(entry) if entry.entry_type == entry_type => decompress(&entry.data),
+            entry => Err(Era1Error::UnexpectedEntry {
+                found: entry.map(|entry| entry.entry_type).unwrap_or_default(),
+                expected: entry_type,
+            }),
+        }
+    }
+}
+
+/// Writes blocks to an era1 file.
+#[derive(Debug)]
+pub struct Era1Writer<W> {
+    inner: E2StoreWriter<W>,
+    records: Vec<HeaderRecord>,
+    header_offsets: Vec<u64>,
+    start_block: Option<BlockNumber>,
+}
+
+impl<W: Write> Era1Writer<W> {
+    /// Creates a new writer, writing the version entry of the file.
+    pub fn new(writer: W) -> Result<Self, Era1Error> {
+        let mut inner = E2StoreWriter::new(writer);
+        inner.write_entry(VERSION, &[])?;
+        Ok(Self { inner, records: Vec::new(), header_offsets: Vec::new(), start_block: None })
+    }
+
+    /// Appends the next block of the file.
+    ///
+    /// Blocks must be sequential and at most [`MAX_BLOCKS_PER_ERA1`] blocks can be written.
+    pub fn append_block(&mut self, block: &Era1Block) -> Result<(), Era1Error> {
+        if self.records.len() as u64 == MAX_BLOCKS_PER_ERA1 {
+            return Err(Era1Error::TooManyBlocks)
+        }
+        let number = block.header.number;
+        let expected = *self.start_block.get_or_insert(number) + self.records.len() as u64;
+        if number != expected {
+            return Err(Era1Error::NonSequentialBlock(GotExpected { got: number, expected }))
+        }
+
+        let offset = self
+            .inner
+            .write_entry(COMPRESSED_HEADER, &compress(&alloy_rlp::encode(&block.header))?)?;
+        self.inner.write_entry(COMPRESSED_BODY, &compress(&alloy_rlp::encode(&block.body))?)?;
+        self.inner
+            .write_entry(COMPRESSED_RECEIPTS, &compress(&alloy_rlp::encode(&block.receipts))?)?;
+        self.inner.write_entry(TOTAL_DIFFICULTY, &block.total_difficulty.to_le_bytes::<32>())?;
+
+        self.records.push(HeaderRecord {
+            block_hash: block.header.hash_slow(),
+            total_difficulty: block.total_difficulty,
+        });
+        self.header_offsets.push(offset);
+
+        Ok(())
+    }
+
+    /// Writes the accumulator and block index, and returns the underlying writer with a summary
+    /// of the file.
+    pub fn finish(mut self) -> Result<(W, Era1Summary), Era1Error> {
+        let Some(start_block) = self.start_block else { return Err(Era1Error::Empty) };
+
+        let root = accumulator_root(&self.records);
+        self.inner.write_entry(ACCUMULATOR, root.as_slice())?;
+
+        let index_offset = self.inner.offset();
+        let mut index = Vec::with_capacity(16 + self.header_offsets.len() * 8);
+        index.extend_from_slice(&start_block.to_le_bytes());
+        for offset in &self.header_offsets {
+            index.extend_from_slice(&(*offset as i64 - index_offset as i64).to_le_bytes());
+        }
+        index.extend_from_slice(&(self.header_offsets.len() as u64).to_le_bytes());
+        self.inner.write_entry(BLOCK_INDEX, &index)?;
+
+        let summary = Era1Summary {
+            start_block,
+            block_count: self.records.len() as u64,
+            accumulator_root: root,
+        };
+        Ok((self.inner.into_inner()?, summary))
+    }
+}
+
+fn parse_block_index(offset: u64, data: &[u8]) -> Result<BlockIndex, Era1Error> {
+    if data.len() < 16 || data.len() % 8 != 0 {
+        return Err(Era1Error::InvalidEntryLength { entry_type: BLOCK_INDEX, len: data.len() })
+    }
+
+    let mut words = data.chunks_exact(8).map(|word| <[u8; 8]>::try_from(word).unwrap());
+    let start_block = u64::from_le_bytes(words.next().unwrap());
+    let count = u64::from_le_bytes(words.next_back().unwrap());
+    let header_offsets = words.map(i64::from_le_bytes).collect::<Vec<_>>();
+    if header_offsets.len() as u64 != count {
+        return Err(Era1Error::InvalidBlockIndex("count does not match the number of offsets"))
+    }
+
+    Ok(BlockIndex { offset, start_block, header_offsets })
+}
+
+fn compress(data: &[u8]) -> Result<Vec<u8>, Era1Error> {
+    let mut encoder = snap::write::FrameEncoder::new(Vec::new());
+    encoder.write_all(data)?;
+    Ok(encoder.into_inner().map_err(|err| err.into_error())?)
+}
+
+fn decompress(data: &[u8]) -> Result<Vec<u8>, Era1Error> {
+    let mut decompressed = Vec::new();
+    snap::read::FrameDecoder::new(data).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{proofs::calculate_receipt_root, ReceiptWithBloom};
+    use reth_testing_utils::generators::{self, random_block_range, random_receipt};
+
+    fn random_era1_blocks(count: u64) -> Vec<Era1Block> {
+        let mut rng = generators::rng();
+        let mut total_difficulty = U256::ZERO;
+        let mut parent_hash = B256::ZERO;
+
+        random_block_range(&mut rng, 100..=100 + count - 1, B256::ZERO, 1..4)
+            .into_iter()
+            .map(|block| {
+                let receipts = block
+                    .body
+                    .iter()
+                    .map(|tx| ReceiptWithBloom::from(random_receipt(&mut rng, tx, Some(1))))
+                    .collect::<Vec<_>>();
+
+                let mut header = block.header.unseal();
+                header.parent_hash = parent_hash;
+                header.difficulty = U256::from(header.number);
+                header.receipts_root = calculate_receipt_root(&receipts);
+                parent_hash = header.hash_slow();
+                total_difficulty += header.difficulty;
+
+                Era1Block {
+                    header,
+                    body: Era1Body { transactions: block.body, ommers: block.ommers },
+                    receipts,
+                    total_difficulty,
+                }
+            })
+            .collect()
+    }
+
+    fn write_era1(blocks: &[Era1Block]) -> (Vec<u8>, Era1Summary) {
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        for block in blocks {
+            writer.append_block(block).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn era1_roundtrip() {
+        let blocks = random_era1_blocks(10);
+        let (file, summary) = write_era1(&blocks);
+        assert_eq!(summary.block_range(), 100..=109);
+
+        let mut reader = Era1Reader::new(file.as_slice()).unwrap();
+        for expected in &blocks {
+            assert_eq!(reader.next_block().unwrap().as_ref(), Some(expected));
+        }
+        assert_eq!(reader.next_block().unwrap(), None);
+        assert_eq!(reader.finish().unwrap(), summary);
+    }
+
+    #[test]
+    fn era1_accumulator_mismatch() {
+        let blocks = random_era1_blocks(3);
+        let (mut file, summary) = write_era1(&blocks);
+
+        // The accumulator entry is right before the block index, which holds 3 offsets.
+        let accumulator = file.len() - (8 + 8 * 5) - 32;
+        assert_eq!(&file[accumulator..accumulator + 32], summary.accumulator_root.as_slice());
+        file[accumulator] ^= 1;
+
+        assert!(matches!(
+            Era1Reader::new(file.as_slice()).unwrap().finish(),
+            Err(Era1Error::AccumulatorMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn era1_invalid_block() {
+        let mut blocks = random_era1_blocks(3);
+        blocks[1].total_difficulty += U256::from(1);
+        let (file, _) = write_era1(&blocks);
+        assert!(matches!(
+            Era1Reader::new(file.as_slice()).unwrap().finish(),
+            Err(Era1Error::TotalDifficultyMismatch { block: 101, .. })
+        ));
+
+        let mut blocks = random_era1_blocks(3);
+        blocks[2].receipts.clear();
+        let (file, _) = write_era1(&blocks);
+        assert!(matches!(
+            Era1Reader::new(file.as_slice()).unwrap().finish(),
+            Err(Era1Error::ReceiptsCountMismatch { block: 102, .. })
+        ));
+    }
+
+    #[test]
+    fn era1_file_names() {
+        assert_eq!(
+            era1_file_name("mainnet", 0, B256::repeat_byte(0x5e)),
+            "mainnet-00000-5e5e5e5e.era1"
+        );
+    }
+}
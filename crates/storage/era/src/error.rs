@@ -0,0 +1,124 @@
+use crate::MAX_BLOCKS_PER_ERA1;
+use reth_primitives::{BlockNumber, GotExpected, B256, U256};
+use thiserror::Error;
+
+/// Errors associated with reading and writing era1 archives.
+#[derive(Error, Debug)]
+pub enum Era1Error {
+    /// I/O error, including snappy (de)compression failures.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// RLP decoding error.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+    /// The reserved bytes of an e2store entry header are not zero.
+    #[error("e2store entry at offset {0} has non-zero reserved bytes")]
+    InvalidReservedBytes(u64),
+    /// The stream does not start with a version entry.
+    #[error("era1 file does not start with a version entry")]
+    MissingVersion,
+    /// An entry of an unexpected type was found.
+    #[error("unexpected e2store entry type {found:#06x}, expected {expected:#06x}")]
+    UnexpectedEntry {
+        /// Type of the entry that was found.
+        found: u16,
+        /// Type of the entry that was expected.
+        expected: u16,
+    },
+    /// An entry has an invalid length.
+    #[error("e2store entry of type {entry_type:#06x} has invalid length {len}")]
+    InvalidEntryLength {
+        /// Type of the entry.
+        entry_type: u16,
+        /// Length of the entry data.
+        len: usize,
+    },
+    /// Data was found after the block index, which must be the last entry.
+    #[error("unexpected data after the block index")]
+    TrailingData,
+    /// The archive does not contain any block.
+    #[error("era1 file contains no blocks")]
+    Empty,
+    /// The archive exceeds the number of blocks of an epoch.
+    #[error("era1 file contains more than {MAX_BLOCKS_PER_ERA1} blocks")]
+    TooManyBlocks,
+    /// Blocks are not sequential.
+    #[error("non-sequential block in era1 file: {0}")]
+    NonSequentialBlock(GotExpected<BlockNumber>),
+    /// The parent hash of a block does not match the hash of the previous block.
+    #[error("parent hash mismatch for block {block}: {hash}")]
+    ParentHashMismatch {
+        /// Block number.
+        block: BlockNumber,
+        /// Parent hash found in the header and the hash of the previous block.
+        hash: GotExpected<B256>,
+    },
+    /// The total difficulty of a block is not the sum of its parent's and its own difficulty.
+    #[error("total difficulty mismatch for block {block}: {difficulty}")]
+    TotalDifficultyMismatch {
+        /// Block number.
+        block: BlockNumber,
+        /// Total difficulty found in the file and the expected one.
+        difficulty: GotExpected<U256>,
+    },
+    /// The transactions of a block do not match its header.
+    #[error("transactions root mismatch for block {block}: {root}")]
+    TransactionsRootMismatch {
+        /// Block number.
+        block: BlockNumber,
+        /// Computed and header transactions root.
+        root: GotExpected<B256>,
+    },
+    /// The ommers of a block do not match its header.
+    #[error("ommers hash mismatch for block {block}: {hash}")]
+    OmmersHashMismatch {
+        /// Block number.
+        block: BlockNumber,
+        /// Computed and header ommers hash.
+        hash: GotExpected<B256>,
+    },
+    /// The receipts of a block do not match its header.
+    #[error("receipts root mismatch for block {block}: {root}")]
+    ReceiptsRootMismatch {
+        /// Block number.
+        block: BlockNumber,
+        /// Computed and header receipts root.
+        root: GotExpected<B256>,
+    },
+    /// The number of receipts does not match the number of transactions.
+    #[error("block {block} has {receipts} receipts for {transactions} transactions")]
+    ReceiptsCountMismatch {
+        /// Block number.
+        block: BlockNumber,
+        /// Number of receipts.
+        receipts: usize,
+        /// Number of transactions.
+        transactions: usize,
+    },
+    /// The archive does not contain an accumulator entry.
+    #[error("era1 file is missing the accumulator")]
+    MissingAccumulator,
+    /// The archive does not contain a block index entry.
+    #[error("era1 file is missing the block index")]
+    MissingBlockIndex,
+    /// The block index does not describe the blocks of the archive.
+    #[error("invalid era1 block index: {0}")]
+    InvalidBlockIndex(&'static str),
+    /// The accumulator root computed from the blocks does not match the one in the archive.
+    #[error("accumulator root mismatch: {0}")]
+    AccumulatorMismatch(GotExpected<B256>),
+    /// The canonical accumulator root of the epoch of the archive is not known.
+    #[error("unknown canonical accumulator root of epoch {0}")]
+    UnknownEpoch(u64),
+    /// The accumulator root of the archive is not the canonical root of its epoch.
+    #[error("non-canonical accumulator root of epoch {epoch}: {root}")]
+    NonCanonicalAccumulator {
+        /// Epoch of the archive.
+        epoch: u64,
+        /// Accumulator root of the archive and the canonical one.
+        root: GotExpected<B256>,
+    },
+    /// A line of a list of epoch accumulator roots is not a root.
+    #[error("invalid epoch accumulator root on line {0}")]
+    InvalidEpochAccumulator(usize),
+}
@@ -0,0 +1,38 @@
+//! Era1 archive format.
+//!
+//! Era1 files store pre-merge block history in epochs of up to [`MAX_BLOCKS_PER_ERA1`] blocks.
+//! Each file is an [e2store](https://github.com/status-im/nimbus-eth2/blob/stable/docs/e2store.md)
+//! stream with the following layout:
+//!
+//! ```text
+//! era1 := Version | block-tuple* | other-entries* | Accumulator | BlockIndex
+//! block-tuple := CompressedHeader | CompressedBody | CompressedReceipts | TotalDifficulty
+//! ```
+//!
+//! Headers, bodies and receipts are RLP encoded and snappy (framed) compressed. The accumulator is
+//! the SSZ hash tree root of the `(block_hash, total_difficulty)` records of the epoch, which is
+//! how an archive is tied to the canonical chain.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod accumulator;
+pub use accumulator::{accumulator_root, HeaderRecord};
+
+pub mod e2s;
+
+mod era1;
+pub use era1::{
+    era1_file_name, Era1Block, Era1Body, Era1Reader, Era1Summary, Era1Writer, MAX_BLOCKS_PER_ERA1,
+};
+
+mod epochs;
+pub use epochs::EpochAccumulators;
+
+mod error;
+pub use error::Era1Error;
@@ -45,6 +45,9 @@ pub enum DatabaseError {
     /// Failed to get database stats.
     #[error("failed to get stats: {0}")]
     Stats(DatabaseErrorInfo),
+    /// Failed to copy the database.
+    #[error("failed to copy the database: {0}")]
+    Copy(DatabaseErrorInfo),
     /// Failed to use the specified log level, as it's not available.
     #[error("log level {0:?} is not available")]
     LogLevelUnavailable(LogLevel),
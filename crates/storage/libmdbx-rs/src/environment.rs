@@ -1,7 +1,7 @@
 use crate::{
     database::Database,
     error::{mdbx_result, Error, Result},
-    flags::EnvironmentFlags,
+    flags::{CopyFlags, EnvironmentFlags},
     transaction::{RO, RW},
     txn_manager::{TxnManager, TxnManagerMessage, TxnPtr},
     Transaction, TransactionKind,
@@ -9,11 +9,11 @@ use crate::{
 use byteorder::{ByteOrder, NativeEndian};
 use mem::size_of;
 use std::{
-    ffi::CString,
+    ffi::{CStr, CString},
     fmt::{self, Debug},
     mem,
     ops::{Bound, RangeBounds},
-    path::Path,
+    path::{Path, PathBuf},
     ptr,
     sync::{mpsc::sync_channel, Arc},
     thread::sleep,
@@ -152,6 +152,28 @@ impl Environment {
         mdbx_result(unsafe { ffi::mdbx_env_sync_ex(self.env_ptr(), force, false) })
     }
 
+    /// Returns the path the environment was opened with.
+    pub fn path(&self) -> Result<PathBuf> {
+        let mut path = ptr::null();
+        mdbx_result(unsafe { ffi::mdbx_env_get_path(self.env_ptr(), &mut path) })?;
+        if path.is_null() {
+            return Err(Error::Invalid)
+        }
+        let path = unsafe { CStr::from_ptr(path) };
+        Ok(PathBuf::from(path.to_str().map_err(|_| Error::Invalid)?))
+    }
+
+    /// Copies the environment to the data file at `dest`, which must not exist.
+    ///
+    /// The copy is made within a read transaction, so it is consistent and the environment can be
+    /// written to in the meantime. With [`CopyFlags::COMPACT`], free pages are omitted and the
+    /// data is renumbered, which results in a smaller file.
+    pub fn copy(&self, dest: &Path, flags: CopyFlags) -> Result<()> {
+        let dest = CString::new(path_to_bytes(dest)).map_err(|_| Error::Invalid)?;
+        mdbx_result(unsafe { ffi::mdbx_env_copy(self.env_ptr(), dest.as_ptr(), flags.bits()) })?;
+        Ok(())
+    }
+
     /// Retrieves statistics about this environment.
     pub fn stat(&self) -> Result<Stat> {
         unsafe {
@@ -225,6 +247,20 @@ impl Environment {
     }
 }
 
+#[cfg(unix)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    use std::os::unix::ffi::OsStrExt;
+    path.as_ref().as_os_str().as_bytes().to_vec()
+}
+
+#[cfg(windows)]
+fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
+    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
+    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
+    // really help.
+    path.as_ref().to_string_lossy().to_string().into_bytes()
+}
+
 /// Container type for Environment internals.
 ///
 /// This holds the raw pointer to the MDBX environment and the transaction manager.
@@ -667,20 +703,6 @@ impl EnvironmentBuilder {
                     ))?;
                 }
 
-                #[cfg(unix)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    use std::os::unix::ffi::OsStrExt;
-                    path.as_ref().as_os_str().as_bytes().to_vec()
-                }
-
-                #[cfg(windows)]
-                fn path_to_bytes<P: AsRef<Path>>(path: P) -> Vec<u8> {
-                    // On Windows, could use std::os::windows::ffi::OsStrExt to encode_wide(),
-                    // but we end up with a Vec<u16> instead of a Vec<u8>, so that doesn't
-                    // really help.
-                    path.as_ref().to_string_lossy().to_string().into_bytes()
-                }
-
                 let path = match CString::new(path_to_bytes(path)) {
                     Ok(path) => path,
                     Err(_) => return Err(Error::Invalid),
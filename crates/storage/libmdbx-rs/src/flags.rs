@@ -223,3 +223,12 @@ bitflags! {
         const MULTIPLE = MDBX_MULTIPLE;
     }
 }
+
+bitflags! {
+    #[doc="Environment copy options."]
+    #[derive(Default)]
+    pub struct CopyFlags: MDBX_copy_flags_t {
+        const COMPACT = MDBX_CP_COMPACT;
+        const FORCE_DYNAMIC_SIZE = MDBX_CP_FORCE_DYNAMIC_SIZE;
+    }
+}
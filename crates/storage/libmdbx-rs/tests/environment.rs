@@ -154,3 +154,41 @@ fn test_freelist() {
     freelist = env.freelist().unwrap();
     assert!(freelist > 0);
 }
+
+#[test]
+fn test_copy_compact() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+    assert_eq!(env.path().unwrap(), dir.path());
+
+    // Write a few values and clear most of them, so that the freelist is not empty.
+    for i in 0..512 {
+        let mut value = [0u8; 8];
+        LittleEndian::write_u64(&mut value, i);
+        let tx = env.begin_rw_txn().expect("begin_rw_txn");
+        tx.put(tx.open_db(None).unwrap().dbi(), value, [0u8; 256], WriteFlags::default())
+            .expect("tx.put");
+        tx.commit().expect("tx.commit");
+    }
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let dbi = tx.open_db(None).unwrap().dbi();
+    for i in 1..512 {
+        let mut key = [0u8; 8];
+        LittleEndian::write_u64(&mut key, i);
+        tx.del(dbi, key, None).expect("tx.del");
+    }
+    tx.commit().expect("tx.commit");
+
+    let copy_dir = tempdir().unwrap();
+    let dest = copy_dir.path().join("mdbx.dat");
+    env.copy(&dest, CopyFlags::COMPACT).unwrap();
+    assert!(
+        std::fs::metadata(&dest).unwrap().len() <
+            std::fs::metadata(dir.path().join("mdbx.dat")).unwrap().len()
+    );
+
+    // The copy holds the remaining value, and copying over an existing file fails.
+    let copy = Environment::builder().open(copy_dir.path()).unwrap();
+    assert_eq!(copy.stat().unwrap().entries(), 1);
+    env.copy(&dest, CopyFlags::COMPACT).unwrap_err();
+}
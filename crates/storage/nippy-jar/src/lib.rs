@@ -15,9 +15,11 @@
 
 use memmap2::Mmap;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::{
     error::Error as StdError,
     fs::{File, OpenOptions},
+    io::Read,
     ops::Range,
     path::{Path, PathBuf},
 };
@@ -51,6 +53,9 @@ const INDEX_FILE_EXTENSION: &str = "idx";
 const OFFSETS_FILE_EXTENSION: &str = "off";
 const CONFIG_FILE_EXTENSION: &str = "conf";
 
+/// SHA-256 checksum of the data and offsets files of a [`NippyJar`].
+pub type NippyJarChecksum = [u8; 32];
+
 /// A [`RefRow`] is a list of column value slices pointing to either an internal buffer or a
 /// memory-mapped file.
 type RefRow<'a> = Vec<&'a [u8]>;
@@ -123,6 +128,11 @@ pub struct NippyJar<H = ()> {
     /// Data path for file. Supporting files will have a format `{path}.{extension}`.
     #[serde(skip)]
     path: PathBuf,
+    /// Checksum of the data and offsets files, set when the jar is frozen. Appending or pruning
+    /// rows clears it.
+    ///
+    /// Must remain the last serialized field, see [`NippyJar::load`].
+    checksum: Option<NippyJarChecksum>,
 }
 
 impl<H: NippyJarHeader> std::fmt::Debug for NippyJar<H> {
@@ -139,6 +149,7 @@ impl<H: NippyJarHeader> std::fmt::Debug for NippyJar<H> {
             .field("offsets_index (size in bytes)", &self.offsets_index.size_in_bytes())
             .field("path", &self.path)
             .field("max_row_size", &self.max_row_size)
+            .field("checksum", &self.checksum)
             .finish_non_exhaustive()
     }
 }
@@ -174,6 +185,7 @@ impl<H: NippyJarHeader> NippyJar<H> {
             phf: None,
             offsets_index: PrefixSummedEliasFano::default(),
             path: path.to_path_buf(),
+            checksum: None,
         }
     }
 
@@ -243,6 +255,11 @@ impl<H: NippyJarHeader> NippyJar<H> {
         self.compressor.as_mut()
     }
 
+    /// Returns the checksum stored when the jar was frozen, if any.
+    pub const fn checksum(&self) -> Option<&NippyJarChecksum> {
+        self.checksum.as_ref()
+    }
+
     /// Loads the file configuration and returns [`Self`] without deserializing filters related
     /// structures or the offset list.
     ///
@@ -253,7 +270,10 @@ impl<H: NippyJarHeader> NippyJar<H> {
         let config_file = File::open(&config_path)
             .map_err(|err| reth_fs_util::FsPathError::open(err, config_path))?;
 
-        let mut obj: Self = bincode::deserialize_from(&config_file)?;
+        // Configurations written before checksums were introduced end before the `checksum`
+        // field. Since `None` is encoded as a single zero byte, appending one lets them load
+        // without a checksum, while it is left unread for newer configurations.
+        let mut obj: Self = bincode::deserialize_from(config_file.chain(&[0u8][..]))?;
         obj.path = path.to_path_buf();
         Ok(obj)
     }
@@ -308,6 +328,24 @@ impl<H: NippyJarHeader> NippyJar<H> {
         DataReader::new(self.data_path())
     }
 
+    /// Computes the checksum of the data and offsets files as they are on disk.
+    pub fn compute_checksum(&self) -> Result<NippyJarChecksum, NippyJarError> {
+        let mut hasher = Sha256::new();
+        let mut buf = vec![0; 1 << 20];
+        for path in [self.data_path().to_path_buf(), self.offsets_path()] {
+            let mut file =
+                File::open(&path).map_err(|err| reth_fs_util::FsPathError::open(err, path))?;
+            loop {
+                let read = file.read(&mut buf)?;
+                if read == 0 {
+                    break
+                }
+                hasher.update(&buf[..read]);
+            }
+        }
+        Ok(hasher.finalize().into())
+    }
+
     /// Writes all necessary configuration to file.
     fn freeze_config(&self) -> Result<(), NippyJarError> {
         // Atomic writes are hard: <https://github.com/paradigmxyz/reth/issues/8622>
@@ -439,8 +477,8 @@ impl<H: NippyJarHeader> NippyJar<H> {
         // Append rows to file while holding offsets in memory
         writer.append_rows(columns, total_rows)?;
 
-        // Flushes configuration and offsets to disk
-        writer.commit()?;
+        // Flushes configuration, offsets and checksum to disk
+        writer.commit_with_checksum()?;
 
         debug!(target: "nippy-jar", ?writer, "Finished writing data.");
 
@@ -588,7 +626,7 @@ mod tests {
     use super::*;
     use compression::Compression;
     use rand::{rngs::SmallRng, seq::SliceRandom, RngCore, SeedableRng};
-    use std::{collections::HashSet, fs::OpenOptions};
+    use std::{collections::HashSet, fs, fs::OpenOptions};
 
     type ColumnResults<T> = Vec<ColumnResult<T>>;
     type ColumnValues = Vec<Vec<u8>>;
@@ -1123,6 +1161,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_checksum() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let file_path = tempfile::NamedTempFile::new().unwrap();
+
+        // Freezing stores the checksum of the data and offsets files
+        let nippy = NippyJar::new_without_header(2, file_path.path())
+            .freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows)
+            .unwrap();
+        let checksum = *nippy.checksum().unwrap();
+        assert_eq!(checksum, nippy.compute_checksum().unwrap());
+
+        let loaded = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(loaded.checksum(), Some(&checksum));
+
+        // Corrupting the data file changes its checksum
+        let mut data = fs::read(file_path.path()).unwrap();
+        data[0] ^= 1;
+        fs::write(file_path.path(), &data).unwrap();
+        assert_ne!(loaded.compute_checksum().unwrap(), checksum);
+        data[0] ^= 1;
+        fs::write(file_path.path(), &data).unwrap();
+
+        // Appending rows clears the checksum
+        let mut writer = NippyJarWriter::new(loaded, ConsistencyFailStrategy::Heal).unwrap();
+        writer.append_rows(vec![vec![Ok(&col1[0])], vec![Ok(&col2[0])]], 1).unwrap();
+        writer.commit().unwrap();
+        assert_eq!(NippyJar::load_without_header(file_path.path()).unwrap().checksum(), None);
+
+        // Configurations written before checksums were introduced can still be loaded
+        let config = bincode::serialize(&writer.into_jar()).unwrap();
+        fs::write(
+            file_path.path().with_extension(CONFIG_FILE_EXTENSION),
+            &config[..config.len() - 1],
+        )
+        .unwrap();
+        let loaded = NippyJar::load_without_header(file_path.path()).unwrap();
+        assert_eq!(loaded.rows(), num_rows as usize + 1);
+        assert_eq!(loaded.checksum(), None);
+    }
+
     fn test_append_consistency_partial_commit(
         file_path: &Path,
         col1: &[Vec<u8>],
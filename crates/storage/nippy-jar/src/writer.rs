@@ -193,6 +193,7 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
                     saturating_sub(OFFSET_SIZE_BYTES as u64) / // expected size of the data file
                     (self.jar.columns as u64)) /
                     OFFSET_SIZE_BYTES as u64) as usize;
+                self.jar.checksum = None;
 
                 // Freeze row count changed
                 self.jar.freeze_config()?;
@@ -286,6 +287,7 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         column: Option<ColumnResult<impl AsRef<[u8]>>>,
     ) -> Result<(), NippyJarError> {
         self.dirty = true;
+        self.jar.checksum = None;
 
         match column {
             Some(Ok(value)) => {
@@ -337,6 +339,7 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
     /// Prunes rows from data and offsets file and updates its configuration on disk
     pub fn prune_rows(&mut self, num_rows: usize) -> Result<(), NippyJarError> {
         self.dirty = true;
+        self.jar.checksum = None;
 
         self.offsets_file.flush()?;
         self.data_file.flush()?;
@@ -442,6 +445,18 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         Ok(())
     }
 
+    /// Commits like [`Self::commit`] and stores the checksum of the data and offsets files in the
+    /// configuration. Should be called once the jar is complete, since the checksum is cleared as
+    /// soon as rows are appended or pruned.
+    pub fn commit_with_checksum(&mut self) -> Result<(), NippyJarError> {
+        self.commit()?;
+
+        self.jar.checksum = Some(self.jar.compute_checksum()?);
+        self.jar.freeze_config()?;
+
+        Ok(())
+    }
+
     #[cfg(feature = "test-utils")]
     pub fn commit_without_sync_all(&mut self) -> Result<(), NippyJarError> {
         self.data_file.flush()?;
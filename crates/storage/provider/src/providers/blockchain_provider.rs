@@ -1,17 +1,20 @@
 use crate::{
-    providers::StaticFileProvider, AccountReader, BlockHashReader, BlockIdReader, BlockNumReader,
-    BlockReader, BlockReaderIdExt, BlockSource, CanonChainTracker, CanonStateNotifications,
-    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    DatabaseProviderRO, EvmEnvProvider, FinalizedBlockReader, HeaderProvider, ProviderError,
-    ProviderFactory, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
-    RequestsProvider, StageCheckpointReader, StateProviderBox, StateProviderFactory,
-    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    providers::StaticFileProvider, AccountReader, AddressTransactionsReader, BadBlockReader,
+    BadBlockWriter, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
+    BlockSource, CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions,
+    ChainSpecProvider, ChangeSetReader, DatabaseCompactor, DatabaseProviderFactory,
+    DatabaseProviderRO, EvmEnvProvider, FinalizedBlockReader, HeaderProvider, HistoryReader,
+    LogIndexReader, ProviderError, ProviderFactory, PruneCheckpointReader, ReceiptProvider,
+    ReceiptProviderIdExt, RequestsProvider, StageCheckpointReader, StateProviderBox,
+    StateProviderFactory, StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
+    WithdrawalsProvider,
 };
 use alloy_rpc_types_engine::ForkchoiceState;
 use reth_chain_state::{BlockState, CanonicalInMemoryState, MemoryOverlayStateProvider};
 use reth_chainspec::{ChainInfo, ChainSpec};
 use reth_db_api::{
     database::Database,
+    database_compaction::DatabaseCompaction,
     models::{AccountBeforeTx, StoredBlockBodyIndices},
 };
 use reth_evm::ConfigureEvmEnv;
@@ -26,7 +29,9 @@ use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_errors::provider::ProviderResult;
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
+    collections::BTreeSet,
     ops::{Add, Bound, RangeBounds, RangeInclusive, Sub},
+    path::PathBuf,
     sync::Arc,
     time::Instant,
 };
@@ -797,6 +802,14 @@ where
         id: BlockHashOrNumber,
         timestamp: u64,
     ) -> ProviderResult<Option<Withdrawals>> {
+        let block_state = match id {
+            BlockHashOrNumber::Hash(hash) => self.canonical_in_memory_state.state_by_hash(hash),
+            BlockHashOrNumber::Number(num) => self.canonical_in_memory_state.state_by_number(num),
+        };
+        if let Some(block_state) = block_state {
+            return Ok(block_state.block().block().withdrawals.clone())
+        }
+
         self.database.withdrawals_by_block(id, timestamp)
     }
 
@@ -814,6 +827,14 @@ where
         id: BlockHashOrNumber,
         timestamp: u64,
     ) -> ProviderResult<Option<reth_primitives::Requests>> {
+        let block_state = match id {
+            BlockHashOrNumber::Hash(hash) => self.canonical_in_memory_state.state_by_hash(hash),
+            BlockHashOrNumber::Number(num) => self.canonical_in_memory_state.state_by_number(num),
+        };
+        if let Some(block_state) = block_state {
+            return Ok(block_state.block().block().requests.clone())
+        }
+
         self.database.requests_by_block(id, timestamp)
     }
 }
@@ -849,7 +870,9 @@ where
     where
         EvmConfig: ConfigureEvmEnv,
     {
-        self.database.provider()?.fill_env_at(cfg, block_env, at, evm_config)
+        let hash = self.convert_number(at)?.ok_or(ProviderError::HeaderNotFound(at))?;
+        let header = self.header(&hash)?.ok_or(ProviderError::HeaderNotFound(at))?;
+        self.fill_env_with_header(cfg, block_env, &header, evm_config)
     }
 
     fn fill_env_with_header<EvmConfig>(
@@ -862,7 +885,17 @@ where
     where
         EvmConfig: ConfigureEvmEnv,
     {
-        self.database.provider()?.fill_env_with_header(cfg, block_env, header, evm_config)
+        let total_difficulty = self
+            .header_td_by_number(header.number)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(header.number.into()))?;
+        evm_config.fill_cfg_and_block_env(
+            cfg,
+            block_env,
+            &self.database.chain_spec(),
+            header,
+            total_difficulty,
+        );
+        Ok(())
     }
 
     fn fill_cfg_env_at<EvmConfig>(
@@ -874,7 +907,9 @@ where
     where
         EvmConfig: ConfigureEvmEnv,
     {
-        self.database.provider()?.fill_cfg_env_at(cfg, at, evm_config)
+        let hash = self.convert_number(at)?.ok_or(ProviderError::HeaderNotFound(at))?;
+        let header = self.header(&hash)?.ok_or(ProviderError::HeaderNotFound(at))?;
+        self.fill_cfg_env_with_header(cfg, &header, evm_config)
     }
 
     fn fill_cfg_env_with_header<EvmConfig>(
@@ -886,7 +921,11 @@ where
     where
         EvmConfig: ConfigureEvmEnv,
     {
-        self.database.provider()?.fill_cfg_env_with_header(cfg, header, evm_config)
+        let total_difficulty = self
+            .header_td_by_number(header.number)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(header.number.into()))?;
+        evm_config.fill_cfg_env(cfg, &self.database.chain_spec(), header, total_difficulty);
+        Ok(())
     }
 }
 
@@ -938,11 +977,19 @@ where
     ) -> ProviderResult<StateProviderBox> {
         trace!(target: "providers::blockchain", ?block_number, "Getting history by block number");
         self.ensure_canonical_block(block_number)?;
+        if let Some(state) = self.canonical_in_memory_state.state_by_number(block_number) {
+            // the block is canonical, but not persisted yet
+            return Ok(Box::new(self.block_state_provider(state)?))
+        }
         self.database.history_by_block_number(block_number)
     }
 
     fn history_by_block_hash(&self, block_hash: BlockHash) -> ProviderResult<StateProviderBox> {
         trace!(target: "providers::blockchain", ?block_hash, "Getting history by block hash");
+        if let Some(state) = self.canonical_in_memory_state.state_by_hash(block_hash) {
+            // the block is canonical, but not persisted yet
+            return Ok(Box::new(self.block_state_provider(state)?))
+        }
         self.database.history_by_block_hash(block_hash)
     }
 
@@ -1170,6 +1217,107 @@ where
     }
 }
 
+impl<DB> BadBlockReader for BlockchainProvider2<DB>
+where
+    DB: Database,
+{
+    fn bad_blocks(&self) -> ProviderResult<Vec<SealedBlock>> {
+        self.database.bad_blocks()
+    }
+
+    fn bad_block_by_hash(&self, hash: B256) -> ProviderResult<Option<SealedBlock>> {
+        self.database.bad_block_by_hash(hash)
+    }
+}
+
+impl<DB> BadBlockWriter for BlockchainProvider2<DB>
+where
+    DB: Database,
+{
+    fn insert_bad_block(&self, block: SealedBlock) -> ProviderResult<()> {
+        self.database.insert_bad_block(block)
+    }
+}
+
+impl<DB> DatabaseCompactor for BlockchainProvider2<DB>
+where
+    DB: Database + DatabaseCompaction,
+{
+    fn compact_database(&self) -> ProviderResult<PathBuf> {
+        self.database.compact_database()
+    }
+}
+
+impl<DB> HistoryReader for BlockchainProvider2<DB>
+where
+    DB: Database,
+{
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.account_history_blocks(address, range)
+    }
+
+    fn storage_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.storage_history_blocks(address, range)
+    }
+
+    fn changed_accounts_in_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        self.database.provider()?.changed_accounts_in_range(range)
+    }
+}
+
+impl<DB> LogIndexReader for BlockchainProvider2<DB>
+where
+    DB: Database,
+{
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.database.provider()?.log_index_range()
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.log_address_blocks(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.log_topic_blocks(topic, range)
+    }
+}
+
+impl<DB> AddressTransactionsReader for BlockchainProvider2<DB>
+where
+    DB: Database,
+{
+    fn address_transactions_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.database.provider()?.address_transactions_range()
+    }
+
+    fn address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.database.provider()?.address_transactions(address, range)
+    }
+}
+
 impl<DB> AccountReader for BlockchainProvider2<DB>
 where
     DB: Database + Sync + Send,
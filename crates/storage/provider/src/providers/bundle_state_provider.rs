@@ -3,10 +3,10 @@ use std::collections::HashMap;
 use crate::{
     AccountReader, BlockHashReader, ExecutionDataProvider, StateProvider, StateRootProvider,
 };
-use reth_primitives::{Account, Address, BlockNumber, Bytecode, Bytes, B256};
-use reth_storage_api::StateProofProvider;
+use reth_primitives::{keccak256, Account, Address, BlockNumber, Bytecode, Bytes, B256, U256};
+use reth_storage_api::{HashedStateRange, StateProofProvider, StateRangeProvider};
 use reth_storage_errors::provider::ProviderResult;
-use reth_trie::{updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage};
+use reth_trie::{updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, TrieAccount};
 use revm::db::BundleState;
 
 /// A state provider that resolves to data from either a wrapped [`crate::ExecutionOutcome`]
@@ -141,6 +141,69 @@ impl<SP: StateProvider, EDP: ExecutionDataProvider> StateProofProvider
     }
 }
 
+impl<SP: StateProvider, EDP: ExecutionDataProvider> StateRangeProvider
+    for BundleStateProvider<SP, EDP>
+{
+    fn hashed_account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStateRange<TrieAccount>> {
+        let bundle_state = self.block_execution_data_provider.execution_outcome().state();
+        let mut state = HashedPostState::from_bundle_state(&bundle_state.state);
+        state.extend(hashed_state);
+        self.state_provider.hashed_account_range(state, start, limit)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_state: HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStateRange<U256>> {
+        let bundle_state = self.block_execution_data_provider.execution_outcome().state();
+        let mut state = HashedPostState::from_bundle_state(&bundle_state.state);
+        state.extend(hashed_state);
+        self.state_provider.hashed_storage_range(state, hashed_address, start, limit)
+    }
+
+    fn account_preimages(
+        &self,
+        hashed_addresses: &[B256],
+    ) -> ProviderResult<HashMap<B256, Address>> {
+        let mut preimages = self.state_provider.account_preimages(hashed_addresses)?;
+        let bundle_state = self.block_execution_data_provider.execution_outcome().state();
+        for address in bundle_state.state.keys() {
+            let hashed_address = keccak256(address);
+            if hashed_addresses.contains(&hashed_address) {
+                preimages.insert(hashed_address, *address);
+            }
+        }
+        Ok(preimages)
+    }
+
+    fn storage_preimages(
+        &self,
+        address: Address,
+        hashed_slots: &[B256],
+    ) -> ProviderResult<HashMap<B256, B256>> {
+        let mut preimages = self.state_provider.storage_preimages(address, hashed_slots)?;
+        let bundle_state = self.block_execution_data_provider.execution_outcome().state();
+        if let Some(account) = bundle_state.state.get(&address) {
+            for slot in account.storage.keys() {
+                let slot = B256::from(*slot);
+                let hashed_slot = keccak256(slot);
+                if hashed_slots.contains(&hashed_slot) {
+                    preimages.insert(hashed_slot, slot);
+                }
+            }
+        }
+        Ok(preimages)
+    }
+}
+
 impl<SP: StateProvider, EDP: ExecutionDataProvider> StateProvider for BundleStateProvider<SP, EDP> {
     fn storage(
         &self,
@@ -0,0 +1,51 @@
+use crate::MAX_BAD_BLOCKS;
+use parking_lot::RwLock;
+use reth_primitives::{SealedBlock, B256};
+use std::sync::Arc;
+
+/// A bounded in-memory store of the blocks that failed validation, shared between all clones of
+/// the [`ProviderFactory`](super::ProviderFactory).
+///
+/// Blocks are recorded here by the engine without touching the database, and persisted to
+/// [`tables::BadBlocks`](reth_db::tables::BadBlocks) separately, e.g. by the persistence task. Like
+/// the table, the store keeps the [`MAX_BAD_BLOCKS`] highest blocks.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BadBlocks {
+    /// The blocks, from the highest to the lowest block number.
+    inner: Arc<RwLock<Vec<SealedBlock>>>,
+}
+
+impl BadBlocks {
+    /// Records a bad block, dropping the lowest block once the limit is reached.
+    pub(crate) fn insert(&self, block: SealedBlock) {
+        let mut blocks = self.inner.write();
+        if blocks.iter().all(|tracked| tracked.hash() != block.hash()) {
+            blocks.push(block);
+            sort_bad_blocks(&mut blocks);
+        }
+    }
+
+    /// Returns the tracked bad block with the given hash.
+    pub(crate) fn get(&self, hash: &B256) -> Option<SealedBlock> {
+        self.inner.read().iter().find(|block| block.hash() == *hash).cloned()
+    }
+
+    /// Returns the given blocks merged with the tracked ones, from the highest to the lowest block
+    /// number, without duplicates and limited to [`MAX_BAD_BLOCKS`].
+    pub(crate) fn merge(&self, mut blocks: Vec<SealedBlock>) -> Vec<SealedBlock> {
+        for block in self.inner.read().iter() {
+            if blocks.iter().all(|tracked| tracked.hash() != block.hash()) {
+                blocks.push(block.clone());
+            }
+        }
+        sort_bad_blocks(&mut blocks);
+        blocks
+    }
+}
+
+/// Sorts the bad blocks from the highest to the lowest block number, and drops the lowest blocks
+/// beyond [`MAX_BAD_BLOCKS`].
+pub(crate) fn sort_bad_blocks(blocks: &mut Vec<SealedBlock>) {
+    blocks.sort_unstable_by(|a, b| b.number.cmp(&a.number).then(b.hash().cmp(&a.hash())));
+    blocks.truncate(MAX_BAD_BLOCKS);
+}
@@ -2,14 +2,18 @@ use crate::{
     providers::{state::latest::LatestStateProvider, StaticFileProvider},
     to_range,
     traits::{BlockSource, ReceiptProvider},
-    BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
-    EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, ProviderError,
-    PruneCheckpointReader, RequestsProvider, StageCheckpointReader, StateProviderBox,
-    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    AddressTransactionsReader, BadBlockReader, BadBlockWriter, BlockHashReader, BlockNumReader,
+    BlockReader, ChainSpecProvider, DatabaseCompactor, DatabaseProviderFactory, EvmEnvProvider,
+    HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, HistoryReader, LogIndexReader,
+    ProviderError, PruneCheckpointReader, RequestsProvider, StageCheckpointReader,
+    StateProviderBox, StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
+    WithdrawalsProvider,
 };
 use reth_chainspec::{ChainInfo, ChainSpec};
 use reth_db::{init_db, mdbx::DatabaseArguments, DatabaseEnv};
-use reth_db_api::{database::Database, models::StoredBlockBodyIndices};
+use reth_db_api::{
+    database::Database, database_compaction::DatabaseCompaction, models::StoredBlockBodyIndices,
+};
 use reth_errors::{RethError, RethResult};
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
@@ -23,17 +27,23 @@ use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_errors::provider::ProviderResult;
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
+    collections::BTreeSet,
     ops::{RangeBounds, RangeInclusive},
-    path::Path,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 use tokio::sync::watch;
 use tracing::trace;
 
+mod bad_blocks;
 mod metrics;
 mod provider;
 
-pub use provider::{DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW};
+use bad_blocks::BadBlocks;
+
+pub use provider::{
+    transaction_addresses, DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW,
+};
 
 /// A common provider that fetches data from a database or static file.
 ///
@@ -48,6 +58,11 @@ pub struct ProviderFactory<DB> {
     static_file_provider: StaticFileProvider,
     /// Optional pruning configuration
     prune_modes: PruneModes,
+    /// Whether the address transactions index includes internal calls, see
+    /// [`ProviderFactory::with_address_transactions_internal_calls`].
+    address_transactions_internal_calls: bool,
+    /// The bad blocks recorded through the factory, shared between all its clones.
+    bad_blocks: BadBlocks,
 }
 
 impl<DB> ProviderFactory<DB> {
@@ -57,7 +72,14 @@ impl<DB> ProviderFactory<DB> {
         chain_spec: Arc<ChainSpec>,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { db: Arc::new(db), chain_spec, static_file_provider, prune_modes: PruneModes::none() }
+        Self {
+            db: Arc::new(db),
+            chain_spec,
+            static_file_provider,
+            prune_modes: PruneModes::none(),
+            address_transactions_internal_calls: false,
+            bad_blocks: BadBlocks::default(),
+        }
     }
 
     /// Enables metrics on the static file provider.
@@ -72,6 +94,15 @@ impl<DB> ProviderFactory<DB> {
         self
     }
 
+    /// Sets whether the address transactions index includes the addresses of internal calls.
+    ///
+    /// Internal calls can only be indexed by re-executing the blocks in the stage, so inserted
+    /// blocks don't extend the index in that case.
+    pub const fn with_address_transactions_internal_calls(mut self, internal_calls: bool) -> Self {
+        self.address_transactions_internal_calls = internal_calls;
+        self
+    }
+
     /// Returns reference to the underlying database.
     pub fn db_ref(&self) -> &DB {
         &self.db
@@ -98,6 +129,8 @@ impl ProviderFactory<DatabaseEnv> {
             chain_spec,
             static_file_provider,
             prune_modes: PruneModes::none(),
+            address_transactions_internal_calls: false,
+            bad_blocks: BadBlocks::default(),
         })
     }
 }
@@ -125,12 +158,15 @@ impl<DB: Database> ProviderFactory<DB> {
     /// open.
     #[track_caller]
     pub fn provider_rw(&self) -> ProviderResult<DatabaseProviderRW<DB>> {
-        Ok(DatabaseProviderRW(DatabaseProvider::new_rw(
-            self.db.tx_mut()?,
-            self.chain_spec.clone(),
-            self.static_file_provider.clone(),
-            self.prune_modes.clone(),
-        )))
+        Ok(DatabaseProviderRW(
+            DatabaseProvider::new_rw(
+                self.db.tx_mut()?,
+                self.chain_spec.clone(),
+                self.static_file_provider.clone(),
+                self.prune_modes.clone(),
+            )
+            .with_address_transactions_internal_calls(self.address_transactions_internal_calls),
+        ))
     }
 
     /// State provider for latest block
@@ -578,6 +614,73 @@ where
     }
 }
 
+impl<DB: DatabaseCompaction + Send + Sync> DatabaseCompactor for ProviderFactory<DB> {
+    fn compact_database(&self) -> ProviderResult<PathBuf> {
+        Ok(self.db.compact()?)
+    }
+}
+
+impl<DB: Database> HistoryReader for ProviderFactory<DB> {
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.account_history_blocks(address, range)
+    }
+
+    fn storage_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.storage_history_blocks(address, range)
+    }
+
+    fn changed_accounts_in_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        self.provider()?.changed_accounts_in_range(range)
+    }
+}
+
+impl<DB: Database> LogIndexReader for ProviderFactory<DB> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.provider()?.log_index_range()
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.log_address_blocks(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.log_topic_blocks(topic, range)
+    }
+}
+
+impl<DB: Database> AddressTransactionsReader for ProviderFactory<DB> {
+    fn address_transactions_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.provider()?.address_transactions_range()
+    }
+
+    fn address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.provider()?.address_transactions(address, range)
+    }
+}
+
 impl<DB: Database> PruneCheckpointReader for ProviderFactory<DB> {
     fn get_prune_checkpoint(
         &self,
@@ -591,6 +694,30 @@ impl<DB: Database> PruneCheckpointReader for ProviderFactory<DB> {
     }
 }
 
+/// Reads the bad blocks recorded through the factory, together with the ones persisted in the
+/// database.
+impl<DB: Database> BadBlockReader for ProviderFactory<DB> {
+    fn bad_blocks(&self) -> ProviderResult<Vec<SealedBlock>> {
+        Ok(self.bad_blocks.merge(self.provider()?.bad_blocks()?))
+    }
+
+    fn bad_block_by_hash(&self, hash: B256) -> ProviderResult<Option<SealedBlock>> {
+        if let Some(block) = self.bad_blocks.get(&hash) {
+            return Ok(Some(block))
+        }
+        self.provider()?.bad_block_by_hash(hash)
+    }
+}
+
+/// Records bad blocks in memory only, so that it never opens a write transaction. They are
+/// persisted with the [`BadBlockWriter`] of [`DatabaseProviderRW`].
+impl<DB: Database> BadBlockWriter for ProviderFactory<DB> {
+    fn insert_bad_block(&self, block: SealedBlock) -> ProviderResult<()> {
+        self.bad_blocks.insert(block);
+        Ok(())
+    }
+}
+
 impl<DB> Clone for ProviderFactory<DB> {
     fn clone(&self) -> Self {
         Self {
@@ -598,6 +725,8 @@ impl<DB> Clone for ProviderFactory<DB> {
             chain_spec: self.chain_spec.clone(),
             static_file_provider: self.static_file_provider.clone(),
             prune_modes: self.prune_modes.clone(),
+            address_transactions_internal_calls: self.address_transactions_internal_calls,
+            bad_blocks: self.bad_blocks.clone(),
         }
     }
 }
@@ -607,7 +736,9 @@ mod tests {
     use crate::{
         providers::{StaticFileProvider, StaticFileWriter},
         test_utils::create_test_provider_factory,
-        BlockHashReader, BlockNumReader, BlockWriter, HeaderSyncGapProvider, TransactionsProvider,
+        AccountExtReader, BlockHashReader, BlockNumReader, BlockWriter, ExecutionOutcome,
+        HeaderSyncGapProvider, HistoryWriter, PruneCheckpointWriter, StageCheckpointWriter,
+        TransactionsProvider, MAX_BAD_BLOCKS,
     };
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
@@ -617,8 +748,18 @@ mod tests {
         mdbx::DatabaseArguments,
         tables,
         test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
+        BlockNumberList,
+    };
+    use reth_db_api::{
+        models::{
+            storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress, ShardedKey,
+        },
+        transaction::DbTxMut,
+    };
+    use reth_primitives::{
+        hex_literal::hex, Bytes, Log, Receipt, SealedBlock, StaticFileSegment, StorageEntry,
+        TxNumber, B256, U256,
     };
-    use reth_primitives::{hex_literal::hex, SealedBlock, StaticFileSegment, TxNumber, B256, U256};
     use reth_prune_types::{PruneMode, PruneModes};
     use reth_storage_errors::provider::ProviderError;
     use reth_testing_utils::{
@@ -709,6 +850,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn insert_block_with_address_transactions_internal_calls() {
+        let mut rng = generators::rng();
+        let genesis = random_block(&mut rng, 0, None, Some(0), None);
+        let block = random_block(&mut rng, 1, Some(genesis.hash()), Some(1), None);
+
+        for internal_calls in [false, true] {
+            let factory = create_test_provider_factory()
+                .with_address_transactions_internal_calls(internal_calls);
+            let provider = factory.provider_rw().unwrap();
+            provider.insert_block(genesis.clone().try_seal_with_senders().unwrap()).unwrap();
+            provider
+                .save_stage_checkpoint(StageId::IndexAddressTransactions, StageCheckpoint::new(0))
+                .unwrap();
+            provider.insert_block(block.clone().try_seal_with_senders().unwrap()).unwrap();
+
+            // The index is only extended by inserted blocks if it doesn't include internal calls
+            let checkpoint =
+                provider.get_stage_checkpoint(StageId::IndexAddressTransactions).unwrap();
+            assert_eq!(
+                checkpoint.map(|checkpoint| checkpoint.block_number),
+                Some(if internal_calls { 0 } else { 1 })
+            );
+        }
+    }
+
     #[test]
     fn take_block_transaction_range_recover_senders() {
         let factory = create_test_provider_factory();
@@ -782,4 +949,282 @@ mod tests {
         assert_eq!(gap.local_head, head);
         assert_eq!(gap.target.tip(), consensus_tip.into());
     }
+
+    #[test]
+    fn account_history_blocks_lookup() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let other = Address::with_last_byte(2);
+
+        {
+            let provider = factory.provider_rw().unwrap();
+            let tx = provider.tx_ref();
+            tx.put::<tables::AccountsHistory>(
+                ShardedKey::new(address, 7),
+                BlockNumberList::new([1, 3, 7]).unwrap(),
+            )
+            .unwrap();
+            tx.put::<tables::AccountsHistory>(
+                ShardedKey::new(address, u64::MAX),
+                BlockNumberList::new([10, 15]).unwrap(),
+            )
+            .unwrap();
+            tx.put::<tables::AccountsHistory>(
+                ShardedKey::new(other, u64::MAX),
+                BlockNumberList::new([2, 4]).unwrap(),
+            )
+            .unwrap();
+            provider.commit().unwrap();
+        }
+
+        assert_eq!(factory.account_history_blocks(address, 0..=20).unwrap(), vec![1, 3, 7, 10, 15]);
+        assert_eq!(factory.account_history_blocks(address, 3..=10).unwrap(), vec![3, 7, 10]);
+        assert_eq!(factory.account_history_blocks(address, 8..=9).unwrap(), Vec::<u64>::new());
+        assert_eq!(factory.account_history_blocks(address, 11..=20).unwrap(), vec![15]);
+        assert_eq!(factory.account_history_blocks(other, 0..=3).unwrap(), vec![2]);
+        assert_eq!(
+            factory.account_history_blocks(Address::with_last_byte(3), 0..=20).unwrap(),
+            Vec::<u64>::new()
+        );
+    }
+
+    #[test]
+    fn storage_history_blocks_lookup() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let other = Address::with_last_byte(2);
+        let (slot, other_slot) = (B256::with_last_byte(1), B256::with_last_byte(2));
+
+        {
+            let provider = factory.provider_rw().unwrap();
+            let tx = provider.tx_ref();
+            tx.put::<tables::StoragesHistory>(
+                StorageShardedKey::new(address, slot, 7),
+                BlockNumberList::new([1, 3, 7]).unwrap(),
+            )
+            .unwrap();
+            tx.put::<tables::StoragesHistory>(
+                StorageShardedKey::new(address, slot, u64::MAX),
+                BlockNumberList::new([10, 15]).unwrap(),
+            )
+            .unwrap();
+            tx.put::<tables::StoragesHistory>(
+                StorageShardedKey::new(address, other_slot, u64::MAX),
+                BlockNumberList::new([3, 8, 20]).unwrap(),
+            )
+            .unwrap();
+            tx.put::<tables::StoragesHistory>(
+                StorageShardedKey::new(other, slot, u64::MAX),
+                BlockNumberList::new([2, 4]).unwrap(),
+            )
+            .unwrap();
+            provider.commit().unwrap();
+        }
+
+        assert_eq!(
+            factory.storage_history_blocks(address, 0..=30).unwrap(),
+            vec![1, 3, 7, 8, 10, 15, 20]
+        );
+        assert_eq!(factory.storage_history_blocks(address, 3..=10).unwrap(), vec![3, 7, 8, 10]);
+        assert_eq!(factory.storage_history_blocks(address, 11..=14).unwrap(), Vec::<u64>::new());
+        assert_eq!(factory.storage_history_blocks(address, 9..=20).unwrap(), vec![10, 15, 20]);
+        assert_eq!(factory.storage_history_blocks(other, 0..=3).unwrap(), vec![2]);
+        assert_eq!(
+            factory.storage_history_blocks(Address::with_last_byte(3), 0..=20).unwrap(),
+            Vec::<u64>::new()
+        );
+    }
+
+    #[test]
+    fn changed_accounts_in_range_lookup() {
+        let factory = create_test_provider_factory();
+        let account = Address::with_last_byte(1);
+        let storage = Address::with_last_byte(2);
+        let both = Address::with_last_byte(3);
+
+        {
+            let provider = factory.provider_rw().unwrap();
+            let tx = provider.tx_ref();
+            for (block, address) in [(1, account), (2, both), (4, account)] {
+                tx.put::<tables::AccountChangeSets>(block, AccountBeforeTx { address, info: None })
+                    .unwrap();
+            }
+            for (block, address) in [(2, both), (3, storage)] {
+                tx.put::<tables::StorageChangeSets>(
+                    BlockNumberAddress((block, address)),
+                    StorageEntry::default(),
+                )
+                .unwrap();
+            }
+            provider.commit().unwrap();
+        }
+
+        assert_eq!(
+            factory.changed_accounts_in_range(1..=4).unwrap(),
+            BTreeSet::from([account, storage, both])
+        );
+        assert_eq!(factory.changed_accounts_in_range(2..=2).unwrap(), BTreeSet::from([both]));
+        assert_eq!(
+            factory.changed_accounts_in_range(3..=4).unwrap(),
+            BTreeSet::from([account, storage])
+        );
+        assert_eq!(factory.changed_accounts_in_range(5..=10).unwrap(), BTreeSet::new());
+    }
+
+    #[test]
+    fn remove_state_prunes_static_file_change_sets() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+
+        // changesets of blocks 0..=3 have been moved to static files
+        {
+            let static_file_provider = factory.static_file_provider();
+            let mut writer =
+                static_file_provider.get_writer(0, StaticFileSegment::StorageChangeSets).unwrap();
+            for block in 0..=3 {
+                writer.append_storage_changeset(block, &Default::default()).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+        {
+            let provider = factory.provider_rw().unwrap();
+            let tx = provider.tx_ref();
+            for block in 3..=5 {
+                tx.put::<tables::BlockBodyIndices>(block, Default::default()).unwrap();
+                tx.put::<tables::AccountChangeSets>(block, AccountBeforeTx { address, info: None })
+                    .unwrap();
+            }
+            provider.commit().unwrap();
+        }
+
+        // the unwound changesets are pruned from static files once they are committed
+        let provider = factory.provider_rw().unwrap();
+        assert_matches!(provider.remove_state(3..=5), Ok(()));
+        assert_eq!(provider.changed_accounts_with_range(3..=5).unwrap(), BTreeSet::new());
+        provider.commit().unwrap();
+        let static_file_provider = factory.static_file_provider();
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
+            Some(3)
+        );
+        static_file_provider.commit().unwrap();
+        assert_eq!(
+            static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn log_index_update_and_lookup() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let topic = B256::with_last_byte(2);
+        let receipt = |address: Address, topics: Vec<B256>| Receipt {
+            success: true,
+            logs: vec![Log::new_unchecked(address, topics, Bytes::default())],
+            ..Default::default()
+        };
+        let outcome = |first_block: u64, receipts: Vec<Vec<Option<Receipt>>>| {
+            ExecutionOutcome::new(Default::default(), receipts.into(), first_block, Vec::new())
+        };
+
+        // the log index isn't maintained until the stage has run
+        let provider = factory.provider_rw().unwrap();
+        provider.update_log_index(&outcome(1, vec![vec![Some(receipt(address, vec![]))]])).unwrap();
+        assert_eq!(provider.log_index_range().unwrap(), None);
+        assert_eq!(provider.log_address_blocks(address, 0..=10).unwrap(), Vec::<u64>::new());
+
+        provider.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(0)).unwrap();
+        provider
+            .update_log_index(&outcome(
+                1,
+                vec![
+                    vec![Some(receipt(address, vec![topic])), Some(receipt(address, vec![]))],
+                    vec![],
+                    vec![Some(receipt(Address::with_last_byte(3), vec![topic, topic]))],
+                ],
+            ))
+            .unwrap();
+        assert_eq!(provider.log_index_range().unwrap(), Some(0..=3));
+        assert_eq!(provider.log_address_blocks(address, 0..=10).unwrap(), vec![1]);
+        assert_eq!(provider.log_topic_blocks(topic, 0..=10).unwrap(), vec![1, 3]);
+        assert_eq!(provider.log_topic_blocks(topic, 2..=10).unwrap(), vec![3]);
+
+        // blocks that don't follow the checkpoint are not indexed
+        provider.update_log_index(&outcome(5, vec![vec![Some(receipt(address, vec![]))]])).unwrap();
+        assert_eq!(provider.log_index_range().unwrap(), Some(0..=3));
+        assert_eq!(provider.log_address_blocks(address, 0..=10).unwrap(), vec![1]);
+
+        // pruned blocks are not covered
+        provider
+            .save_prune_checkpoint(
+                PruneSegment::LogIndex,
+                PruneCheckpoint {
+                    block_number: Some(1),
+                    tx_number: None,
+                    prune_mode: PruneMode::Before(2),
+                },
+            )
+            .unwrap();
+        assert_eq!(provider.log_index_range().unwrap(), Some(2..=3));
+    }
+
+    #[test]
+    fn bad_blocks_keep_highest() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+        let blocks = (0..=MAX_BAD_BLOCKS as u64)
+            .map(|number| random_block(&mut rng, number, None, Some(1), None))
+            .collect::<Vec<_>>();
+
+        for block in blocks.iter().skip(1) {
+            factory.insert_bad_block(block.clone()).unwrap();
+        }
+        // the lowest block doesn't fit anymore
+        factory.insert_bad_block(blocks[0].clone()).unwrap();
+
+        let expected = blocks.iter().skip(1).rev().cloned().collect::<Vec<_>>();
+        assert_eq!(factory.bad_blocks().unwrap(), expected);
+        assert_eq!(factory.bad_block_by_hash(blocks[0].hash()).unwrap(), None);
+        assert_eq!(factory.bad_block_by_hash(blocks[1].hash()).unwrap(), Some(blocks[1].clone()));
+
+        // higher blocks evict the lowest one
+        let block = random_block(&mut rng, MAX_BAD_BLOCKS as u64 + 1, None, Some(1), None);
+        factory.insert_bad_block(block.clone()).unwrap();
+        let bad_blocks = factory.bad_blocks().unwrap();
+        assert_eq!(bad_blocks.len(), MAX_BAD_BLOCKS);
+        assert_eq!(bad_blocks.first(), Some(&block));
+        assert_eq!(factory.bad_block_by_hash(blocks[1].hash()).unwrap(), None);
+
+        // the blocks recorded through the factory are only kept in memory
+        assert!(factory.provider().unwrap().bad_blocks().unwrap().is_empty());
+    }
+
+    #[test]
+    fn bad_blocks_merge_persisted() {
+        let factory = create_test_provider_factory();
+        let mut rng = generators::rng();
+        let blocks = (0..=MAX_BAD_BLOCKS as u64)
+            .map(|number| random_block(&mut rng, number, None, Some(1), None))
+            .collect::<Vec<_>>();
+
+        // the even blocks are persisted, and the odd ones recorded in memory
+        let provider = factory.provider_rw().unwrap();
+        for block in blocks.iter().step_by(2) {
+            provider.insert_bad_block(block.clone()).unwrap();
+        }
+        provider.commit().unwrap();
+        for block in blocks.iter().skip(1).step_by(2) {
+            factory.insert_bad_block(block.clone()).unwrap();
+        }
+        // a block recorded in memory and persisted is returned once
+        factory.insert_bad_block(blocks[2].clone()).unwrap();
+
+        let expected = blocks.iter().skip(1).rev().cloned().collect::<Vec<_>>();
+        assert_eq!(factory.bad_blocks().unwrap(), expected);
+        assert_eq!(factory.bad_block_by_hash(blocks[1].hash()).unwrap(), Some(blocks[1].clone()));
+        assert_eq!(factory.bad_block_by_hash(blocks[2].hash()).unwrap(), Some(blocks[2].clone()));
+    }
 }
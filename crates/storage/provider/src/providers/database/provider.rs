@@ -6,15 +6,17 @@ use crate::{
         AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
     },
     writer::UnifiedStorageWriter,
-    AccountReader, BlockExecutionReader, BlockExecutionWriter, BlockHashReader, BlockNumReader,
-    BlockReader, BlockWriter, BundleStateInit, EvmEnvProvider, FinalizedBlockReader,
-    FinalizedBlockWriter, HashingWriter, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
-    HistoricalStateProvider, HistoryWriter, LatestStateProvider, OriginalValuesKnown,
-    ProviderError, PruneCheckpointReader, PruneCheckpointWriter, RequestsProvider, RevertsInit,
+    AccountReader, AddressTransactionsReader, BadBlockReader, BadBlockWriter, BlockExecutionReader,
+    BlockExecutionWriter, BlockHashReader, BlockNumReader, BlockReader, BlockWriter,
+    BundleStateInit, EvmEnvProvider, FinalizedBlockReader, FinalizedBlockWriter, HashingWriter,
+    HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, HistoricalStateProvider, HistoryReader,
+    HistoryWriter, LatestStateProvider, LogIndexReader, OriginalValuesKnown, ProviderError,
+    PruneCheckpointReader, PruneCheckpointWriter, RequestsProvider, RevertsInit,
     StageCheckpointReader, StateChangeWriter, StateProviderBox, StateWriter, StatsReader,
     StorageReader, StorageTrieWriter, TransactionVariant, TransactionsProvider,
-    TransactionsProviderExt, TrieWriter, WithdrawalsProvider,
+    TransactionsProviderExt, TrieWriter, WithdrawalsProvider, MAX_BAD_BLOCKS,
 };
+use alloy_rlp::{Decodable, Encodable};
 use itertools::{izip, Itertools};
 use rayon::slice::ParallelSliceMut;
 use reth_chainspec::{ChainInfo, ChainSpec, EthereumHardforks};
@@ -39,9 +41,9 @@ use reth_network_p2p::headers::downloader::SyncTarget;
 use reth_primitives::{
     keccak256, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber,
     BlockWithSenders, Bytecode, GotExpected, Header, Receipt, Requests, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, StaticFileSegment, StorageEntry, TransactionMeta,
-    TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber,
-    Withdrawal, Withdrawals, B256, U256,
+    SealedBlockWithSenders, SealedHeader, StaticFileSegment, StorageEntry, Transaction,
+    TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash,
+    TxHash, TxKind, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneLimiter, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -115,6 +117,9 @@ pub struct DatabaseProvider<TX> {
     static_file_provider: StaticFileProvider,
     /// Pruning configuration
     prune_modes: PruneModes,
+    /// Whether the address transactions index includes internal calls, in which case inserted
+    /// blocks don't extend it.
+    address_transactions_internal_calls: bool,
 }
 
 impl<TX> DatabaseProvider<TX> {
@@ -137,7 +142,22 @@ impl<TX: DbTxMut> DatabaseProvider<TX> {
         static_file_provider: StaticFileProvider,
         prune_modes: PruneModes,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes }
+        Self {
+            tx,
+            chain_spec,
+            static_file_provider,
+            prune_modes,
+            address_transactions_internal_calls: false,
+        }
+    }
+
+    /// Sets whether the address transactions index includes the addresses of internal calls.
+    ///
+    /// Internal calls can only be indexed by re-executing the blocks in the stage, so
+    /// [`BlockWriter::insert_block`] leaves the index and its checkpoint untouched in that case.
+    pub const fn with_address_transactions_internal_calls(mut self, internal_calls: bool) -> Self {
+        self.address_transactions_internal_calls = internal_calls;
+        self
     }
 }
 
@@ -267,6 +287,73 @@ where
     Ok(Vec::new())
 }
 
+/// Returns the block (or transaction) numbers within the given range from the history shards of a
+/// key, in ascending order.
+///
+/// Shards are keyed by their highest number, so the walk starts at the first shard with a key at or
+/// above the range start.
+fn history_blocks_in_range<T, C>(
+    cursor: &mut C,
+    start_key: T::Key,
+    shard_belongs_to_key: impl Fn(&T::Key) -> bool,
+    range: RangeInclusive<BlockNumber>,
+) -> ProviderResult<Vec<BlockNumber>>
+where
+    T: Table<Value = BlockNumberList>,
+    C: DbCursorRO<T>,
+{
+    let mut blocks = Vec::new();
+    for entry in cursor.walk(Some(start_key))? {
+        let (sharded_key, list) = entry?;
+        if !shard_belongs_to_key(&sharded_key) {
+            break
+        }
+
+        for block in list.iter().skip_while(|block| block < range.start()) {
+            if block > *range.end() {
+                return Ok(blocks)
+            }
+            blocks.push(block);
+        }
+    }
+
+    Ok(blocks)
+}
+
+/// Collects the log index entries of the given blocks' receipts: the numbers of the blocks with
+/// logs emitted by each address and with logs containing each topic.
+fn log_index_entries<'a>(
+    blocks: impl IntoIterator<Item = (BlockNumber, impl IntoIterator<Item = &'a Receipt>)>,
+) -> (BTreeMap<Address, Vec<u64>>, BTreeMap<B256, Vec<u64>>) {
+    let mut address_blocks = BTreeMap::<Address, Vec<u64>>::new();
+    let mut topic_blocks = BTreeMap::<B256, Vec<u64>>::new();
+    for (block_number, receipts) in blocks {
+        for log in receipts.into_iter().flat_map(|receipt| &receipt.logs) {
+            let blocks = address_blocks.entry(log.address).or_default();
+            if blocks.last() != Some(&block_number) {
+                blocks.push(block_number);
+            }
+            for topic in log.topics() {
+                let blocks = topic_blocks.entry(*topic).or_default();
+                if blocks.last() != Some(&block_number) {
+                    blocks.push(block_number);
+                }
+            }
+        }
+    }
+    (address_blocks, topic_blocks)
+}
+
+/// Returns the addresses a transaction touches directly: its sender, and its recipient or the
+/// contract it creates.
+pub fn transaction_addresses(sender: Address, transaction: &Transaction) -> [Address; 2] {
+    let to = match transaction.kind() {
+        TxKind::Call(to) => to,
+        TxKind::Create => sender.create(transaction.nonce()),
+    };
+    [sender, to]
+}
+
 impl<TX: DbTx> DatabaseProvider<TX> {
     /// Creates a provider with an inner read-only transaction.
     pub const fn new(
@@ -275,7 +362,13 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         static_file_provider: StaticFileProvider,
         prune_modes: PruneModes,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider, prune_modes }
+        Self {
+            tx,
+            chain_spec,
+            static_file_provider,
+            prune_modes,
+            address_transactions_internal_calls: false,
+        }
     }
 
     /// Consume `DbTx` or `DbTxMut`.
@@ -329,6 +422,65 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         self.tx.cursor_read::<T>()?.walk_range(range)?.collect::<Result<Vec<_>, _>>()
     }
 
+    /// Returns the account changesets of a block range.
+    ///
+    /// Blocks whose changesets have already been moved to static files are read from static files,
+    /// only the blocks above them are read from the database.
+    fn account_changesets(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        let range = to_range(range);
+
+        let mut changesets = Vec::new();
+        let mut database_start = range.start;
+        if let Some(static_file_range) = self
+            .static_file_provider
+            .change_sets_in_static_files(StaticFileSegment::AccountChangeSets, range.clone())
+        {
+            database_start = static_file_range.end() + 1;
+            let static_file_changesets =
+                self.static_file_provider.account_changesets_range(static_file_range.clone())?;
+            for (block, changeset) in static_file_range.zip(static_file_changesets) {
+                changesets.extend(changeset.changes.into_iter().map(|change| (block, change)));
+            }
+        }
+        changesets.extend(self.get::<tables::AccountChangeSets>(database_start..range.end)?);
+
+        Ok(changesets)
+    }
+
+    /// Returns the storage changesets of a block range.
+    ///
+    /// Blocks whose changesets have already been moved to static files are read from static files,
+    /// only the blocks above them are read from the database.
+    fn storage_changesets(
+        &self,
+        range: Range<BlockNumberAddress>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        let mut changesets = Vec::new();
+        let mut database_start = range.start;
+        if let Some(static_file_range) = self.static_file_provider.change_sets_in_static_files(
+            StaticFileSegment::StorageChangeSets,
+            range.start.block_number()..range.end.block_number(),
+        ) {
+            database_start = BlockNumberAddress((static_file_range.end() + 1, Address::ZERO));
+            let static_file_changesets =
+                self.static_file_provider.storage_changesets_range(static_file_range.clone())?;
+            for (block, changeset) in static_file_range.zip(static_file_changesets) {
+                changesets.extend(
+                    changeset
+                        .changes
+                        .into_iter()
+                        .map(|change| (BlockNumberAddress((block, change.address)), change.entry)),
+                );
+            }
+        }
+        changesets.extend(self.get::<tables::StorageChangeSets>(database_start..range.end)?);
+
+        Ok(changesets)
+    }
+
     /// Iterates over read only values in the given table and collects them into a vector.
     ///
     /// Early-returns if the range is empty, without opening a cursor transaction.
@@ -798,8 +950,8 @@ impl<TX: DbTx> DatabaseProvider<TX> {
 
         let storage_range = BlockNumberAddress::range(range.clone());
 
-        let storage_changeset = self.get::<tables::StorageChangeSets>(storage_range)?;
-        let account_changeset = self.get::<tables::AccountChangeSets>(range)?;
+        let storage_changeset = self.storage_changesets(storage_range)?;
+        let account_changeset = self.account_changesets(range)?;
 
         // This is not working for blocks that are not at tip. as plain state is not the last
         // state of end range. We should rename the functions or add support to access
@@ -921,6 +1073,23 @@ impl<TX: DbTx> DatabaseProvider<TX> {
 
         Ok((state, reverts))
     }
+
+    /// Returns the range of blocks covered by an optional index, from the block after the last
+    /// pruned one to the stage checkpoint of the index.
+    fn indexed_block_range(
+        &self,
+        stage_id: StageId,
+        prune_segment: PruneSegment,
+    ) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        let Some(checkpoint) = self.get_stage_checkpoint(stage_id)? else { return Ok(None) };
+        let first_unpruned = self
+            .get_prune_checkpoint(prune_segment)?
+            .and_then(|checkpoint| checkpoint.block_number)
+            .map_or(0, |block_number| block_number + 1);
+
+        Ok((first_unpruned <= checkpoint.block_number)
+            .then_some(first_unpruned..=checkpoint.block_number))
+    }
 }
 
 impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
@@ -966,8 +1135,8 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
 
         let storage_range = BlockNumberAddress::range(range.clone());
 
-        let storage_changeset = self.take::<tables::StorageChangeSets>(storage_range)?;
-        let account_changeset = self.take::<tables::AccountChangeSets>(range)?;
+        let storage_changeset = self.take_storage_changesets(storage_range)?;
+        let account_changeset = self.take_account_changesets(range)?;
 
         // This is not working for blocks that are not at tip. as plain state is not the last
         // state of end range. We should rename the functions or add support to access
@@ -1062,8 +1231,8 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
 
         let storage_range = BlockNumberAddress::range(range.clone());
 
-        let storage_changeset = self.take::<tables::StorageChangeSets>(storage_range)?;
-        let account_changeset = self.take::<tables::AccountChangeSets>(range)?;
+        let storage_changeset = self.take_storage_changesets(storage_range)?;
+        let account_changeset = self.take_account_changesets(range)?;
 
         // This is not working for blocks that are not at tip. as plain state is not the last
         // state of end range. We should rename the functions or add support to access
@@ -1155,6 +1324,59 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         Ok(entries)
     }
 
+    /// Takes the account changesets of a block range, see [`Self::account_changesets`].
+    ///
+    /// The changesets are removed from the database, and from static files on the next commit
+    /// of the static file provider.
+    fn take_account_changesets(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        let changesets = self.account_changesets(range.clone())?;
+        self.remove::<tables::AccountChangeSets>(range.clone())?;
+        self.prune_static_file_change_sets(StaticFileSegment::AccountChangeSets, *range.start())?;
+        Ok(changesets)
+    }
+
+    /// Takes the storage changesets of a block range, see [`Self::storage_changesets`].
+    ///
+    /// The changesets are removed from the database, and from static files on the next commit
+    /// of the static file provider.
+    fn take_storage_changesets(
+        &self,
+        range: Range<BlockNumberAddress>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        let changesets = self.storage_changesets(range.clone())?;
+        self.remove::<tables::StorageChangeSets>(range.clone())?;
+        self.prune_static_file_change_sets(
+            StaticFileSegment::StorageChangeSets,
+            range.start.block_number(),
+        )?;
+        Ok(changesets)
+    }
+
+    /// Queues the removal of the static file changesets of all blocks starting from `block`.
+    ///
+    /// Like receipts, the static files are committed after the database. If that commit doesn't
+    /// happen, the rows above the [`StageId::Execution`] checkpoint are pruned by the consistency
+    /// check of the static file provider on the next startup.
+    fn prune_static_file_change_sets(
+        &self,
+        segment: StaticFileSegment,
+        block: BlockNumber,
+    ) -> ProviderResult<()> {
+        if let Some(highest_block) = self
+            .static_file_provider
+            .get_highest_static_file_block(segment)
+            .filter(|highest_block| *highest_block >= block)
+        {
+            self.static_file_provider
+                .latest_writer(segment)?
+                .prune_change_sets(highest_block - block + 1)?;
+        }
+        Ok(())
+    }
+
     /// Return a list of entries from the table, and remove them, based on the given range.
     #[inline]
     pub fn take<T: Table>(
@@ -1685,6 +1907,25 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         }
         Ok(())
     }
+
+    /// Unwinds an optional index for the given block range if it's maintained and covers any of
+    /// its blocks, lowering the stage checkpoint of the index to the block preceding the range.
+    ///
+    /// Must be called before the receipts and block bodies of the range are removed.
+    fn unwind_maintained_index(
+        &self,
+        stage_id: StageId,
+        range: &RangeInclusive<BlockNumber>,
+        unwind: impl FnOnce(RangeInclusive<BlockNumber>) -> ProviderResult<usize>,
+    ) -> ProviderResult<()> {
+        let Some(checkpoint) = self.get_stage_checkpoint(stage_id)? else { return Ok(()) };
+        if checkpoint.block_number < *range.start() {
+            return Ok(())
+        }
+
+        unwind(*range.start()..=checkpoint.block_number.min(*range.end()))?;
+        self.save_stage_checkpoint(stage_id, StageCheckpoint::new(range.start().saturating_sub(1)))
+    }
 }
 
 impl<TX: DbTx> AccountReader for DatabaseProvider<TX> {
@@ -1698,13 +1939,11 @@ impl<TX: DbTx> AccountExtReader for DatabaseProvider<TX> {
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<BTreeSet<Address>> {
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(_, account_before)| account_before.address).map_err(Into::into)
-            })
-            .collect()
+        Ok(self
+            .account_changesets(range)?
+            .into_iter()
+            .map(|(_, account_before)| account_before.address)
+            .collect())
     }
 
     fn basic_accounts(
@@ -1722,16 +1961,13 @@ impl<TX: DbTx> AccountExtReader for DatabaseProvider<TX> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::AccountChangeSets>()?;
-
-        let account_transitions = changeset_cursor.walk_range(range)?.try_fold(
+        let account_transitions = self.account_changesets(range)?.into_iter().fold(
             BTreeMap::new(),
-            |mut accounts: BTreeMap<Address, Vec<u64>>, entry| -> ProviderResult<_> {
-                let (index, account) = entry?;
+            |mut accounts: BTreeMap<Address, Vec<u64>>, (index, account)| {
                 accounts.entry(account.address).or_default().push(index);
-                Ok(accounts)
+                accounts
             },
-        )?;
+        );
 
         Ok(account_transitions)
     }
@@ -1743,14 +1979,11 @@ impl<TX: DbTx> ChangeSetReader for DatabaseProvider<TX> {
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         let range = block_number..=block_number;
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|result| -> ProviderResult<_> {
-                let (_, account_before) = result?;
-                Ok(account_before)
-            })
-            .collect()
+        Ok(self
+            .account_changesets(range)?
+            .into_iter()
+            .map(|(_, account_before)| account_before)
+            .collect())
     }
 }
 
@@ -2307,24 +2540,27 @@ impl<TX: DbTx> TransactionsProvider for DatabaseProvider<TX> {
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<Vec<Vec<TransactionSigned>>> {
-        let mut tx_cursor = self.tx.cursor_read::<tables::Transactions>()?;
-        let mut results = Vec::new();
-        let mut body_cursor = self.tx.cursor_read::<tables::BlockBodyIndices>()?;
-        for entry in body_cursor.walk_range(range)? {
-            let (_, body) = entry?;
-            let tx_num_range = body.tx_num_range();
-            if tx_num_range.is_empty() {
-                results.push(Vec::new());
-            } else {
-                results.push(
-                    self.transactions_by_tx_range_with_cursor(tx_num_range, &mut tx_cursor)?
-                        .into_iter()
-                        .map(Into::into)
-                        .collect(),
-                );
-            }
-        }
-        Ok(results)
+        let bodies = self
+            .tx
+            .cursor_read::<tables::BlockBodyIndices>()?
+            .walk_range(range)?
+            .map(|entry| entry.map(|(_, body)| body))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (Some(first), Some(last)) = (bodies.first(), bodies.last()) else {
+            return Ok(Vec::new())
+        };
+
+        // Read the transactions of all blocks at once, so that consecutive transactions are
+        // streamed from the same static file cursor instead of being looked up block by block.
+        let mut transactions = self
+            .transactions_by_tx_range(first.first_tx_num()..last.next_tx_num())?
+            .into_iter()
+            .map(Into::into);
+
+        Ok(bodies
+            .iter()
+            .map(|body| transactions.by_ref().take(body.tx_count as usize).collect())
+            .collect())
     }
 
     fn transactions_by_tx_range(
@@ -2599,36 +2835,33 @@ impl<TX: DbTx> StorageReader for DatabaseProvider<TX> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>> {
-        self.tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(BlockNumberAddress::range(range))?
+        Ok(self
+            .storage_changesets(BlockNumberAddress::range(range))?
+            .into_iter()
             // fold all storages and save its old state so we can remove it from HashedStorage
             // it is needed as it is dup table.
-            .try_fold(BTreeMap::new(), |mut accounts: BTreeMap<Address, BTreeSet<B256>>, entry| {
-                let (BlockNumberAddress((_, address)), storage_entry) = entry?;
+            .fold(BTreeMap::new(), |mut accounts: BTreeMap<Address, BTreeSet<B256>>, entry| {
+                let (BlockNumberAddress((_, address)), storage_entry) = entry;
                 accounts.entry(address).or_default().insert(storage_entry.key);
-                Ok(accounts)
-            })
+                accounts
+            }))
     }
 
     fn changed_storages_and_blocks_with_range(
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<(Address, B256), Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::StorageChangeSets>()?;
-
         let storage_changeset_lists =
-            changeset_cursor.walk_range(BlockNumberAddress::range(range))?.try_fold(
+            self.storage_changesets(BlockNumberAddress::range(range))?.into_iter().fold(
                 BTreeMap::new(),
-                |mut storages: BTreeMap<(Address, B256), Vec<u64>>, entry| -> ProviderResult<_> {
-                    let (index, storage) = entry?;
+                |mut storages: BTreeMap<(Address, B256), Vec<u64>>, (index, storage)| {
                     storages
                         .entry((index.address(), storage.key))
                         .or_default()
                         .push(index.block_number());
-                    Ok(storages)
+                    storages
                 },
-            )?;
+            );
 
         Ok(storage_changeset_lists)
     }
@@ -2900,12 +3133,9 @@ impl<TX: DbTxMut + DbTx> HashingWriter for DatabaseProvider<TX> {
         // Note that collecting and then reversing the order is necessary to ensure that the
         // changes are applied in the correct order.
         let hashed_accounts = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| entry.map(|(_, e)| (keccak256(e.address), e.info)))
-            .collect::<Result<Vec<_>, _>>()?
+            .account_changesets(range)?
             .into_iter()
+            .map(|(_, e)| (keccak256(e.address), e.info))
             .rev()
             .collect::<BTreeMap<_, _>>();
 
@@ -2944,15 +3174,13 @@ impl<TX: DbTxMut + DbTx> HashingWriter for DatabaseProvider<TX> {
         range: Range<BlockNumberAddress>,
     ) -> ProviderResult<HashMap<B256, BTreeSet<B256>>> {
         // Aggregate all block changesets and make list of accounts that have been changed.
-        let mut changesets = self.tx.cursor_read::<tables::StorageChangeSets>()?;
-        let mut hashed_storages = changesets
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(BlockNumberAddress((_, address)), storage_entry)| {
-                    (keccak256(address), keccak256(storage_entry.key), storage_entry.value)
-                })
+        let mut hashed_storages = self
+            .storage_changesets(range)?
+            .into_iter()
+            .map(|(BlockNumberAddress((_, address)), storage_entry)| {
+                (keccak256(address), keccak256(storage_entry.key), storage_entry.value)
             })
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<Vec<_>>();
         hashed_storages.sort_by_key(|(ha, hk, _)| (*ha, *hk));
 
         // Apply values to HashedState, and remove the account if it's None.
@@ -3096,17 +3324,134 @@ impl<TX: DbTxMut + DbTx> HashingWriter for DatabaseProvider<TX> {
     }
 }
 
+impl<TX: DbTx> HistoryReader for DatabaseProvider<TX> {
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        history_blocks_in_range(
+            &mut self.tx.cursor_read::<tables::AccountsHistory>()?,
+            ShardedKey::new(address, *range.start()),
+            |sharded_key| sharded_key.key == address,
+            range,
+        )
+    }
+
+    fn storage_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let mut cursor = self.tx.cursor_read::<tables::StoragesHistory>()?;
+        let mut blocks = BTreeSet::new();
+        let mut entry = cursor.seek(StorageShardedKey::new(address, B256::ZERO, *range.start()))?;
+        while let Some((key, list)) = entry {
+            if key.address != address {
+                break
+            }
+
+            let slot = key.sharded_key.key;
+            if key.sharded_key.highest_block_number < *range.start() {
+                // skip the shards of the slot below the range
+                entry = cursor.seek(StorageShardedKey::new(address, slot, *range.start()))?;
+                continue
+            }
+
+            blocks.extend(
+                list.iter()
+                    .skip_while(|block| block < range.start())
+                    .take_while(|block| block <= range.end()),
+            );
+
+            entry = if key.sharded_key.highest_block_number < *range.end() {
+                cursor.next()?
+            } else {
+                // the remaining shards of the slot are above the range
+                let Some(next_slot) = U256::from_be_bytes(slot.0).checked_add(U256::from(1)) else {
+                    break
+                };
+                cursor.seek(StorageShardedKey::new(address, next_slot.into(), *range.start()))?
+            };
+        }
+
+        Ok(blocks.into_iter().collect())
+    }
+
+    fn changed_accounts_in_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        let mut accounts = self.changed_accounts_with_range(range.clone())?;
+        accounts.extend(self.changed_storages_with_range(range)?.into_keys());
+        Ok(accounts)
+    }
+}
+
+impl<TX: DbTx> LogIndexReader for DatabaseProvider<TX> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.indexed_block_range(StageId::IndexLogs, PruneSegment::LogIndex)
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        history_blocks_in_range(
+            &mut self.tx.cursor_read::<tables::LogAddressIndex>()?,
+            ShardedKey::new(address, *range.start()),
+            |sharded_key| sharded_key.key == address,
+            range,
+        )
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        history_blocks_in_range(
+            &mut self.tx.cursor_read::<tables::LogTopicIndex>()?,
+            ShardedKey::new(topic, *range.start()),
+            |sharded_key| sharded_key.key == topic,
+            range,
+        )
+    }
+}
+
+impl<TX: DbTx> AddressTransactionsReader for DatabaseProvider<TX> {
+    fn address_transactions_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.indexed_block_range(
+            StageId::IndexAddressTransactions,
+            PruneSegment::AddressTransactions,
+        )
+    }
+
+    fn address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        history_blocks_in_range(
+            &mut self.tx.cursor_read::<tables::AddressTransactions>()?,
+            ShardedKey::new(address, *range.start()),
+            |sharded_key| sharded_key.key == address,
+            range,
+        )
+    }
+}
+
 impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
     fn unwind_account_history_indices(
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<usize> {
         let mut last_indices = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| entry.map(|(index, account)| (account.address, index)))
-            .collect::<Result<Vec<_>, _>>()?;
+            .account_changesets(range)?
+            .into_iter()
+            .map(|(index, account)| (account.address, index))
+            .collect::<Vec<_>>();
         last_indices.sort_by_key(|(a, _)| *a);
 
         // Unwind the account history index.
@@ -3148,13 +3493,10 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
         range: Range<BlockNumberAddress>,
     ) -> ProviderResult<usize> {
         let mut storage_changesets = self
-            .tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(BlockNumberAddress((bn, address)), storage)| (address, storage.key, bn))
-            })
-            .collect::<Result<Vec<_>, _>>()?;
+            .storage_changesets(range)?
+            .into_iter()
+            .map(|(BlockNumberAddress((bn, address)), storage)| (address, storage.key, bn))
+            .collect::<Vec<_>>();
         storage_changesets.sort_by_key(|(address, key, _)| (*address, *key));
 
         let mut cursor = self.tx.cursor_write::<tables::StoragesHistory>()?;
@@ -3210,6 +3552,151 @@ impl<TX: DbTxMut + DbTx> HistoryWriter for DatabaseProvider<TX> {
 
         Ok(())
     }
+
+    fn unwind_log_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize> {
+        let mut blocks = Vec::new();
+        for block_number in range {
+            if let Some(receipts) = self.receipts_by_block(block_number.into())? {
+                blocks.push((block_number, receipts));
+            }
+        }
+        let (address_blocks, topic_blocks) = log_index_entries(
+            blocks.iter().map(|(block_number, receipts)| (*block_number, receipts)),
+        );
+
+        // The first block of each key is the lowest one that needs to be removed from its shards.
+        let mut cursor = self.tx.cursor_write::<tables::LogAddressIndex>()?;
+        for (&address, blocks) in &address_blocks {
+            let partial_shard = unwind_history_shards::<_, tables::LogAddressIndex, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                blocks[0],
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        let mut cursor = self.tx.cursor_write::<tables::LogTopicIndex>()?;
+        for (&topic, blocks) in &topic_blocks {
+            let partial_shard = unwind_history_shards::<_, tables::LogTopicIndex, _>(
+                &mut cursor,
+                ShardedKey::last(topic),
+                blocks[0],
+                |sharded_key| sharded_key.key == topic,
+            )?;
+
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(topic),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        let logs = blocks.iter().flat_map(|(_, receipts)| receipts).map(|r| r.logs.len()).sum();
+        Ok(logs)
+    }
+
+    fn insert_log_index(
+        &self,
+        address_blocks: BTreeMap<Address, Vec<u64>>,
+        topic_blocks: BTreeMap<B256, Vec<u64>>,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::LogAddressIndex>(address_blocks, ShardedKey::new)?;
+        self.append_history_index::<_, tables::LogTopicIndex>(topic_blocks, ShardedKey::new)
+    }
+
+    fn update_log_index(&self, execution_outcome: &ExecutionOutcome) -> ProviderResult<()> {
+        if execution_outcome.is_empty() {
+            return Ok(())
+        }
+        let first_block = execution_outcome.first_block();
+        let last_block = first_block + execution_outcome.len() as u64 - 1;
+
+        // Only extend the log index if it's maintained and has no gap before the new blocks.
+        match self.get_stage_checkpoint(StageId::IndexLogs)? {
+            Some(checkpoint) if checkpoint.block_number + 1 == first_block => {}
+            _ => return Ok(()),
+        }
+
+        let (address_blocks, topic_blocks) =
+            log_index_entries(
+                execution_outcome.receipts().iter().enumerate().map(|(index, receipts)| {
+                    (first_block + index as u64, receipts.iter().flatten())
+                }),
+            );
+        self.insert_log_index(address_blocks, topic_blocks)?;
+        self.save_stage_checkpoint(StageId::IndexLogs, StageCheckpoint::new(last_block))
+    }
+
+    fn unwind_address_transactions(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let Some(first_tx) = self.block_body_indices(*range.start())?.map(|b| b.first_tx_num)
+        else {
+            return Ok(0)
+        };
+        let end_tx =
+            self.block_body_indices(*range.end())?.map_or(TxNumber::MAX, |b| b.next_tx_num());
+
+        let transaction_addresses = self.take::<tables::TransactionAddresses>(first_tx..end_tx)?;
+
+        // Entries are sorted by transaction number, so the first one of each address is the lowest
+        // transaction that needs to be removed from its shards.
+        let mut first_transactions = BTreeMap::new();
+        for (tx_num, address) in &transaction_addresses {
+            first_transactions.entry(*address).or_insert(*tx_num);
+        }
+
+        let mut cursor = self.tx.cursor_write::<tables::AddressTransactions>()?;
+        for (address, first_tx) in first_transactions {
+            let partial_shard = unwind_history_shards::<_, tables::AddressTransactions, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                first_tx,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(transaction_addresses.len())
+    }
+
+    fn insert_address_transactions(
+        &self,
+        transaction_addresses: BTreeMap<TxNumber, BTreeSet<Address>>,
+    ) -> ProviderResult<()> {
+        let mut address_transactions = BTreeMap::<Address, Vec<u64>>::new();
+        let mut cursor = self.tx.cursor_dup_write::<tables::TransactionAddresses>()?;
+        for (tx_num, addresses) in transaction_addresses {
+            for address in addresses {
+                cursor.append_dup(tx_num, address)?;
+                address_transactions.entry(address).or_default().push(tx_num);
+            }
+        }
+
+        self.append_history_index::<_, tables::AddressTransactions>(
+            address_transactions,
+            ShardedKey::new,
+        )
+    }
 }
 
 impl<TX: DbTx> BlockExecutionReader for DatabaseProvider<TX> {
@@ -3264,6 +3751,14 @@ impl<TX: DbTxMut + DbTx> BlockExecutionWriter for DatabaseProvider<TX> {
         // Unwind storage history indices.
         self.unwind_storage_history_indices(storage_range)?;
 
+        // Unwind the optional log and address transactions indices.
+        self.unwind_maintained_index(StageId::IndexLogs, &range, |range| {
+            self.unwind_log_index(range)
+        })?;
+        self.unwind_maintained_index(StageId::IndexAddressTransactions, &range, |range| {
+            self.unwind_address_transactions(range)
+        })?;
+
         // Calculate the reverted merkle root.
         // This is the same as `StateRoot::incremental_root_with_updates`, only the prefix sets
         // are pre-loaded.
@@ -3352,6 +3847,14 @@ impl<TX: DbTxMut + DbTx> BlockExecutionWriter for DatabaseProvider<TX> {
         // Unwind storage history indices.
         self.unwind_storage_history_indices(storage_range)?;
 
+        // Unwind the optional log and address transactions indices.
+        self.unwind_maintained_index(StageId::IndexLogs, &range, |range| {
+            self.unwind_log_index(range)
+        })?;
+        self.unwind_maintained_index(StageId::IndexAddressTransactions, &range, |range| {
+            self.unwind_address_transactions(range)
+        })?;
+
         // Calculate the reverted merkle root.
         // This is the same as `StateRoot::incremental_root_with_updates`, only the prefix sets
         // are pre-loaded.
@@ -3478,6 +3981,14 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
 
         let tx_count = block.block.body.len() as u64;
 
+        // Only extend the address transactions index if it's maintained and has no gap before the
+        // block. Internal calls can only be indexed by the stage, so the index is left to it if
+        // they're included.
+        let index_addresses = !self.address_transactions_internal_calls &&
+            self.get_stage_checkpoint(StageId::IndexAddressTransactions)?
+                .is_some_and(|checkpoint| checkpoint.block_number + 1 == block_number);
+        let mut addresses = BTreeMap::new();
+
         // Ensures we have all the senders for the block's transactions.
         let mut tx_senders_elapsed = Duration::default();
         let mut transactions_elapsed = Duration::default();
@@ -3486,6 +3997,13 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
         for (transaction, sender) in block.block.body.into_iter().zip(block.senders.iter()) {
             let hash = transaction.hash();
 
+            if index_addresses {
+                addresses.insert(
+                    next_tx_num,
+                    BTreeSet::from(transaction_addresses(*sender, &transaction)),
+                );
+            }
+
             if self
                 .prune_modes
                 .sender_recovery
@@ -3534,6 +4052,14 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
             tx_hash_numbers_elapsed,
         );
 
+        if index_addresses {
+            self.insert_address_transactions(addresses)?;
+            self.save_stage_checkpoint(
+                StageId::IndexAddressTransactions,
+                StageCheckpoint::new(block_number),
+            )?;
+        }
+
         if let Some(withdrawals) = block.block.withdrawals {
             if !withdrawals.is_empty() {
                 self.tx.put::<tables::BlockWithdrawals>(
@@ -3596,6 +4122,9 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
             durations_recorder.record_relative(metrics::Action::InsertBlock);
         }
 
+        // Extend the log index before the execution outcome is consumed.
+        self.update_log_index(&execution_outcome)?;
+
         // Write state and changesets to the database.
         // Must be written after blocks because of the receipt lookup.
         // TODO: should _these_ be moved to storagewriter? seems like storagewriter should be
@@ -3684,6 +4213,42 @@ impl<TX: DbTxMut> FinalizedBlockWriter for DatabaseProvider<TX> {
     }
 }
 
+impl<TX: DbTx> BadBlockReader for DatabaseProvider<TX> {
+    fn bad_blocks(&self) -> ProviderResult<Vec<SealedBlock>> {
+        let mut blocks = self
+            .tx
+            .cursor_read::<tables::BadBlocks>()?
+            .walk(None)?
+            .map(|entry| decode_bad_block(&entry?.1))
+            .collect::<ProviderResult<Vec<_>>>()?;
+        blocks.sort_unstable_by(|a, b| b.number.cmp(&a.number).then(b.hash().cmp(&a.hash())));
+        Ok(blocks)
+    }
+
+    fn bad_block_by_hash(&self, hash: B256) -> ProviderResult<Option<SealedBlock>> {
+        self.tx.get::<tables::BadBlocks>(hash)?.map(|block| decode_bad_block(&block)).transpose()
+    }
+}
+
+impl<TX: DbTxMut + DbTx> BadBlockWriter for DatabaseProvider<TX> {
+    fn insert_bad_block(&self, block: SealedBlock) -> ProviderResult<()> {
+        let mut encoded = Vec::with_capacity(block.length());
+        block.encode(&mut encoded);
+        self.tx.put::<tables::BadBlocks>(block.hash(), encoded)?;
+
+        // drop the lowest blocks beyond the limit, which may include the one just inserted
+        for block in self.bad_blocks()?.into_iter().skip(MAX_BAD_BLOCKS) {
+            self.tx.delete::<tables::BadBlocks>(block.hash(), None)?;
+        }
+        Ok(())
+    }
+}
+
+/// Decodes a block stored in [`tables::BadBlocks`].
+fn decode_bad_block(mut encoded: &[u8]) -> ProviderResult<SealedBlock> {
+    Ok(SealedBlock::decode(&mut encoded).map_err(|_| DatabaseError::Decode)?)
+}
+
 /// Helper method to recover senders for any blocks in the db which do not have senders. This
 /// compares the length of the input senders [`Vec`], with the length of given transactions [`Vec`],
 /// and will add to the input senders vec if there are more transactions.
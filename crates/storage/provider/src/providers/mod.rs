@@ -1,11 +1,13 @@
 use crate::{
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockSource, BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotifications,
-    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    EvmEnvProvider, FinalizedBlockReader, FullExecutionDataProvider, HeaderProvider, ProviderError,
-    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, RequestsProvider,
-    StageCheckpointReader, StateProviderBox, StateProviderFactory, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider, TreeViewer, WithdrawalsProvider,
+    AccountReader, AddressTransactionsReader, BadBlockReader, BadBlockWriter, BlockHashReader,
+    BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource,
+    BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotifications,
+    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, DatabaseCompactor,
+    DatabaseProviderFactory, EvmEnvProvider, FinalizedBlockReader, FullExecutionDataProvider,
+    HeaderProvider, HistoryReader, LogIndexReader, ProviderError, PruneCheckpointReader,
+    ReceiptProvider, ReceiptProviderIdExt, RequestsProvider, StageCheckpointReader,
+    StateProviderBox, StateProviderFactory, StaticFileProviderFactory, TransactionVariant,
+    TransactionsProvider, TreeViewer, WithdrawalsProvider,
 };
 use reth_blockchain_tree_api::{
     error::{CanonicalError, InsertBlockError},
@@ -16,6 +18,7 @@ use reth_chain_state::ChainInfoTracker;
 use reth_chainspec::{ChainInfo, ChainSpec};
 use reth_db_api::{
     database::Database,
+    database_compaction::DatabaseCompaction,
     models::{AccountBeforeTx, StoredBlockBodyIndices},
 };
 use reth_evm::ConfigureEvmEnv;
@@ -30,8 +33,9 @@ use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_errors::provider::ProviderResult;
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     ops::{RangeBounds, RangeInclusive},
+    path::PathBuf,
     sync::Arc,
     time::Instant,
 };
@@ -48,7 +52,7 @@ pub use static_file::{
 
 mod state;
 pub use state::{
-    historical::{HistoricalStateProvider, HistoricalStateProviderRef},
+    historical::{HistoricalStateProvider, HistoricalStateProviderRef, LowestAvailableBlocks},
     latest::{LatestStateProvider, LatestStateProviderRef},
 };
 
@@ -952,6 +956,107 @@ where
     }
 }
 
+impl<DB> BadBlockReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn bad_blocks(&self) -> ProviderResult<Vec<SealedBlock>> {
+        self.database.bad_blocks()
+    }
+
+    fn bad_block_by_hash(&self, hash: B256) -> ProviderResult<Option<SealedBlock>> {
+        self.database.bad_block_by_hash(hash)
+    }
+}
+
+impl<DB> BadBlockWriter for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn insert_bad_block(&self, block: SealedBlock) -> ProviderResult<()> {
+        self.database.insert_bad_block(block)
+    }
+}
+
+impl<DB> DatabaseCompactor for BlockchainProvider<DB>
+where
+    DB: Database + DatabaseCompaction,
+{
+    fn compact_database(&self) -> ProviderResult<PathBuf> {
+        self.database.compact_database()
+    }
+}
+
+impl<DB> HistoryReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.account_history_blocks(address, range)
+    }
+
+    fn storage_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.storage_history_blocks(address, range)
+    }
+
+    fn changed_accounts_in_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        self.database.provider()?.changed_accounts_in_range(range)
+    }
+}
+
+impl<DB> LogIndexReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.database.provider()?.log_index_range()
+    }
+
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.log_address_blocks(address, range)
+    }
+
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.provider()?.log_topic_blocks(topic, range)
+    }
+}
+
+impl<DB> AddressTransactionsReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn address_transactions_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.database.provider()?.address_transactions_range()
+    }
+
+    fn address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.database.provider()?.address_transactions(address, range)
+    }
+}
+
 impl<DB> AccountReader for BlockchainProvider<DB>
 where
     DB: Database + Sync + Send,
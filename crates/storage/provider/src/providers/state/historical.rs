@@ -1,29 +1,40 @@
 use crate::{
-    providers::{state::macros::delegate_provider_impls, StaticFileProvider},
+    providers::{
+        state::{macros::delegate_provider_impls, range},
+        StaticFileProvider,
+    },
     AccountReader, BlockHashReader, ProviderError, StateProvider, StateRootProvider,
 };
 use reth_db::{tables, BlockNumberList};
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
-    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    models::{
+        storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress, ShardedKey,
+        StorageBeforeTx,
+    },
     table::Table,
     transaction::DbTx,
 };
 use reth_primitives::{
-    constants::EPOCH_SLOTS, Account, Address, BlockNumber, Bytecode, Bytes, StaticFileSegment,
-    StorageKey, StorageValue, B256,
+    constants::EPOCH_SLOTS, keccak256, Account, Address, BlockNumber, Bytecode, Bytes,
+    StaticFileSegment, StorageKey, StorageValue, B256, U256,
 };
-use reth_storage_api::StateProofProvider;
+use reth_storage_api::{HashedStateRange, StateProofProvider, StateRangeProvider};
 use reth_storage_errors::provider::ProviderResult;
 use reth_trie::{
     proof::Proof, updates::TrieUpdates, witness::TrieWitness, AccountProof, HashedPostState,
-    HashedStorage, StateRoot, StorageRoot,
+    HashedStorage, StateRoot, StorageRoot, TrieAccount,
 };
 use reth_trie_db::{
     DatabaseHashedPostState, DatabaseProof, DatabaseStateRoot, DatabaseStorageRoot,
     DatabaseTrieWitness,
 };
-use std::{collections::HashMap, fmt::Debug};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    sync::OnceLock,
+};
 
 /// State provider for a given block number which takes a tx reference.
 ///
@@ -36,6 +47,8 @@ use std::{collections::HashMap, fmt::Debug};
 /// - [`tables::StoragesHistory`]
 /// - [`tables::AccountChangeSets`]
 /// - [`tables::StorageChangeSets`]
+///
+/// Changesets which have already been moved to static files are read from there instead.
 #[derive(Debug)]
 pub struct HistoricalStateProviderRef<'b, TX: DbTx> {
     /// Transaction
@@ -46,6 +59,8 @@ pub struct HistoricalStateProviderRef<'b, TX: DbTx> {
     lowest_available_blocks: LowestAvailableBlocks,
     /// Static File provider
     static_file_provider: StaticFileProvider,
+    /// Revert hashed state of the block, computed on first use.
+    revert_state: Cow<'b, OnceLock<HashedPostState>>,
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -63,7 +78,12 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         block_number: BlockNumber,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { tx, block_number, lowest_available_blocks: Default::default(), static_file_provider }
+        Self::new_with_lowest_available_blocks(
+            tx,
+            block_number,
+            Default::default(),
+            static_file_provider,
+        )
     }
 
     /// Create new `StateProvider` for historical block number and lowest block numbers at which
@@ -74,7 +94,13 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
         lowest_available_blocks: LowestAvailableBlocks,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { tx, block_number, lowest_available_blocks, static_file_provider }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks,
+            static_file_provider,
+            revert_state: Cow::Owned(OnceLock::new()),
+        }
     }
 
     /// Lookup an account in the `AccountsHistory` table
@@ -112,7 +138,20 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
     }
 
     /// Retrieve revert hashed state for this history provider.
+    ///
+    /// The state is only read from the database once per provider and cloned afterwards.
     fn revert_state(&self) -> ProviderResult<HashedPostState> {
+        if let Some(revert_state) = self.revert_state.get() {
+            return Ok(revert_state.clone())
+        }
+
+        let revert_state = self.read_revert_state()?;
+        Ok(self.revert_state.get_or_init(|| revert_state).clone())
+    }
+
+    /// Reads the revert hashed state for this history provider from the database and static
+    /// files.
+    fn read_revert_state(&self) -> ProviderResult<HashedPostState> {
         if !self.lowest_available_blocks.is_account_history_available(self.block_number) ||
             !self.lowest_available_blocks.is_storage_history_available(self.block_number)
         {
@@ -137,7 +176,119 @@ impl<'b, TX: DbTx> HistoricalStateProviderRef<'b, TX> {
             );
         }
 
-        Ok(HashedPostState::from_reverts(self.tx, self.block_number)?)
+        let mut revert_state = HashedPostState::from_reverts(self.tx, self.block_number)?;
+        // Static file changesets precede the ones above the highest static file block, and the
+        // database can only hold parts of the changesets of the blocks below it, so the values from
+        // static files take priority.
+        revert_state.extend(self.static_file_revert_state()?);
+        Ok(revert_state)
+    }
+
+    /// Retrieve revert hashed state for the blocks whose changesets have been moved to static
+    /// files.
+    fn static_file_revert_state(&self) -> ProviderResult<HashedPostState> {
+        let mut accounts = HashMap::<Address, Option<Account>>::default();
+        if let Some(range) = self.static_file_provider.change_sets_in_static_files(
+            StaticFileSegment::AccountChangeSets,
+            self.block_number..BlockNumber::MAX,
+        ) {
+            for changeset in self.static_file_provider.account_changesets_range(range)? {
+                for AccountBeforeTx { address, info } in changeset.changes {
+                    accounts.entry(address).or_insert(info);
+                }
+            }
+        }
+
+        let mut storages = HashMap::<Address, HashMap<B256, U256>>::default();
+        if let Some(range) = self.static_file_provider.change_sets_in_static_files(
+            StaticFileSegment::StorageChangeSets,
+            self.block_number..BlockNumber::MAX,
+        ) {
+            for changeset in self.static_file_provider.storage_changesets_range(range)? {
+                for StorageBeforeTx { address, entry } in changeset.changes {
+                    storages.entry(address).or_default().entry(entry.key).or_insert(entry.value);
+                }
+            }
+        }
+
+        Ok(HashedPostState {
+            accounts: accounts
+                .into_iter()
+                .map(|(address, info)| (keccak256(address), info))
+                .collect(),
+            storages: storages
+                .into_iter()
+                .map(|(address, storage)| {
+                    (
+                        keccak256(address),
+                        HashedStorage::from_iter(
+                            false,
+                            storage.into_iter().map(|(slot, value)| (keccak256(slot), value)),
+                        ),
+                    )
+                })
+                .collect(),
+        })
+    }
+
+    /// Retrieve the addresses of the accounts that changed after this block.
+    fn account_revert_addresses(&self) -> ProviderResult<HashSet<Address>> {
+        if !self.lowest_available_blocks.is_account_history_available(self.block_number) {
+            return Err(ProviderError::StateAtBlockPruned(self.block_number))
+        }
+
+        // Static file changesets precede the ones in the database, so the earliest change of an
+        // account is found in them if they hold any.
+        let mut addresses = HashSet::new();
+        if let Some(range) = self.static_file_provider.change_sets_in_static_files(
+            StaticFileSegment::AccountChangeSets,
+            self.block_number..BlockNumber::MAX,
+        ) {
+            for changeset in self.static_file_provider.account_changesets_range(range)? {
+                addresses.extend(changeset.changes.into_iter().map(|change| change.address));
+            }
+        }
+        for entry in
+            self.tx.cursor_read::<tables::AccountChangeSets>()?.walk_range(self.block_number..)?
+        {
+            let (_, AccountBeforeTx { address, .. }) = entry?;
+            addresses.insert(address);
+        }
+        Ok(addresses)
+    }
+
+    /// Retrieve the slots of the account's storage that changed after this block.
+    fn storage_revert_slots(&self, address: Address) -> ProviderResult<HashSet<B256>> {
+        if !self.lowest_available_blocks.is_storage_history_available(self.block_number) {
+            return Err(ProviderError::StateAtBlockPruned(self.block_number))
+        }
+
+        let mut slots = HashSet::new();
+        if let Some(range) = self.static_file_provider.change_sets_in_static_files(
+            StaticFileSegment::StorageChangeSets,
+            self.block_number..BlockNumber::MAX,
+        ) {
+            for changeset in self.static_file_provider.storage_changesets_range(range)? {
+                slots.extend(
+                    changeset
+                        .changes
+                        .into_iter()
+                        .filter(|change| change.address == address)
+                        .map(|change| change.entry.key),
+                );
+            }
+        }
+        for entry in self
+            .tx
+            .cursor_read::<tables::StorageChangeSets>()?
+            .walk_range(BlockNumberAddress((self.block_number, Address::ZERO))..)?
+        {
+            let (BlockNumberAddress((_, changed_address)), entry) = entry?;
+            if changed_address == address {
+                slots.insert(entry.key);
+            }
+        }
+        Ok(slots)
     }
 
     fn history_info<T, K>(
@@ -210,10 +361,23 @@ impl<'b, TX: DbTx> AccountReader for HistoricalStateProviderRef<'b, TX> {
         match self.account_history_lookup(address)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(self
-                .tx
-                .cursor_dup_read::<tables::AccountChangeSets>()?
-                .seek_by_key_subkey(changeset_block_number, address)?
-                .filter(|acc| acc.address == address)
+                .static_file_provider
+                .get_with_static_file_or_database(
+                    StaticFileSegment::AccountChangeSets,
+                    changeset_block_number,
+                    |static_file| {
+                        Ok(static_file
+                            .account_changeset(changeset_block_number)?
+                            .and_then(|changeset| changeset.get(address).cloned()))
+                    },
+                    || {
+                        Ok(self
+                            .tx
+                            .cursor_dup_read::<tables::AccountChangeSets>()?
+                            .seek_by_key_subkey(changeset_block_number, address)?
+                            .filter(|acc| acc.address == address))
+                    },
+                )?
                 .ok_or(ProviderError::AccountChangesetNotFound {
                     block_number: changeset_block_number,
                     address,
@@ -316,6 +480,63 @@ impl<'b, TX: DbTx> StateProofProvider for HistoricalStateProviderRef<'b, TX> {
     }
 }
 
+impl<'b, TX: DbTx> StateRangeProvider for HistoricalStateProviderRef<'b, TX> {
+    fn hashed_account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStateRange<TrieAccount>> {
+        let mut revert_state = self.revert_state()?;
+        revert_state.extend(hashed_state);
+        range::hashed_account_range(self.tx, revert_state, start, limit)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_state: HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStateRange<U256>> {
+        let mut revert_state = self.revert_state()?;
+        revert_state.extend(hashed_state);
+        range::hashed_storage_range(self.tx, revert_state, hashed_address, start, limit)
+    }
+
+    fn account_preimages(
+        &self,
+        hashed_addresses: &[B256],
+    ) -> ProviderResult<HashMap<B256, Address>> {
+        // the accounts changed after the block are known from the changesets
+        Ok(self
+            .account_revert_addresses()?
+            .into_iter()
+            .map(|address| (keccak256(address), address))
+            .filter(|(hashed_address, _)| hashed_addresses.contains(hashed_address))
+            .collect())
+    }
+
+    fn storage_preimages(
+        &self,
+        address: Address,
+        hashed_slots: &[B256],
+    ) -> ProviderResult<HashMap<B256, B256>> {
+        // the slots changed after the block are known from the changesets, and the others are
+        // still in the plain state
+        let mut preimages = self
+            .storage_revert_slots(address)?
+            .into_iter()
+            .map(|slot| (keccak256(slot), slot))
+            .filter(|(hashed_slot, _)| hashed_slots.contains(hashed_slot))
+            .collect::<HashMap<_, _>>();
+        if preimages.len() < hashed_slots.len() {
+            preimages.extend(range::storage_preimages(self.tx, address, hashed_slots)?);
+        }
+        Ok(preimages)
+    }
+}
+
 impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
     /// Get storage.
     fn storage(
@@ -326,10 +547,26 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
         match self.storage_history_lookup(address, storage_key)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
-                self.tx
-                    .cursor_dup_read::<tables::StorageChangeSets>()?
-                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
-                    .filter(|entry| entry.key == storage_key)
+                self.static_file_provider
+                    .get_with_static_file_or_database(
+                        StaticFileSegment::StorageChangeSets,
+                        changeset_block_number,
+                        |static_file| {
+                            Ok(static_file
+                                .storage_changeset(changeset_block_number)?
+                                .and_then(|changeset| changeset.get(address, storage_key).copied()))
+                        },
+                        || {
+                            Ok(self
+                                .tx
+                                .cursor_dup_read::<tables::StorageChangeSets>()?
+                                .seek_by_key_subkey(
+                                    (changeset_block_number, address).into(),
+                                    storage_key,
+                                )?
+                                .filter(|entry| entry.key == storage_key))
+                        },
+                    )?
                     .ok_or_else(|| ProviderError::StorageChangesetNotFound {
                         block_number: changeset_block_number,
                         address,
@@ -365,6 +602,8 @@ pub struct HistoricalStateProvider<TX: DbTx> {
     lowest_available_blocks: LowestAvailableBlocks,
     /// Static File provider
     static_file_provider: StaticFileProvider,
+    /// Revert hashed state of the block, shared by all references to this provider.
+    revert_state: OnceLock<HashedPostState>,
 }
 
 impl<TX: DbTx> HistoricalStateProvider<TX> {
@@ -374,7 +613,13 @@ impl<TX: DbTx> HistoricalStateProvider<TX> {
         block_number: BlockNumber,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { tx, block_number, lowest_available_blocks: Default::default(), static_file_provider }
+        Self {
+            tx,
+            block_number,
+            lowest_available_blocks: Default::default(),
+            static_file_provider,
+            revert_state: OnceLock::new(),
+        }
     }
 
     /// Set the lowest block number at which the account history is available.
@@ -398,12 +643,13 @@ impl<TX: DbTx> HistoricalStateProvider<TX> {
     /// Returns a new provider that takes the `TX` as reference
     #[inline(always)]
     fn as_ref(&self) -> HistoricalStateProviderRef<'_, TX> {
-        HistoricalStateProviderRef::new_with_lowest_available_blocks(
-            &self.tx,
-            self.block_number,
-            self.lowest_available_blocks,
-            self.static_file_provider.clone(),
-        )
+        HistoricalStateProviderRef {
+            tx: &self.tx,
+            block_number: self.block_number,
+            lowest_available_blocks: self.lowest_available_blocks,
+            static_file_provider: self.static_file_provider.clone(),
+            revert_state: Cow::Borrowed(&self.revert_state),
+        }
     }
 }
 
@@ -441,18 +687,27 @@ impl LowestAvailableBlocks {
 #[cfg(test)]
 mod tests {
     use crate::{
-        providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
+        providers::{
+            state::historical::{HistoryInfo, LowestAvailableBlocks},
+            StaticFileWriter,
+        },
         test_utils::create_test_provider_factory,
-        AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
-        StaticFileProviderFactory,
+        AccountExtReader, AccountReader, HistoricalStateProvider, HistoricalStateProviderRef,
+        StateProvider, StaticFileProviderFactory, StorageReader,
     };
     use reth_db::{tables, BlockNumberList};
     use reth_db_api::{
-        models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
+        models::{
+            storage_sharded_key::StorageShardedKey, AccountBeforeTx, AccountChangeSet,
+            BlockNumberAddress, ShardedKey, StorageBeforeTx, StorageChangeSet,
+        },
         transaction::{DbTx, DbTxMut},
     };
-    use reth_primitives::{address, b256, Account, Address, StorageEntry, B256, U256};
+    use reth_primitives::{
+        address, b256, keccak256, Account, Address, StaticFileSegment, StorageEntry, B256, U256,
+    };
     use reth_storage_errors::provider::ProviderError;
+    use std::collections::{BTreeMap, BTreeSet, HashMap};
 
     const ADDRESS: Address = address!("0000000000000000000000000000000000000001");
     const HIGHER_ADDRESS: Address = address!("0000000000000000000000000000000000000005");
@@ -695,6 +950,169 @@ mod tests {
         );
     }
 
+    #[test]
+    fn history_provider_get_from_static_files() {
+        let factory = create_test_provider_factory();
+        let static_file_provider = factory.static_file_provider();
+
+        let acc_plain = Account { nonce: 100, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at10 = Account { nonce: 10, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at7 = Account { nonce: 7, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at3 = Account { nonce: 3, balance: U256::ZERO, bytecode_hash: None };
+        let entry_plain = StorageEntry { key: STORAGE, value: U256::from(100) };
+        let entry_at3 = StorageEntry { key: STORAGE, value: U256::from(3) };
+        let entry_at1 = StorageEntry { key: STORAGE, value: U256::ZERO };
+
+        // changesets of blocks 0..=7 have been moved to static files and deleted from the database
+        {
+            let mut writer =
+                static_file_provider.get_writer(0, StaticFileSegment::AccountChangeSets).unwrap();
+            for block in 0..=7 {
+                let changes = match block {
+                    1 => vec![AccountBeforeTx { address: ADDRESS, info: None }],
+                    3 => vec![AccountBeforeTx { address: ADDRESS, info: Some(acc_at3) }],
+                    7 => vec![AccountBeforeTx { address: ADDRESS, info: Some(acc_at7) }],
+                    _ => Vec::new(),
+                };
+                writer.append_account_changeset(block, &AccountChangeSet { changes }).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+        {
+            let mut writer =
+                static_file_provider.get_writer(0, StaticFileSegment::StorageChangeSets).unwrap();
+            for block in 0..=7 {
+                let changes = match block {
+                    1 => vec![StorageBeforeTx { address: ADDRESS, entry: entry_at1 }],
+                    3 => vec![StorageBeforeTx { address: ADDRESS, entry: entry_at3 }],
+                    _ => Vec::new(),
+                };
+                writer.append_storage_changeset(block, &StorageChangeSet { changes }).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        let tx = factory.provider_rw().unwrap().into_tx();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([1, 3, 7, 10]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::StoragesHistory>(
+            StorageShardedKey {
+                address: ADDRESS,
+                sharded_key: ShardedKey { key: STORAGE, highest_block_number: u64::MAX },
+            },
+            BlockNumberList::new([1, 3]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            10,
+            AccountBeforeTx { address: ADDRESS, info: Some(acc_at10) },
+        )
+        .unwrap();
+        tx.put::<tables::PlainAccountState>(ADDRESS, acc_plain).unwrap();
+        tx.put::<tables::PlainStorageState>(ADDRESS, entry_plain).unwrap();
+        tx.commit().unwrap();
+
+        let tx = factory.provider().unwrap().into_tx();
+        let at = |block| HistoricalStateProviderRef::new(&tx, block, static_file_provider.clone());
+
+        assert_eq!(at(1).basic_account(ADDRESS), Ok(None));
+        assert_eq!(at(2).basic_account(ADDRESS), Ok(Some(acc_at3)));
+        assert_eq!(at(5).basic_account(ADDRESS), Ok(Some(acc_at7)));
+        assert_eq!(at(8).basic_account(ADDRESS), Ok(Some(acc_at10)));
+        assert_eq!(at(11).basic_account(ADDRESS), Ok(Some(acc_plain)));
+
+        assert_eq!(at(2).storage(ADDRESS, STORAGE), Ok(Some(entry_at3.value)));
+        assert_eq!(at(4).storage(ADDRESS, STORAGE), Ok(Some(entry_plain.value)));
+    }
+
+    #[test]
+    fn history_provider_partly_pruned_change_sets() {
+        let factory = create_test_provider_factory();
+        let static_file_provider = factory.static_file_provider();
+
+        let acc_at10 = Account { nonce: 10, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at5 = Account { nonce: 5, balance: U256::ZERO, bytecode_hash: None };
+        let entry_at5 = StorageEntry { key: STORAGE, value: U256::from(5) };
+
+        // changesets of blocks 0..=7 have been moved to static files
+        {
+            let mut writer =
+                static_file_provider.get_writer(0, StaticFileSegment::AccountChangeSets).unwrap();
+            for block in 0..=7 {
+                let changes = match block {
+                    5 => vec![
+                        AccountBeforeTx { address: ADDRESS, info: Some(acc_at5) },
+                        AccountBeforeTx { address: HIGHER_ADDRESS, info: None },
+                    ],
+                    _ => Vec::new(),
+                };
+                writer.append_account_changeset(block, &AccountChangeSet { changes }).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+        {
+            let mut writer =
+                static_file_provider.get_writer(0, StaticFileSegment::StorageChangeSets).unwrap();
+            for block in 0..=7 {
+                let changes = match block {
+                    5 => vec![
+                        StorageBeforeTx { address: ADDRESS, entry: entry_at5 },
+                        StorageBeforeTx { address: HIGHER_ADDRESS, entry: entry_at5 },
+                    ],
+                    _ => Vec::new(),
+                };
+                writer.append_storage_changeset(block, &StorageChangeSet { changes }).unwrap();
+            }
+            writer.commit().unwrap();
+        }
+
+        // pruning the database stopped halfway through block 5
+        let tx = factory.provider_rw().unwrap().into_tx();
+        tx.put::<tables::CanonicalHeaders>(10, B256::ZERO).unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            5,
+            AccountBeforeTx { address: HIGHER_ADDRESS, info: None },
+        )
+        .unwrap();
+        tx.put::<tables::AccountChangeSets>(
+            10,
+            AccountBeforeTx { address: ADDRESS, info: Some(acc_at10) },
+        )
+        .unwrap();
+        tx.put::<tables::StorageChangeSets>(BlockNumberAddress((5, HIGHER_ADDRESS)), entry_at5)
+            .unwrap();
+        tx.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(
+            provider.changed_accounts_with_range(5..=5).unwrap(),
+            BTreeSet::from([ADDRESS, HIGHER_ADDRESS])
+        );
+        assert_eq!(
+            provider.changed_storages_with_range(5..=5).unwrap(),
+            BTreeMap::from([
+                (ADDRESS, BTreeSet::from([STORAGE])),
+                (HIGHER_ADDRESS, BTreeSet::from([STORAGE]))
+            ])
+        );
+
+        let tx = provider.into_tx();
+        let revert_state =
+            HistoricalStateProviderRef::new(&tx, 5, static_file_provider).revert_state().unwrap();
+        assert_eq!(
+            revert_state.accounts,
+            HashMap::from([(keccak256(ADDRESS), Some(acc_at5)), (keccak256(HIGHER_ADDRESS), None)])
+        );
+        assert_eq!(revert_state.storages.len(), 2);
+        assert_eq!(
+            revert_state.storages[&keccak256(ADDRESS)].storage,
+            HashMap::from([(keccak256(STORAGE), entry_at5.value)])
+        );
+    }
+
     #[test]
     fn history_provider_unavailable() {
         let factory = create_test_provider_factory();
@@ -755,4 +1173,33 @@ mod tests {
             Ok(HistoryInfo::MaybeInPlainState)
         );
     }
+
+    #[test]
+    fn history_provider_caches_revert_state() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+        let static_file_provider = factory.static_file_provider();
+
+        let acc = Account { nonce: 1, balance: U256::ZERO, bytecode_hash: None };
+        tx.put::<tables::CanonicalHeaders>(5, B256::ZERO).unwrap();
+        tx.put::<tables::AccountChangeSets>(3, AccountBeforeTx { address: ADDRESS, info: None })
+            .unwrap();
+        tx.put::<tables::PlainAccountState>(ADDRESS, acc).unwrap();
+
+        let provider = HistoricalStateProviderRef::new(&tx, 2, static_file_provider.clone());
+        let revert_state = provider.revert_state().unwrap();
+        assert_eq!(revert_state.accounts.get(&keccak256(ADDRESS)), Some(&None));
+        assert!(!revert_state.accounts.contains_key(&keccak256(HIGHER_ADDRESS)));
+
+        // changes made after the first read are not picked up by the same provider
+        tx.put::<tables::AccountChangeSets>(
+            4,
+            AccountBeforeTx { address: HIGHER_ADDRESS, info: None },
+        )
+        .unwrap();
+        assert_eq!(provider.revert_state().unwrap(), revert_state);
+
+        let provider = HistoricalStateProviderRef::new(&tx, 2, static_file_provider);
+        assert!(provider.revert_state().unwrap().accounts.contains_key(&keccak256(HIGHER_ADDRESS)));
+    }
 }
@@ -1,7 +1,10 @@
 use std::collections::HashMap;
 
 use crate::{
-    providers::{state::macros::delegate_provider_impls, StaticFileProvider},
+    providers::{
+        state::{macros::delegate_provider_impls, range},
+        StaticFileProvider,
+    },
     AccountReader, BlockHashReader, StateProvider, StateRootProvider,
 };
 use reth_db::tables;
@@ -11,13 +14,13 @@ use reth_db_api::{
 };
 use reth_primitives::{
     Account, Address, BlockNumber, Bytecode, Bytes, StaticFileSegment, StorageKey, StorageValue,
-    B256,
+    B256, U256,
 };
-use reth_storage_api::StateProofProvider;
+use reth_storage_api::{HashedStateRange, StateProofProvider, StateRangeProvider};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie::{
     proof::Proof, updates::TrieUpdates, witness::TrieWitness, AccountProof, HashedPostState,
-    HashedStorage, StateRoot, StorageRoot,
+    HashedStorage, StateRoot, StorageRoot, TrieAccount,
 };
 use reth_trie_db::{DatabaseProof, DatabaseStateRoot, DatabaseStorageRoot, DatabaseTrieWitness};
 
@@ -124,6 +127,42 @@ impl<'b, TX: DbTx> StateProofProvider for LatestStateProviderRef<'b, TX> {
     }
 }
 
+impl<'b, TX: DbTx> StateRangeProvider for LatestStateProviderRef<'b, TX> {
+    fn hashed_account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStateRange<TrieAccount>> {
+        range::hashed_account_range(self.tx, hashed_state, start, limit)
+    }
+
+    fn hashed_storage_range(
+        &self,
+        hashed_state: HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStateRange<U256>> {
+        range::hashed_storage_range(self.tx, hashed_state, hashed_address, start, limit)
+    }
+
+    fn account_preimages(
+        &self,
+        _hashed_addresses: &[B256],
+    ) -> ProviderResult<HashMap<B256, Address>> {
+        Ok(HashMap::new())
+    }
+
+    fn storage_preimages(
+        &self,
+        address: Address,
+        hashed_slots: &[B256],
+    ) -> ProviderResult<HashMap<B256, B256>> {
+        range::storage_preimages(self.tx, address, hashed_slots)
+    }
+}
+
 impl<'b, TX: DbTx> StateProvider for LatestStateProviderRef<'b, TX> {
     /// Get storage.
     fn storage(
@@ -53,6 +53,12 @@ macro_rules! delegate_provider_impls {
                 fn hashed_proof(&self, state: reth_trie::HashedPostState, address: reth_primitives::Address, slots: &[reth_primitives::B256]) -> reth_storage_errors::provider::ProviderResult<reth_trie::AccountProof>;
                 fn witness(&self, state: reth_trie::HashedPostState, target: reth_trie::HashedPostState) -> reth_storage_errors::provider::ProviderResult<std::collections::HashMap<reth_primitives::B256, reth_primitives::Bytes>>;
             }
+            StateRangeProvider $(where [$($generics)*])? {
+                fn hashed_account_range(&self, state: reth_trie::HashedPostState, start: reth_primitives::B256, limit: usize) -> reth_storage_errors::provider::ProviderResult<reth_storage_api::HashedStateRange<reth_trie::TrieAccount>>;
+                fn hashed_storage_range(&self, state: reth_trie::HashedPostState, hashed_address: reth_primitives::B256, start: reth_primitives::B256, limit: usize) -> reth_storage_errors::provider::ProviderResult<reth_storage_api::HashedStateRange<reth_primitives::U256>>;
+                fn account_preimages(&self, hashed_addresses: &[reth_primitives::B256]) -> reth_storage_errors::provider::ProviderResult<std::collections::HashMap<reth_primitives::B256, reth_primitives::Address>>;
+                fn storage_preimages(&self, address: reth_primitives::Address, hashed_slots: &[reth_primitives::B256]) -> reth_storage_errors::provider::ProviderResult<std::collections::HashMap<reth_primitives::B256, reth_primitives::B256>>;
+            }
         );
     }
 }
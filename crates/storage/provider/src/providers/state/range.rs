@@ -0,0 +1,205 @@
+//! Helpers for iterating over the state stored in the database.
+
+use crate::ProviderError;
+use reth_db::tables;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    transaction::DbTx,
+};
+use reth_primitives::{keccak256, Address, B256, U256};
+use reth_storage_api::HashedStateRange;
+use reth_storage_errors::{db::DatabaseError, provider::ProviderResult};
+use reth_trie::{
+    hashed_cursor::{HashedCursor, HashedCursorFactory, HashedPostStateCursorFactory},
+    HashedPostState, HashedStorage, StorageRoot, TrieAccount,
+};
+use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseStorageRoot};
+use std::collections::{HashMap, HashSet};
+
+/// The maximum number of slots of an account's plain storage that are scanned for the preimages
+/// of hashed slots.
+const STORAGE_PREIMAGES_MAX_SCANNED: usize = 8192;
+
+/// Returns at most `limit` accounts of the hashed state in the database with the given
+/// [`HashedPostState`] applied on top, starting at the hashed address `start`.
+pub(crate) fn hashed_account_range<TX: DbTx>(
+    tx: &TX,
+    hashed_state: HashedPostState,
+    start: B256,
+    limit: usize,
+) -> ProviderResult<HashedStateRange<TrieAccount>> {
+    let state_sorted = hashed_state.clone().into_sorted();
+    let factory =
+        HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(tx), &state_sorted);
+    let HashedStateRange { entries, next_key } =
+        walk_range(&mut factory.hashed_account_cursor()?, start, limit)?;
+
+    let mut accounts = Vec::with_capacity(entries.len());
+    for (hashed_address, account) in entries {
+        let hashed_storage = hashed_state
+            .storages
+            .get(&hashed_address)
+            .cloned()
+            .unwrap_or_else(|| HashedStorage::new(false));
+        let storage_root = StorageRoot::overlay_root_hashed(tx, hashed_address, hashed_storage)
+            .map_err(|err| ProviderError::Database(err.into()))?;
+        accounts.push((hashed_address, TrieAccount::from((account, storage_root))));
+    }
+
+    Ok(HashedStateRange { entries: accounts, next_key })
+}
+
+/// Returns at most `limit` storage slots of the account with the given hashed address in the
+/// database with the given [`HashedPostState`] applied on top, starting at the hashed slot
+/// `start`.
+pub(crate) fn hashed_storage_range<TX: DbTx>(
+    tx: &TX,
+    hashed_state: HashedPostState,
+    hashed_address: B256,
+    start: B256,
+    limit: usize,
+) -> ProviderResult<HashedStateRange<U256>> {
+    let state_sorted = hashed_state.into_sorted();
+    let factory =
+        HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(tx), &state_sorted);
+    Ok(walk_range(&mut factory.hashed_storage_cursor(hashed_address)?, start, limit)?)
+}
+
+/// Returns the plain slots of the account's storage in the plain state of the database that hash
+/// to the given hashed slots.
+///
+/// Slots can only be found by hashing the plain ones, so the scan stops once all slots are found
+/// or [`STORAGE_PREIMAGES_MAX_SCANNED`] slots are scanned, and the slots not found by then are
+/// missing from the result.
+pub(crate) fn storage_preimages<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    hashed_slots: &[B256],
+) -> ProviderResult<HashMap<B256, B256>> {
+    let mut remaining = hashed_slots.iter().copied().collect::<HashSet<_>>();
+    let mut preimages = HashMap::with_capacity(remaining.len());
+
+    let mut cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+    let mut entry = cursor.seek_exact(address)?;
+    let mut scanned = 0;
+    while let Some((_, storage_entry)) = entry {
+        if remaining.is_empty() || scanned == STORAGE_PREIMAGES_MAX_SCANNED {
+            break
+        }
+        let hashed_slot = keccak256(storage_entry.key);
+        if remaining.remove(&hashed_slot) {
+            preimages.insert(hashed_slot, storage_entry.key);
+        }
+        scanned += 1;
+        entry = cursor.next_dup()?;
+    }
+    Ok(preimages)
+}
+
+/// Collects at most `limit` entries of the cursor, starting at the given key.
+fn walk_range<C: HashedCursor>(
+    cursor: &mut C,
+    start: B256,
+    limit: usize,
+) -> Result<HashedStateRange<C::Value>, DatabaseError> {
+    let mut entries = Vec::new();
+    let mut entry = cursor.seek(start)?;
+    while let Some((key, value)) = entry {
+        if entries.len() == limit {
+            return Ok(HashedStateRange { entries, next_key: Some(key) })
+        }
+        entries.push((key, value));
+        entry = cursor.next()?;
+    }
+    Ok(HashedStateRange { entries, next_key: None })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_provider_factory;
+    use reth_db_api::transaction::DbTxMut;
+    use reth_primitives::{Account, StorageEntry};
+
+    #[test]
+    fn account_range_pages_over_overlay() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        let account = |nonce| Account { nonce, ..Default::default() };
+        for i in [1u8, 3, 5] {
+            tx.put::<tables::HashedAccounts>(B256::with_last_byte(i), account(i as u64)).unwrap();
+        }
+
+        // insert a new account and destroy an existing one
+        let mut hashed_state = HashedPostState::default();
+        hashed_state.accounts.insert(B256::with_last_byte(2), Some(account(2)));
+        hashed_state.accounts.insert(B256::with_last_byte(3), None);
+
+        let range = hashed_account_range(&tx, hashed_state.clone(), B256::ZERO, 2).unwrap();
+        assert_eq!(
+            range.entries.iter().map(|(key, account)| (*key, account.nonce)).collect::<Vec<_>>(),
+            vec![(B256::with_last_byte(1), 1), (B256::with_last_byte(2), 2)]
+        );
+        assert_eq!(range.next_key, Some(B256::with_last_byte(5)));
+
+        let range = hashed_account_range(&tx, hashed_state, B256::with_last_byte(5), 2).unwrap();
+        assert_eq!(range.entries.len(), 1);
+        assert_eq!(range.next_key, None);
+    }
+
+    #[test]
+    fn storage_range_pages_over_overlay() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        let hashed_address = B256::with_last_byte(1);
+        for i in [1u8, 2, 3] {
+            tx.put::<tables::HashedStorages>(
+                hashed_address,
+                StorageEntry { key: B256::with_last_byte(i), value: U256::from(i) },
+            )
+            .unwrap();
+        }
+
+        let mut hashed_storage = HashedStorage::new(false);
+        hashed_storage.storage.insert(B256::with_last_byte(2), U256::ZERO);
+        hashed_storage.storage.insert(B256::with_last_byte(4), U256::from(4));
+        let mut hashed_state = HashedPostState::default();
+        hashed_state.storages.insert(hashed_address, hashed_storage);
+
+        let range = hashed_storage_range(&tx, hashed_state, hashed_address, B256::ZERO, 2).unwrap();
+        assert_eq!(
+            range.entries,
+            vec![
+                (B256::with_last_byte(1), U256::from(1)),
+                (B256::with_last_byte(3), U256::from(3))
+            ]
+        );
+        assert_eq!(range.next_key, Some(B256::with_last_byte(4)));
+    }
+
+    #[test]
+    fn storage_preimages_of_plain_state() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        let address = Address::with_last_byte(1);
+        for i in [1u8, 2, 3] {
+            tx.put::<tables::PlainStorageState>(
+                address,
+                StorageEntry { key: B256::with_last_byte(i), value: U256::from(i) },
+            )
+            .unwrap();
+        }
+
+        let slot = B256::with_last_byte(2);
+        let unknown = B256::with_last_byte(4);
+        let preimages =
+            storage_preimages(&tx, address, &[keccak256(slot), keccak256(unknown)]).unwrap();
+        assert_eq!(preimages, HashMap::from([(keccak256(slot), slot)]));
+        assert!(storage_preimages(&tx, Address::with_last_byte(2), &[keccak256(slot)])
+            .unwrap()
+            .is_empty());
+    }
+}
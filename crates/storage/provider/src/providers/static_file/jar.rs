@@ -7,8 +7,11 @@ use crate::{
     TransactionsProvider,
 };
 use reth_chainspec::ChainInfo;
-use reth_db::static_file::{HeaderMask, ReceiptMask, StaticFileCursor, TransactionMask};
-use reth_db_api::models::CompactU256;
+use reth_db::static_file::{
+    AccountChangeSetMask, HeaderMask, ReceiptMask, StaticFileCursor, StorageChangeSetMask,
+    TransactionMask,
+};
+use reth_db_api::models::{AccountChangeSet, CompactU256, StorageChangeSet};
 use reth_primitives::{
     Address, BlockHash, BlockHashOrNumber, BlockNumber, Header, Receipt, SealedHeader,
     TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, B256, U256,
@@ -72,6 +75,16 @@ impl<'a> StaticFileJarProvider<'a> {
         self.metrics = Some(metrics);
         self
     }
+
+    /// Returns the account changeset of the given block.
+    pub fn account_changeset(&self, num: BlockNumber) -> ProviderResult<Option<AccountChangeSet>> {
+        self.cursor()?.get_one::<AccountChangeSetMask<AccountChangeSet>>(num.into())
+    }
+
+    /// Returns the storage changeset of the given block.
+    pub fn storage_changeset(&self, num: BlockNumber) -> ProviderResult<Option<StorageChangeSet>> {
+        self.cursor()?.get_one::<StorageChangeSetMask<StorageChangeSet>>(num.into())
+    }
 }
 
 impl<'a> HeaderProvider for StaticFileJarProvider<'a> {
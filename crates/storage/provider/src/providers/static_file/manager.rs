@@ -13,12 +13,15 @@ use parking_lot::RwLock;
 use reth_chainspec::ChainInfo;
 use reth_db::{
     lockfile::StorageLock,
-    static_file::{iter_static_files, HeaderMask, ReceiptMask, StaticFileCursor, TransactionMask},
+    static_file::{
+        iter_static_files, AccountChangeSetMask, HeaderMask, ReceiptMask, StaticFileCursor,
+        StorageChangeSetMask, TransactionMask,
+    },
     tables,
 };
 use reth_db_api::{
     cursor::DbCursorRO,
-    models::{CompactU256, StoredBlockBodyIndices},
+    models::{AccountChangeSet, CompactU256, StorageChangeSet, StoredBlockBodyIndices},
     table::Table,
     transaction::DbTx,
 };
@@ -575,6 +578,11 @@ impl StaticFileProvider {
             //   accordingly.
             self.ensure_file_consistency(segment)?;
 
+            if segment.is_change_sets() {
+                self.ensure_change_set_invariants(provider, segment)?;
+                continue
+            }
+
             // Only applies to block-based static files. (Headers)
             //
             // The updated `highest_block` may have decreased if we healed from a pruning
@@ -647,6 +655,8 @@ impl StaticFileProvider {
                     highest_tx,
                     highest_block,
                 )?,
+                // Handled by `ensure_change_set_invariants` above.
+                StaticFileSegment::AccountChangeSets | StaticFileSegment::StorageChangeSets => None,
             } {
                 update_unwind_target(unwind);
             }
@@ -711,7 +721,9 @@ impl StaticFileProvider {
             .get_stage_checkpoint(match segment {
                 StaticFileSegment::Headers => StageId::Headers,
                 StaticFileSegment::Transactions => StageId::Bodies,
-                StaticFileSegment::Receipts => StageId::Execution,
+                StaticFileSegment::Receipts |
+                StaticFileSegment::AccountChangeSets |
+                StaticFileSegment::StorageChangeSets => StageId::Execution,
             })?
             .unwrap_or_default()
             .block_number;
@@ -756,6 +768,39 @@ impl StaticFileProvider {
         Ok(None)
     }
 
+    /// Prunes changeset static file rows of blocks above the [`StageId::Execution`] checkpoint.
+    ///
+    /// Changesets are only moved to static files once finalized, so unlike other segments they
+    /// never require a pipeline unwind. Rows past the checkpoint can only be left behind by an
+    /// unwind that committed to the database but not to static files.
+    fn ensure_change_set_invariants<TX: DbTx>(
+        &self,
+        provider: &DatabaseProvider<TX>,
+        segment: StaticFileSegment,
+    ) -> ProviderResult<()> {
+        let Some(highest_static_file_block) = self.get_highest_static_file_block(segment) else {
+            return Ok(())
+        };
+
+        let checkpoint_block_number =
+            provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+
+        if checkpoint_block_number < highest_static_file_block {
+            info!(
+                target: "reth::providers",
+                ?segment,
+                from = highest_static_file_block,
+                to = checkpoint_block_number,
+                "Unwinding static file segment."
+            );
+            let mut writer = self.latest_writer(segment)?;
+            writer.prune_change_sets(highest_static_file_block - checkpoint_block_number)?;
+            writer.commit()?;
+        }
+
+        Ok(())
+    }
+
     /// Gets the highest static file block if it exists for a static file segment.
     ///
     /// If there is nothing on disk for the given segment, this will return [`None`].
@@ -779,9 +824,85 @@ impl StaticFileProvider {
             headers: self.get_highest_static_file_block(StaticFileSegment::Headers),
             receipts: self.get_highest_static_file_block(StaticFileSegment::Receipts),
             transactions: self.get_highest_static_file_block(StaticFileSegment::Transactions),
+            account_changesets: self
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            storage_changesets: self
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
         }
     }
 
+    /// Returns the part of a block range whose changesets are read from static files.
+    ///
+    /// Every block up to the highest static file block is read from static files, even if some of
+    /// its changesets are still in the database: pruning them from the database can stop halfway
+    /// through a block, which leaves only part of its changeset there.
+    pub(crate) fn change_sets_in_static_files(
+        &self,
+        segment: StaticFileSegment,
+        range: Range<BlockNumber>,
+    ) -> Option<RangeInclusive<BlockNumber>> {
+        debug_assert!(segment.is_change_sets());
+        let highest_static_file_block = self.get_highest_static_file_block(segment)?;
+        let end = range.end.min(highest_static_file_block.saturating_add(1));
+        (range.start < end).then(|| range.start..=end - 1)
+    }
+
+    /// Returns the account changeset of the given block, if it has been moved to static files.
+    pub fn account_changeset(&self, num: BlockNumber) -> ProviderResult<Option<AccountChangeSet>> {
+        self.get_segment_provider_from_block(StaticFileSegment::AccountChangeSets, num, None)
+            .and_then(|provider| provider.account_changeset(num))
+            .or_else(|err| {
+                if let ProviderError::MissingStaticFileBlock(_, _) = err {
+                    Ok(None)
+                } else {
+                    Err(err)
+                }
+            })
+    }
+
+    /// Returns the account changesets of a block range, one per block, from static files.
+    pub fn account_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<AccountChangeSet>> {
+        self.fetch_range_with_predicate(
+            StaticFileSegment::AccountChangeSets,
+            to_range(range),
+            |cursor, number| {
+                cursor.get_one::<AccountChangeSetMask<AccountChangeSet>>(number.into())
+            },
+            |_| true,
+        )
+    }
+
+    /// Returns the storage changeset of the given block, if it has been moved to static files.
+    pub fn storage_changeset(&self, num: BlockNumber) -> ProviderResult<Option<StorageChangeSet>> {
+        self.get_segment_provider_from_block(StaticFileSegment::StorageChangeSets, num, None)
+            .and_then(|provider| provider.storage_changeset(num))
+            .or_else(|err| {
+                if let ProviderError::MissingStaticFileBlock(_, _) = err {
+                    Ok(None)
+                } else {
+                    Err(err)
+                }
+            })
+    }
+
+    /// Returns the storage changesets of a block range, one per block, from static files.
+    pub fn storage_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<StorageChangeSet>> {
+        self.fetch_range_with_predicate(
+            StaticFileSegment::StorageChangeSets,
+            to_range(range),
+            |cursor, number| {
+                cursor.get_one::<StorageChangeSetMask<StorageChangeSet>>(number.into())
+            },
+            |_| true,
+        )
+    }
+
     /// Iterates through segment `static_files` in reverse order, executing a function until it
     /// returns some object. Useful for finding objects by [`TxHash`] or [`BlockHash`].
     pub fn find_static_file<T>(
@@ -821,11 +942,10 @@ impl StaticFileProvider {
         F: FnMut(&mut StaticFileCursor<'_>, u64) -> ProviderResult<Option<T>>,
         P: FnMut(&T) -> bool,
     {
-        let get_provider = |start: u64| match segment {
-            StaticFileSegment::Headers => {
+        let get_provider = |start: u64| {
+            if segment.is_block_based() {
                 self.get_segment_provider_from_block(segment, start, None)
-            }
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
+            } else {
                 self.get_segment_provider_from_transaction(segment, start, None)
             }
         };
@@ -860,7 +980,7 @@ impl StaticFileProvider {
                                 "Could not find block or tx number on a range request"
                             );
 
-                            let err = if segment.is_headers() {
+                            let err = if segment.is_block_based() {
                                 ProviderError::MissingStaticFileBlock(segment, number)
                             } else {
                                 ProviderError::MissingStaticFileTx(segment, number)
@@ -897,11 +1017,10 @@ impl StaticFileProvider {
         F: Fn(&mut StaticFileCursor<'_>, u64) -> ProviderResult<Option<T>> + 'a,
         T: std::fmt::Debug,
     {
-        let get_provider = move |start: u64| match segment {
-            StaticFileSegment::Headers => {
+        let get_provider = move |start: u64| {
+            if segment.is_block_based() {
                 self.get_segment_provider_from_block(segment, start, None)
-            }
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
+            } else {
                 self.get_segment_provider_from_transaction(segment, start, None)
             }
         };
@@ -949,11 +1068,10 @@ impl StaticFileProvider {
         FD: Fn() -> ProviderResult<Option<T>>,
     {
         // If there is, check the maximum block or transaction number of the segment.
-        let static_file_upper_bound = match segment {
-            StaticFileSegment::Headers => self.get_highest_static_file_block(segment),
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
-                self.get_highest_static_file_tx(segment)
-            }
+        let static_file_upper_bound = if segment.is_block_based() {
+            self.get_highest_static_file_block(segment)
+        } else {
+            self.get_highest_static_file_tx(segment)
         };
 
         if static_file_upper_bound
@@ -991,11 +1109,10 @@ impl StaticFileProvider {
         let mut data = Vec::new();
 
         // If there is, check the maximum block or transaction number of the segment.
-        if let Some(static_file_upper_bound) = match segment {
-            StaticFileSegment::Headers => self.get_highest_static_file_block(segment),
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
-                self.get_highest_static_file_tx(segment)
-            }
+        if let Some(static_file_upper_bound) = if segment.is_block_based() {
+            self.get_highest_static_file_block(segment)
+        } else {
+            self.get_highest_static_file_tx(segment)
         } {
             if block_or_tx_range.start <= static_file_upper_bound {
                 let end = block_or_tx_range.end.min(static_file_upper_bound + 1);
@@ -4,7 +4,7 @@ use super::{
 use crate::providers::static_file::metrics::StaticFileProviderOperation;
 use parking_lot::{lock_api::RwLockWriteGuard, RawRwLock, RwLock};
 use reth_codecs::Compact;
-use reth_db_api::models::CompactU256;
+use reth_db_api::models::{AccountChangeSet, CompactU256, StorageChangeSet};
 use reth_nippy_jar::{ConsistencyFailStrategy, NippyJar, NippyJarError, NippyJarWriter};
 use reth_primitives::{
     static_file::{find_fixed_range, SegmentHeader, SegmentRangeInclusive},
@@ -29,6 +29,8 @@ pub(crate) struct StaticFileWriters {
     headers: RwLock<Option<StaticFileProviderRW>>,
     transactions: RwLock<Option<StaticFileProviderRW>>,
     receipts: RwLock<Option<StaticFileProviderRW>>,
+    account_changesets: RwLock<Option<StaticFileProviderRW>>,
+    storage_changesets: RwLock<Option<StaticFileProviderRW>>,
 }
 
 impl StaticFileWriters {
@@ -41,6 +43,8 @@ impl StaticFileWriters {
             StaticFileSegment::Headers => self.headers.write(),
             StaticFileSegment::Transactions => self.transactions.write(),
             StaticFileSegment::Receipts => self.receipts.write(),
+            StaticFileSegment::AccountChangeSets => self.account_changesets.write(),
+            StaticFileSegment::StorageChangeSets => self.storage_changesets.write(),
         };
 
         if write_guard.is_none() {
@@ -51,7 +55,13 @@ impl StaticFileWriters {
     }
 
     pub(crate) fn commit(&self) -> ProviderResult<()> {
-        for writer_lock in [&self.headers, &self.transactions, &self.receipts] {
+        for writer_lock in [
+            &self.headers,
+            &self.transactions,
+            &self.receipts,
+            &self.account_changesets,
+            &self.storage_changesets,
+        ] {
             let mut writer = writer_lock.write();
             if let Some(writer) = writer.as_mut() {
                 writer.commit()?;
@@ -205,7 +215,7 @@ impl StaticFileProviderRW {
         })?;
 
         // If we have lost rows (in this run or previous), we need to update the [SegmentHeader].
-        let expected_rows = if self.user_header().segment().is_headers() {
+        let expected_rows = if self.user_header().segment().is_block_based() {
             self.user_header().block_len().unwrap_or_default()
         } else {
             self.user_header().tx_len().unwrap_or_default()
@@ -238,6 +248,10 @@ impl StaticFileProviderRW {
                 StaticFileSegment::Receipts => {
                     self.prune_receipt_data(to_delete, last_block_number.expect("should exist"))?
                 }
+                segment @ (StaticFileSegment::AccountChangeSets |
+                StaticFileSegment::StorageChangeSets) => {
+                    self.prune_change_set_data(segment, to_delete)?
+                }
             }
         }
 
@@ -343,6 +357,12 @@ impl StaticFileProviderRW {
                 // Commits offsets and new user_header to disk
                 self.commit()?;
 
+                // Stores the checksum of the finished static file while its writer is still held,
+                // so that no append or prune can change it in the meantime
+                self.writer
+                    .commit_with_checksum()
+                    .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+
                 // Opens the new static file
                 let (writer, data_path) =
                     Self::open(segment, last_block + 1, self.reader.clone(), self.metrics.clone())?;
@@ -408,13 +428,10 @@ impl StaticFileProviderRW {
     ) -> ProviderResult<()> {
         let mut remaining_rows = num_rows;
         while remaining_rows > 0 {
-            let len = match segment {
-                StaticFileSegment::Headers => {
-                    self.writer.user_header().block_len().unwrap_or_default()
-                }
-                StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
-                    self.writer.user_header().tx_len().unwrap_or_default()
-                }
+            let len = if segment.is_block_based() {
+                self.writer.user_header().block_len().unwrap_or_default()
+            } else {
+                self.writer.user_header().tx_len().unwrap_or_default()
             };
 
             if remaining_rows >= len {
@@ -554,6 +571,61 @@ impl StaticFileProviderRW {
         Ok(block_number)
     }
 
+    /// Appends the account changeset of a block to static file.
+    ///
+    /// It **CALLS** `increment_block()`, since every block has exactly one changeset row, even if
+    /// it's empty.
+    ///
+    /// Returns the current [`BlockNumber`] as seen in the static file.
+    pub fn append_account_changeset(
+        &mut self,
+        block: BlockNumber,
+        changeset: &AccountChangeSet,
+    ) -> ProviderResult<BlockNumber> {
+        self.append_change_set(StaticFileSegment::AccountChangeSets, block, changeset)
+    }
+
+    /// Appends the storage changeset of a block to static file.
+    ///
+    /// It **CALLS** `increment_block()`, since every block has exactly one changeset row, even if
+    /// it's empty.
+    ///
+    /// Returns the current [`BlockNumber`] as seen in the static file.
+    pub fn append_storage_changeset(
+        &mut self,
+        block: BlockNumber,
+        changeset: &StorageChangeSet,
+    ) -> ProviderResult<BlockNumber> {
+        self.append_change_set(StaticFileSegment::StorageChangeSets, block, changeset)
+    }
+
+    /// Appends a changeset row to a block-based changeset static file.
+    fn append_change_set<V: Compact>(
+        &mut self,
+        segment: StaticFileSegment,
+        block: BlockNumber,
+        changeset: V,
+    ) -> ProviderResult<BlockNumber> {
+        let start = Instant::now();
+        self.ensure_no_queued_prune()?;
+
+        debug_assert!(self.writer.user_header().segment() == segment);
+
+        let block_number = self.increment_block(block)?;
+
+        self.append_column(changeset)?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                segment,
+                StaticFileProviderOperation::Append,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(block_number)
+    }
+
     /// Appends transaction to static file.
     ///
     /// It **DOES NOT CALL** `increment_block()`, it should be handled elsewhere. There might be
@@ -678,6 +750,12 @@ impl StaticFileProviderRW {
         self.queue_prune(to_delete, None)
     }
 
+    /// Adds an instruction to prune the changesets of the last `to_delete` blocks during commit.
+    pub fn prune_change_sets(&mut self, to_delete: u64) -> ProviderResult<()> {
+        debug_assert!(self.writer.user_header().segment().is_change_sets());
+        self.queue_prune(to_delete, None)
+    }
+
     /// Adds an instruction to prune `to_delete` elements during commit.
     ///
     /// Note: `last_block` refers to the block the unwinds ends at if dealing with transaction-based
@@ -770,6 +848,29 @@ impl StaticFileProviderRW {
         Ok(())
     }
 
+    /// Prunes the changesets of the last `to_delete` blocks from the data file.
+    fn prune_change_set_data(
+        &mut self,
+        segment: StaticFileSegment,
+        to_delete: u64,
+    ) -> ProviderResult<()> {
+        let start = Instant::now();
+
+        debug_assert!(self.writer.user_header().segment() == segment);
+
+        self.truncate(segment, to_delete, None)?;
+
+        if let Some(metrics) = &self.metrics {
+            metrics.record_segment_operation(
+                segment,
+                StaticFileProviderOperation::Prune,
+                Some(start.elapsed()),
+            );
+        }
+
+        Ok(())
+    }
+
     fn reader(&self) -> StaticFileProvider {
         Self::upgrade_provider_to_strong_reference(&self.reader)
     }
@@ -824,7 +925,7 @@ fn create_jar(
 
     // Transaction and Receipt already have the compression scheme used natively in its encoding.
     // (zstd-dictionary)
-    if segment.is_headers() {
+    if segment.is_block_based() {
         jar = jar.with_lz4();
     }
 
@@ -1,9 +1,11 @@
 use crate::{
     traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, ReceiptProviderIdExt,
-    RequestsProvider, StateProvider, StateProviderBox, StateProviderFactory, StateRootProvider,
-    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    AccountReader, AddressTransactionsReader, BadBlockReader, BadBlockWriter, BlockHashReader,
+    BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, ChainSpecProvider,
+    ChangeSetReader, DatabaseCompactor, EvmEnvProvider, HeaderProvider, HistoryReader,
+    LogIndexReader, PruneCheckpointReader, ReceiptProviderIdExt, RequestsProvider, StateProvider,
+    StateProviderBox, StateProviderFactory, StateRootProvider, TransactionVariant,
+    TransactionsProvider, WithdrawalsProvider,
 };
 use parking_lot::Mutex;
 use reth_chainspec::{ChainInfo, ChainSpec};
@@ -16,14 +18,18 @@ use reth_primitives::{
     TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
     U256,
 };
+use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
-use reth_storage_api::{StageCheckpointReader, StateProofProvider};
+use reth_storage_api::{
+    HashedStateRange, StageCheckpointReader, StateProofProvider, StateRangeProvider,
+};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
-use reth_trie::{updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage};
+use reth_trie::{updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, TrieAccount};
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     ops::{RangeBounds, RangeInclusive},
+    path::PathBuf,
     sync::Arc,
 };
 
@@ -604,6 +610,42 @@ impl StateProofProvider for MockEthProvider {
     }
 }
 
+impl StateRangeProvider for MockEthProvider {
+    fn hashed_account_range(
+        &self,
+        _hashed_state: HashedPostState,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<HashedStateRange<TrieAccount>> {
+        Ok(HashedStateRange::default())
+    }
+
+    fn hashed_storage_range(
+        &self,
+        _hashed_state: HashedPostState,
+        _hashed_address: B256,
+        _start: B256,
+        _limit: usize,
+    ) -> ProviderResult<HashedStateRange<U256>> {
+        Ok(HashedStateRange::default())
+    }
+
+    fn account_preimages(
+        &self,
+        _hashed_addresses: &[B256],
+    ) -> ProviderResult<HashMap<B256, Address>> {
+        Ok(HashMap::default())
+    }
+
+    fn storage_preimages(
+        &self,
+        _address: Address,
+        _hashed_slots: &[B256],
+    ) -> ProviderResult<HashMap<B256, B256>> {
+        Ok(HashMap::default())
+    }
+}
+
 impl StateProvider for MockEthProvider {
     fn storage(
         &self,
@@ -734,10 +776,10 @@ impl StateProviderFactory for MockEthProvider {
 impl WithdrawalsProvider for MockEthProvider {
     fn withdrawals_by_block(
         &self,
-        _id: BlockHashOrNumber,
+        id: BlockHashOrNumber,
         _timestamp: u64,
     ) -> ProviderResult<Option<Withdrawals>> {
-        Ok(None)
+        Ok(self.block(id)?.and_then(|block| block.withdrawals))
     }
     fn latest_withdrawal(&self) -> ProviderResult<Option<Withdrawal>> {
         Ok(None)
@@ -747,10 +789,10 @@ impl WithdrawalsProvider for MockEthProvider {
 impl RequestsProvider for MockEthProvider {
     fn requests_by_block(
         &self,
-        _id: BlockHashOrNumber,
+        id: BlockHashOrNumber,
         _timestamp: u64,
     ) -> ProviderResult<Option<reth_primitives::Requests>> {
-        Ok(None)
+        Ok(self.block(id)?.and_then(|block| block.requests))
     }
 }
 
@@ -762,3 +804,99 @@ impl ChangeSetReader for MockEthProvider {
         Ok(Vec::default())
     }
 }
+
+impl BadBlockReader for MockEthProvider {
+    fn bad_blocks(&self) -> ProviderResult<Vec<SealedBlock>> {
+        Ok(Vec::new())
+    }
+
+    fn bad_block_by_hash(&self, _hash: B256) -> ProviderResult<Option<SealedBlock>> {
+        Ok(None)
+    }
+}
+
+impl BadBlockWriter for MockEthProvider {
+    fn insert_bad_block(&self, _block: SealedBlock) -> ProviderResult<()> {
+        Ok(())
+    }
+}
+
+impl DatabaseCompactor for MockEthProvider {
+    fn compact_database(&self) -> ProviderResult<PathBuf> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
+impl HistoryReader for MockEthProvider {
+    fn account_history_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn storage_history_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn changed_accounts_in_range(
+        &self,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>> {
+        Ok(BTreeSet::default())
+    }
+}
+
+impl LogIndexReader for MockEthProvider {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn log_address_blocks(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+
+    fn log_topic_blocks(
+        &self,
+        _topic: B256,
+        _range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(Vec::default())
+    }
+}
+
+impl AddressTransactionsReader for MockEthProvider {
+    fn address_transactions_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        Ok(None)
+    }
+
+    fn address_transactions(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<TxNumber>,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        Ok(Vec::default())
+    }
+}
+
+impl PruneCheckpointReader for MockEthProvider {
+    fn get_prune_checkpoint(
+        &self,
+        _segment: PruneSegment,
+    ) -> ProviderResult<Option<PruneCheckpoint>> {
+        Ok(None)
+    }
+
+    fn get_prune_checkpoints(&self) -> ProviderResult<Vec<(PruneSegment, PruneCheckpoint)>> {
+        Ok(Vec::new())
+    }
+}
@@ -0,0 +1,25 @@
+use auto_impl::auto_impl;
+use reth_primitives::{Address, BlockNumber, TxNumber};
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Address transactions index reader
+#[auto_impl(&, Arc, Box)]
+pub trait AddressTransactionsReader: Send + Sync {
+    /// Returns the range of blocks covered by the address transactions index, or `None` if the
+    /// index isn't maintained.
+    ///
+    /// The range starts after the last pruned block and ends at the `IndexAddressTransactions`
+    /// stage checkpoint.
+    fn address_transactions_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns the numbers of the transactions within the given range sent by, sent to or
+    /// internally calling the address, in ascending order.
+    ///
+    /// This is read from the address transactions index and does not take pruning into account.
+    fn address_transactions(
+        &self,
+        address: Address,
+        range: RangeInclusive<TxNumber>,
+    ) -> ProviderResult<Vec<TxNumber>>;
+}
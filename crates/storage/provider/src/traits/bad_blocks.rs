@@ -0,0 +1,26 @@
+use reth_primitives::{SealedBlock, B256};
+use reth_storage_errors::provider::ProviderResult;
+
+/// The maximum number of bad blocks that are kept around, like geth does.
+pub const MAX_BAD_BLOCKS: usize = 10;
+
+/// Functionality to read the most recent blocks that failed validation.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait BadBlockReader: Send + Sync {
+    /// Returns the most recent bad blocks, from the highest to the lowest block number.
+    fn bad_blocks(&self) -> ProviderResult<Vec<SealedBlock>>;
+
+    /// Returns the bad block with the given hash, if it is still tracked.
+    fn bad_block_by_hash(&self, hash: B256) -> ProviderResult<Option<SealedBlock>>;
+}
+
+/// Functionality to record blocks that failed validation, so that they can be read with
+/// [`BadBlockReader`].
+#[auto_impl::auto_impl(&, Arc)]
+pub trait BadBlockWriter: Send + Sync {
+    /// Records a block that failed validation.
+    ///
+    /// At most [`MAX_BAD_BLOCKS`] blocks are kept, once the limit is reached the blocks with the
+    /// lowest numbers are dropped.
+    fn insert_bad_block(&self, block: SealedBlock) -> ProviderResult<()>;
+}
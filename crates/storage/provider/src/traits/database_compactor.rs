@@ -0,0 +1,13 @@
+use reth_storage_errors::provider::ProviderResult;
+use std::path::PathBuf;
+
+/// Functionality to compact the database while it is in use.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait DatabaseCompactor: Send + Sync {
+    /// Writes a compacted copy of the database next to it, replacing any previous copy, and returns
+    /// the directory of the copy.
+    ///
+    /// The database itself is left untouched, see
+    /// [`DatabaseCompaction::compact`](reth_db_api::database_compaction::DatabaseCompaction::compact).
+    fn compact_database(&self) -> ProviderResult<PathBuf>;
+}
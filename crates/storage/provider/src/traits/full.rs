@@ -1,9 +1,10 @@
 //! Helper provider traits to encapsulate all provider traits for simplicity.
 
 use crate::{
-    AccountReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    EvmEnvProvider, HeaderProvider, StageCheckpointReader, StateProviderFactory,
-    StaticFileProviderFactory, TransactionsProvider,
+    AccountReader, AddressTransactionsReader, BadBlockReader, BlockReaderIdExt, ChainSpecProvider,
+    ChangeSetReader, DatabaseCompactor, DatabaseProviderFactory, EvmEnvProvider, HeaderProvider,
+    HistoryReader, LogIndexReader, PruneCheckpointReader, StageCheckpointReader,
+    StateProviderFactory, StaticFileProviderFactory, TransactionsProvider,
 };
 use reth_chain_state::CanonStateSubscriptions;
 use reth_db_api::database::Database;
@@ -20,6 +21,12 @@ pub trait FullProvider<DB: Database>:
     + ChangeSetReader
     + CanonStateSubscriptions
     + StageCheckpointReader
+    + HistoryReader
+    + LogIndexReader
+    + AddressTransactionsReader
+    + PruneCheckpointReader
+    + BadBlockReader
+    + DatabaseCompactor
     + Clone
     + Unpin
     + 'static
@@ -37,6 +44,12 @@ impl<T, DB: Database> FullProvider<DB> for T where
         + ChangeSetReader
         + CanonStateSubscriptions
         + StageCheckpointReader
+        + HistoryReader
+        + LogIndexReader
+        + AddressTransactionsReader
+        + PruneCheckpointReader
+        + BadBlockReader
+        + DatabaseCompactor
         + Clone
         + Unpin
         + 'static
@@ -53,6 +66,12 @@ pub trait FullRpcProvider:
     + HeaderProvider
     + TransactionsProvider
     + StageCheckpointReader
+    + HistoryReader
+    + LogIndexReader
+    + AddressTransactionsReader
+    + PruneCheckpointReader
+    + BadBlockReader
+    + DatabaseCompactor
     + Clone
     + Unpin
     + 'static
@@ -67,6 +86,12 @@ impl<T> FullRpcProvider for T where
         + HeaderProvider
         + TransactionsProvider
         + StageCheckpointReader
+        + HistoryReader
+        + LogIndexReader
+        + AddressTransactionsReader
+        + PruneCheckpointReader
+        + BadBlockReader
+        + DatabaseCompactor
         + Clone
         + Unpin
         + 'static
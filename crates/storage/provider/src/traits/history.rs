@@ -1,12 +1,47 @@
 use auto_impl::auto_impl;
 use reth_db_api::models::BlockNumberAddress;
-use reth_primitives::{Address, BlockNumber, B256};
+use reth_execution_types::ExecutionOutcome;
+use reth_primitives::{Address, BlockNumber, TxNumber, B256};
 use reth_storage_errors::provider::ProviderResult;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     ops::{Range, RangeInclusive},
 };
 
+/// History Reader
+#[auto_impl(&, Arc, Box)]
+pub trait HistoryReader: Send + Sync {
+    /// Returns the block numbers within the given range in which the account was changed, in
+    /// ascending order.
+    ///
+    /// This is read from the account history index and does not take pruning into account.
+    fn account_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns the block numbers within the given range in which any storage slot of the account
+    /// was changed, in ascending order.
+    ///
+    /// This is read from the storage history index and does not take pruning into account.
+    fn storage_history_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns the addresses of all accounts whose info or storage changed in the given block
+    /// range.
+    ///
+    /// This is read from the account and storage changesets and does not take pruning into
+    /// account.
+    fn changed_accounts_in_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<Address>>;
+}
+
 /// History Writer
 #[auto_impl(&, Arc, Box)]
 pub trait HistoryWriter: Send + Sync {
@@ -40,4 +75,38 @@ pub trait HistoryWriter: Send + Sync {
 
     /// Read account/storage changesets and update account/storage history indices.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
+
+    /// Unwind and clear the log index for the given block range.
+    ///
+    /// Reads the receipts of the range, so it must be called before they are removed. Returns
+    /// number of logs walked.
+    fn unwind_log_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<usize>;
+
+    /// Insert log index entries to database. Used when updating the log index outside of the
+    /// `IndexLogs` stage.
+    fn insert_log_index(
+        &self,
+        address_blocks: BTreeMap<Address, Vec<u64>>,
+        topic_blocks: BTreeMap<B256, Vec<u64>>,
+    ) -> ProviderResult<()>;
+
+    /// Index the logs of the execution outcome, if the log index is maintained and up to date
+    /// with the block preceding the outcome. Advances the `IndexLogs` stage checkpoint.
+    fn update_log_index(&self, execution_outcome: &ExecutionOutcome) -> ProviderResult<()>;
+
+    /// Unwind and clear the address transactions index for the given block range.
+    ///
+    /// Reads the indexed addresses of the range's transactions, so it must be called before the
+    /// block bodies are removed. Returns number of transaction addresses removed.
+    fn unwind_address_transactions(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize>;
+
+    /// Insert the addresses of each transaction into the address transactions index. Used when
+    /// updating the index outside of the `IndexAddressTransactions` stage.
+    fn insert_address_transactions(
+        &self,
+        transaction_addresses: BTreeMap<TxNumber, BTreeSet<Address>>,
+    ) -> ProviderResult<()>;
 }
@@ -0,0 +1,34 @@
+use auto_impl::auto_impl;
+use reth_primitives::{Address, BlockNumber, B256};
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Log index reader
+#[auto_impl(&, Arc, Box)]
+pub trait LogIndexReader: Send + Sync {
+    /// Returns the range of blocks covered by the log index, or `None` if the log index isn't
+    /// maintained.
+    ///
+    /// The range starts after the last pruned block and ends at the `IndexLogs` stage checkpoint.
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Returns the block numbers within the given range with logs emitted by the address, in
+    /// ascending order.
+    ///
+    /// This is read from the log index and does not take pruning into account.
+    fn log_address_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+
+    /// Returns the block numbers within the given range with logs containing the topic in any
+    /// position, in ascending order.
+    ///
+    /// This is read from the log index and does not take pruning into account.
+    fn log_topic_blocks(
+        &self,
+        topic: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
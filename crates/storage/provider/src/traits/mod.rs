@@ -9,6 +9,12 @@ pub use reth_evm::provider::EvmEnvProvider;
 mod block;
 pub use block::*;
 
+mod bad_blocks;
+pub use bad_blocks::{BadBlockReader, BadBlockWriter, MAX_BAD_BLOCKS};
+
+mod database_compactor;
+pub use database_compactor::DatabaseCompactor;
+
 mod chain_info;
 pub use chain_info::CanonChainTracker;
 
@@ -27,7 +33,13 @@ mod trie;
 pub use trie::{StorageTrieWriter, TrieWriter};
 
 mod history;
-pub use history::HistoryWriter;
+pub use history::{HistoryReader, HistoryWriter};
+
+mod log_index;
+pub use log_index::LogIndexReader;
+
+mod address_transactions;
+pub use address_transactions::AddressTransactionsReader;
 
 mod database_provider;
 pub use database_provider::DatabaseProviderFactory;
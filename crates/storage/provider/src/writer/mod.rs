@@ -187,6 +187,9 @@ where
             let execution_outcome = block.execution_outcome().clone();
             state_writer.write_to_storage(execution_outcome, OriginalValuesKnown::No)?;
 
+            // extend the log index with the receipts of the block
+            self.database().update_log_index(block.execution_outcome())?;
+
             // insert hashes and intermediate merkle nodes
             {
                 let trie_updates = block.trie_updates().clone();
@@ -1,6 +1,6 @@
-use reth_primitives::{Address, Bytes, B256};
+use reth_primitives::{Address, Bytes, B256, U256};
 use reth_storage_errors::provider::ProviderResult;
-use reth_trie::{updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage};
+use reth_trie::{updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, TrieAccount};
 use revm::db::BundleState;
 use std::collections::HashMap;
 
@@ -77,3 +77,59 @@ pub trait StateProofProvider: Send + Sync {
         target: HashedPostState,
     ) -> ProviderResult<HashMap<B256, Bytes>>;
 }
+
+/// A type that can iterate over the hashed state on top of a given post state.
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait StateRangeProvider: Send + Sync {
+    /// Returns at most `limit` accounts of the `HashedPostState` on top of the current state,
+    /// starting at the hashed address `start`. Accounts are returned along with their storage
+    /// root and ordered by their hashed address.
+    fn hashed_account_range(
+        &self,
+        hashed_state: HashedPostState,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStateRange<TrieAccount>>;
+
+    /// Returns at most `limit` storage slots of the account with the given hashed address in the
+    /// `HashedPostState` on top of the current state, starting at the hashed slot `start`. Slots
+    /// are ordered by their hashed key.
+    fn hashed_storage_range(
+        &self,
+        hashed_state: HashedPostState,
+        hashed_address: B256,
+        start: B256,
+        limit: usize,
+    ) -> ProviderResult<HashedStateRange<U256>>;
+
+    /// Returns the addresses that hash to the given hashed addresses, keyed by their hashed
+    /// address.
+    ///
+    /// No preimages are stored, so only the addresses of the accounts changed since the current
+    /// state are known, and the others are missing from the result.
+    fn account_preimages(
+        &self,
+        hashed_addresses: &[B256],
+    ) -> ProviderResult<HashMap<B256, Address>>;
+
+    /// Returns the plain slots of the storage of the given account that hash to the given hashed
+    /// slots, keyed by their hashed slot.
+    ///
+    /// No preimages are stored, so the slots are found among the slots changed since the current
+    /// state and a bounded scan of the account's plain storage, and the others are missing from the
+    /// result.
+    fn storage_preimages(
+        &self,
+        address: Address,
+        hashed_slots: &[B256],
+    ) -> ProviderResult<HashMap<B256, B256>>;
+}
+
+/// A page of hashed state entries, ordered by their hashed keys.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HashedStateRange<T> {
+    /// The entries of the page along with their hashed keys.
+    pub entries: Vec<(B256, T)>,
+    /// The hashed key of the first entry after this page, if there are more entries.
+    pub next_key: Option<B256>,
+}
@@ -1,11 +1,18 @@
 //! A simple diskstore for blobs
 
-use crate::blobstore::{BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize};
+use crate::blobstore::{
+    BlobAndProof, BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize,
+};
 use alloy_rlp::{Decodable, Encodable};
 use parking_lot::{Mutex, RwLock};
 use reth_primitives::{BlobTransactionSidecar, TxHash, B256};
 use schnellru::{ByLength, LruMap};
-use std::{collections::HashSet, fmt, fs, io, path::PathBuf, sync::Arc};
+use std::{
+    collections::{hash_map::Entry, HashMap, HashSet},
+    fmt, fs, io,
+    path::PathBuf,
+    sync::Arc,
+};
 use tracing::{debug, trace};
 
 /// How many [`BlobTransactionSidecar`] to cache in memory.
@@ -82,6 +89,7 @@ impl BlobStore for DiskFileBlobStore {
         let mut stat = BlobStoreCleanupStat::default();
         let mut subsize = 0;
         debug!(target:"txpool::blob", num_blobs=%txs_to_delete.len(), "Removing blobs from disk");
+        self.inner.versioned_hashes_to_txhash.write().retain(|_, tx| !txs_to_delete.contains(tx));
         for tx in txs_to_delete {
             let path = self.inner.blob_disk_file(tx);
             let filesize = fs::metadata(&path).map_or(0, |meta| meta.len());
@@ -127,6 +135,37 @@ impl BlobStore for DiskFileBlobStore {
         self.inner.get_exact(txs)
     }
 
+    fn get_by_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError> {
+        let txs = {
+            let index = self.inner.versioned_hashes_to_txhash.read();
+            versioned_hashes.iter().map(|hash| index.get(hash).copied()).collect::<Vec<_>>()
+        };
+
+        // Sidecars of transactions with multiple requested blobs are only retrieved once
+        let mut sidecars = HashMap::<TxHash, Option<BlobTransactionSidecar>>::new();
+        let mut result = Vec::with_capacity(versioned_hashes.len());
+        for (versioned_hash, tx) in versioned_hashes.iter().zip(txs) {
+            let Some(tx) = tx else {
+                result.push(None);
+                continue
+            };
+            let sidecar = match sidecars.entry(tx) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(self.inner.get_one(tx)?),
+            };
+            result.push(
+                sidecar
+                    .as_ref()
+                    .and_then(|sidecar| BlobAndProof::from_sidecar(sidecar, versioned_hash)),
+            );
+        }
+
+        Ok(result)
+    }
+
     fn data_size_hint(&self) -> Option<usize> {
         Some(self.inner.size_tracker.data_size())
     }
@@ -142,6 +181,8 @@ struct DiskFileBlobStoreInner {
     size_tracker: BlobStoreSize,
     file_lock: RwLock<()>,
     txs_to_delete: RwLock<HashSet<B256>>,
+    /// Index of the transactions of the stored blobs by their versioned hashes.
+    versioned_hashes_to_txhash: RwLock<HashMap<B256, TxHash>>,
 }
 
 impl DiskFileBlobStoreInner {
@@ -153,6 +194,7 @@ impl DiskFileBlobStoreInner {
             size_tracker: Default::default(),
             file_lock: Default::default(),
             txs_to_delete: Default::default(),
+            versioned_hashes_to_txhash: Default::default(),
         }
     }
 
@@ -179,6 +221,9 @@ impl DiskFileBlobStoreInner {
     fn insert_one(&self, tx: B256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
         let mut buf = Vec::with_capacity(data.fields_len());
         data.encode(&mut buf);
+        self.versioned_hashes_to_txhash
+            .write()
+            .extend(data.versioned_hashes().map(|versioned_hash| (versioned_hash, tx)));
         self.blob_cache.lock().insert(tx, data);
         let size = self.write_one_encoded(tx, &buf)?;
 
@@ -198,6 +243,12 @@ impl DiskFileBlobStoreInner {
             })
             .collect::<Vec<_>>();
 
+        {
+            let mut index = self.versioned_hashes_to_txhash.write();
+            for (tx, data) in &txs {
+                index.extend(data.versioned_hashes().map(|versioned_hash| (versioned_hash, *tx)));
+            }
+        }
         {
             let mut cache = self.blob_cache.lock();
             for (tx, data) in txs {
@@ -398,6 +449,10 @@ impl fmt::Debug for DiskFileBlobStoreInner {
             .field("blob_dir", &self.blob_dir)
             .field("cached_blobs", &self.blob_cache.try_lock().map(|lock| lock.len()))
             .field("txs_to_delete", &self.txs_to_delete.try_read())
+            .field(
+                "indexed_blobs",
+                &self.versioned_hashes_to_txhash.try_read().map(|index| index.len()),
+            )
             .finish()
     }
 }
@@ -465,6 +520,7 @@ pub enum OpenDiskFileBlobStore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use alloy_eips::eip4844::{Blob, Bytes48};
     use std::sync::atomic::Ordering;
 
     fn tmp_store() -> (DiskFileBlobStore, tempfile::TempDir) {
@@ -519,4 +575,45 @@ mod tests {
         assert_eq!(store.data_size_hint(), Some(0));
         assert_eq!(store.inner.size_tracker.num_blobs.load(Ordering::Relaxed), 0);
     }
+
+    #[test]
+    fn disk_get_by_versioned_hashes() {
+        let (store, _dir) = tmp_store();
+
+        let tx = TxHash::random();
+        let sidecar = BlobTransactionSidecar {
+            blobs: vec![Blob::repeat_byte(1), Blob::repeat_byte(2)],
+            commitments: vec![Bytes48::repeat_byte(3), Bytes48::repeat_byte(4)],
+            proofs: vec![Bytes48::repeat_byte(5), Bytes48::repeat_byte(6)],
+        };
+        let versioned_hashes = sidecar.versioned_hashes().collect::<Vec<_>>();
+        store.insert(tx, sidecar.clone()).unwrap();
+
+        let request = [versioned_hashes[1], B256::random(), versioned_hashes[0]];
+        let assert_found = |store: &DiskFileBlobStore| {
+            let blobs = store.get_by_versioned_hashes(&request).unwrap();
+            let blobs = blobs
+                .iter()
+                .map(|blob| blob.as_ref().map(|blob| (&*blob.blob, blob.proof)))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                blobs,
+                vec![
+                    Some((&sidecar.blobs[1], sidecar.proofs[1])),
+                    None,
+                    Some((&sidecar.blobs[0], sidecar.proofs[0])),
+                ]
+            );
+        };
+        assert_found(&store);
+
+        // served from disk once the cache is gone
+        store.clear_cache();
+        assert_found(&store);
+
+        store.delete(tx).unwrap();
+        store.cleanup();
+        assert_eq!(store.get_by_versioned_hashes(&request).unwrap(), vec![None, None, None]);
+        assert!(store.inner.versioned_hashes_to_txhash.read().is_empty());
+    }
 }
@@ -1,5 +1,6 @@
 use crate::blobstore::{
-    BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize, BlobTransactionSidecar,
+    BlobAndProof, BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize,
+    BlobTransactionSidecar,
 };
 use parking_lot::RwLock;
 use reth_primitives::B256;
@@ -113,6 +114,24 @@ impl BlobStore for InMemoryBlobStore {
         Ok(items)
     }
 
+    fn get_by_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError> {
+        let mut result = vec![None; versioned_hashes.len()];
+        for sidecar in self.inner.store.read().values() {
+            for (hash, slot) in versioned_hashes.iter().zip(result.iter_mut()) {
+                if slot.is_none() {
+                    *slot = BlobAndProof::from_sidecar(sidecar, hash);
+                }
+            }
+            if result.iter().all(Option::is_some) {
+                break
+            }
+        }
+        Ok(result)
+    }
+
     fn data_size_hint(&self) -> Option<usize> {
         Some(self.inner.size_tracker.data_size())
     }
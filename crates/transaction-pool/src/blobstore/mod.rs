@@ -1,5 +1,6 @@
 //! Storage for blob data of EIP4844 transactions.
 
+use alloy_eips::eip4844::{Blob, Bytes48};
 pub use disk::{DiskFileBlobStore, DiskFileBlobStoreConfig, OpenDiskFileBlobStore};
 pub use mem::InMemoryBlobStore;
 pub use noop::NoopBlobStore;
@@ -64,6 +65,15 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
     /// Returns an error if any of the blobs are not found in the blob store.
     fn get_exact(&self, txs: Vec<B256>) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError>;
 
+    /// Returns the blobs and proofs for the given versioned hashes, in the order they were
+    /// requested.
+    ///
+    /// Blobs that are not in the store are returned as `None`.
+    fn get_by_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError>;
+
     /// Data size of all transactions in the blob store.
     fn data_size_hint(&self) -> Option<usize>;
 
@@ -71,6 +81,26 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
     fn blobs_len(&self) -> usize;
 }
 
+/// A blob and its KZG proof, as retrieved by versioned hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobAndProof {
+    /// The blob.
+    pub blob: Box<Blob>,
+    /// The KZG proof of the blob.
+    pub proof: Bytes48,
+}
+
+impl BlobAndProof {
+    /// Returns the blob and proof with the given versioned hash from the sidecar, if any.
+    pub fn from_sidecar(sidecar: &BlobTransactionSidecar, versioned_hash: &B256) -> Option<Self> {
+        let index = sidecar.versioned_hashes().position(|hash| hash == *versioned_hash)?;
+        Some(Self {
+            blob: Box::new(*sidecar.blobs.get(index)?),
+            proof: *sidecar.proofs.get(index)?,
+        })
+    }
+}
+
 /// Error variants that can occur when interacting with a blob store.
 #[derive(Debug, thiserror::Error)]
 pub enum BlobStoreError {
@@ -24,6 +24,14 @@ pub trait DatabaseStorageRoot<'a, TX> {
         address: Address,
         hashed_storage: HashedStorage,
     ) -> Result<B256, StorageRootError>;
+
+    /// Calculates the storage root for this [`HashedStorage`] of the account with the given hashed
+    /// address and returns it.
+    fn overlay_root_hashed(
+        tx: &'a TX,
+        hashed_address: B256,
+        hashed_storage: HashedStorage,
+    ) -> Result<B256, StorageRootError>;
 }
 
 /// Extends [`HashedStorage`] with operations specific for working with a database transaction.
@@ -60,14 +68,22 @@ impl<'a, TX: DbTx> DatabaseStorageRoot<'a, TX>
         tx: &'a TX,
         address: Address,
         hashed_storage: HashedStorage,
+    ) -> Result<B256, StorageRootError> {
+        Self::overlay_root_hashed(tx, keccak256(address), hashed_storage)
+    }
+
+    fn overlay_root_hashed(
+        tx: &'a TX,
+        hashed_address: B256,
+        hashed_storage: HashedStorage,
     ) -> Result<B256, StorageRootError> {
         let prefix_set = hashed_storage.construct_prefix_set().freeze();
         let state_sorted =
-            HashedPostState::from_hashed_storage(keccak256(address), hashed_storage).into_sorted();
-        StorageRoot::new(
+            HashedPostState::from_hashed_storage(hashed_address, hashed_storage).into_sorted();
+        StorageRoot::new_hashed(
             DatabaseTrieCursorFactory::new(tx),
             HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(tx), &state_sorted),
-            address,
+            hashed_address,
             #[cfg(feature = "metrics")]
             TrieRootMetrics::new(TrieType::Storage),
         )